@@ -0,0 +1,50 @@
+//! Times packing large word lists with the default first fit packer.
+//!
+//! Run with `cargo bench --bench pack`. Each word list is packed a few times
+//! and the fastest run is reported.
+//!
+//! Probing row offsets without allocating sped packing up 3 to 5 times. The
+//! fastest runs on one machine, before and after the change:
+//!
+//! | Words  | Rows  | Allocating | Without allocating |
+//! |--------|-------|------------|--------------------|
+//! | 10 000 | 2 500 | 92 ms      | 21 ms              |
+//! | 10 000 | 1 250 | 54 ms      | 17 ms              |
+//! | 20 000 | 5 000 | 341 ms     | 65 ms              |
+//! | 20 000 | 2 500 | 194 ms     | 48 ms              |
+
+use std::time::{Duration, Instant};
+
+use msmp::{generate_hash_with_options, Charset, FnvAlgorithm, Options, WordList};
+
+/// The number of times each word list is packed.
+const RUNS: usize = 3;
+
+fn main() {
+    // Fewer rows than words gives rows of several words, so each probe checks
+    // many columns.
+    for (num_words, num_rows) in [
+        (10_000, 2_500),
+        (10_000, 1_250),
+        (20_000, 5_000),
+        (20_000, 2_500),
+    ] {
+        let word_list: WordList = (0..num_words).map(|i| format!("KEY_{i}")).collect();
+        let options = Options::new()
+            .charset(Charset::Identifier)
+            .slack_percent(50);
+        let mut fastest = Duration::MAX;
+        for _ in 0..RUNS {
+            let start = Instant::now();
+            let hash = generate_hash_with_options(
+                &word_list,
+                FnvAlgorithm::new(num_rows, 1 << 16),
+                &options,
+            )
+            .unwrap();
+            fastest = fastest.min(start.elapsed());
+            assert!(hash.table_len >= num_words);
+        }
+        println!("{num_words} words in {num_rows} rows: {fastest:?}");
+    }
+}
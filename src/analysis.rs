@@ -0,0 +1,164 @@
+//! Analyzes how a hash algorithm spreads a word list over the 2D array.
+//!
+//! Packing is the expensive part of generating a hash. The distribution of
+//! the words over the rows and columns of the 2D array is cheap to compute and
+//! tells a lot about how hard packing will be, so candidate algorithms can be
+//! compared before any of them is packed.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::key::compare_key;
+use crate::{Error, HashAlgorithm, Key, WordList};
+
+/// How the words of a word list are spread over the 2D array.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Distribution {
+    /// The number of distinct words.
+    pub num_words: usize,
+
+    /// The number of rows holding at least one word.
+    pub num_rows: usize,
+
+    /// One more than the largest row index, the length of the row lookup table.
+    pub row_span: usize,
+
+    /// One more than the largest column index.
+    pub col_span: usize,
+
+    /// The number of rows of each size, keyed by the number of words in the row.
+    pub row_size_histogram: BTreeMap<usize, usize>,
+
+    /// The number of words in the biggest row.
+    pub max_row_size: usize,
+
+    /// The average distance from the first to the last column of a row, counting
+    /// both. Rows that spread wide are harder to fit between other rows.
+    pub avg_col_spread: f64,
+
+    /// The fraction of the cells of the used rows, up to `col_span`, that hold
+    /// no word.
+    pub sparsity: f64,
+
+    /// The number of words that land on a cell already holding a different
+    /// word. Any collision makes packing impossible.
+    pub collisions: usize,
+
+    /// The sum of the squared row sizes divided by the number of words. It is
+    /// 1 when every row holds a single word and grows as words crowd into
+    /// fewer rows, which are harder to place. Only meaningful relative to
+    /// other algorithms on the same word list.
+    pub packing_difficulty: f64,
+}
+
+/// Computes how a hash algorithm spreads a word list over the 2D array.
+///
+/// Repeated words are counted once.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `hash_algorithm` - A hash algorithm.
+///
+/// # Returns
+/// The distribution of the words. All counts are 0 for an empty list.
+///
+/// # Errors
+/// Will return `Err` if the hash algorithm rejects a word.
+pub fn analyze<K: Key + ?Sized, W: Borrow<K>>(
+    word_list: &WordList<W>,
+    hash_algorithm: &impl HashAlgorithm<K>,
+) -> Result<Distribution, Error> {
+    let fold_case = hash_algorithm.folds_case();
+    let mut cells: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    let mut seen = BTreeSet::new();
+    let mut collisions = 0;
+    for (word_index, word) in word_list.iter().map(Borrow::borrow).enumerate() {
+        if !seen.insert(compare_key(word, fold_case)) {
+            continue;
+        }
+        let (row, col) = hash_algorithm.hash_pair(word)?;
+        if cells.insert((row, col), word_index).is_some() {
+            collisions += 1;
+        }
+    }
+
+    // The cells are sorted by row, then column.
+    let mut rows: BTreeMap<usize, (usize, usize, usize)> = BTreeMap::new();
+    for (row, col) in cells.keys() {
+        let (size, _, last) = rows.entry(*row).or_insert((0, *col, *col));
+        *size += 1;
+        *last = *col;
+    }
+    let mut row_size_histogram = BTreeMap::new();
+    for (size, _, _) in rows.values() {
+        *row_size_histogram.entry(*size).or_insert(0) += 1;
+    }
+
+    let num_words = seen.len();
+    let num_cells = cells.len();
+    let col_span = cells.keys().map(|(_, col)| col + 1).max().unwrap_or(0);
+    let num_row_cells = rows.len() * col_span;
+    let spread: usize = rows.values().map(|(_, first, last)| last - first + 1).sum();
+    let squares: usize = rows.values().map(|(size, _, _)| size * size).sum();
+    Ok(Distribution {
+        num_words,
+        num_rows: rows.len(),
+        row_span: rows.keys().next_back().map_or(0, |row| row + 1),
+        col_span,
+        max_row_size: rows.values().map(|(size, _, _)| *size).max().unwrap_or(0),
+        row_size_histogram,
+        avg_col_spread: ratio(spread, rows.len()),
+        sparsity: ratio(num_row_cells - num_cells, num_row_cells),
+        collisions,
+        packing_difficulty: ratio(squares, num_words),
+    })
+}
+
+/// Divides two counts.
+///
+/// # Parameters
+/// * `numerator` - The count to divide.
+/// * `denominator` - The count to divide by.
+///
+/// # Returns
+/// The quotient, 0 when `denominator` is 0.
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        return 0.0;
+    }
+    f64::from(u32::try_from(numerator).unwrap_or(u32::MAX))
+        / f64::from(u32::try_from(denominator).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ElcAlgorithm;
+
+    #[test]
+    fn analyze_unit_test() {
+        let word_list: WordList = ["AND", "ADD", "BEGIN", "END", "ELSE", "END"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let distribution = analyze(&word_list, &ElcAlgorithm::default()).unwrap();
+        println!("{distribution:?}");
+        assert_eq!(distribution.num_words, 5);
+        assert_eq!(distribution.num_rows, 3);
+        assert_eq!(distribution.row_span, 5);
+        assert_eq!(distribution.col_span, 14);
+        assert_eq!(
+            distribution.row_size_histogram,
+            BTreeMap::from([(1, 2), (2, 1)])
+        );
+        assert_eq!(distribution.max_row_size, 2);
+        assert_eq!(distribution.collisions, 1);
+        // Rows A {3}, B {13} and E {3, 4} over 14 columns.
+        assert!((distribution.avg_col_spread - 4.0 / 3.0).abs() < 1e-9);
+        assert!((distribution.sparsity - (1.0 - 4.0 / 42.0)).abs() < 1e-9);
+        assert!((distribution.packing_difficulty - 6.0 / 5.0).abs() < 1e-9);
+
+        let distribution = analyze(&WordList::<String>::new(), &ElcAlgorithm::default()).unwrap();
+        assert_eq!(distribution, Distribution::default());
+    }
+}
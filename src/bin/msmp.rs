@@ -0,0 +1,1344 @@
+//! The `msmp` command line front end, built with the `cli` feature.
+//!
+//! `msmp generate words.txt --elc 2 --emit c -o out/` reads a word file, one
+//! word per line, generates a minimal perfect hash with the ELC algorithm, or
+//! another algorithm of the `AlgorithmRegistry` selected with `--algorithm`,
+//! and emits its tables. The hash function itself is emitted as the pseudo code of
+//! `HashData::as_string`, in a comment, for porting by hand.
+//!
+//! `msmp check words.txt table.json` verifies the tables emitted as JSON
+//! against a word list, to catch tables that went stale when the list changed.
+//!
+//! `msmp analyze words.txt` prints statistics of a word list and how each
+//! `--elc` spreads it over the 2D array, and recommends one, without packing.
+//!
+//! `msmp bench words.txt --elc 1..4 --strategies first-fit,backtracking` times
+//! generating a hash, and looking up every word, with each configuration.
+
+use std::fmt::Write as _;
+use std::hint::black_box;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use msmp::analysis::{self, Distribution};
+use msmp::{
+    algorithm_from_spec, c_recognizer, c_recognizer_self_test, generate_hash_with_options,
+    rust_recognizer, rust_recognizer_self_test, test_vectors, value_table_from_pairs, verify,
+    AlgorithmRegistry, Backtracking, ElcAlgorithm, FirstFit, HashAlgorithm, HashData, Language,
+    Options, ParamValue, Rlt, RltElementType, VectorFormat, WordList, WordListBuilder,
+    MAX_HASH_BASE,
+};
+use serde_json::{json, Value};
+
+/// The number of values of a letter, the letters 'A' to 'Z'.
+const NUM_VALS: usize = 26;
+
+/// The largest `--elc` compared by `msmp analyze`.
+const MAX_ELC: usize = 4;
+
+/// The number of times `msmp bench` looks up every word.
+const LOOKUP_ROUNDS: u32 = 100;
+
+/// The usage text printed by `--help` and after a usage error.
+const USAGE: &str = "\
+Usage: msmp generate <WORD_FILE> [--algorithm <NAME>] [--param <NAME>=<VALUE>]... [--elc <N>]
+                     [--emit c|c-switch|rust|rust-enum|python|json] [--values <FILE>]
+                     [--base <N>] [--self-test] [--vectors <FILE>] [-o <DIR>]
+       msmp check <WORD_FILE> <TABLE_FILE>
+       msmp analyze <WORD_FILE>
+       msmp bench <WORD_FILE> [--elc <N>|<A>..<B>] [--strategies <LIST>] [--timeout <SECS>]
+
+generate: Generates a minimal perfect hash of the words in WORD_FILE, one word
+          per line.
+check:    Verifies the tables in TABLE_FILE, emitted by generate as JSON,
+          against the words in WORD_FILE.
+analyze:  Compares the values of --elc for the words in WORD_FILE and
+          recommends one, without generating a hash.
+bench:    Times generating a hash of the words in WORD_FILE, and looking up
+          every word, for each --elc and packing strategy.
+
+Options:
+  --algorithm <NAME>
+                   Hash algorithm used by generate: cichelli, elc, elc+len,
+                   fnv, position or seeded [default: elc]. Without --param,
+                   cichelli, elc+len, position and seeded search the words for
+                   their parameters. fnv needs num_rows and num_cols
+  --param <NAME>=<VALUE>
+                   A parameter of the hash algorithm, such as elc=2 or
+                   num_rows=32. Lists are comma separated
+  --elc <N>        Letters taken from each end of a word [default: 1], the
+                   same as --param elc=N. bench also takes an inclusive range
+                   such as 1..4
+  --strategies <LIST>
+                   Comma separated packing strategies compared by bench:
+                   first-fit, backtracking [default: first-fit]
+  --timeout <SECS> Time budget of each generation by bench [default: 10]
+  --emit <FORMAT>  Format of the tables: c, c-switch, rust, rust-enum, python
+                   or json [default: json]. c-switch emits a token_recognize
+                   function returning TOKEN_ constants, and rust-enum a
+                   Keyword enum with a from_str recognizing the words
+  --values <FILE>  Appends a value table, indexed by the hash value, to the
+                   source formats. Each line of FILE holds a word and its
+                   value, such as a token number: BEGIN 12
+  --base <N>       Smallest hash value, 1 for tables indexed from 1
+                   [default: 0]. Tables indexed by the hash value start with
+                   N unused entries
+  --self-test      Appends a self-test of the recognizer to rust-enum, a test
+                   module, and to c-switch, a token_self_test function compiled
+                   with MSMP_SELF_TEST defined
+  --vectors <FILE> Also writes the hash value of every word to FILE, as CSV
+                   or as JSON when FILE ends in .json
+  -o <DIR>         Writes the tables to DIR/<WORD_FILE stem>.<ext> instead of
+                   standard output
+  -h, --help       Prints this text";
+
+/// The formats the tables can be emitted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emit {
+    /// A C source file.
+    C,
+
+    /// A C source file recognizing the words with a `switch` on the hash
+    /// value, see `c_recognizer`.
+    CSwitch,
+
+    /// A Rust source file.
+    Rust,
+
+    /// A Rust source file recognizing the words with an enum, see
+    /// `rust_recognizer`.
+    RustEnum,
+
+    /// A Python module.
+    Python,
+
+    /// A JSON document.
+    Json,
+}
+
+impl Emit {
+    /// Parses the value of `--emit`.
+    ///
+    /// # Parameters
+    /// * `value` - The value given.
+    ///
+    /// # Returns
+    /// The format, or None if `value` names no format.
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "c" => Some(Emit::C),
+            "c-switch" => Some(Emit::CSwitch),
+            "rust" => Some(Emit::Rust),
+            "rust-enum" => Some(Emit::RustEnum),
+            "python" => Some(Emit::Python),
+            "json" => Some(Emit::Json),
+            _ => None,
+        }
+    }
+
+    /// Gets the file extension of the format.
+    ///
+    /// # Returns
+    /// The extension, without the dot.
+    fn extension(self) -> &'static str {
+        match self {
+            Emit::C | Emit::CSwitch => "c",
+            Emit::Rust | Emit::RustEnum => "rs",
+            Emit::Python => "py",
+            Emit::Json => "json",
+        }
+    }
+
+    /// Gets the language of the source formats.
+    ///
+    /// # Returns
+    /// The language, or None for JSON.
+    fn language(self) -> Option<Language> {
+        match self {
+            Emit::C | Emit::CSwitch => Some(Language::C),
+            Emit::Rust | Emit::RustEnum => Some(Language::Rust),
+            Emit::Python => Some(Language::Python),
+            Emit::Json => None,
+        }
+    }
+}
+
+/// The arguments of `msmp generate`.
+#[derive(Debug, PartialEq, Eq)]
+struct Generate {
+    /// The word file.
+    word_file: PathBuf,
+
+    /// The name of the hash algorithm in the `AlgorithmRegistry`.
+    algorithm: String,
+
+    /// The name and value of each parameter of the hash algorithm, as given.
+    params: Vec<(String, String)>,
+
+    /// The format of the tables.
+    emit: Emit,
+
+    /// The file holding the value of each word, or None for no value table.
+    values_file: Option<PathBuf>,
+
+    /// The smallest hash value.
+    hash_base: usize,
+
+    /// Whether a self-test is appended to the recognizer.
+    self_test: bool,
+
+    /// The file the test vectors are written to, or None for no test vectors.
+    vectors_file: Option<PathBuf>,
+
+    /// The directory the tables are written to, or None for standard output.
+    out_dir: Option<PathBuf>,
+}
+
+/// The arguments of `msmp check`.
+#[derive(Debug, PartialEq, Eq)]
+struct Check {
+    /// The word file.
+    word_file: PathBuf,
+
+    /// The tables emitted as JSON.
+    table_file: PathBuf,
+}
+
+/// The arguments of `msmp analyze`.
+#[derive(Debug, PartialEq, Eq)]
+struct Analyze {
+    /// The word file.
+    word_file: PathBuf,
+}
+
+/// The strategies `msmp bench` can compare, see `Options::packing_strategy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Strategy {
+    /// `FirstFit`.
+    FirstFit,
+
+    /// `Backtracking`.
+    Backtracking,
+}
+
+impl Strategy {
+    /// Parses a strategy name.
+    ///
+    /// # Parameters
+    /// * `name` - The name given.
+    ///
+    /// # Returns
+    /// The strategy, or None if `name` names no strategy.
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "first-fit" => Some(Strategy::FirstFit),
+            "backtracking" => Some(Strategy::Backtracking),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of the strategy.
+    ///
+    /// # Returns
+    /// The name accepted by `parse`.
+    fn name(self) -> &'static str {
+        match self {
+            Strategy::FirstFit => "first-fit",
+            Strategy::Backtracking => "backtracking",
+        }
+    }
+}
+
+/// The arguments of `msmp bench`.
+#[derive(Debug, PartialEq, Eq)]
+struct Bench {
+    /// The word file.
+    word_file: PathBuf,
+
+    /// The letters taken from each end of a word, each value is compared.
+    elcs: RangeInclusive<usize>,
+
+    /// The packing strategies compared.
+    strategies: Vec<Strategy>,
+
+    /// The time budget of each generation.
+    timeout: Duration,
+}
+
+/// A parsed command line.
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    /// Prints the usage text.
+    Help,
+
+    /// Generates a hash.
+    Generate(Generate),
+
+    /// Verifies emitted tables.
+    Check(Check),
+
+    /// Recommends parameters.
+    Analyze(Analyze),
+
+    /// Compares configurations.
+    Bench(Bench),
+}
+
+/// Parses the command line.
+///
+/// # Parameters
+/// * `args` - The arguments, without the program name.
+///
+/// # Returns
+/// The command to run.
+///
+/// # Errors
+/// Will return `Err` describing the first argument that is missing or not
+/// understood.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("generate") => parse_generate(args),
+        Some("check") => parse_check(args),
+        Some("analyze") => parse_analyze(args),
+        Some("bench") => parse_bench(args),
+        Some("-h" | "--help") => Ok(Command::Help),
+        Some(command) => Err(format!("unknown command '{command}'")),
+        None => Err("missing command".to_string()),
+    }
+}
+
+/// Parses the arguments of `msmp generate`.
+///
+/// # Parameters
+/// * `args` - The arguments following the command.
+///
+/// # Returns
+/// The command to run.
+///
+/// # Errors
+/// Will return `Err` describing the first argument that is missing or not
+/// understood.
+fn parse_generate(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut word_file = None;
+    let mut algorithm = String::from("elc");
+    let mut params = Vec::new();
+    let mut emit = Emit::Json;
+    let mut values_file = None;
+    let mut hash_base = 0;
+    let mut self_test = false;
+    let mut vectors_file = None;
+    let mut out_dir = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for '{name}'"));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--algorithm" => algorithm = value("--algorithm")?,
+            "--param" => {
+                let param = value("--param")?;
+                let (name, value) = param
+                    .split_once('=')
+                    .ok_or(format!("invalid value '{param}' for '--param'"))?;
+                params.push((name.to_string(), value.to_string()));
+            }
+            "--elc" => {
+                let elc = parse_elc(&value("--elc")?)?;
+                params.push(("elc".to_string(), elc.to_string()));
+            }
+            "--emit" => {
+                let format = value("--emit")?;
+                emit =
+                    Emit::parse(&format).ok_or(format!("invalid value '{format}' for '--emit'"))?;
+            }
+            "--values" => values_file = Some(PathBuf::from(value("--values")?)),
+            "--base" => {
+                let base = value("--base")?;
+                hash_base = base
+                    .parse()
+                    .map_err(|_| format!("invalid value '{base}' for '--base'"))?;
+            }
+            "--self-test" => self_test = true,
+            "--vectors" => vectors_file = Some(PathBuf::from(value("--vectors")?)),
+            "-o" => out_dir = Some(PathBuf::from(value("-o")?)),
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ if word_file.is_none() => word_file = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    if values_file.is_some() && emit.language().is_none() {
+        return Err("'--values' needs a source format for '--emit'".to_string());
+    }
+    if self_test && !matches!(emit, Emit::CSwitch | Emit::RustEnum) {
+        return Err("'--self-test' needs '--emit c-switch' or '--emit rust-enum'".to_string());
+    }
+
+    Ok(Command::Generate(Generate {
+        word_file: word_file.ok_or("missing word file")?,
+        algorithm,
+        params,
+        emit,
+        values_file,
+        hash_base,
+        self_test,
+        vectors_file,
+        out_dir,
+    }))
+}
+
+/// Parses the arguments of `msmp bench`.
+///
+/// # Parameters
+/// * `args` - The arguments following the command.
+///
+/// # Returns
+/// The command to run.
+///
+/// # Errors
+/// Will return `Err` describing the first argument that is missing or not
+/// understood.
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut word_file = None;
+    let mut elcs = 1..=1;
+    let mut strategies = vec![Strategy::FirstFit];
+    let mut timeout = Duration::from_secs(10);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for '{name}'"));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--elc" => {
+                let range = value("--elc")?;
+                elcs = match range.split_once("..") {
+                    Some((first, last)) => parse_elc(first)?..=parse_elc(last)?,
+                    None => parse_elc(&range).map(|elc| elc..=elc)?,
+                };
+                if elcs.is_empty() {
+                    return Err(format!("invalid value '{range}' for '--elc'"));
+                }
+            }
+            "--strategies" => {
+                let names = value("--strategies")?;
+                strategies = names
+                    .split(',')
+                    .map(|name| {
+                        Strategy::parse(name)
+                            .ok_or(format!("invalid value '{name}' for '--strategies'"))
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+            "--timeout" => {
+                let secs = value("--timeout")?;
+                timeout = secs
+                    .parse()
+                    .ok()
+                    .filter(|secs| *secs > 0)
+                    .map(Duration::from_secs)
+                    .ok_or(format!("invalid value '{secs}' for '--timeout'"))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ if word_file.is_none() => word_file = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    Ok(Command::Bench(Bench {
+        word_file: word_file.ok_or("missing word file")?,
+        elcs,
+        strategies,
+        timeout,
+    }))
+}
+
+/// Parses a value of `--elc`.
+///
+/// # Parameters
+/// * `n` - The value given.
+///
+/// # Returns
+/// The letters taken from each end of a word.
+///
+/// # Errors
+/// Will return `Err` if `n` is not a positive number.
+fn parse_elc(n: &str) -> Result<usize, String> {
+    n.parse()
+        .ok()
+        .filter(|elc| *elc > 0)
+        .ok_or(format!("invalid value '{n}' for '--elc'"))
+}
+
+/// Parses the arguments of `msmp check`.
+///
+/// # Parameters
+/// * `args` - The arguments following the command.
+///
+/// # Returns
+/// The command to run.
+///
+/// # Errors
+/// Will return `Err` describing the first argument that is missing or not
+/// understood.
+fn parse_check(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let Some(files) = parse_files(args, &["word file", "table file"])? else {
+        return Ok(Command::Help);
+    };
+    let mut files = files.into_iter();
+    Ok(Command::Check(Check {
+        word_file: files.next().unwrap_or_default(),
+        table_file: files.next().unwrap_or_default(),
+    }))
+}
+
+/// Parses the arguments of `msmp analyze`.
+///
+/// # Parameters
+/// * `args` - The arguments following the command.
+///
+/// # Returns
+/// The command to run.
+///
+/// # Errors
+/// Will return `Err` describing the first argument that is missing or not
+/// understood.
+fn parse_analyze(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let Some(files) = parse_files(args, &["word file"])? else {
+        return Ok(Command::Help);
+    };
+    Ok(Command::Analyze(Analyze {
+        word_file: files.into_iter().next().unwrap_or_default(),
+    }))
+}
+
+/// Parses the arguments of a command that only takes files.
+///
+/// # Parameters
+/// * `args` - The arguments following the command.
+/// * `names` - The name of each file, in order, for the error messages.
+///
+/// # Returns
+/// One path per name, or None if help was asked for.
+///
+/// # Errors
+/// Will return `Err` if a file is missing, or an argument is not understood.
+fn parse_files(
+    args: impl Iterator<Item = String>,
+    names: &[&str],
+) -> Result<Option<Vec<PathBuf>>, String> {
+    let mut files = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+            _ if files.len() < names.len() => files.push(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    if let Some(name) = names.get(files.len()) {
+        return Err(format!("missing {name}"));
+    }
+    Ok(Some(files))
+}
+
+/// Reads a word file, one word per line. Surrounding whitespace and blank lines
+/// are dropped.
+///
+/// # Parameters
+/// * `word_file` - The word file.
+///
+/// # Returns
+/// The word list.
+///
+/// # Errors
+/// Will return `Err` if the file cannot be read.
+fn load_word_list(word_file: &Path) -> Result<WordList, String> {
+    let text = std::fs::read_to_string(word_file)
+        .map_err(|e| format!("unable to read {}: {e}", word_file.display()))?;
+    Ok(WordListBuilder::new()
+        .trim()
+        .skip_empty()
+        .build(text.lines()))
+}
+
+/// Reads a value file, holding a word and its value on each line. Blank lines
+/// are dropped.
+///
+/// # Parameters
+/// * `values_file` - The value file.
+///
+/// # Returns
+/// Each word and its value, in file order.
+///
+/// # Errors
+/// Will return `Err` if the file cannot be read, or a line does not hold a
+/// word and a value.
+fn load_values(values_file: &Path) -> Result<Vec<(String, String)>, String> {
+    let text = std::fs::read_to_string(values_file)
+        .map_err(|e| format!("unable to read {}: {e}", values_file.display()))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.trim()
+                .split_once(char::is_whitespace)
+                .map(|(word, value)| (word.to_string(), value.trim().to_string()))
+                .ok_or(format!(
+                    "{}:{}: expected a word and its value",
+                    values_file.display(),
+                    i + 1
+                ))
+        })
+        .collect()
+}
+
+/// Quotes a word as a string literal. The quoting is valid in every format.
+///
+/// # Parameters
+/// * `word` - The word to quote.
+///
+/// # Returns
+/// The word in double quotes, with double quotes and backslashes escaped.
+fn quote(word: &str) -> String {
+    let mut quoted = String::with_capacity(word.len() + 2);
+    quoted.push('"');
+    for c in word.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Joins values into a comma separated string.
+///
+/// # Parameters
+/// * `values` - The values to join.
+///
+/// # Returns
+/// The values as a string.
+fn join<T: ToString>(values: impl IntoIterator<Item = T>) -> String {
+    values
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Emits the tables of a hash.
+///
+/// The row lookup table uses the narrowest integer type that holds its
+/// values, and `keys` holds the word of each hash value, so a lookup can
+/// confirm a match. Hash values no word hashes to, which only exist with
+/// slack, hold an empty key.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `algorithm` - The hash algorithm, see `algorithm_to_json`, recorded in the
+///   JSON document for `msmp check`.
+/// * `emit` - The format to emit.
+///
+/// # Returns
+/// The tables as source code, or as a JSON document.
+///
+/// # Errors
+/// Will return `Err` if a recognizer cannot be emitted for the hash.
+fn emit_tables(
+    word_list: &WordList,
+    hash_data: &HashData,
+    algorithm: &Value,
+    emit: Emit,
+) -> Result<String, String> {
+    let rlt = hash_data.as_fn.get_rlt();
+    let element_type = rlt.element_type();
+    let table_len = hash_data.table_len;
+    // Indexed by the hash value, so hash values below the base have no key.
+    let hash_base = rlt.get_hash_base();
+    let keys: Vec<Option<&str>> = std::iter::repeat_n(None, hash_base)
+        .chain(
+            hash_data
+                .permutation
+                .words_in_hash_order()
+                .iter()
+                .map(|word| word.and_then(|w| word_list.get(w)).map(String::as_str)),
+        )
+        .collect();
+    let keys_len = match hash_base {
+        0 => "TABLE_LEN".to_string(),
+        _ => format!("{hash_base} + TABLE_LEN"),
+    };
+    let pseudo_code = hash_data.as_string.trim_end();
+
+    let mut text = String::new();
+    match emit {
+        Emit::C => {
+            let _ = writeln!(text, "/* Generated by msmp. The hash function:\n *");
+            for line in pseudo_code.lines() {
+                let _ = writeln!(text, " * {line}");
+            }
+            let _ = writeln!(text, " */\n\n#include <stddef.h>\n#include <stdint.h>\n");
+            let _ = writeln!(text, "#define TABLE_LEN {table_len}\n");
+            let _ = writeln!(
+                text,
+                "static const {} row_lookup_table[{}] = {{{}}};\n",
+                element_type.c_name(),
+                rlt.len(),
+                join(rlt.iter())
+            );
+            let keys = join(keys.iter().map(|key| key.map_or("NULL".to_string(), quote)));
+            let _ = writeln!(
+                text,
+                "static const char *const keys[{keys_len}] = {{{keys}}};"
+            );
+        }
+        Emit::Rust => {
+            let _ = writeln!(text, "// Generated by msmp. The hash function:\n//");
+            for line in pseudo_code.lines() {
+                let _ = writeln!(text, "// {line}");
+            }
+            let _ = writeln!(text, "\npub const TABLE_LEN: usize = {table_len};\n");
+            let _ = writeln!(
+                text,
+                "pub static ROW_LOOKUP_TABLE: [{}; {}] = [{}];\n",
+                element_type.name(),
+                rlt.len(),
+                join(rlt.iter())
+            );
+            let keys = join(keys.iter().map(|key| quote(key.unwrap_or_default())));
+            let _ = writeln!(text, "pub static KEYS: [&str; {keys_len}] = [{keys}];");
+        }
+        Emit::CSwitch => {
+            text = c_recognizer(word_list, hash_data, "token").map_err(|e| e.to_string())?;
+        }
+        Emit::RustEnum => {
+            text = rust_recognizer(word_list, hash_data, "Keyword").map_err(|e| e.to_string())?;
+        }
+        Emit::Python => {
+            let _ = writeln!(text, "# Generated by msmp. The hash function:\n#");
+            for line in pseudo_code.lines() {
+                let _ = writeln!(text, "# {line}");
+            }
+            let _ = writeln!(text, "\nTABLE_LEN = {table_len}\n");
+            let _ = writeln!(text, "ROW_LOOKUP_TABLE = [{}]\n", join(rlt.iter()));
+            let keys = join(keys.iter().map(|key| key.map_or("None".to_string(), quote)));
+            let _ = writeln!(text, "KEYS = [{keys}]");
+        }
+        Emit::Json => {
+            let document = json!({
+                "algorithm": algorithm,
+                "table_len": table_len,
+                "hash_base": hash_base,
+                "row_lookup_table": rlt.as_slice(),
+                "keys": keys,
+                "hash_function": pseudo_code,
+            });
+            let _ = writeln!(text, "{document:#}");
+        }
+    }
+    Ok(text)
+}
+
+/// Describes a hash algorithm for the JSON document, so that `msmp check` can
+/// rebuild it with `algorithm_from_spec`.
+///
+/// # Parameters
+/// * `hash_algorithm` - The hash algorithm.
+///
+/// # Returns
+/// An object with the `name` and `parameters` of the algorithm.
+fn algorithm_to_json(hash_algorithm: &dyn HashAlgorithm) -> Value {
+    fn param(value: &ParamValue) -> Value {
+        match value {
+            ParamValue::Number(n) => json!(n),
+            ParamValue::Bool(b) => json!(b),
+            ParamValue::Text(text) => json!(text),
+            ParamValue::List(values) => values.iter().map(param).collect(),
+        }
+    }
+    let parameters: serde_json::Map<String, Value> = hash_algorithm
+        .parameters()
+        .iter()
+        .map(|(name, value)| ((*name).to_string(), param(value)))
+        .collect();
+    json!({"name": hash_algorithm.name(), "parameters": parameters})
+}
+
+/// Rebuilds a hash algorithm described by `algorithm_to_json`.
+///
+/// # Parameters
+/// * `algorithm` - The object with the `name` and `parameters` of the algorithm.
+///
+/// # Returns
+/// The hash algorithm.
+///
+/// # Errors
+/// Will return `Err` if the object is malformed or names no algorithm that can
+/// be rebuilt.
+fn algorithm_from_json(algorithm: &Value) -> Result<Box<dyn HashAlgorithm>, String> {
+    fn param(value: &Value) -> Option<ParamValue> {
+        match value {
+            Value::Number(n) => n.as_u64().map(ParamValue::Number),
+            Value::Bool(b) => Some(ParamValue::Bool(*b)),
+            Value::String(text) => Some(ParamValue::Text(text.clone())),
+            Value::Array(values) => values
+                .iter()
+                .map(param)
+                .collect::<Option<_>>()
+                .map(ParamValue::List),
+            _ => None,
+        }
+    }
+    let name = algorithm["name"]
+        .as_str()
+        .ok_or("missing or invalid 'algorithm.name'")?;
+    let parameters = algorithm["parameters"]
+        .as_object()
+        .and_then(|parameters| {
+            parameters
+                .iter()
+                .map(|(name, value)| Some((name.as_str(), param(value)?)))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or("missing or invalid 'algorithm.parameters'")?;
+    algorithm_from_spec(name, &parameters).map_err(|e| e.to_string())
+}
+
+/// Runs `msmp generate`.
+///
+/// # Parameters
+/// * `generate` - The arguments.
+///
+/// # Returns
+/// The tables, or the file they were written to with `-o`.
+///
+/// # Errors
+/// Will return `Err` if the word file cannot be read, the hash cannot be
+/// generated or the tables or test vectors cannot be written.
+fn generate(generate: &Generate) -> Result<String, String> {
+    let word_list = load_word_list(&generate.word_file)?;
+    let params: Vec<(String, ParamValue)> = generate
+        .params
+        .iter()
+        .map(|(name, value)| (name.clone(), ParamValue::parse(value)))
+        .collect();
+    let hash_algorithm = AlgorithmRegistry::new()
+        .build_for(&generate.algorithm, &params, &word_list)
+        .map_err(|e| e.to_string())?;
+    let algorithm = algorithm_to_json(hash_algorithm.as_ref());
+    let options = Options::new().hash_base(generate.hash_base);
+    let hash_data = generate_hash_with_options(&word_list, hash_algorithm, &options)
+        .map_err(|e| e.to_string())?;
+    if let Some(vectors_file) = &generate.vectors_file {
+        let format = if vectors_file.extension().is_some_and(|ext| ext == "json") {
+            VectorFormat::Json
+        } else {
+            VectorFormat::Csv
+        };
+        std::fs::write(vectors_file, test_vectors(&word_list, &hash_data, format))
+            .map_err(|e| format!("unable to write {}: {e}", vectors_file.display()))?;
+    }
+    let mut text = emit_tables(&word_list, &hash_data, &algorithm, generate.emit)?;
+    if let (Some(values_file), Some(language)) = (&generate.values_file, generate.emit.language()) {
+        let pairs = load_values(values_file)?;
+        let value_table = value_table_from_pairs(&word_list, &hash_data, &pairs, language)
+            .map_err(|e| e.to_string())?;
+        let _ = write!(text, "\n{value_table}");
+    }
+    if generate.self_test {
+        let self_test = if generate.emit == Emit::CSwitch {
+            c_recognizer_self_test(&word_list, &hash_data, "token")
+        } else {
+            rust_recognizer_self_test(&word_list, &hash_data, "Keyword")
+        };
+        let _ = write!(text, "\n{self_test}");
+    }
+
+    let Some(out_dir) = &generate.out_dir else {
+        return Ok(text);
+    };
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| format!("unable to create {}: {e}", out_dir.display()))?;
+    let stem = generate
+        .word_file
+        .file_stem()
+        .map_or("words".into(), |stem| stem.to_string_lossy());
+    let out_file = out_dir.join(format!("{stem}.{}", generate.emit.extension()));
+    std::fs::write(&out_file, text)
+        .map_err(|e| format!("unable to write {}: {e}", out_file.display()))?;
+    Ok(format!("wrote {}", out_file.display()))
+}
+
+/// Reads a field of the tables emitted as JSON.
+///
+/// # Parameters
+/// * `tables` - The JSON document.
+/// * `name` - The name of the field.
+///
+/// # Returns
+/// The value of the field.
+///
+/// # Errors
+/// Will return `Err` if the field is missing or is not a non-negative integer.
+fn usize_field(tables: &Value, name: &str) -> Result<usize, String> {
+    tables[name]
+        .as_u64()
+        .and_then(|value| usize::try_from(value).ok())
+        .ok_or(format!("missing or invalid '{name}'"))
+}
+
+/// Runs `msmp check`.
+///
+/// # Parameters
+/// * `check` - The arguments.
+///
+/// # Returns
+/// The report of the verification, when the tables hash every word to its own
+/// hash value and every hash value is used.
+///
+/// # Errors
+/// Will return `Err` if a file cannot be read, the tables are malformed, a word
+/// cannot be hashed with them, or they are not a minimal perfect hash of the
+/// words.
+fn check(check: &Check) -> Result<String, String> {
+    let word_list = load_word_list(&check.word_file)?;
+    let table_file = check.table_file.display();
+    let text = std::fs::read_to_string(&check.table_file)
+        .map_err(|e| format!("unable to read {table_file}: {e}"))?;
+    let tables: Value =
+        serde_json::from_str(&text).map_err(|e| format!("unable to parse {table_file}: {e}"))?;
+    // Tables written before the algorithm was recorded only hold `elc`.
+    let hash_algorithm = match tables.get("algorithm") {
+        Some(algorithm) => algorithm_from_json(algorithm)
+            .map_err(|e| format!("unable to rebuild the hash algorithm of {table_file}: {e}"))?,
+        None => Box::new(ElcAlgorithm::new(usize_field(&tables, "elc")?, NUM_VALS)),
+    };
+    let table_len = usize_field(&tables, "table_len")?;
+    // Tables written before the base was recorded are 0 based.
+    let hash_base = match tables.get("hash_base") {
+        Some(_) => usize_field(&tables, "hash_base")?,
+        None => 0,
+    };
+    if hash_base > MAX_HASH_BASE {
+        return Err(format!(
+            "invalid 'hash_base': {hash_base} is larger than {MAX_HASH_BASE}"
+        ));
+    }
+    if hash_base.checked_add(table_len).is_none() {
+        return Err(format!("invalid 'table_len': {table_len} is too large"));
+    }
+    let values = tables["row_lookup_table"]
+        .as_array()
+        .and_then(|values| {
+            values
+                .iter()
+                .map(|value| value.as_i64().and_then(|value| isize::try_from(value).ok()))
+                .collect::<Option<Vec<isize>>>()
+        })
+        .ok_or("missing or invalid 'row_lookup_table'")?;
+
+    let rlt = Rlt::with_values(values, table_len).with_hash_base(hash_base);
+    let report = verify(&word_list, &rlt, &hash_algorithm)
+        .map_err(|e| format!("{table_file} is stale: {e}"))?;
+    if !report.is_minimal() {
+        return Err(format!("{table_file} is stale: {report}"));
+    }
+    Ok(format!(
+        "{table_file} is a minimal perfect hash of {} words",
+        word_list.len()
+    ))
+}
+
+/// Estimates the size of the row lookup table of a distribution.
+///
+/// Packing shifts each row by less than the number of columns to the left and
+/// by less than the table length to the right, which bounds the values.
+///
+/// # Parameters
+/// * `distribution` - The distribution of the words over the 2D array.
+///
+/// # Returns
+/// The number of bytes of the row lookup table, stored with the narrowest
+/// integer type that holds the bounds.
+fn estimated_rlt_bytes(distribution: &Distribution) -> usize {
+    let min = isize::try_from(distribution.col_span).map_or(isize::MIN, |cols| -cols);
+    let max = isize::try_from(distribution.num_words).unwrap_or(isize::MAX);
+    distribution.row_span * RltElementType::signed(min, max).size()
+}
+
+/// Runs `msmp analyze`.
+///
+/// # Parameters
+/// * `analyze` - The arguments.
+///
+/// # Returns
+/// The statistics of the word list, a line per `--elc` compared and the
+/// recommended `--elc`.
+///
+/// # Errors
+/// Will return `Err` if the word file cannot be read, holds no words, or holds
+/// a word the ELC algorithm rejects.
+fn analyze(analyze: &Analyze) -> Result<String, String> {
+    let word_file = analyze.word_file.display();
+    let word_list = load_word_list(&analyze.word_file)?;
+    if word_list.is_empty() {
+        return Err(format!("{word_file} holds no words"));
+    }
+    let profile = word_list.profile(1);
+
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "words:    {} ({} duplicates)",
+        word_list.len(),
+        profile.duplicates
+    );
+    let _ = writeln!(
+        text,
+        "length:   {} to {}, {:.1} on average",
+        profile.min_len, profile.max_len, profile.avg_len
+    );
+    let _ = writeln!(
+        text,
+        "alphabet: {} characters ({})\n",
+        profile.alphabet.len(),
+        profile.alphabet.iter().collect::<String>()
+    );
+    let _ = writeln!(
+        text,
+        "elc  collisions   rows  rlt entries  max row size  difficulty  rlt bytes"
+    );
+    let max_elc = profile.min_len.min(MAX_ELC);
+    let mut recommended = None;
+    for elc in 1..=max_elc {
+        let distribution = analysis::analyze(&word_list, &ElcAlgorithm::new(elc, NUM_VALS))
+            .map_err(|e| e.to_string())?;
+        let _ = writeln!(
+            text,
+            "{elc:>3}  {:>10}  {:>5}  {:>11}  {:>12}  {:>10.2}  {:>9}",
+            distribution.collisions,
+            distribution.num_rows,
+            distribution.row_span,
+            distribution.max_row_size,
+            distribution.packing_difficulty,
+            estimated_rlt_bytes(&distribution)
+        );
+        if distribution.collisions == 0 && recommended.is_none() {
+            recommended = Some(elc);
+        }
+    }
+
+    // Collisions make a hash impossible, and a larger elc only grows the table.
+    match recommended {
+        Some(elc) => {
+            let _ = write!(text, "\nrecommended: msmp generate {word_file} --elc {elc}");
+        }
+        None => {
+            let _ = write!(
+                text,
+                "\nno --elc up to {max_elc} hashes every word to its own cell"
+            );
+        }
+    }
+    Ok(text)
+}
+
+/// Formats a duration for the table printed by `msmp bench`.
+///
+/// # Parameters
+/// * `duration` - The duration.
+///
+/// # Returns
+/// The duration with one decimal, such as `1.5ms`.
+fn format_duration(duration: Duration) -> String {
+    format!("{duration:.1?}")
+}
+
+/// Runs `msmp bench`.
+///
+/// Every word is looked up `LOOKUP_ROUNDS` times to time the lookups.
+///
+/// # Parameters
+/// * `bench` - The arguments.
+///
+/// # Returns
+/// A line per configuration with the time to generate the hash, the time to
+/// look up a word and the size of the row lookup table, or why generation
+/// failed.
+///
+/// # Errors
+/// Will return `Err` if the word file cannot be read.
+fn bench(bench: &Bench) -> Result<String, String> {
+    let word_list = load_word_list(&bench.word_file)?;
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "elc  strategy          generate  lookup/word  rlt entries  result"
+    );
+    for elc in bench.elcs.clone() {
+        for strategy in &bench.strategies {
+            let options = Options::new().max_duration(bench.timeout);
+            let options = match strategy {
+                Strategy::FirstFit => options.packing_strategy(FirstFit),
+                Strategy::Backtracking => options.packing_strategy(Backtracking),
+            };
+            let start = Instant::now();
+            let hash_data =
+                generate_hash_with_options(&word_list, ElcAlgorithm::new(elc, NUM_VALS), &options);
+            let generate = format_duration(start.elapsed());
+            let hash_data = match hash_data {
+                Ok(hash_data) => hash_data,
+                Err(e) => {
+                    let _ = writeln!(
+                        text,
+                        "{elc:>3}  {:<14}  {generate:>10}  {:>11}  {:>11}  {e}",
+                        strategy.name(),
+                        "-",
+                        "-"
+                    );
+                    continue;
+                }
+            };
+
+            let start = Instant::now();
+            for _ in 0..LOOKUP_ROUNDS {
+                for word in &word_list {
+                    black_box(hash_data.as_fn.hash(black_box(word.as_str())));
+                }
+            }
+            let lookups = u32::try_from(word_list.len())
+                .unwrap_or(u32::MAX)
+                .saturating_mul(LOOKUP_ROUNDS)
+                .max(1);
+            let _ = writeln!(
+                text,
+                "{elc:>3}  {:<14}  {generate:>10}  {:>11}  {:>11}  ok",
+                strategy.name(),
+                format_duration(start.elapsed() / lookups),
+                hash_data.as_fn.get_rlt().len()
+            );
+        }
+    }
+    Ok(text)
+}
+
+fn main() -> ExitCode {
+    let result = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Help) => Ok(USAGE.to_string()),
+        Ok(Command::Generate(args)) => generate(&args),
+        Ok(Command::Check(args)) => check(&args),
+        Ok(Command::Analyze(args)) => analyze(&args),
+        Ok(Command::Bench(args)) => bench(&args),
+        Err(e) => {
+            eprintln!("msmp: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(text) => {
+            println!("{}", text.trim_end());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("msmp: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a command line given as one string.
+    fn parse(args: &str) -> Result<Command, String> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parse_args_unit_test() {
+        assert_eq!(
+            parse("generate words.txt --elc 2 --emit c -o out/"),
+            Ok(Command::Generate(Generate {
+                word_file: PathBuf::from("words.txt"),
+                algorithm: "elc".to_string(),
+                params: vec![("elc".to_string(), "2".to_string())],
+                emit: Emit::C,
+                values_file: None,
+                hash_base: 0,
+                self_test: false,
+                vectors_file: None,
+                out_dir: Some(PathBuf::from("out/")),
+            }))
+        );
+        assert_eq!(
+            parse("generate words.txt"),
+            Ok(Command::Generate(Generate {
+                word_file: PathBuf::from("words.txt"),
+                algorithm: "elc".to_string(),
+                params: Vec::new(),
+                emit: Emit::Json,
+                values_file: None,
+                hash_base: 0,
+                self_test: false,
+                vectors_file: None,
+                out_dir: None,
+            }))
+        );
+        assert_eq!(
+            parse("generate words.txt --algorithm fnv --param num_rows=8 --param num_cols=16"),
+            Ok(Command::Generate(Generate {
+                word_file: PathBuf::from("words.txt"),
+                algorithm: "fnv".to_string(),
+                params: vec![
+                    ("num_rows".to_string(), "8".to_string()),
+                    ("num_cols".to_string(), "16".to_string())
+                ],
+                emit: Emit::Json,
+                values_file: None,
+                hash_base: 0,
+                self_test: false,
+                vectors_file: None,
+                out_dir: None,
+            }))
+        );
+        assert_eq!(
+            parse("generate words.txt --emit rust --values tokens.txt"),
+            Ok(Command::Generate(Generate {
+                word_file: PathBuf::from("words.txt"),
+                algorithm: "elc".to_string(),
+                params: Vec::new(),
+                emit: Emit::Rust,
+                values_file: Some(PathBuf::from("tokens.txt")),
+                hash_base: 0,
+                self_test: false,
+                vectors_file: None,
+                out_dir: None,
+            }))
+        );
+        assert_eq!(
+            parse("generate words.txt --base 1"),
+            Ok(Command::Generate(Generate {
+                word_file: PathBuf::from("words.txt"),
+                algorithm: "elc".to_string(),
+                params: Vec::new(),
+                emit: Emit::Json,
+                values_file: None,
+                hash_base: 1,
+                self_test: false,
+                vectors_file: None,
+                out_dir: None,
+            }))
+        );
+        assert_eq!(
+            parse("generate words.txt --emit c-switch --self-test --vectors words.csv"),
+            Ok(Command::Generate(Generate {
+                word_file: PathBuf::from("words.txt"),
+                algorithm: "elc".to_string(),
+                params: Vec::new(),
+                emit: Emit::CSwitch,
+                values_file: None,
+                hash_base: 0,
+                self_test: true,
+                vectors_file: Some(PathBuf::from("words.csv")),
+                out_dir: None,
+            }))
+        );
+        assert_eq!(
+            parse("generate words.txt --base one"),
+            Err("invalid value 'one' for '--base'".to_string())
+        );
+        assert_eq!(
+            parse("generate words.txt --emit json --self-test"),
+            Err("'--self-test' needs '--emit c-switch' or '--emit rust-enum'".to_string())
+        );
+        assert_eq!(
+            parse("generate words.txt --values tokens.txt"),
+            Err("'--values' needs a source format for '--emit'".to_string())
+        );
+        assert_eq!(
+            parse("generate words.txt --param elc"),
+            Err("invalid value 'elc' for '--param'".to_string())
+        );
+        assert_eq!(
+            parse("check words.txt table.json"),
+            Ok(Command::Check(Check {
+                word_file: PathBuf::from("words.txt"),
+                table_file: PathBuf::from("table.json"),
+            }))
+        );
+        assert_eq!(
+            parse("check words.txt"),
+            Err("missing table file".to_string())
+        );
+        assert_eq!(
+            parse("check words.txt table.json more.json"),
+            Err("unexpected argument 'more.json'".to_string())
+        );
+        assert_eq!(
+            parse("analyze words.txt"),
+            Ok(Command::Analyze(Analyze {
+                word_file: PathBuf::from("words.txt"),
+            }))
+        );
+        assert_eq!(parse("analyze"), Err("missing word file".to_string()));
+        assert_eq!(
+            parse("bench words.txt --elc 1..4 --strategies first-fit,backtracking --timeout 5"),
+            Ok(Command::Bench(Bench {
+                word_file: PathBuf::from("words.txt"),
+                elcs: 1..=4,
+                strategies: vec![Strategy::FirstFit, Strategy::Backtracking],
+                timeout: Duration::from_secs(5),
+            }))
+        );
+        assert_eq!(
+            parse("bench words.txt --elc 2"),
+            Ok(Command::Bench(Bench {
+                word_file: PathBuf::from("words.txt"),
+                elcs: 2..=2,
+                strategies: vec![Strategy::FirstFit],
+                timeout: Duration::from_secs(10),
+            }))
+        );
+        assert_eq!(
+            parse("bench words.txt --elc 3..1"),
+            Err("invalid value '3..1' for '--elc'".to_string())
+        );
+        assert_eq!(
+            parse("bench words.txt --elc 0..2"),
+            Err("invalid value '0' for '--elc'".to_string())
+        );
+        assert_eq!(
+            parse("bench words.txt --strategies first-fit,greedy"),
+            Err("invalid value 'greedy' for '--strategies'".to_string())
+        );
+        assert_eq!(parse("--help"), Ok(Command::Help));
+        assert_eq!(parse("generate words.txt -h"), Ok(Command::Help));
+
+        assert_eq!(parse(""), Err("missing command".to_string()));
+        assert_eq!(parse("pack"), Err("unknown command 'pack'".to_string()));
+        assert_eq!(parse("generate"), Err("missing word file".to_string()));
+        assert_eq!(
+            parse("generate words.txt --elc 0"),
+            Err("invalid value '0' for '--elc'".to_string())
+        );
+        assert_eq!(
+            parse("generate words.txt --emit go"),
+            Err("invalid value 'go' for '--emit'".to_string())
+        );
+        assert_eq!(
+            parse("generate words.txt --emit"),
+            Err("missing value for '--emit'".to_string())
+        );
+        assert_eq!(
+            parse("generate words.txt more.txt"),
+            Err("unexpected argument 'more.txt'".to_string())
+        );
+        assert_eq!(
+            parse("generate words.txt --slack 5"),
+            Err("unknown option '--slack'".to_string())
+        );
+
+        assert_eq!(quote("A\"B\\"), "\"A\\\"B\\\\\"");
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{Error, Expr, HashAlgorithm, Key, ParamValue};
+
+/// A hash algorithm that ignores the case of ASCII letters.
+///
+/// Words are converted to ASCII uppercase before they are passed to the
+/// wrapped algorithm, so "begin", "Begin" and "BEGIN" all hash to the same
+/// value. Word lists hashed with a `CaseFold` algorithm may contain lowercase
+/// letters, and words that differ only in case are treated as repeats.
+#[derive(Debug, Clone, Default)]
+pub struct CaseFold<A> {
+    /// The algorithm the folded words are passed to.
+    inner: A,
+}
+
+impl<A> CaseFold<A> {
+    /// Creates a new case insensitive algorithm.
+    ///
+    /// # Parameters
+    /// * `inner` - The algorithm that hashes the folded words.
+    #[must_use]
+    pub fn new(inner: A) -> Self {
+        Self { inner }
+    }
+
+    /// Gets the wrapped algorithm.
+    ///
+    /// # Returns
+    /// The algorithm that hashes the folded words.
+    #[must_use]
+    pub fn get_inner(&self) -> &A {
+        &self.inner
+    }
+}
+
+impl<K: Key + ?Sized, A: HashAlgorithm<K>> HashAlgorithm<K> for CaseFold<A> {
+    fn h1(&self, word: &K) -> Result<usize, Error> {
+        self.inner.h1(&word.fold_case())
+    }
+
+    fn h2(&self, word: &K) -> Result<usize, Error> {
+        self.inner.h2(&word.fold_case())
+    }
+
+    fn hash_pair(&self, word: &K) -> Result<(usize, usize), Error> {
+        self.inner.hash_pair(&word.fold_case())
+    }
+
+    fn h1_as_text(&self) -> String {
+        format!(
+            "\n        word_letters = upper(word_letters){}",
+            self.inner.h1_as_text()
+        )
+    }
+
+    fn h2_as_text(&self) -> String {
+        format!(
+            "\n        word_letters = upper(word_letters){}",
+            self.inner.h2_as_text()
+        )
+    }
+
+    fn folds_case(&self) -> bool {
+        true
+    }
+
+    fn h1_expr(&self) -> Option<Expr> {
+        self.inner.h1_expr()
+    }
+
+    fn h2_expr(&self) -> Option<Expr> {
+        self.inner.h2_expr()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        self.inner.parameters()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ByteElcAlgorithm, ElcAlgorithm};
+
+    #[test]
+    fn case_fold_unit_test() {
+        let hash_algorithm = CaseFold::new(ElcAlgorithm::default());
+        println!("{hash_algorithm:?}");
+        assert!(hash_algorithm.folds_case());
+        assert!(!hash_algorithm.get_inner().folds_case());
+        assert_eq!(
+            hash_algorithm.h1("begin").unwrap(),
+            hash_algorithm.h1("BEGIN").unwrap()
+        );
+        assert_eq!(
+            hash_algorithm.h2("bEgIn").unwrap(),
+            hash_algorithm.h2("BEGIN").unwrap()
+        );
+        assert!(hash_algorithm.h1("3egin").is_err());
+        assert!(hash_algorithm.h1_as_text().contains("upper(word_letters)"));
+
+        let hash_algorithm = CaseFold::new(ByteElcAlgorithm::default());
+        assert_eq!(
+            hash_algorithm.h1(b"begin").unwrap(),
+            hash_algorithm.h1(b"BEGIN").unwrap()
+        );
+    }
+}
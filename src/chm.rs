@@ -0,0 +1,285 @@
+use crate::{Error, Kind};
+
+/// The number of seeds tried before giving up on peeling the hypergraph.
+const MAX_SEEDS: u64 = 64;
+
+/// The pseudo code of the function mixing a cell of the 2D array into a vertex.
+pub(crate) const MIX_AS_TEXT: &str = "function mix(z):
+    z = ((z ^ (z >> 30)) * 0xbf58476d1ce4e5b9) & 0xffffffffffffffff
+    z = ((z ^ (z >> 27)) * 0x94d049bb133111eb) & 0xffffffffffffffff
+    return z ^ (z >> 31)
+";
+
+/// Mixes a value, with the finalizer of SplitMix64.
+///
+/// # Parameters
+/// * `z` - The value to mix.
+///
+/// # Returns
+/// The mixed value.
+pub(crate) fn mix(z: u64) -> u64 {
+    let z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// An order preserving minimal perfect hash in the style of CHM (Czech, Havas
+/// and Majewski), built by peeling a 3-hypergraph.
+///
+/// Each cell of the 2D array is mixed into three vertices, one in each third of
+/// the vertices, and the hash value is the sum of the values of the three
+/// vertices modulo the number of words. The values are assigned by peeling the
+/// hypergraph, so that every cell gets the hash value it was given.
+#[derive(Debug, Clone)]
+pub(crate) struct Chm {
+    /// The value of each vertex.
+    values: Vec<usize>,
+
+    /// The value each cell is combined with before mixing, one per vertex.
+    keys: [u64; 3],
+
+    /// The factor the row index is multiplied by before the column index is
+    /// added, to combine a cell into one value.
+    stride: u64,
+
+    /// The number of vertices in each third.
+    part_len: usize,
+
+    /// The number of hash values.
+    table_len: usize,
+}
+
+impl Chm {
+    /// Creates a new hash for the cells of a 2D array.
+    ///
+    /// Peeling fails when the hypergraph has a 2-core, in which case the next
+    /// seed is tried.
+    ///
+    /// # Parameters
+    /// * `cells` - The row and column index of each cell, the hash value of a
+    ///   cell is its position in `cells`.
+    ///
+    /// # Returns
+    /// A hash giving each cell its position.
+    ///
+    /// # Errors
+    /// Will return `Err` if no seed gives a hypergraph that can be peeled.
+    pub(crate) fn new(cells: &[(usize, usize)]) -> Result<Self, Error> {
+        let table_len = cells.len();
+        let part_len = (table_len * 41).div_ceil(100) + 1;
+        let stride = cells
+            .iter()
+            .map(|(_, col)| *col)
+            .max()
+            .map_or(1, |col| col + 1);
+        for seed in 0..MAX_SEEDS {
+            let mut chm = Self {
+                values: vec![0; 3 * part_len],
+                keys: [0, 1, 2].map(|i| mix(seed * 3 + i)),
+                stride: u64::try_from(stride).unwrap_or(u64::MAX),
+                part_len,
+                table_len,
+            };
+            if chm.assign(cells) {
+                return Ok(chm);
+            }
+        }
+        Err(Error::new(Kind::ChmError(format!(
+            "Unable to peel the hypergraph with {MAX_SEEDS} seeds."
+        ))))
+    }
+
+    /// Computes the vertices of a cell.
+    ///
+    /// # Parameters
+    /// * `row_index` - The row index of the cell.
+    /// * `col_index` - The column index of the cell.
+    ///
+    /// # Returns
+    /// One vertex in each third of the vertices.
+    fn vertices(&self, row_index: usize, col_index: usize) -> [usize; 3] {
+        let x = u64::try_from(row_index)
+            .unwrap_or(u64::MAX)
+            .wrapping_mul(self.stride)
+            .wrapping_add(u64::try_from(col_index).unwrap_or(u64::MAX));
+        let part_len = u64::try_from(self.part_len).unwrap_or(u64::MAX);
+        [0, 1, 2].map(|i| {
+            i * self.part_len + usize::try_from(mix(x ^ self.keys[i]) % part_len).unwrap_or(0)
+        })
+    }
+
+    /// Peels the hypergraph of the cells and assigns the vertex values.
+    ///
+    /// # Parameters
+    /// * `cells` - The row and column index of each cell.
+    ///
+    /// # Returns
+    /// True if the hypergraph was peeled and the values assigned.
+    fn assign(&mut self, cells: &[(usize, usize)]) -> bool {
+        let edges: Vec<[usize; 3]> = cells
+            .iter()
+            .map(|(row_index, col_index)| self.vertices(*row_index, *col_index))
+            .collect();
+
+        // Each vertex keeps its degree and the xor of its edges, so the last
+        // edge of a vertex of degree 1 is known without a list of edges.
+        let mut degree = vec![0_usize; self.values.len()];
+        let mut incident = vec![0_usize; self.values.len()];
+        for (e, edge) in edges.iter().enumerate() {
+            for v in edge {
+                degree[*v] += 1;
+                incident[*v] ^= e;
+            }
+        }
+        let mut queue: Vec<usize> = (0..self.values.len()).filter(|v| degree[*v] == 1).collect();
+        let mut peeled: Vec<(usize, usize)> = Vec::with_capacity(edges.len());
+        while let Some(v) = queue.pop() {
+            if degree[v] != 1 {
+                continue;
+            }
+            let e = incident[v];
+            peeled.push((e, v));
+            for u in edges[e] {
+                degree[u] -= 1;
+                incident[u] ^= e;
+                if degree[u] == 1 {
+                    queue.push(u);
+                }
+            }
+        }
+        if peeled.len() != edges.len() {
+            return false;
+        }
+
+        // In reverse peeling order the free vertex of each edge is not used by
+        // any edge assigned before it, so its value can be chosen freely.
+        for (e, v) in peeled.into_iter().rev() {
+            let others: usize = edges[e]
+                .iter()
+                .filter(|u| **u != v)
+                .map(|u| self.values[*u])
+                .sum();
+            self.values[v] = (e + self.table_len - others % self.table_len) % self.table_len;
+        }
+        true
+    }
+
+    /// Hashes a cell of the 2D array.
+    ///
+    /// # Parameters
+    /// * `row_index` - The row index of the cell.
+    /// * `col_index` - The column index of the cell.
+    ///
+    /// # Returns
+    /// A hash value.
+    pub(crate) fn hash(&self, row_index: usize, col_index: usize) -> usize {
+        self.vertices(row_index, col_index)
+            .iter()
+            .map(|v| self.values[*v])
+            .sum::<usize>()
+            % self.table_len.max(1)
+    }
+
+    /// Gets the number of vertices.
+    ///
+    /// # Returns
+    /// The length of the vertex value table.
+    pub(crate) fn get_num_vertices(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Gets the vertex values.
+    ///
+    /// # Returns
+    /// The value of each vertex.
+    pub(crate) fn get_values(&self) -> &[usize] {
+        &self.values
+    }
+
+    /// Gets the values each cell is combined with before mixing.
+    ///
+    /// # Returns
+    /// One key per third of the vertices.
+    pub(crate) fn get_keys(&self) -> &[u64; 3] {
+        &self.keys
+    }
+
+    /// Gets the factor the row index is multiplied by to combine a cell.
+    ///
+    /// # Returns
+    /// The stride.
+    pub(crate) fn get_stride(&self) -> u64 {
+        self.stride
+    }
+
+    /// Gets the number of vertices in each third.
+    ///
+    /// # Returns
+    /// The length of a third of the vertex value table.
+    pub(crate) fn get_part_len(&self) -> usize {
+        self.part_len
+    }
+
+    /// Gets the vertex values as a string.
+    ///
+    /// # Returns
+    /// The value of each vertex as a string.
+    pub(crate) fn get_values_as_text(&self) -> String {
+        self.values
+            .iter()
+            .map(|x| format!("{x}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// A pseudo code representation of the hash of a cell, given as
+    /// `row_index` and `col_index`, using the `vertex_values` table and the
+    /// `mix` function.
+    ///
+    /// # Returns
+    /// The statements leaving the hash in `hash_value`.
+    pub(crate) fn hash_as_text(&self) -> String {
+        let p = self.part_len;
+        format!(
+            "x = (row_index * {stride} + col_index) & 0xffffffffffffffff\n\
+             hash_value = (vertex_values[mix(x ^ {k0:#x}) % {p}] + \
+             vertex_values[{p} + mix(x ^ {k1:#x}) % {p}] + \
+             vertex_values[{p2} + mix(x ^ {k2:#x}) % {p}]) % {len}\n",
+            stride = self.stride,
+            k0 = self.keys[0],
+            k1 = self.keys[1],
+            k2 = self.keys[2],
+            p2 = 2 * p,
+            len = self.table_len,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chm_unit_test() {
+        let cells: Vec<(usize, usize)> = (0..1000).map(|i| (i % 7, i * 13)).collect();
+        let chm = Chm::new(&cells).unwrap();
+        println!("{chm:?}");
+        for (i, (row_index, col_index)) in cells.iter().enumerate() {
+            assert_eq!(chm.hash(*row_index, *col_index), i);
+        }
+        assert_eq!(chm.get_num_vertices(), 3 * 411);
+        assert!(chm.hash_as_text().ends_with("% 1000\n"));
+
+        let chm = Chm::new(&[(3, 4)]).unwrap();
+        assert_eq!(chm.hash(3, 4), 0);
+        assert_eq!(chm.get_values_as_text(), "0, 0, 0, 0, 0, 0");
+
+        // The same cell twice is the same edge twice, which never peels.
+        match Chm::new(&[(1, 2), (1, 2)]).unwrap_err().kind() {
+            Kind::ChmError(s) => {
+                assert_eq!(s, "Unable to peel the hypergraph with 64 seeds.");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+    }
+}
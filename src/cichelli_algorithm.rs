@@ -0,0 +1,346 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::codegen::quote;
+use crate::{Error, Expr, HashAlgorithm, Kind, ParamValue, Position, WordList};
+
+/// The maximum number of weights tried while searching for a weight assignment.
+const MAX_SEARCH_STEPS: usize = 1 << 20;
+
+/// A hash algorithm in the spirit of Cichelli's method.
+///
+/// A word hashes to `len + weight[first_char] + weight[last_char]`, where the
+/// weight of each character is searched for so that no two words of the word
+/// list collide. All words share row 0, the hash value is the column index.
+/// The search succeeds for many keyword sets where the `ElcAlgorithm`
+/// collides, since words with the same end characters are told apart by
+/// their length.
+#[derive(Debug, Clone, Default)]
+pub struct CichelliAlgorithm {
+    /// The weight of each character that may start or end a word.
+    weights: BTreeMap<char, usize>,
+}
+
+/// The state of a weight search.
+struct Search<'a> {
+    /// The characters in the order their weights are assigned.
+    chars: &'a [char],
+
+    /// The words whose hash value is known once the weight of the character at
+    /// the same position of `chars` is assigned, as `(first, last, len)` with
+    /// the characters given as positions in `chars`.
+    completed: &'a [Vec<(usize, usize, usize)>],
+
+    /// The number of hash values, each word must have a different one.
+    table_len: usize,
+
+    /// The weight assigned to each character so far.
+    weights: Vec<usize>,
+
+    /// Whether each hash value is used by a word.
+    used: Vec<bool>,
+
+    /// The number of weights tried so far.
+    steps: usize,
+}
+
+impl Search<'_> {
+    /// Assigns weights to the characters from a position of `chars` on.
+    ///
+    /// # Parameters
+    /// * `k` - The position of the first character without a weight.
+    ///
+    /// # Returns
+    /// True if a weight assignment without collisions was found.
+    fn assign(&mut self, k: usize) -> bool {
+        if k == self.chars.len() {
+            return true;
+        }
+        for weight in 0..self.table_len {
+            self.steps += 1;
+            if self.steps > MAX_SEARCH_STEPS {
+                return false;
+            }
+            self.weights[k] = weight;
+            let values: Vec<usize> = self.completed[k]
+                .iter()
+                .map(|(first, last, len)| {
+                    (len + self.weights[*first] + self.weights[*last]) % self.table_len
+                })
+                .collect();
+            let fits = values
+                .iter()
+                .enumerate()
+                .all(|(i, value)| !self.used[*value] && !values[..i].contains(value));
+            if !fits {
+                continue;
+            }
+            for value in &values {
+                self.used[*value] = true;
+            }
+            if self.assign(k + 1) {
+                return true;
+            }
+            for value in &values {
+                self.used[*value] = false;
+            }
+        }
+        false
+    }
+}
+
+impl CichelliAlgorithm {
+    /// Creates a new CichelliAlgorithm with given character weights.
+    ///
+    /// # Parameters
+    /// * `weights` - The weight of each character that may start or end a word.
+    #[must_use]
+    pub fn new(weights: impl IntoIterator<Item = (char, usize)>) -> Self {
+        Self {
+            weights: weights.into_iter().collect(),
+        }
+    }
+
+    /// Creates a new CichelliAlgorithm with weights searched for a word list.
+    ///
+    /// The weights are searched with backtracking, assigning the most frequent
+    /// end characters first. Every word gets a different hash value modulo the
+    /// number of distinct words, so the hash is minimal when the table is
+    /// packed without slack.
+    ///
+    /// # Parameters
+    /// * `word_list` - The word list the algorithm will be used on.
+    ///
+    /// # Errors
+    /// Will return `Err` if the word list is empty or has an empty word, if two
+    /// words have the same length and end characters, or if no weights are
+    /// found within the search budget.
+    pub fn from_word_list<W: Borrow<str>>(word_list: &WordList<W>) -> Result<Self, Error> {
+        let words: BTreeSet<&str> = word_list.iter().map(Borrow::borrow).collect();
+        if words.is_empty() {
+            return Err(Error::new(Kind::CichelliAlgorithmError(
+                "Empty word list.".to_string(),
+            )));
+        }
+
+        // Words that share their length and end characters always collide.
+        let mut ends: BTreeMap<(char, char, usize), &str> = BTreeMap::new();
+        for word in &words {
+            let (Some(first), Some(last)) = (word.chars().next(), word.chars().next_back()) else {
+                return Err(Error::new(Kind::CichelliAlgorithmError(
+                    "Empty word.".to_string(),
+                )));
+            };
+            if let Some(other) = ends.insert((first, last, word.chars().count()), word) {
+                return Err(Error::new(Kind::CichelliAlgorithmError(format!(
+                    "Words {other} and {word} have the same length and end characters."
+                ))));
+            }
+        }
+
+        // The most frequent end characters are assigned first, so that words
+        // are checked for collisions as early as possible.
+        let mut frequencies: BTreeMap<char, usize> = BTreeMap::new();
+        for (first, last, _) in ends.keys() {
+            *frequencies.entry(*first).or_default() += 1;
+            *frequencies.entry(*last).or_default() += 1;
+        }
+        let mut chars: Vec<char> = frequencies.keys().copied().collect();
+        chars.sort_by_key(|c| std::cmp::Reverse(frequencies[c]));
+        let position: BTreeMap<char, usize> =
+            chars.iter().enumerate().map(|(k, c)| (*c, k)).collect();
+
+        let mut completed = vec![Vec::new(); chars.len()];
+        for (first, last, len) in ends.keys() {
+            let (first, last) = (position[first], position[last]);
+            completed[first.max(last)].push((first, last, *len));
+        }
+
+        let mut search = Search {
+            chars: &chars,
+            completed: &completed,
+            table_len: words.len(),
+            weights: vec![0; chars.len()],
+            used: vec![false; words.len()],
+            steps: 0,
+        };
+        if !search.assign(0) {
+            return Err(Error::new(Kind::CichelliAlgorithmError(format!(
+                "No weights found within {MAX_SEARCH_STEPS} steps."
+            ))));
+        }
+        let weights = search.weights;
+        Ok(Self::new(chars.into_iter().zip(weights)))
+    }
+
+    /// Gets the character weights.
+    ///
+    /// # Returns
+    /// The weight of each character that may start or end a word.
+    #[must_use]
+    pub fn get_weights(&self) -> &BTreeMap<char, usize> {
+        &self.weights
+    }
+
+    /// Gets the weight of a character.
+    ///
+    /// # Parameters
+    /// * `word` - The word the character is from, for error messages.
+    /// * `c` - The character.
+    ///
+    /// # Errors
+    /// Will return `Err` if `c` has no weight.
+    fn weight(&self, word: &str, c: char) -> Result<usize, Error> {
+        self.weights.get(&c).copied().ok_or_else(|| {
+            Error::new(Kind::CichelliAlgorithmError(format!(
+                "Unexpected character '{c}' at an end of ({word})."
+            )))
+        })
+    }
+}
+
+impl HashAlgorithm for CichelliAlgorithm {
+    /// Hash function h1 that is used internally to generate row indices.
+    /// All words share row 0.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    fn h1(&self, word: &str) -> Result<usize, Error> {
+        if word.is_empty() {
+            return Err(Error::new(Kind::CichelliAlgorithmError(
+                "Empty word.".to_string(),
+            )));
+        }
+        Ok(0)
+    }
+
+    /// A string representation of the h1 function.
+    ///
+    /// # Returns
+    /// A string representation of the h1 function.
+    fn h1_as_text(&self) -> String {
+        "\n        val = 0".to_string()
+    }
+
+    /// Hash function h2 that is used internally to generate column indices.
+    /// The hash value is the length of the word plus the weights of its first
+    /// and last characters.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    fn h2(&self, word: &str) -> Result<usize, Error> {
+        let (Some(first), Some(last)) = (word.chars().next(), word.chars().next_back()) else {
+            return Err(Error::new(Kind::CichelliAlgorithmError(
+                "Empty word.".to_string(),
+            )));
+        };
+        Ok(word.chars().count() + self.weight(word, first)? + self.weight(word, last)?)
+    }
+
+    /// A string representation of the h2 function.
+    ///
+    /// # Returns
+    /// A string representation of the h2 function.
+    fn h2_as_text(&self) -> String {
+        format!(
+            "\n        weights = {{{}}}
+        val = len(word_letters) + weights[word_letters[0]] + weights[word_letters[-1]]",
+            self.weights
+                .iter()
+                .map(|(c, weight)| format!("{}: {weight}", quote(&c.to_string())))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn h1_expr(&self) -> Option<Expr> {
+        Some(Expr::Const(0))
+    }
+
+    fn h2_expr(&self) -> Option<Expr> {
+        let weight = |position| Expr::Lookup {
+            keys: self.weights.keys().copied().collect(),
+            values: self.weights.values().copied().collect(),
+            of: Box::new(Expr::Char(position)),
+        };
+        Some(Expr::Len + weight(Position::Start(0)) + weight(Position::End(0)))
+    }
+
+    fn name(&self) -> &'static str {
+        "cichelli"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            (
+                "letters",
+                ParamValue::from(self.weights.keys().collect::<String>()),
+            ),
+            (
+                "weights",
+                ParamValue::List(
+                    self.weights
+                        .values()
+                        .copied()
+                        .map(ParamValue::from)
+                        .collect(),
+                ),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_hash, ElcAlgorithm};
+
+    #[test]
+    fn cichelli_algorithm_unit_test() {
+        let hash_algorithm = CichelliAlgorithm::new([('A', 1), ('D', 2)]);
+        assert_eq!(hash_algorithm.get_weights().len(), 2);
+        assert_eq!(hash_algorithm.h1("AND").unwrap(), 0);
+        assert_eq!(hash_algorithm.h2("AND").unwrap(), 6);
+        assert_eq!(hash_algorithm.h2("A").unwrap(), 3);
+        assert!(hash_algorithm.h1("").is_err());
+        match hash_algorithm.h2("BAD").unwrap_err().kind() {
+            Kind::CichelliAlgorithmError(s) => {
+                assert_eq!(s, "Unexpected character 'B' at an end of (BAD).");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        assert!(hash_algorithm.h2_as_text().contains("\"A\": 1, \"D\": 2"));
+
+        // Plain ELC collides on words with the same end letters.
+        let word_list: WordList = ["DO", "DOWNTO", "END", "ELSE", "EXIT", "ENDIF"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+        let hash_algorithm = CichelliAlgorithm::from_word_list(&word_list).unwrap();
+        let hash = generate_hash(&word_list, hash_algorithm).unwrap();
+        let hash_values: BTreeSet<usize> = word_list.iter().map(|w| hash.as_fn.hash(w)).collect();
+        assert_eq!(hash_values, (0..word_list.len()).collect());
+
+        let word_list: WordList = ["ABC", "AXC"].into_iter().map(String::from).collect();
+        match CichelliAlgorithm::from_word_list(&word_list)
+            .unwrap_err()
+            .kind()
+        {
+            Kind::CichelliAlgorithmError(s) => {
+                assert_eq!(
+                    s,
+                    "Words ABC and AXC have the same length and end characters."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        assert!(CichelliAlgorithm::from_word_list(&WordList::new()).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+use crate::{Error, HashAlgorithm, MaybeSync};
+
+/// A hash algorithm made of user supplied closures.
+///
+/// Lets ad-hoc row and column functions be tried without writing a struct and
+/// implementing `HashAlgorithm` for it. The pseudo code of each closure has to
+/// be supplied along with it, since it cannot be derived from the closure.
+#[derive(Clone)]
+pub struct ClosureAlgorithm<F1, F2> {
+    /// The closure computing row indices.
+    h1: F1,
+
+    /// The closure computing column indices.
+    h2: F2,
+
+    /// The pseudo code of `h1`.
+    h1_text: String,
+
+    /// The pseudo code of `h2`.
+    h2_text: String,
+}
+
+impl<F1, F2> ClosureAlgorithm<F1, F2> {
+    /// Creates a new ClosureAlgorithm.
+    ///
+    /// # Parameters
+    /// * `h1` - The closure computing row indices.
+    /// * `h2` - The closure computing column indices.
+    /// * `h1_text` - The pseudo code of `h1`.
+    /// * `h2_text` - The pseudo code of `h2`.
+    #[must_use]
+    pub fn new(h1: F1, h2: F2, h1_text: impl Into<String>, h2_text: impl Into<String>) -> Self {
+        Self {
+            h1,
+            h2,
+            h1_text: h1_text.into(),
+            h2_text: h2_text.into(),
+        }
+    }
+}
+
+impl<F1, F2> fmt::Debug for ClosureAlgorithm<F1, F2> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClosureAlgorithm")
+            .field("h1_text", &self.h1_text)
+            .field("h2_text", &self.h2_text)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K: ?Sized, F1: Fn(&K) -> usize + MaybeSync, F2: Fn(&K) -> usize + MaybeSync> HashAlgorithm<K>
+    for ClosureAlgorithm<F1, F2>
+{
+    fn h1(&self, word: &K) -> Result<usize, Error> {
+        Ok((self.h1)(word))
+    }
+
+    fn h2(&self, word: &K) -> Result<usize, Error> {
+        Ok((self.h2)(word))
+    }
+
+    fn h1_as_text(&self) -> String {
+        self.h1_text.clone()
+    }
+
+    fn h2_as_text(&self) -> String {
+        self.h2_text.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "closure"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closure_algorithm_unit_test() {
+        let hash_algorithm = ClosureAlgorithm::new(
+            |word: &str| word.len(),
+            |word: &str| usize::from(word.as_bytes()[0]),
+            "len(word)",
+            "ord(word[0])",
+        );
+        assert_eq!(hash_algorithm.h1("ABC").unwrap(), 3);
+        assert_eq!(hash_algorithm.h2("ABC").unwrap(), 65);
+        assert_eq!(hash_algorithm.h1_as_text(), "len(word)");
+        assert_eq!(hash_algorithm.h2_as_text(), "ord(word[0])");
+        assert!(!hash_algorithm.folds_case());
+        assert_eq!(
+            format!("{hash_algorithm:?}"),
+            "ClosureAlgorithm { h1_text: \"len(word)\", h2_text: \"ord(word[0])\", .. }"
+        );
+
+        let byte_algorithm =
+            ClosureAlgorithm::new(|word: &[u8]| word.len(), |_: &[u8]| 0, "len(word)", "0");
+        assert_eq!(byte_algorithm.h1(&b"\xff\x00"[..]).unwrap(), 2);
+    }
+}
@@ -0,0 +1,1901 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::chm::MIX_AS_TEXT;
+use crate::hash_spec;
+use crate::key::compare_key;
+use crate::{Error, Expr, HashData, Kind, Position, Rlt, RltElementType, WordList};
+
+/// The target languages supported by the code generators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// The same pseudo code dialect used by `HashData::as_string`.
+    Pseudo,
+
+    /// C source code.
+    C,
+
+    /// Rust source code.
+    Rust,
+
+    /// Python source code.
+    Python,
+}
+
+impl Language {
+    /// The value emitted for a slot when the caller did not supply one.
+    ///
+    /// # Returns
+    /// A placeholder value literal.
+    fn placeholder(self) -> &'static str {
+        match self {
+            Language::Pseudo => "?",
+            Language::C | Language::Rust => "0",
+            Language::Python => "None",
+        }
+    }
+}
+
+/// The formats of the test vectors written by `test_vectors`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorFormat {
+    /// Comma separated values, with a `word,hash_value` header line.
+    Csv,
+
+    /// A JSON array of objects with a `word` and a `hash_value`.
+    Json,
+}
+
+/// The trade-off a generated hash function is tuned for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Smallest footprint, for firmware. Tables use the narrowest integer type
+    /// that holds their values, the hash is reduced with a modulo, the function
+    /// is not inlined and a match is confirmed with a single key comparison.
+    Size,
+
+    /// Fastest lookup, for hosts. Tables use native integers, the hash is
+    /// reduced with a mask when the table size is a power of two, the function
+    /// is inlined and a key length table rejects most misses before the key
+    /// comparison.
+    Speed,
+}
+
+/// The hash function emitted for every profile from a single generation.
+#[derive(Debug, Clone)]
+pub struct ProfileVariants {
+    /// The hash function emitted with `Profile::Size`.
+    pub size: String,
+
+    /// The hash function emitted with `Profile::Speed`.
+    pub speed: String,
+}
+
+/// Emits a hash function, including the confirmation of a match, as pseudo code
+/// tuned for a profile.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `profile` - The trade-off to tune the hash function for.
+///
+/// # Returns
+/// The hash function as pseudo code.
+///
+/// # Errors
+/// Will return `Err` if `hash_data` was not generated from `word_list`.
+pub fn hash_fn_text(
+    word_list: &WordList,
+    hash_data: &HashData,
+    profile: Profile,
+) -> Result<String, Error> {
+    check_word_list(word_list, hash_data)?;
+    let rlt = hash_data.as_fn.get_rlt();
+    let hash_algorithm = hash_data.as_fn.get_hash_algorithm();
+    let len = rlt.get_num_entries();
+    let fold_case = hash_algorithm.folds_case();
+    let keys: Vec<Cow<str>> = hash_data
+        .permutation
+        .words_in_hash_order()
+        .iter()
+        .map(|word_index| {
+            word_index.map_or(Cow::Borrowed(""), |w| {
+                compare_key(word_list[w].as_str(), fold_case)
+            })
+        })
+        .collect();
+    let key_table = keys
+        .iter()
+        .map(|key| quote(key))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let (rlt_type, inline, reduce, length_table, length_check) = match profile {
+        Profile::Size => {
+            let rlt_type = if rlt.get_chm().is_some() {
+                RltElementType::signed(0, isize::try_from(len).unwrap_or(isize::MAX))
+            } else {
+                rlt.element_type()
+            };
+            (
+                rlt_type.name(),
+                "",
+                rlt.get_reduce_as_text(),
+                String::new(),
+                "",
+            )
+        }
+        Profile::Speed => {
+            let lengths = keys
+                .iter()
+                .map(|key| key.len().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            (
+                if rlt.is_normalized() { "uint" } else { "int" },
+                "inline ",
+                if len.is_power_of_two() {
+                    format!("& {}", len - 1)
+                } else {
+                    format!("% {len}")
+                },
+                format!("key_length: int[{len}] = [{lengths}]\n"),
+                "    if len(word) != key_length[hash_value]:\n        not_found\n",
+            )
+        }
+    };
+
+    let (tables, hash_value) = tables_text(rlt, rlt_type, &reduce);
+
+    Ok(format!(
+        "{tables}\
+         {length_table}\
+         key_table = [{key_table}]\n\
+         max_word_len = {max_word_len}\n\
+         {inline}function hash(word):\n    \
+         if len(word) > max_word_len:\n        \
+         not_found\n    \
+         row_index = {h1}\n    \
+         col_index = {h2}\n\
+         {hash_value}\
+         {length_check}    \
+         if {key_word} != key_table[hash_value]:\n        \
+         not_found\n    \
+         return hash_value{add_base}\n",
+        max_word_len = hash_data.max_word_len,
+        add_base = match rlt.get_hash_base() {
+            0 => String::new(),
+            hash_base => format!(" + {hash_base}"),
+        },
+        h1 = hash_algorithm.h1_as_text(),
+        h2 = hash_algorithm.h2_as_text(),
+        key_word = if fold_case { "upper(word)" } else { "word" },
+    ))
+}
+
+/// Emits the tables of a hash function and the statement computing the hash
+/// value from them.
+///
+/// # Parameters
+/// * `rlt` - The row lookup table of the hash.
+/// * `rlt_type` - The integer type of the table values.
+/// * `reduce` - The operation reducing a sum into the range of hash values.
+///
+/// # Returns
+/// The tables, and the statement computing `hash_value` indented to the body
+/// of the hash function.
+fn tables_text(rlt: &Rlt, rlt_type: &str, reduce: &str) -> (String, String) {
+    if let Some(chm) = rlt.get_chm() {
+        (
+            format!(
+                "vertex_values: {rlt_type}[{}] = [{}]\n{MIX_AS_TEXT}",
+                chm.get_num_vertices(),
+                chm.get_values_as_text()
+            ),
+            format!(
+                "    {}\n",
+                chm.hash_as_text().trim_end().replace('\n', "\n    ")
+            ),
+        )
+    } else {
+        let (multiplier_table, scaled_col) = match rlt.get_multipliers_as_text() {
+            Some(multipliers) => (
+                format!(
+                    "row_multiplier: int[{rows}] = [{multipliers}]\n",
+                    rows = rlt.len()
+                ),
+                "row_multiplier[row_index] * col_index",
+            ),
+            None => (String::new(), "col_index"),
+        };
+        let (rows_table, rows_lookup) = match (rlt.get_rows(), rlt.get_rows_as_text()) {
+            (Some(rows), Some(rows_text)) => (
+                format!(
+                    "row_keys: {}[{}] = [{rows_text}]\n",
+                    RltElementType::unsigned(
+                        rows.last()
+                            .map_or(0, |row| isize::try_from(*row).unwrap_or(isize::MAX))
+                    )
+                    .name(),
+                    rows.len()
+                ),
+                "    row_index = binary_search(row_keys, row_index)\n    \
+                 if row_index == -1:\n        \
+                 not_found\n",
+            ),
+            _ => (String::new(), ""),
+        };
+        let (rlt_table, rlt_value) = match rlt.get_two_level() {
+            Some(two_level) => (
+                format!(
+                    "row_block: {block_type}[{blocks}] = [{first_level}]\n\
+                     row_lookup_table: {rlt_type}[{len}] = [{values}]\n",
+                    block_type = RltElementType::unsigned(
+                        isize::try_from(two_level.get_blocks().len()).unwrap_or(isize::MAX)
+                    )
+                    .name(),
+                    blocks = two_level.get_first_level().len(),
+                    first_level = two_level.get_first_level_as_text(),
+                    len = two_level.get_blocks().len(),
+                    values = two_level.get_blocks_as_text(),
+                ),
+                two_level.lookup_as_text(),
+            ),
+            None => (
+                format!(
+                    "row_lookup_table: {rlt_type}[{rows}] = [{rlt}]\n",
+                    rows = rlt.len(),
+                    rlt = rlt.get_as_text(),
+                ),
+                String::from("row_lookup_table[row_index]"),
+            ),
+        };
+        (
+            format!("{rows_table}{rlt_table}{multiplier_table}"),
+            format!(
+                "{rows_lookup}    \
+                 hash_value = ({rlt_value} + {scaled_col}) {reduce}\n"
+            ),
+        )
+    }
+}
+
+/// Emits the hash function for every profile from a single generation, so that
+/// each build can pick its preferred trade-off from one artifact.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+///
+/// # Returns
+/// The hash function emitted with each profile.
+///
+/// # Errors
+/// Will return `Err` if `hash_data` was not generated from `word_list`.
+pub fn hash_fn_variants(
+    word_list: &WordList,
+    hash_data: &HashData,
+) -> Result<ProfileVariants, Error> {
+    Ok(ProfileVariants {
+        size: hash_fn_text(word_list, hash_data, Profile::Size)?,
+        speed: hash_fn_text(word_list, hash_data, Profile::Speed)?,
+    })
+}
+
+/// Checks that a hash was generated from a word list, so that the word indices
+/// of its permutation can index the word list.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+///
+/// # Errors
+/// Will return `Err` if the hash was generated from a different number of
+/// words, or a hash value holds a word outside of the word list.
+pub(crate) fn check_word_list(word_list: &WordList, hash_data: &HashData) -> Result<(), Error> {
+    let permutation = &hash_data.permutation;
+    if permutation.num_words() != word_list.len() {
+        return Err(Error::new(Kind::CodegenError(format!(
+            "The hash was generated from {} words, the word list holds {}.",
+            permutation.num_words(),
+            word_list.len()
+        ))));
+    }
+    if let Some((hash_value, w)) = permutation
+        .words_in_hash_order()
+        .iter()
+        .enumerate()
+        .find_map(|(hash_value, w)| w.filter(|w| *w >= word_list.len()).map(|w| (hash_value, w)))
+    {
+        return Err(Error::new(Kind::CodegenError(format!(
+            "Hash value {hash_value} holds word {w}, outside of the word list of {} words.",
+            word_list.len()
+        ))));
+    }
+    Ok(())
+}
+
+/// Quotes text as a pseudo code string literal.
+///
+/// # Parameters
+/// * `text` - The text to quote.
+///
+/// # Returns
+/// The text in double quotes, with double quotes and backslashes escaped.
+pub(crate) fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Generates the skeleton of a value table laid out in hash order.
+///
+/// Each slot of the table is annotated with its hash value and the word that
+/// hashes to it, so that associated data can be filled in by hand without
+/// misaligning entries with hash slots. Slots no word hashes to, which only
+/// exist when the table was packed with slack, hold a placeholder. When the
+/// hash values start above 0, see `Options::hash_base`, the hash values below
+/// the base hold a placeholder too, so the table is indexed by the hash value
+/// itself.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `values` - Optional value literals, one per word in word list order. When
+///   `None` a placeholder is emitted for every slot.
+/// * `language` - The language to emit the table in.
+///
+/// # Returns
+/// The value table as source code text.
+///
+/// # Errors
+/// Will return `Err` if `hash_data` was not generated from `word_list`, or if
+/// the number of values does not match the number of words.
+pub fn value_table_scaffold(
+    word_list: &WordList,
+    hash_data: &HashData,
+    values: Option<&[String]>,
+    language: Language,
+) -> Result<String, Error> {
+    check_word_list(word_list, hash_data)?;
+    if let Some(values) = values {
+        if values.len() != word_list.len() {
+            return Err(Error::new(Kind::CodegenError(format!(
+                "Expected {} values, found {}.",
+                word_list.len(),
+                values.len()
+            ))));
+        }
+    }
+
+    let slots = hash_data.permutation.words_in_hash_order();
+    let hash_base = hash_data.as_fn.get_rlt().get_hash_base();
+    let len = hash_base + slots.len();
+
+    let mut rv = match language {
+        Language::Pseudo | Language::Python => "value_table = [\n".to_string(),
+        Language::C => format!("static const int value_table[{len}] = {{\n"),
+        Language::Rust => format!("static VALUE_TABLE: [i32; {len}] = [\n"),
+    };
+
+    let below_base =
+        (0..hash_base).map(|hash_value| (hash_value, "(below base)", language.placeholder()));
+    let in_range = slots.iter().enumerate().map(|(slot, word_index)| {
+        let (word, value) = match word_index {
+            Some(w) => (
+                word_list[*w].as_str(),
+                values.map_or(language.placeholder(), |v| v[*w].as_str()),
+            ),
+            None => ("(empty)", language.placeholder()),
+        };
+        (hash_base + slot, word, value)
+    });
+    for (hash_value, word, value) in below_base.chain(in_range) {
+        let line = match language {
+            Language::Pseudo | Language::Python => {
+                format!("    {value},  # {hash_value}: {word}\n")
+            }
+            Language::C => format!("    {value}, /* {hash_value}: {word} */\n"),
+            Language::Rust => format!("    {value}, // {hash_value}: {word}\n"),
+        };
+        rv.push_str(&line);
+    }
+
+    rv.push_str(match language {
+        Language::Pseudo | Language::Python => "]\n",
+        Language::C => "};\n",
+        Language::Rust => "];\n",
+    });
+
+    Ok(rv)
+}
+
+/// Generates a value table laid out in hash order from the value of each word,
+/// such as the token number of each keyword or the opcode of each mnemonic.
+///
+/// Emitted next to the hash function, the table maps each word to its value:
+/// the hash value of a word indexes its value. See `value_table_scaffold` for
+/// the layout.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `pairs` - Each word and its value literal, in any order.
+/// * `language` - The language to emit the table in.
+///
+/// # Returns
+/// The value table as source code text.
+///
+/// # Errors
+/// Will return `Err` if a word of the pairs is not in the word list or is
+/// given two values, or a word of the word list has no value.
+pub fn value_table_from_pairs<W: AsRef<str>, V: AsRef<str>>(
+    word_list: &WordList,
+    hash_data: &HashData,
+    pairs: &[(W, V)],
+    language: Language,
+) -> Result<String, Error> {
+    let mut value_of: BTreeMap<&str, &str> = BTreeMap::new();
+    for (word, value) in pairs {
+        let word = word.as_ref();
+        if !word_list.iter().any(|w| w == word) {
+            return Err(Error::new(Kind::CodegenError(format!(
+                "The word {word:?} is not in the word list."
+            ))));
+        }
+        if value_of.insert(word, value.as_ref()).is_some() {
+            return Err(Error::new(Kind::CodegenError(format!(
+                "The word {word:?} has two values."
+            ))));
+        }
+    }
+    let values = word_list
+        .iter()
+        .map(|word| {
+            value_of
+                .get(word.as_str())
+                .map(|value| (*value).to_string())
+                .ok_or_else(|| {
+                    Error::new(Kind::CodegenError(format!(
+                        "The word {word:?} has no value."
+                    )))
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    value_table_scaffold(word_list, hash_data, Some(&values), language)
+}
+
+/// Writes the hash value of every word as test vectors, one word per line in
+/// word list order. Diffing the vectors of two generations shows the words
+/// whose hash value changed, and a port of the hash function to another
+/// language can be checked against them.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `format` - The format to write the vectors in.
+///
+/// # Returns
+/// The test vectors.
+#[must_use]
+pub fn test_vectors(word_list: &WordList, hash_data: &HashData, format: VectorFormat) -> String {
+    let vectors = word_list
+        .iter()
+        .map(|word| (word.as_str(), (hash_data.as_closure.cls)(word)));
+    match format {
+        VectorFormat::Csv => {
+            let mut rv = "word,hash_value\n".to_string();
+            for (word, hash_value) in vectors {
+                let _ = writeln!(rv, "{},{hash_value}", csv_field(word));
+            }
+            rv
+        }
+        VectorFormat::Json => {
+            let lines = vectors
+                .map(|(word, hash_value)| {
+                    format!(
+                        "  {{\"word\": {}, \"hash_value\": {hash_value}}}",
+                        hash_spec::string(word)
+                    )
+                })
+                .collect::<Vec<_>>();
+            if lines.is_empty() {
+                "[]\n".to_string()
+            } else {
+                format!("[\n{}\n]\n", lines.join(",\n"))
+            }
+        }
+    }
+}
+
+/// Quotes a CSV field when it holds a comma, a quote or a line break, doubling
+/// its quotes.
+///
+/// # Parameters
+/// * `text` - The field.
+///
+/// # Returns
+/// The field as written to a CSV line.
+fn csv_field(text: &str) -> Cow<'_, str> {
+    if text.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Emits a Rust recognizer of the words: an enum with one variant per word,
+/// and a `from_str` that hashes a word, looks its hash value up in a static
+/// word table and confirms the match.
+///
+/// Variants are the words in camel case, `end_if` becomes `EndIf`. Words that
+/// do not start with a letter are prefixed with `Word`, and a word whose
+/// variant is already taken is suffixed with its index in the word list.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `enum_name` - The name of the enum, such as `Keyword`.
+///
+/// # Returns
+/// The enum and its `from_str` and `as_str` methods as Rust source code.
+///
+/// # Errors
+/// Will return `Err` if `hash_data` was not generated from `word_list`, the
+/// hash was built by the CHM backend, or its hash algorithm cannot describe h1
+/// and h2 as expressions, see `HashAlgorithm::h1_expr`.
+pub fn rust_recognizer(
+    word_list: &WordList,
+    hash_data: &HashData,
+    enum_name: &str,
+) -> Result<String, Error> {
+    check_word_list(word_list, hash_data)?;
+    let rlt = hash_data.as_fn.get_rlt();
+    let hash_algorithm = hash_data.as_fn.get_hash_algorithm();
+    let (h1, h2) = recognizer_exprs(hash_data)?;
+    let fold_case = hash_algorithm.folds_case();
+    let slots = hash_data.permutation.words_in_hash_order();
+    let variants = recognizer_names(word_list, slots, variant_name, &[]);
+    let words: Vec<(&str, &str)> = word_list
+        .iter()
+        .zip(&variants)
+        .filter_map(|(word, variant)| Some((word.as_str(), variant.as_deref()?)))
+        .collect();
+
+    let mut rv = format!(
+        "// Generated by msmp.\n\n\
+         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n\
+         pub enum {enum_name} {{\n"
+    );
+    for (word, variant) in &words {
+        let _ = writeln!(rv, "    /// {word:?}\n    {variant},");
+    }
+    rv.push_str("}\n\n");
+
+    let mut table = String::new();
+    for slot in slots {
+        match slot.and_then(|w| Some((w, variants[w].as_ref()?))) {
+            Some((w, variant)) => {
+                let _ = writeln!(
+                    table,
+                    "            Some(({}, {enum_name}::{variant})),",
+                    quote(&compare_key(word_list[w].as_str(), fold_case))
+                );
+            }
+            None => table.push_str("            None,\n"),
+        }
+    }
+    let mut lookups = Lookups::new();
+    let h1 = rust_expr(&h1, fold_case, false, &mut lookups);
+    let h2 = rust_expr(&h2, fold_case, false, &mut lookups);
+    let (row_tables, find_row, rlt_value, scaled_col) = rust_row_lookup(rlt);
+    let (compare, keys) = if fold_case {
+        ("key.eq_ignore_ascii_case(word)", "The uppercase words")
+    } else {
+        ("key == word", "The words")
+    };
+
+    let _ = write!(
+        rv,
+        "impl {enum_name} {{\n    \
+         /// Recognizes a word.\n    \
+         #[allow(clippy::should_implement_trait)]\n    \
+         pub fn from_str(word: &str) -> Option<{enum_name}> {{\n\
+         {row_tables}\
+         {lookup_fns}        \
+         // {keys} in hash value order.\n        \
+         static WORDS: [Option<(&str, {enum_name})>; {table_len}] = [\n\
+         {table}        \
+         ];\n\n        \
+         if word.len() > {max_word_len} {{\n            \
+         return None;\n        \
+         }}\n        \
+         let row_index: usize = {h1};\n        \
+         let col_index: usize = {h2};\n\
+         {find_row}        \
+         let sum = {rlt_value} as isize + {scaled_col} as isize;\n        \
+         let hash_value = usize::try_from(sum).ok()? {reduce};\n        \
+         match WORDS[hash_value] {{\n            \
+         Some((key, keyword)) if {compare} => Some(keyword),\n            \
+         _ => None,\n        \
+         }}\n    \
+         }}\n\n    \
+         /// Gets the word.\n    \
+         pub fn as_str(self) -> &'static str {{\n        \
+         match self {{\n",
+        lookup_fns = rust_lookup_fns(&lookups),
+        table_len = slots.len(),
+        max_word_len = hash_data.max_word_len,
+        reduce = rlt.get_reduce_as_text(),
+    );
+    for (word, variant) in &words {
+        let _ = writeln!(rv, "            {enum_name}::{variant} => {},", quote(word));
+    }
+    rv.push_str("        }\n    }\n}\n");
+    Ok(rv)
+}
+
+/// Emits a self-test of the recognizer of `rust_recognizer`, to be appended
+/// to it: a test module that recognizes every word of the word list and checks
+/// its variant, so a table that was edited by hand or miscompiled fails the
+/// consumer's test suite.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `enum_name` - The name of the enum given to `rust_recognizer`.
+///
+/// # Returns
+/// The `#[cfg(test)]` module as Rust source code.
+#[must_use]
+pub fn rust_recognizer_self_test(
+    word_list: &WordList,
+    hash_data: &HashData,
+    enum_name: &str,
+) -> String {
+    let slots = hash_data.permutation.words_in_hash_order();
+    let variants = recognizer_names(word_list, slots, variant_name, &[]);
+    let mut rv = format!(
+        "#[cfg(test)]\n\
+         mod {module}_self_test {{\n    \
+         use super::{enum_name};\n\n    \
+         #[test]\n    \
+         fn recognizes_every_word() {{\n        \
+         let words = [\n",
+        module = enum_name.to_lowercase(),
+    );
+    for (word, variant) in word_list.iter().zip(&variants) {
+        if let Some(variant) = variant {
+            let _ = writeln!(rv, "            ({}, {enum_name}::{variant}),", quote(word));
+        }
+    }
+    let _ = write!(
+        rv,
+        "        ];\n        \
+         for (word, variant) in words {{\n            \
+         assert_eq!({enum_name}::from_str(word), Some(variant), \"{{word}}\");\n            \
+         assert_eq!(variant.as_str(), word);\n        \
+         }}\n        \
+         assert_eq!({enum_name}::from_str(\"\"), None);\n    \
+         }}\n\
+         }}\n"
+    );
+    rv
+}
+
+/// Gets the expressions a recognizer computes h1 and h2 with.
+///
+/// # Parameters
+/// * `hash_data` - The hash to recognize the words of.
+///
+/// # Returns
+/// The expressions of h1 and h2.
+///
+/// # Errors
+/// Will return `Err` if the hash was built by the CHM backend, or its hash
+/// algorithm cannot describe h1 and h2 as expressions.
+fn recognizer_exprs(hash_data: &HashData) -> Result<(Expr, Expr), Error> {
+    let hash_algorithm = hash_data.as_fn.get_hash_algorithm();
+    if hash_data.as_fn.get_rlt().get_chm().is_some() {
+        return Err(Error::new(Kind::CodegenError(
+            "A recognizer cannot be emitted for the CHM backend.".to_string(),
+        )));
+    }
+    match (hash_algorithm.h1_expr(), hash_algorithm.h2_expr()) {
+        (Some(h1), Some(h2)) => Ok((h1, h2)),
+        _ => Err(Error::new(Kind::CodegenError(format!(
+            "The {} algorithm cannot describe h1 and h2 as expressions.",
+            hash_algorithm.name()
+        )))),
+    }
+}
+
+/// Names the enum variant, or constant, of each word hashed by a recognizer.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `slots` - The word index of each hash value.
+/// * `name_of` - Names a word, a name already taken is suffixed with the index
+///   of the word.
+/// * `reserved` - Names that are taken by the recognizer itself.
+///
+/// # Returns
+/// The name of each word, in word list order. None for the words no hash
+/// value holds, which are duplicates.
+fn recognizer_names(
+    word_list: &WordList,
+    slots: &[Option<usize>],
+    name_of: fn(&str) -> String,
+    reserved: &[&str],
+) -> Vec<Option<String>> {
+    let placed: BTreeSet<usize> = slots.iter().flatten().copied().collect();
+    let mut taken: BTreeSet<String> = reserved.iter().map(|name| (*name).to_string()).collect();
+    word_list
+        .iter()
+        .enumerate()
+        .map(|(w, word)| {
+            if !placed.contains(&w) {
+                return None;
+            }
+            let mut name = name_of(word);
+            if !taken.insert(name.clone()) {
+                name.push_str(&w.to_string());
+                taken.insert(name.clone());
+            }
+            Some(name)
+        })
+        .collect()
+}
+
+/// Emits the tables a Rust recognizer reads the value of a row from.
+///
+/// # Parameters
+/// * `rlt` - The row lookup table of the hash.
+///
+/// # Returns
+/// The static tables, the statement finding a sparse row, the expression
+/// reading the value of the row and the expression scaling `col_index`.
+fn rust_row_lookup(rlt: &Rlt) -> (String, &'static str, String, String) {
+    let mut tables = format!(
+        "        static ROW_LOOKUP_TABLE: [{}; {}] = [{}];\n",
+        rlt.element_type().name(),
+        rlt.len(),
+        join(rlt.iter())
+    );
+    let (find_row, row) = match rlt.get_rows() {
+        Some(rows) => {
+            let _ = writeln!(
+                tables,
+                "        static ROW_KEYS: [usize; {}] = [{}];",
+                rows.len(),
+                join(rows)
+            );
+            (
+                "        let row = ROW_KEYS.binary_search(&row_index).ok()?;\n",
+                "row",
+            )
+        }
+        None => ("", "row_index"),
+    };
+    let scaled_col = match rlt.get_multipliers() {
+        Some(multipliers) => {
+            let _ = writeln!(
+                tables,
+                "        static ROW_MULTIPLIER: [usize; {}] = [{}];",
+                multipliers.len(),
+                join(multipliers)
+            );
+            format!(
+                "(col_index % {} * ROW_MULTIPLIER[{row}])",
+                rlt.get_num_entries()
+            )
+        }
+        None => "col_index".to_string(),
+    };
+    (
+        tables,
+        find_row,
+        format!("*ROW_LOOKUP_TABLE.get({row})?"),
+        scaled_col,
+    )
+}
+
+/// Emits the function of each lookup of a Rust recognizer, see `rust_expr`.
+///
+/// # Parameters
+/// * `lookups` - The lookups.
+///
+/// # Returns
+/// The functions, `lookup_value{i}` for the lookup at index `i`.
+fn rust_lookup_fns(lookups: &Lookups) -> String {
+    let mut rv = String::new();
+    for (i, (keys, values)) in lookups.iter().enumerate() {
+        let _ = write!(
+            rv,
+            "        fn lookup_value{i}(c: char) -> Option<usize> {{\n            \
+             Some(match c {{\n"
+        );
+        for (key, value) in keys.iter().zip(*values) {
+            let _ = writeln!(rv, "                {key:?} => {value},");
+        }
+        rv.push_str(
+            "                _ => return None,\n            \
+             })\n        \
+             }\n",
+        );
+    }
+    rv
+}
+
+/// Gets the enum variant of a word, see `rust_recognizer`.
+///
+/// # Parameters
+/// * `word` - The word.
+///
+/// # Returns
+/// The word in camel case.
+fn variant_name(word: &str) -> String {
+    let mut variant: String = word
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+            })
+        })
+        .collect();
+    if !variant.starts_with(|c: char| c.is_ascii_alphabetic()) || variant == "Self" {
+        variant.insert_str(0, "Word");
+    }
+    variant
+}
+
+/// The lookups of the expressions of h1 and h2, each emitted as a function
+/// from a character to its value.
+type Lookups<'a> = Vec<(&'a [char], &'a [usize])>;
+
+/// Translates an expression of h1 or h2 to Rust, evaluated for `word: &str`
+/// in a function returning an `Option`.
+///
+/// # Parameters
+/// * `expr` - The expression.
+/// * `fold_case` - Whether the characters of the word are converted to
+///   uppercase.
+/// * `operand` - Whether the expression is the operand of an operation, which
+///   puts operations in parentheses.
+/// * `lookups` - The lookups emitted so far. Lookups the expression needs are
+///   added, `lookup_value{i}` is the function of the lookup at index `i`.
+///
+/// # Returns
+/// The expression as Rust source code, evaluating to a `usize`.
+fn rust_expr<'a>(
+    expr: &'a Expr,
+    fold_case: bool,
+    operand: bool,
+    lookups: &mut Lookups<'a>,
+) -> String {
+    let parenthesize = |text: String| {
+        if operand {
+            format!("({text})")
+        } else {
+            text
+        }
+    };
+    match expr {
+        Expr::Const(value) => value.to_string(),
+        Expr::Len => "word.chars().count()".to_string(),
+        Expr::Char(position) => parenthesize(format!(
+            "u32::from({}) as usize",
+            rust_char(position, fold_case)
+        )),
+        Expr::Lookup { keys, values, of } => {
+            let lookup = (keys.as_slice(), values.as_slice());
+            let index = lookups
+                .iter()
+                .position(|known| *known == lookup)
+                .unwrap_or_else(|| {
+                    lookups.push(lookup);
+                    lookups.len() - 1
+                });
+            let c = match of.as_ref() {
+                Expr::Char(position) => rust_char(position, fold_case),
+                of => format!(
+                    "char::from_u32(u32::try_from({}).ok()?)?",
+                    rust_expr(of, fold_case, false, lookups)
+                ),
+            };
+            format!("lookup_value{index}({c})?")
+        }
+        Expr::Add(a, b) => parenthesize(format!(
+            "{} + {}",
+            rust_expr(a, fold_case, true, lookups),
+            rust_expr(b, fold_case, true, lookups)
+        )),
+        Expr::Mul(a, b) => parenthesize(format!(
+            "{} * {}",
+            rust_expr(a, fold_case, true, lookups),
+            rust_expr(b, fold_case, true, lookups)
+        )),
+        Expr::Sub(a, b) => {
+            let minuend = match a.as_ref() {
+                Expr::Const(value) => format!("{value}_usize"),
+                a => rust_expr(a, fold_case, true, lookups),
+            };
+            format!(
+                "{minuend}.checked_sub({})?",
+                rust_expr(b, fold_case, false, lookups)
+            )
+        }
+    }
+}
+
+/// Translates the character at a position of the word to Rust.
+///
+/// # Parameters
+/// * `position` - The position of the character.
+/// * `fold_case` - Whether the character is converted to uppercase.
+///
+/// # Returns
+/// An expression evaluating to the `char`, returning None if the word is too
+/// short.
+fn rust_char(position: &Position, fold_case: bool) -> String {
+    let c = match position {
+        Position::Start(0) => "word.chars().next()?".to_string(),
+        Position::Start(i) => format!("word.chars().nth({i})?"),
+        Position::End(0) => "word.chars().next_back()?".to_string(),
+        Position::End(i) => format!("word.chars().nth_back({i})?"),
+    };
+    if fold_case {
+        c + ".to_ascii_uppercase()"
+    } else {
+        c
+    }
+}
+
+/// Emits a C recognizer of the words, in the shape of gperf's output: a token
+/// constant `#define`d for each word, and a function that hashes a word,
+/// `switch`es on the hash value and confirms the match with `strcmp`.
+///
+/// The constants are the prefix and the word in uppercase, with every run of
+/// other characters than letters and digits replaced by `_`, so `end-if`
+/// becomes `TOKEN_END_IF` with the prefix `token`. A constant already taken is
+/// suffixed with the index of the word. The constants count up from 1 in word
+/// list order, and `TOKEN_NONE`, 0, is returned for every other word.
+///
+/// Only the constants and the function carry the prefix, the tables are named
+/// `row_lookup_table` and so on. Two recognizers emitted with `c_recognizer`
+/// therefore collide in one file, use `c_recognizers` to emit them together.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `prefix` - The prefix of the constants and of the function, such as
+///   `token`, which gives `TOKEN_BEGIN` and `int token_recognize(const char *word)`.
+///
+/// # Returns
+/// The constants and the function as C source code.
+///
+/// # Errors
+/// Will return `Err` if `hash_data` was not generated from `word_list`, a word
+/// is not ASCII, the hash was built by the CHM backend, or its hash algorithm
+/// cannot describe h1 and h2 as expressions, see `HashAlgorithm::h1_expr`.
+pub fn c_recognizer(
+    word_list: &WordList,
+    hash_data: &HashData,
+    prefix: &str,
+) -> Result<String, Error> {
+    emit_c_recognizers(&[(prefix, word_list, hash_data)], false)
+}
+
+/// Emits the C recognizers of several word lists into one file, such as the
+/// statements, functions and operators of a language, each recognizer named
+/// after its prefix: `STMT_` constants and `stmt_recognize` with the prefix
+/// `stmt`. See `c_recognizer` for each recognizer.
+///
+/// The includes and the helper functions, such as the lookups of the
+/// characters and the comparison ignoring case, are emitted once and shared.
+/// Unlike `c_recognizer`, the tables of each recognizer are prefixed too,
+/// `stmt_row_lookup_table`.
+///
+/// Only C recognizers can be emitted together, and only through the library:
+/// `msmp generate` emits the recognizer of one word list at a time. There is no
+/// Rust counterpart, since `rust_recognizer` keeps its tables inside `from_str`,
+/// so the enums of several word lists can be appended to one file as long as
+/// their names differ.
+///
+/// # Parameters
+/// * `recognizers` - The prefix, the word list and the hash generated from
+///   the word list of each recognizer.
+///
+/// # Returns
+/// The constants, tables and functions of every recognizer as C source code.
+///
+/// # Errors
+/// Will return `Err` if two recognizers have the same prefix, or a recognizer
+/// cannot be emitted, see `c_recognizer`.
+pub fn c_recognizers(recognizers: &[(&str, &WordList, &HashData)]) -> Result<String, Error> {
+    let mut prefixes = BTreeSet::new();
+    if let Some((prefix, _, _)) = recognizers
+        .iter()
+        .find(|(prefix, _, _)| !prefixes.insert(prefix.to_ascii_uppercase()))
+    {
+        return Err(Error::new(Kind::CodegenError(format!(
+            "The prefix {prefix:?} names two recognizers."
+        ))));
+    }
+    emit_c_recognizers(recognizers, true)
+}
+
+/// Emits C recognizers into one file, see `c_recognizers`.
+///
+/// # Parameters
+/// * `recognizers` - The prefix, the word list and the hash of each
+///   recognizer.
+/// * `prefix_tables` - Whether the tables are prefixed like the functions, so
+///   that the tables of several recognizers do not collide.
+///
+/// # Returns
+/// The recognizers as C source code.
+///
+/// # Errors
+/// Will return `Err` if a recognizer cannot be emitted, see `c_recognizer`.
+fn emit_c_recognizers(
+    recognizers: &[(&str, &WordList, &HashData)],
+    prefix_tables: bool,
+) -> Result<String, Error> {
+    let mut exprs = Vec::with_capacity(recognizers.len());
+    for (_, word_list, hash_data) in recognizers {
+        check_word_list(word_list, hash_data)?;
+        if let Some(word) = word_list.iter().find(|word| !word.is_ascii()) {
+            return Err(Error::new(Kind::CodegenError(format!(
+                "A C recognizer needs ASCII words, found {word:?}."
+            ))));
+        }
+        exprs.push(recognizer_exprs(hash_data)?);
+    }
+    let fold_case = recognizers
+        .iter()
+        .any(|(_, _, hash_data)| hash_data.as_fn.get_hash_algorithm().folds_case());
+
+    let mut lookups = Lookups::new();
+    let mut declarations = Vec::with_capacity(recognizers.len());
+    let mut functions = String::new();
+    for ((prefix, word_list, hash_data), (h1, h2)) in recognizers.iter().zip(&exprs) {
+        let table_prefix = if prefix_tables {
+            format!("{prefix}_")
+        } else {
+            String::new()
+        };
+        let (declaration, function) = c_recognizer_parts(
+            word_list,
+            hash_data,
+            prefix,
+            &table_prefix,
+            (h1, h2),
+            &mut lookups,
+        );
+        declarations.push(declaration);
+        functions.push_str(&function);
+    }
+
+    Ok(format!(
+        "/* Generated by msmp. */\n\n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\
+         #include <string.h>\n\
+         {ctype}\n\
+         {declarations}\
+         {helpers}\
+         {functions}",
+        ctype = if fold_case {
+            "#include <ctype.h>\n"
+        } else {
+            ""
+        },
+        declarations = declarations.join("\n"),
+        helpers = c_helper_fns(&lookups, fold_case),
+    ))
+}
+
+/// Emits the parts of a C recognizer, see `c_recognizer`.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `prefix` - The prefix of the constants and of the function.
+/// * `table_prefix` - The prefix of the tables, empty or `prefix_`.
+/// * `(h1, h2)` - The expressions of h1 and h2, see `recognizer_exprs`.
+/// * `lookups` - The lookups emitted so far, see `c_expr`.
+///
+/// # Returns
+/// The constants and tables, which precede the helper functions, and the
+/// function recognizing the words, which follows them.
+fn c_recognizer_parts<'a>(
+    word_list: &WordList,
+    hash_data: &HashData,
+    prefix: &str,
+    table_prefix: &str,
+    (h1, h2): (&'a Expr, &'a Expr),
+    lookups: &mut Lookups<'a>,
+) -> (String, String) {
+    let rlt = hash_data.as_fn.get_rlt();
+    let fold_case = hash_data.as_fn.get_hash_algorithm().folds_case();
+    let slots = hash_data.permutation.words_in_hash_order();
+    let upper_prefix = prefix.to_ascii_uppercase();
+    let constants = recognizer_names(word_list, slots, constant_name, &["NONE"]);
+
+    let mut declaration = format!("#define {upper_prefix}_NONE 0\n");
+    for (token, constant) in constants.iter().flatten().enumerate() {
+        let _ = writeln!(
+            declaration,
+            "#define {upper_prefix}_{constant} {}",
+            token + 1
+        );
+    }
+
+    let h1_text = c_expr(h1, fold_case, lookups);
+    let h2_text = c_expr(h2, fold_case, lookups);
+    let none = format!("{upper_prefix}_NONE");
+    let (row_tables, find_row) = c_row_lookup(rlt, table_prefix, &none);
+    let _ = write!(declaration, "\n{row_tables}");
+    let scaled_col = match rlt.get_multipliers() {
+        Some(_) => format!(
+            "col_index % {} * {table_prefix}row_multiplier[row_index]",
+            rlt.get_num_entries()
+        ),
+        None => "col_index".to_string(),
+    };
+
+    let mut function = format!(
+        "\nint {prefix}_recognize(const char *word)\n{{\n    \
+         size_t len = strlen(word);\n    \
+         long row_index, col_index, sum;\n\n    \
+         if (len < {min_len} || len > {max_word_len})\n        \
+         return {none};\n    \
+         row_index = {h1_text};\n    \
+         col_index = {h2_text};\n\
+         {find_row}    \
+         sum = {table_prefix}row_lookup_table[row_index] + {scaled_col};\n    \
+         if (sum < 0)\n        \
+         return {none};\n\n    \
+         switch (sum {reduce}) {{\n",
+        min_len = c_min_len(h1).max(c_min_len(h2)),
+        max_word_len = hash_data.max_word_len,
+        reduce = rlt.get_reduce_as_text(),
+    );
+    for (hash_value, slot) in slots.iter().enumerate() {
+        let Some((w, constant)) = slot.and_then(|w| Some((w, constants[w].as_ref()?))) else {
+            continue;
+        };
+        let key = quote(&compare_key(word_list[w].as_str(), fold_case));
+        let compare = if fold_case {
+            format!("equal_fold(word, {key})")
+        } else {
+            format!("strcmp(word, {key}) == 0")
+        };
+        let _ = write!(
+            function,
+            "    case {hash_value}:\n        \
+             return {compare} ? {upper_prefix}_{constant} : {none};\n"
+        );
+    }
+    let _ = write!(
+        function,
+        "    default:\n        \
+         return {none};\n    \
+         }}\n\
+         }}\n"
+    );
+    (declaration, function)
+}
+
+/// Emits a self-test of the recognizer of `c_recognizer`, to be appended to
+/// it: a function, compiled when `MSMP_SELF_TEST` is defined, that recognizes
+/// every word of the word list and counts the words not given their constant,
+/// so a table that was edited by hand or miscompiled is caught by the
+/// consumer's test suite.
+///
+/// # Parameters
+/// * `word_list` - The word list the hash was generated from.
+/// * `hash_data` - The hash generated from `word_list`.
+/// * `prefix` - The prefix given to `c_recognizer`, such as `token`, which
+///   gives `int token_self_test(void)`.
+///
+/// # Returns
+/// The `#ifdef MSMP_SELF_TEST` section as C source code.
+#[must_use]
+pub fn c_recognizer_self_test(word_list: &WordList, hash_data: &HashData, prefix: &str) -> String {
+    let slots = hash_data.permutation.words_in_hash_order();
+    let upper_prefix = prefix.to_ascii_uppercase();
+    let constants = recognizer_names(word_list, slots, constant_name, &["NONE"]);
+    let mut rv = format!(
+        "#ifdef MSMP_SELF_TEST\n\
+         /* Returns the number of words {prefix}_recognize gets wrong, 0 when the\n \
+         * tables are intact. */\n\
+         int {prefix}_self_test(void)\n\
+         {{\n    \
+         int failures = 0;\n\n"
+    );
+    for (word, constant) in word_list.iter().zip(&constants) {
+        if let Some(constant) = constant {
+            let _ = writeln!(
+                rv,
+                "    failures += {prefix}_recognize({}) != {upper_prefix}_{constant};",
+                quote(word)
+            );
+        }
+    }
+    let _ = write!(
+        rv,
+        "    failures += {prefix}_recognize(\"\") != {upper_prefix}_NONE;\n    \
+         return failures;\n\
+         }}\n\
+         #endif /* MSMP_SELF_TEST */\n"
+    );
+    rv
+}
+
+/// Emits the functions a C recognizer calls: the function of each lookup, see
+/// `c_expr`, and the comparison of a word with a key in uppercase.
+///
+/// # Parameters
+/// * `lookups` - The lookups.
+/// * `fold_case` - Whether the words are compared ignoring case.
+///
+/// # Returns
+/// The functions, `lookup_value{i}` for the lookup at index `i` and
+/// `equal_fold` when case is folded.
+fn c_helper_fns(lookups: &Lookups, fold_case: bool) -> String {
+    let mut rv = String::new();
+    for (i, (keys, values)) in lookups.iter().enumerate() {
+        let _ = write!(
+            rv,
+            "\nstatic long lookup_value{i}(long c)\n{{\n    \
+             switch (c) {{\n"
+        );
+        for (key, value) in keys.iter().zip(*values) {
+            let _ = writeln!(rv, "    case {}: return {value};", c_char_literal(*key));
+        }
+        rv.push_str("    default: return -1;\n    }\n}\n");
+    }
+    if fold_case {
+        rv.push_str(
+            "\nstatic int equal_fold(const char *word, const char *key)\n{\n    \
+             while (*word != '\\0' && toupper((unsigned char)*word) == *key) {\n        \
+             word++;\n        \
+             key++;\n    \
+             }\n    \
+             return *word == '\\0' && *key == '\\0';\n\
+             }\n",
+        );
+    }
+    rv
+}
+
+/// Gets the constant of a word, see `c_recognizer`.
+///
+/// # Parameters
+/// * `word` - The word.
+///
+/// # Returns
+/// The word in uppercase with other characters than letters and digits
+/// replaced by `_`, or `WORD` if it has no letters or digits.
+fn constant_name(word: &str) -> String {
+    let constant = word
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_ascii_uppercase)
+        .collect::<Vec<_>>()
+        .join("_");
+    if constant.is_empty() {
+        "WORD".to_string()
+    } else {
+        constant
+    }
+}
+
+/// Emits the tables a C recognizer reads the value of a row from.
+///
+/// # Parameters
+/// * `rlt` - The row lookup table of the hash.
+/// * `table_prefix` - The prefix of the names of the tables and functions.
+/// * `none` - The constant returned for words that are not recognized.
+///
+/// # Returns
+/// The static tables and functions, and the statements replacing
+/// `row_index` by the index of its entry, or rejecting it when out of range.
+fn c_row_lookup(rlt: &Rlt, table_prefix: &str, none: &str) -> (String, String) {
+    let mut tables = format!(
+        "static const {} {table_prefix}row_lookup_table[{}] = {{{}}};\n",
+        rlt.element_type().c_name(),
+        rlt.len(),
+        join(rlt.iter())
+    );
+    if let Some(multipliers) = rlt.get_multipliers() {
+        let _ = writeln!(
+            tables,
+            "static const long {table_prefix}row_multiplier[{}] = {{{}}};",
+            multipliers.len(),
+            join(multipliers)
+        );
+    }
+    match rlt.get_rows() {
+        Some(rows) => {
+            let _ = write!(
+                tables,
+                "static const size_t {table_prefix}row_keys[{len}] = {{{rows}}};\n\n\
+                 static long {table_prefix}find_row(long row_index)\n{{\n    \
+                 size_t low = 0, high = {len};\n\n    \
+                 while (low < high) {{\n        \
+                 size_t mid = low + (high - low) / 2;\n        \
+                 if ((long){table_prefix}row_keys[mid] < row_index)\n            \
+                 low = mid + 1;\n        \
+                 else if ((long){table_prefix}row_keys[mid] > row_index)\n            \
+                 high = mid;\n        \
+                 else\n            \
+                 return (long)mid;\n    \
+                 }}\n    \
+                 return -1;\n\
+                 }}\n",
+                len = rows.len(),
+                rows = join(rows)
+            );
+            (
+                tables,
+                format!(
+                    "    row_index = {table_prefix}find_row(row_index);\n    \
+                     if (row_index < 0)\n        \
+                     return {none};\n"
+                ),
+            )
+        }
+        None => (
+            tables,
+            format!(
+                "    if (row_index < 0 || row_index >= {})\n        \
+                 return {none};\n",
+                rlt.len()
+            ),
+        ),
+    }
+}
+
+/// Translates an expression of h1 or h2 to C, evaluated for `word`, a string
+/// of `len` characters at least as long as `c_min_len` gives.
+///
+/// Unlike `Expr::eval` the translation does not stop at values out of range,
+/// a lookup of a missing character gives -1, since the recognizer rejects the
+/// hash values of such words when it compares the word.
+///
+/// # Parameters
+/// * `expr` - The expression.
+/// * `fold_case` - Whether the characters of the word are converted to
+///   uppercase.
+/// * `lookups` - The lookups emitted so far, see `rust_expr`.
+///
+/// # Returns
+/// The expression as C source code, evaluating to a `long`.
+fn c_expr<'a>(expr: &'a Expr, fold_case: bool, lookups: &mut Lookups<'a>) -> String {
+    match expr {
+        Expr::Const(value) => value.to_string(),
+        Expr::Len => "(long)len".to_string(),
+        Expr::Char(position) => {
+            let c = match position {
+                Position::Start(i) => format!("(unsigned char)word[{i}]"),
+                Position::End(0) => "(unsigned char)word[len - 1]".to_string(),
+                Position::End(i) => format!("(unsigned char)word[len - {}]", i + 1),
+            };
+            if fold_case {
+                format!("(long)toupper({c})")
+            } else {
+                format!("(long){c}")
+            }
+        }
+        Expr::Lookup { keys, values, of } => {
+            let lookup = (keys.as_slice(), values.as_slice());
+            let index = lookups
+                .iter()
+                .position(|known| *known == lookup)
+                .unwrap_or_else(|| {
+                    lookups.push(lookup);
+                    lookups.len() - 1
+                });
+            format!("lookup_value{index}({})", c_expr(of, fold_case, lookups))
+        }
+        Expr::Add(a, b) => format!(
+            "({} + {})",
+            c_expr(a, fold_case, lookups),
+            c_expr(b, fold_case, lookups)
+        ),
+        Expr::Sub(a, b) => format!(
+            "({} - {})",
+            c_expr(a, fold_case, lookups),
+            c_expr(b, fold_case, lookups)
+        ),
+        Expr::Mul(a, b) => format!(
+            "({} * {})",
+            c_expr(a, fold_case, lookups),
+            c_expr(b, fold_case, lookups)
+        ),
+    }
+}
+
+/// Finds the length a word needs for every character of an expression.
+///
+/// # Parameters
+/// * `expr` - The expression.
+///
+/// # Returns
+/// One more than the furthest position read from either end, or 0.
+fn c_min_len(expr: &Expr) -> usize {
+    match expr {
+        Expr::Const(_) | Expr::Len => 0,
+        Expr::Char(Position::Start(i) | Position::End(i)) => i + 1,
+        Expr::Lookup { of, .. } => c_min_len(of),
+        Expr::Add(a, b) | Expr::Sub(a, b) | Expr::Mul(a, b) => c_min_len(a).max(c_min_len(b)),
+    }
+}
+
+/// Writes a character as a C character constant.
+///
+/// # Parameters
+/// * `c` - The character.
+///
+/// # Returns
+/// The character in single quotes when it is printable ASCII, otherwise its
+/// code.
+fn c_char_literal(c: char) -> String {
+    match c {
+        '\'' | '\\' => format!("'\\{c}'"),
+        c if c.is_ascii_graphic() || c == ' ' => format!("'{c}'"),
+        c => u32::from(c).to_string(),
+    }
+}
+
+/// Joins values into a comma separated string.
+///
+/// # Parameters
+/// * `values` - The values to join.
+///
+/// # Returns
+/// The values as a string.
+fn join<T: ToString>(values: impl IntoIterator<Item = T>) -> String {
+    values
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_hash, generate_hash_with_options, ElcAlgorithm, Options};
+
+    #[test]
+    fn codegen_unit_test() {
+        let word_list: WordList = ["AND", "BEGIN", "CHAR", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_data = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+
+        let text = value_table_scaffold(&word_list, &hash_data, None, Language::Python).unwrap();
+        println!("{text}");
+        assert!(text.starts_with("value_table = [\n"));
+        assert_eq!(text.lines().count(), 6);
+        for word in &word_list {
+            let slot = (hash_data.as_closure.cls)(word);
+            assert!(text.contains(&format!("    None,  # {slot}: {word}\n")));
+        }
+
+        let values: Vec<String> = ["1", "2", "3", "4"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let text =
+            value_table_scaffold(&word_list, &hash_data, Some(&values), Language::C).unwrap();
+        assert!(text.starts_with("static const int value_table[4] = {\n"));
+        assert!(text.ends_with("};\n"));
+        let slot = (hash_data.as_closure.cls)("CHAR");
+        assert!(text.contains(&format!("    3, /* {slot}: CHAR */\n")));
+
+        let text =
+            value_table_scaffold(&word_list, &hash_data, Some(&values), Language::Rust).unwrap();
+        assert!(text.starts_with("static VALUE_TABLE: [i32; 4] = [\n"));
+        assert!(text.contains(&format!("    3, // {slot}: CHAR\n")));
+
+        let text = value_table_scaffold(&word_list, &hash_data, None, Language::Pseudo).unwrap();
+        assert!(text.contains(&format!("    ?,  # {slot}: CHAR\n")));
+
+        let options = Options::new().hash_base(1);
+        let one_based =
+            generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+        let text =
+            value_table_scaffold(&word_list, &one_based, Some(&values), Language::C).unwrap();
+        assert!(
+            text.starts_with("static const int value_table[5] = {\n    0, /* 0: (below base) */\n")
+        );
+        assert!(text.contains(&format!("    3, /* {}: CHAR */\n", slot + 1)));
+
+        match value_table_scaffold(&word_list, &hash_data, Some(&values[1..]), Language::C) {
+            Ok(_) => panic!("Value count mismatch not detected."),
+            Err(e) => match e.kind() {
+                Kind::CodegenError(s) => assert_eq!(s, "Expected 4 values, found 3."),
+                _ => panic!("Unexpected error type"),
+            },
+        }
+    }
+
+    #[test]
+    fn codegen_word_list_mismatch_unit_test() {
+        let word_list: WordList = ["AND", "BEGIN", "CHAR", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_data = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+        let other: WordList = ["AND", "BEGIN"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+
+        let expected = "The hash was generated from 4 words, the word list holds 2.";
+        for result in [
+            value_table_scaffold(&other, &hash_data, None, Language::C),
+            hash_fn_text(&other, &hash_data, Profile::Size),
+            rust_recognizer(&other, &hash_data, "Keyword"),
+            c_recognizer(&other, &hash_data, "token"),
+            c_recognizers(&[("token", &other, &hash_data)]),
+        ] {
+            match result {
+                Ok(_) => panic!("Word list mismatch not detected."),
+                Err(e) => match e.kind() {
+                    Kind::CodegenError(s) => assert_eq!(s, expected),
+                    _ => panic!("Unexpected error type"),
+                },
+            }
+        }
+        assert!(hash_fn_text(&word_list, &hash_data, Profile::Size).is_ok());
+    }
+
+    #[test]
+    fn value_table_from_pairs_unit_test() {
+        let word_list: WordList = ["AND", "BEGIN", "CHAR", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_data = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+
+        let pairs = [
+            ("END", "13"),
+            ("AND", "10"),
+            ("CHAR", "12"),
+            ("BEGIN", "11"),
+        ];
+        let text = value_table_from_pairs(&word_list, &hash_data, &pairs, Language::C).unwrap();
+        println!("{text}");
+        for (word, value) in pairs {
+            let slot = (hash_data.as_closure.cls)(word);
+            assert!(text.contains(&format!("    {value}, /* {slot}: {word} */\n")));
+        }
+
+        for (pairs, message) in [
+            (
+                &[("AND", "1"), ("BEGIN", "2"), ("CHAR", "3"), ("ELSE", "4")][..],
+                "The word \"ELSE\" is not in the word list.",
+            ),
+            (
+                &[("AND", "1"), ("BEGIN", "2"), ("AND", "3")][..],
+                "The word \"AND\" has two values.",
+            ),
+            (
+                &[("AND", "1"), ("BEGIN", "2"), ("CHAR", "3")][..],
+                "The word \"END\" has no value.",
+            ),
+        ] {
+            match value_table_from_pairs(&word_list, &hash_data, pairs, Language::Rust) {
+                Ok(_) => panic!("{message}"),
+                Err(e) => assert_eq!(e.to_string(), format!("Codegen Error: {message}")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_vectors_unit_test() {
+        assert_eq!(csv_field("END"), "END");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+
+        let word_list: WordList = ["END", "AND", "BEGIN"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_data = generate_hash_with_options(
+            &word_list,
+            ElcAlgorithm::default(),
+            &Options::new().hash_base(1),
+        )
+        .unwrap();
+        let hash = |word: &str| (hash_data.as_closure.cls)(word);
+
+        let csv = test_vectors(&word_list, &hash_data, VectorFormat::Csv);
+        println!("{csv}");
+        assert_eq!(
+            csv,
+            format!(
+                "word,hash_value\nEND,{}\nAND,{}\nBEGIN,{}\n",
+                hash("END"),
+                hash("AND"),
+                hash("BEGIN")
+            )
+        );
+        assert!(["END", "AND", "BEGIN"].iter().all(|word| hash(word) >= 1));
+
+        let json = test_vectors(&word_list, &hash_data, VectorFormat::Json);
+        println!("{json}");
+        assert_eq!(
+            json,
+            format!(
+                "[\n  {{\"word\": \"END\", \"hash_value\": {}}},\n  \
+                 {{\"word\": \"AND\", \"hash_value\": {}}},\n  \
+                 {{\"word\": \"BEGIN\", \"hash_value\": {}}}\n]\n",
+                hash("END"),
+                hash("AND"),
+                hash("BEGIN")
+            )
+        );
+        assert_eq!(
+            test_vectors(&WordList::new(), &hash_data, VectorFormat::Json),
+            "[]\n"
+        );
+    }
+
+    #[test]
+    fn codegen_profile_unit_test() {
+        let word_list: WordList = ["AND", "BEGIN", "CHAR", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_data = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+
+        let variants = hash_fn_variants(&word_list, &hash_data).unwrap();
+        println!("{}\n{}", variants.size, variants.speed);
+        assert_eq!(
+            variants.size,
+            hash_fn_text(&word_list, &hash_data, Profile::Size).unwrap()
+        );
+        assert_eq!(
+            variants.speed,
+            hash_fn_text(&word_list, &hash_data, Profile::Speed).unwrap()
+        );
+
+        assert!(variants.size.starts_with("row_lookup_table: i8["));
+        assert!(variants.size.contains("\nfunction hash(word):\n"));
+        assert!(variants.size.contains("max_word_len = 5\n"));
+        assert!(variants.size.contains("    if len(word) > max_word_len:\n"));
+        assert!(variants.size.contains(") % 4\n"));
+        assert!(!variants.size.contains("key_length"));
+
+        assert!(variants.speed.starts_with("row_lookup_table: int["));
+        assert!(variants.speed.contains("\ninline function hash(word):\n"));
+        assert!(variants.speed.contains(") & 3\n"));
+        assert!(variants
+            .speed
+            .contains("if len(word) != key_length[hash_value]:"));
+
+        let slot = (hash_data.as_closure.cls)("BEGIN");
+        let key_length = variants.speed.lines().nth(1).unwrap();
+        assert!(key_length.starts_with("key_length: int[4] = ["));
+        let lengths: Vec<&str> = key_length
+            .trim_start_matches("key_length: int[4] = [")
+            .trim_end_matches(']')
+            .split(", ")
+            .collect();
+        assert_eq!(lengths[slot], "5");
+
+        let options = Options::new().normalize_rlt(true);
+        let hash_data =
+            generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+        let variants = hash_fn_variants(&word_list, &hash_data).unwrap();
+        assert!(variants.size.starts_with("row_lookup_table: u8["));
+        assert!(variants.speed.starts_with("row_lookup_table: uint["));
+    }
+
+    #[test]
+    fn rust_recognizer_unit_test() {
+        assert_eq!(variant_name("BEGIN"), "Begin");
+        assert_eq!(variant_name("end_if"), "EndIf");
+        assert_eq!(variant_name("self"), "WordSelf");
+        assert_eq!(variant_name("2d"), "Word2d");
+        assert_eq!(variant_name("+="), "Word");
+
+        let word_list: WordList = ["ELSE", "END", "END_IF", "end_if", "+", "-"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let options = Options::new().charset(crate::Charset::Printable);
+        let hash_data = generate_hash_with_options(
+            &word_list,
+            crate::PositionAlgorithm::with_alphabet(
+                [Position::Start(0)],
+                [Position::End(0)],
+                "+-DEFSdef".chars(),
+            )
+            .unwrap(),
+            &options,
+        )
+        .unwrap();
+        let text = rust_recognizer(&word_list, &hash_data, "Token").unwrap();
+        println!("{text}");
+        assert!(text.contains("pub enum Token {\n    /// \"ELSE\"\n    Else,\n"));
+        assert!(text.contains("    EndIf,\n    /// \"end_if\"\n    EndIf3,\n"));
+        assert!(text.contains("    Word,\n    /// \"-\"\n    Word5,\n"));
+        assert!(text.contains("            Token::EndIf3 => \"end_if\",\n"));
+        assert!(text.contains("        fn lookup_value0(c: char) -> Option<usize> {\n"));
+        assert!(text.contains("                'E' => 3,\n"));
+
+        let word_list: WordList = ["ELSE", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let options = Options::new().backend(crate::Backend::Chm);
+        let hash_data =
+            generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+        match rust_recognizer(&word_list, &hash_data, "Token") {
+            Ok(_) => panic!("CHM hash not detected."),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Codegen Error: A recognizer cannot be emitted for the CHM backend."
+            ),
+        }
+    }
+
+    #[test]
+    fn c_recognizer_unit_test() {
+        assert_eq!(constant_name("end-if"), "END_IF");
+        assert_eq!(constant_name("+"), "WORD");
+        assert_eq!(c_char_literal('A'), "'A'");
+        assert_eq!(c_char_literal('\''), "'\\''");
+        assert_eq!(c_char_literal('\t'), "9");
+
+        let word_list: WordList = ["ELSE", "END", "none", "+"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let options = Options::new().charset(crate::Charset::Printable);
+        let hash_data = generate_hash_with_options(
+            &word_list,
+            crate::CaseFold::new(
+                crate::PositionAlgorithm::with_alphabet(
+                    [Position::Start(0)],
+                    [Position::End(0)],
+                    "+DEN".chars(),
+                )
+                .unwrap(),
+            ),
+            &options,
+        )
+        .unwrap();
+        let text = c_recognizer(&word_list, &hash_data, "tok").unwrap();
+        println!("{text}");
+        assert!(text.contains("#include <ctype.h>\n"));
+        assert!(text.contains(
+            "#define TOK_NONE 0\n\
+             #define TOK_ELSE 1\n\
+             #define TOK_END 2\n\
+             #define TOK_NONE2 3\n\
+             #define TOK_WORD 4\n"
+        ));
+        assert!(text.contains("\nint tok_recognize(const char *word)\n"));
+        assert!(text.contains("    if (len < 1 || len > 4)\n        return TOK_NONE;\n"));
+        assert!(text.contains("    case '+': return 0;\n"));
+        assert!(text.contains("lookup_value0((long)toupper((unsigned char)word[len - 1]))"));
+        let slot = (hash_data.as_closure.cls)("none");
+        assert!(text.contains(&format!(
+            "    case {slot}:\n        \
+             return equal_fold(word, \"NONE\") ? TOK_NONE2 : TOK_NONE;\n"
+        )));
+
+        let text = c_recognizer_self_test(&word_list, &hash_data, "tok");
+        assert!(text.starts_with("#ifdef MSMP_SELF_TEST\n"));
+        assert!(text.contains("\nint tok_self_test(void)\n"));
+        assert!(text.contains(
+            "    failures += tok_recognize(\"none\") != TOK_NONE2;\n    \
+             failures += tok_recognize(\"+\") != TOK_WORD;\n    \
+             failures += tok_recognize(\"\") != TOK_NONE;\n"
+        ));
+        assert!(text.ends_with("\n#endif /* MSMP_SELF_TEST */\n"));
+
+        let word_list: WordList = ["ÉTÉ", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let options = Options::new().charset(crate::Charset::Unicode);
+        let hash_data = generate_hash_with_options(
+            &word_list,
+            crate::PositionAlgorithm::with_alphabet(
+                [Position::Start(0)],
+                [Position::End(0)],
+                "DEÉ".chars(),
+            )
+            .unwrap(),
+            &options,
+        )
+        .unwrap();
+        match c_recognizer(&word_list, &hash_data, "tok") {
+            Ok(_) => panic!("Non ASCII word not detected."),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Codegen Error: A C recognizer needs ASCII words, found \"ÉTÉ\"."
+            ),
+        }
+    }
+
+    #[test]
+    fn c_recognizers_unit_test() {
+        let statements: WordList = ["begin", "end", "if"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let statement_hash = generate_hash_with_options(
+            &statements,
+            crate::CaseFold::new(
+                crate::PositionAlgorithm::with_alphabet(
+                    [Position::Start(0)],
+                    [Position::End(0)],
+                    "BDEFGINO".chars(),
+                )
+                .unwrap(),
+            ),
+            &Options::new(),
+        )
+        .unwrap();
+        let functions: WordList = ["ABS", "ORD", "EOF"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let function_hash = generate_hash(&functions, ElcAlgorithm::default()).unwrap();
+
+        let text = c_recognizers(&[
+            ("stmt", &statements, &statement_hash),
+            ("func", &functions, &function_hash),
+        ])
+        .unwrap();
+        println!("{text}");
+        assert_eq!(text.matches("#include <string.h>\n").count(), 1);
+        assert_eq!(text.matches("static int equal_fold(").count(), 1);
+        assert!(text.contains("#define STMT_NONE 0\n#define STMT_BEGIN 1\n"));
+        assert!(text.contains("#define FUNC_NONE 0\n#define FUNC_ABS 1\n"));
+        assert!(text.contains("static const int8_t stmt_row_lookup_table["));
+        assert!(text.contains("static const int8_t func_row_lookup_table["));
+        assert!(text.contains("\nint stmt_recognize(const char *word)\n"));
+        assert!(text.contains("\nint func_recognize(const char *word)\n"));
+        assert!(text.contains("    sum = func_row_lookup_table[row_index] + col_index;\n"));
+        assert!(text.contains("return strcmp(word, \"ORD\") == 0 ? FUNC_ORD : FUNC_NONE;"));
+        assert!(text.contains("return equal_fold(word, \"IF\") ? STMT_IF : STMT_NONE;"));
+        assert!(
+            text.find("static int equal_fold(").unwrap()
+                < text.find("int stmt_recognize(").unwrap()
+        );
+
+        match c_recognizers(&[
+            ("func", &functions, &function_hash),
+            ("FUNC", &statements, &statement_hash),
+        ]) {
+            Ok(_) => panic!("Repeated prefix not detected."),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "Codegen Error: The prefix \"FUNC\" names two recognizers."
+            ),
+        }
+    }
+}
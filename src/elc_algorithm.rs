@@ -1,171 +1,1121 @@
-use crate::{Error, HashAlgorithm, Kind};
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::codegen::quote;
+use crate::{ByteWordList, Charset, Error, Expr, HashAlgorithm, Kind, ParamValue, WordList};
 
 /// The ElcAlgorithm.
 ///
-/// The ElcAlgorithm is a hash algorithm that uses the first and last characters of a word to generate
-/// a hash value. The hash value is then used to generate a row and column index.
-/// The number of values that can be represented by the hash value is determined by the number of
-/// characters in the alphabet and the number of characters to use from the beginning and end of a
-/// word. The number of values that can be represented by the hash value is equal to the number of
-/// characters in the alphabet raised to the power of the number of characters to use from the
-/// beginning and end of a word.
-#[derive(Debug)]
-pub struct ElcAlgorithm {
-    /// The number of characters to use from the beginning and end of a word.
-    elc: usize,
+/// The ElcAlgorithm is a hash algorithm that uses the first and last characters of a word to generate
+/// a hash value. The hash value is then used to generate a row and column index.
+/// The number of values that can be represented by the hash value is determined by the number of
+/// characters in the alphabet and the number of characters to use from the beginning and end of a
+/// word. The number of values that can be represented by the hash value is equal to the number of
+/// characters in the alphabet raised to the power of the number of characters to use from the
+/// beginning and end of a word.
+#[derive(Debug, Clone)]
+pub struct ElcAlgorithm {
+    /// The number of characters to use from the beginning of a word for h1.
+    elc_h1: usize,
+
+    /// The number of characters to use from the end of a word for h2.
+    elc_h2: usize,
+
+    /// The number of values that can be represented by a letter from the word.
+    num_vals: usize,
+
+    /// The sorted characters that may appear in a word. The index of a character
+    /// in the alphabet is its value. When `None` the letters 'A' to 'Z' are used.
+    alphabet: Option<Vec<char>>,
+
+    /// Whether every character of the alphabet is ASCII, so that the ends of a
+    /// word can be taken by byte index.
+    ascii: bool,
+}
+
+impl ElcAlgorithm {
+    /// Creates a new ElcAlgorithm.
+    ///
+    /// # Parameters
+    /// * `elc` - The number of characters to use from the beginning and end of a word.
+    /// * `num_vals` - The number of values that can be represented by a letter from the word.
+    #[must_use]
+    pub fn new(elc: usize, num_vals: usize) -> Self {
+        Self {
+            elc_h1: elc,
+            elc_h2: elc,
+            num_vals,
+            alphabet: None,
+            ascii: true,
+        }
+    }
+
+    /// Creates a new ElcAlgorithm that accepts the characters of a character set.
+    ///
+    /// The radix `num_vals` is the number of characters in the set.
+    ///
+    /// # Parameters
+    /// * `elc` - The number of characters to use from the beginning and end of a word.
+    /// * `charset` - The characters words may be made of.
+    ///
+    /// # Errors
+    /// Will return `Err` for `Charset::Unicode`, which has too many characters
+    /// to use as an alphabet. Use `from_word_list` instead.
+    pub fn with_charset(elc: usize, charset: Charset) -> Result<Self, Error> {
+        match (charset, charset.alphabet()) {
+            (Charset::Uppercase, _) => Ok(Self::new(elc, 26)),
+            (_, Some(alphabet)) => Self::with_alphabet(elc, alphabet),
+            (_, None) => Err(Error::new(Kind::ElcAlgorithmError(format!(
+                "The {charset:?} character set can not be used as an alphabet."
+            )))),
+        }
+    }
+
+    /// Creates a new ElcAlgorithm with a custom alphabet.
+    ///
+    /// Each character is mapped to its index in the sorted alphabet, and the
+    /// radix `num_vals` is the number of characters. Supplying only the
+    /// characters that appear at the ends of the words keeps the radix, and
+    /// with it the row lookup table, small.
+    ///
+    /// # Parameters
+    /// * `elc` - The number of characters to use from the beginning and end of a word.
+    /// * `alphabet` - The characters that may appear in a word. Repeats are ignored.
+    ///
+    /// # Errors
+    /// Will return `Err` if `alphabet` is empty.
+    pub fn with_alphabet(
+        elc: usize,
+        alphabet: impl IntoIterator<Item = char>,
+    ) -> Result<Self, Error> {
+        let alphabet: BTreeSet<char> = alphabet.into_iter().collect();
+        if alphabet.is_empty() {
+            return Err(Error::new(Kind::ElcAlgorithmError(
+                "Empty alphabet.".to_string(),
+            )));
+        }
+        Ok(Self {
+            elc_h1: elc,
+            elc_h2: elc,
+            num_vals: alphabet.len(),
+            ascii: alphabet.iter().all(char::is_ascii),
+            alphabet: Some(alphabet.into_iter().collect()),
+        })
+    }
+
+    /// Checks that every word of a word list can be hashed.
+    ///
+    /// # Parameters
+    /// * `word_list` - The word list the algorithm will be used on.
+    ///
+    /// # Errors
+    /// Will return `Err` with the first word that is shorter than `elc`, or that
+    /// has a character outside of the alphabet in its first or last `elc`
+    /// characters.
+    pub fn check_word_list<W: Borrow<str>>(&self, word_list: &WordList<W>) -> Result<(), Error> {
+        for word in word_list.iter().map(Borrow::borrow) {
+            self.hash_pair(word)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a new ElcAlgorithm with an alphabet inferred from a word list.
+    ///
+    /// Only the characters that actually appear in the first and last `elc`
+    /// positions of the words are given values, which keeps `num_vals` and the
+    /// resulting row and column index ranges as small as possible.
+    ///
+    /// # Parameters
+    /// * `elc` - The number of characters to use from the beginning and end of a word.
+    /// * `word_list` - The word list the algorithm will be used on.
+    #[must_use]
+    pub fn from_word_list<W: Borrow<str>>(elc: usize, word_list: &WordList<W>) -> Self {
+        Self::from_word_list_with_elcs(elc, elc, word_list)
+    }
+
+    /// Creates a new ElcAlgorithm with separate character counts for h1 and
+    /// h2, and an alphabet inferred from a word list.
+    ///
+    /// # Parameters
+    /// * `elc_h1` - The number of characters to use from the beginning of a word.
+    /// * `elc_h2` - The number of characters to use from the end of a word.
+    /// * `word_list` - The word list the algorithm will be used on.
+    #[must_use]
+    pub fn from_word_list_with_elcs<W: Borrow<str>>(
+        elc_h1: usize,
+        elc_h2: usize,
+        word_list: &WordList<W>,
+    ) -> Self {
+        let alphabet: BTreeSet<char> = word_list
+            .iter()
+            .map(Borrow::borrow)
+            .flat_map(|word| {
+                word.chars()
+                    .take(elc_h1)
+                    .chain(word.chars().rev().take(elc_h2))
+            })
+            .collect();
+        Self {
+            elc_h1,
+            elc_h2,
+            num_vals: alphabet.len().max(1),
+            ascii: alphabet.iter().all(char::is_ascii),
+            alphabet: Some(alphabet.into_iter().collect()),
+        }
+    }
+
+    /// Sets separate character counts for h1 and h2.
+    ///
+    /// Fewer characters for h1 keep the number of rows down, while more
+    /// characters for h2 spread the words of a row over more columns.
+    ///
+    /// # Parameters
+    /// * `elc_h1` - The number of characters to use from the beginning of a word.
+    /// * `elc_h2` - The number of characters to use from the end of a word.
+    #[must_use]
+    pub fn with_elcs(mut self, elc_h1: usize, elc_h2: usize) -> Self {
+        self.elc_h1 = elc_h1;
+        self.elc_h2 = elc_h2;
+        self
+    }
+
+    /// Gets the number of characters a word needs to be hashed.
+    ///
+    /// # Returns
+    /// The elc value, the larger of `elc_h1` and `elc_h2` when they differ.
+    #[must_use]
+    pub fn get_elc(&self) -> usize {
+        self.elc_h1.max(self.elc_h2)
+    }
+
+    /// Gets the number of characters used from the beginning of a word for h1.
+    ///
+    /// # Returns
+    /// The elc value of h1.
+    #[must_use]
+    pub fn get_elc_h1(&self) -> usize {
+        self.elc_h1
+    }
+
+    /// Gets the number of characters used from the end of a word for h2.
+    ///
+    /// # Returns
+    /// The elc value of h2.
+    #[must_use]
+    pub fn get_elc_h2(&self) -> usize {
+        self.elc_h2
+    }
+
+    /// Gets the number of values that can be represented by a letter from the word.
+    ///
+    /// # Returns
+    /// The radix used to combine characters.
+    #[must_use]
+    pub fn get_num_vals(&self) -> usize {
+        self.num_vals
+    }
+
+    /// Gets the alphabet inferred from a word list or supplied by the user.
+    ///
+    /// # Returns
+    /// The characters in value order, or `None` if the letters 'A' to 'Z' are used.
+    #[must_use]
+    pub fn get_alphabet(&self) -> Option<&[char]> {
+        self.alphabet.as_deref()
+    }
+
+    /// Returns true if every character of the alphabet is ASCII.
+    ///
+    /// The ends of a word are then taken by byte index rather than by decoding
+    /// characters.
+    ///
+    /// # Returns
+    /// True if the alphabet is ASCII only.
+    #[must_use]
+    pub fn is_ascii(&self) -> bool {
+        self.ascii
+    }
+
+    /// Converts a character to an index.
+    ///
+    /// # Parameters
+    /// * `c` - A character.
+    ///
+    /// # Returns
+    /// An index, or `None` if the character is not part of the alphabet.
+    fn char_to_index(&self, c: char) -> Option<usize> {
+        match &self.alphabet {
+            Some(alphabet) => alphabet.binary_search(&c).ok(),
+            None => c
+                .is_ascii_uppercase()
+                .then(|| (c as usize) - ('A' as usize)),
+        }
+    }
+
+    /// Combines characters of a word into an index.
+    ///
+    /// # Parameters
+    /// * `word` - The word the characters were taken from.
+    /// * `elc` - The number of characters taken, for error messages.
+    /// * `chars` - The characters to combine.
+    ///
+    /// # Returns
+    /// An index.
+    ///
+    /// # Errors
+    /// Will return `Err` if a character is not part of the alphabet.
+    fn combine(
+        &self,
+        word: impl fmt::Display,
+        elc: usize,
+        mut chars: impl Iterator<Item = char>,
+    ) -> Result<usize, Error> {
+        chars
+            .try_fold(0, |acc, c| self.char_to_index(c).map(|x| self.step(acc, x)))
+            .ok_or_else(|| {
+                Error::new(Kind::ElcAlgorithmError(format!(
+                    "Unexpected character(s) encountered ({word}) in indices (0..{elc})."
+                )))
+            })
+    }
+
+    /// Converts the first `elc_h1` characters of a word to an index. The caller
+    /// must have checked the length of the word, see `check_len`.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Errors
+    /// Will return `Err` if a character is not in the alphabet.
+    fn prefix_index(&self, word: &str) -> Result<usize, Error> {
+        if self.ascii {
+            // Non ASCII bytes are not part of an ASCII alphabet, so they are rejected.
+            let prefix = &word.as_bytes()[..self.elc_h1];
+            return self.combine(word, self.elc_h1, prefix.iter().map(|b| char::from(*b)));
+        }
+        self.combine(word, self.elc_h1, word.chars().take(self.elc_h1))
+    }
+
+    /// Converts the last `elc_h2` characters of a word to an index. The caller
+    /// must have checked the length of the word, see `check_len`.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Errors
+    /// Will return `Err` if a character is not in the alphabet.
+    fn suffix_index(&self, word: &str) -> Result<usize, Error> {
+        if self.ascii {
+            // Non ASCII bytes are not part of an ASCII alphabet, so they are rejected.
+            let suffix = &word.as_bytes()[word.len() - self.elc_h2..];
+            return self.combine(
+                word,
+                self.elc_h2,
+                suffix.iter().rev().map(|b| char::from(*b)),
+            );
+        }
+        self.combine(word, self.elc_h2, word.chars().rev().take(self.elc_h2))
+    }
+
+    /// Checks that a word is long enough to be hashed.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Errors
+    /// Will return `Err` if `word` has fewer than `elc` characters, see `get_elc`.
+    fn check_len(&self, word: &str) -> Result<(), Error> {
+        // Characters are counted, not bytes, unless only ASCII is accepted.
+        self.check_count(if self.ascii {
+            word.len()
+        } else {
+            word.chars().count()
+        })
+    }
+
+    /// Checks that a word has enough characters to be hashed.
+    ///
+    /// # Parameters
+    /// * `word_len` - The number of characters in the word.
+    ///
+    /// # Errors
+    /// Will return `Err` if `word_len` is less than `elc`.
+    fn check_count(&self, word_len: usize) -> Result<(), Error> {
+        if word_len < self.get_elc() {
+            return Err(Error::new(Kind::ElcAlgorithmError(format!(
+                "Expected word length ({word_len}) to be greater than or equal to elc ({elc}).",
+                elc = self.get_elc()
+            ))));
+        }
+        Ok(())
+    }
+
+    /// A pseudo code representation of the conversion of a character `x` to an index.
+    ///
+    /// # Returns
+    /// The lines defining the alphabet, if any, and the index expression.
+    fn index_as_text(&self) -> (String, &'static str) {
+        match &self.alphabet {
+            Some(alphabet) => (
+                format!(
+                    "\n        alphabet = {}",
+                    quote(&alphabet.iter().collect::<String>())
+                ),
+                "alphabet.index(x)",
+            ),
+            None => (String::new(), "ord(x) - ord('A')"),
+        }
+    }
+
+    /// Builds the expression converting the character at a position to an index.
+    ///
+    /// # Parameters
+    /// * `position` - The position of the character.
+    ///
+    /// # Returns
+    /// The index of the character in the alphabet.
+    fn index_expr(&self, position: Position) -> Expr {
+        let c = Expr::Char(position);
+        match &self.alphabet {
+            Some(alphabet) => Expr::Lookup {
+                keys: alphabet.clone(),
+                values: (0..alphabet.len()).collect(),
+                of: Box::new(c),
+            },
+            None => c - Expr::Const(usize::from(b'A')),
+        }
+    }
+
+    /// Builds the expression combining the characters at some positions into
+    /// an index, the counterpart of `combine`.
+    ///
+    /// # Parameters
+    /// * `positions` - The positions of the characters, in the order they are
+    ///   combined.
+    ///
+    /// # Returns
+    /// The index.
+    fn combine_expr(&self, positions: impl IntoIterator<Item = Position>) -> Expr {
+        positions
+            .into_iter()
+            .map(|position| self.index_expr(position))
+            .reduce(|acc, x| acc * Expr::Const(self.num_vals) + x)
+            .unwrap_or(Expr::Const(0))
+    }
+
+    /// Gets the parameters converting characters to values.
+    ///
+    /// # Returns
+    /// The radix `num_vals`, and the `alphabet` when the algorithm accepts a
+    /// character set rather than the letters 'A' to 'Z'.
+    fn alphabet_parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        let mut parameters = vec![("num_vals", ParamValue::from(self.num_vals))];
+        if let Some(alphabet) = &self.alphabet {
+            parameters.push((
+                "alphabet",
+                ParamValue::from(alphabet.iter().collect::<String>()),
+            ));
+        }
+        parameters
+    }
+
+    /// Step to the next index level for each elc position.
+    ///
+    /// # Parameters
+    /// * `acc` - The accumulated value.
+    /// * `x` - The current value.
+    ///
+    /// # Returns
+    /// The next value.
+    fn step(&self, acc: usize, x: usize) -> usize {
+        (acc * self.num_vals) + x
+    }
+}
+
+impl HashAlgorithm for ElcAlgorithm {
+    /// Hash function h1 that is used internally to generate row indices.
+    /// The hash value is generated by taking the first `elc_h1` characters of the word.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    ///
+    /// # Errors
+    /// Will return `Err` if `word` is not a valid word.
+    fn h1(&self, word: &str) -> Result<usize, Error> {
+        self.check_len(word)?;
+        self.prefix_index(word)
+    }
+
+    /// A string representation of the h1 function.
+    /// The string representation can be used to implement the h1 hash function in another language.
+    ///
+    /// # Returns
+    /// A string representation of the h1 function.
+    fn h1_as_text(&self) -> String {
+        let (alphabet, index) = self.index_as_text();
+        format!(
+            "{alphabet}\n        val = 0
+        for x in word_letters[0..{elc}]:
+            val += (val * {num_vals}) + {index}",
+            elc = self.elc_h1 - 1,
+            num_vals = self.num_vals
+        )
+    }
+
+    /// Hash function h2 that is used internally to generate column indices.
+    /// The hash value is generated by taking the last `elc_h2` characters of the word.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    fn h2(&self, word: &str) -> Result<usize, Error> {
+        self.check_len(word)?;
+        self.suffix_index(word)
+    }
+
+    /// Hashes a word with both h1 and h2, checking its length only once.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// The row and column indices.
+    fn hash_pair(&self, word: &str) -> Result<(usize, usize), Error> {
+        self.check_len(word)?;
+        Ok((self.prefix_index(word)?, self.suffix_index(word)?))
+    }
+
+    /// A string representation of the h2 function.
+    /// The string representation can be used to implement the h2 hash function in another language.
+    ///
+    /// # Returns
+    /// A string representation of the h2 function.
+    fn h2_as_text(&self) -> String {
+        let (alphabet, index) = self.index_as_text();
+        format!(
+            "{alphabet}\n        val = 0
+        for x in word_letters[-1..-{elc}]:
+            val += (val * {num_vals}) + {index}",
+            elc = self.elc_h2,
+            num_vals = self.num_vals
+        )
+    }
+
+    fn h1_expr(&self) -> Option<Expr> {
+        Some(self.combine_expr((0..self.elc_h1).map(Position::Start)))
+    }
+
+    fn h2_expr(&self) -> Option<Expr> {
+        Some(self.combine_expr((0..self.elc_h2).map(Position::End)))
+    }
+
+    fn name(&self) -> &'static str {
+        "elc"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        let mut parameters = vec![
+            ("elc_h1", ParamValue::from(self.elc_h1)),
+            ("elc_h2", ParamValue::from(self.elc_h2)),
+        ];
+        parameters.extend(self.alphabet_parameters());
+        parameters
+    }
+}
+
+/// The ElcAlgorithm for byte strings that need not be valid UTF-8.
+///
+/// Each byte stands for the character with the same value, so the byte `0x41`
+/// is 'A' and the byte `0xff` is 'ÿ'. Lengths are counted in bytes.
+#[derive(Debug, Clone, Default)]
+pub struct ByteElcAlgorithm {
+    /// The algorithm the bytes are hashed with, as characters.
+    elc_algorithm: ElcAlgorithm,
+}
+
+impl ByteElcAlgorithm {
+    /// Creates a new ByteElcAlgorithm for the bytes of the letters 'A' to 'Z'.
+    ///
+    /// # Parameters
+    /// * `elc` - The number of bytes to use from the beginning and end of a byte string.
+    /// * `num_vals` - The number of values that can be represented by a byte.
+    #[must_use]
+    pub fn new(elc: usize, num_vals: usize) -> Self {
+        Self {
+            elc_algorithm: ElcAlgorithm::new(elc, num_vals),
+        }
+    }
+
+    /// Creates a new ByteElcAlgorithm with a custom alphabet.
+    ///
+    /// # Parameters
+    /// * `elc` - The number of bytes to use from the beginning and end of a byte string.
+    /// * `alphabet` - The bytes that may appear in a byte string. Repeats are ignored.
+    ///
+    /// # Errors
+    /// Will return `Err` if `alphabet` is empty.
+    pub fn with_alphabet(
+        elc: usize,
+        alphabet: impl IntoIterator<Item = u8>,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            elc_algorithm: ElcAlgorithm::with_alphabet(elc, alphabet.into_iter().map(char::from))?,
+        })
+    }
+
+    /// Creates a new ByteElcAlgorithm with an alphabet inferred from a byte
+    /// string list.
+    ///
+    /// # Parameters
+    /// * `elc` - The number of bytes to use from the beginning and end of a byte string.
+    /// * `word_list` - The byte string list the algorithm will be used on.
+    #[must_use]
+    pub fn from_word_list(elc: usize, word_list: &ByteWordList) -> Self {
+        let alphabet: BTreeSet<char> = word_list
+            .iter()
+            .flat_map(|word| word.iter().take(elc).chain(word.iter().rev().take(elc)))
+            .map(|b| char::from(*b))
+            .collect();
+        Self {
+            elc_algorithm: ElcAlgorithm {
+                elc_h1: elc,
+                elc_h2: elc,
+                num_vals: alphabet.len().max(1),
+                ascii: alphabet.iter().all(char::is_ascii),
+                alphabet: Some(alphabet.into_iter().collect()),
+            },
+        }
+    }
+
+    /// Gets the algorithm the bytes are hashed with.
+    ///
+    /// # Returns
+    /// The algorithm, with each byte standing for a character.
+    #[must_use]
+    pub fn get_elc_algorithm(&self) -> &ElcAlgorithm {
+        &self.elc_algorithm
+    }
+}
+
+impl HashAlgorithm<[u8]> for ByteElcAlgorithm {
+    fn h1(&self, word: &[u8]) -> Result<usize, Error> {
+        let elc = &self.elc_algorithm;
+        elc.check_count(word.len())?;
+        elc.combine(
+            word.escape_ascii(),
+            elc.elc_h1,
+            word[..elc.elc_h1].iter().map(|b| char::from(*b)),
+        )
+    }
+
+    fn h2(&self, word: &[u8]) -> Result<usize, Error> {
+        let elc = &self.elc_algorithm;
+        elc.check_count(word.len())?;
+        elc.combine(
+            word.escape_ascii(),
+            elc.elc_h2,
+            word[word.len() - elc.elc_h2..]
+                .iter()
+                .rev()
+                .map(|b| char::from(*b)),
+        )
+    }
+
+    fn h1_as_text(&self) -> String {
+        self.elc_algorithm.h1_as_text()
+    }
+
+    fn h2_as_text(&self) -> String {
+        self.elc_algorithm.h2_as_text()
+    }
+
+    fn h1_expr(&self) -> Option<Expr> {
+        self.elc_algorithm.h1_expr()
+    }
+
+    fn h2_expr(&self) -> Option<Expr> {
+        self.elc_algorithm.h2_expr()
+    }
+
+    fn name(&self) -> &'static str {
+        "byte_elc"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        self.elc_algorithm.parameters()
+    }
+}
+
+/// The ElcAlgorithm with the word length mixed into the row index.
+///
+/// The row index is `h1 * max_len + len`, so words that share their end
+/// characters but differ in length no longer collide. Many keyword sets, such
+/// as the reserved words of Pascal, can be hashed with an `elc` of 1 this way.
+/// The length goes into the row index rather than the column index because
+/// many short rows pack into a minimal table more easily than a few wide ones.
+/// Lengths are counted in characters.
+#[derive(Debug, Clone)]
+pub struct ElcWithLength {
+    /// The algorithm the row and column indices are computed with.
+    elc_algorithm: ElcAlgorithm,
+
+    /// One more than the length of the longest word that can be hashed, the
+    /// multiplier of the row index.
+    max_len: usize,
+}
+
+impl ElcWithLength {
+    /// Creates a new ElcWithLength.
+    ///
+    /// # Parameters
+    /// * `elc_algorithm` - The algorithm the row and column indices are computed with.
+    /// * `max_len` - One more than the length of the longest word that can be hashed.
+    #[must_use]
+    pub fn new(elc_algorithm: ElcAlgorithm, max_len: usize) -> Self {
+        Self {
+            elc_algorithm,
+            max_len,
+        }
+    }
+
+    /// Creates a new ElcWithLength with an alphabet and a maximum length
+    /// inferred from a word list.
+    ///
+    /// # Parameters
+    /// * `elc` - The number of characters to use from the beginning and end of a word.
+    /// * `word_list` - The word list the algorithm will be used on.
+    #[must_use]
+    pub fn from_word_list<W: Borrow<str>>(elc: usize, word_list: &WordList<W>) -> Self {
+        let longest = word_list
+            .iter()
+            .map(|word| word.borrow().chars().count())
+            .max()
+            .unwrap_or(0);
+        Self::new(ElcAlgorithm::from_word_list(elc, word_list), longest + 1)
+    }
+
+    /// Gets the algorithm the row and column indices are computed with.
+    ///
+    /// # Returns
+    /// The algorithm.
+    #[must_use]
+    pub fn get_elc_algorithm(&self) -> &ElcAlgorithm {
+        &self.elc_algorithm
+    }
+
+    /// Gets one more than the length of the longest word that can be hashed.
+    ///
+    /// # Returns
+    /// The multiplier of the row index.
+    #[must_use]
+    pub fn get_max_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+impl HashAlgorithm for ElcWithLength {
+    fn h1(&self, word: &str) -> Result<usize, Error> {
+        let len = word.chars().count();
+        if len >= self.max_len {
+            return Err(Error::new(Kind::ElcAlgorithmError(format!(
+                "Word ({word}) is longer than {} characters.",
+                self.max_len - 1
+            ))));
+        }
+        Ok(self.elc_algorithm.h1(word)? * self.max_len + len)
+    }
+
+    fn h2(&self, word: &str) -> Result<usize, Error> {
+        self.elc_algorithm.h2(word)
+    }
+
+    fn h1_as_text(&self) -> String {
+        format!(
+            "{}\n        val = (val * {}) + len(word_letters)",
+            self.elc_algorithm.h1_as_text(),
+            self.max_len
+        )
+    }
+
+    fn h2_as_text(&self) -> String {
+        self.elc_algorithm.h2_as_text()
+    }
+
+    fn h1_expr(&self) -> Option<Expr> {
+        Some(self.elc_algorithm.h1_expr()? * Expr::Const(self.max_len) + Expr::Len)
+    }
+
+    fn h2_expr(&self) -> Option<Expr> {
+        self.elc_algorithm.h2_expr()
+    }
+
+    fn name(&self) -> &'static str {
+        "elc_with_length"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        let mut parameters = self.elc_algorithm.parameters();
+        parameters.push(("max_len", ParamValue::from(self.max_len)));
+        parameters
+    }
+}
+
+/// A character position in a word, counted from either end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Position {
+    /// The position counted from the start of the word, 0 is the first character.
+    Start(usize),
+
+    /// The position counted from the end of the word, 0 is the last character.
+    End(usize),
+}
+
+impl Position {
+    /// Gets the number of characters a word needs to have this position.
+    ///
+    /// # Returns
+    /// The minimum word length.
+    fn min_len(self) -> usize {
+        match self {
+            Position::Start(i) | Position::End(i) => i + 1,
+        }
+    }
+
+    /// Gets the character of a word at this position.
+    ///
+    /// # Parameters
+    /// * `chars` - The characters of the word.
+    ///
+    /// # Returns
+    /// The character, or `None` if the word is too short.
+    pub(crate) fn char_of(self, chars: &[char]) -> Option<char> {
+        match self {
+            Position::Start(i) => chars.get(i).copied(),
+            Position::End(i) => chars.len().checked_sub(i + 1).map(|i| chars[i]),
+        }
+    }
+
+    /// A pseudo code representation of the character at this position.
+    ///
+    /// # Returns
+    /// The indexing expression.
+    fn as_text(self) -> String {
+        match self {
+            Position::Start(i) => format!("word_letters[{i}]"),
+            Position::End(i) => format!("word_letters[-{}]", i + 1),
+        }
+    }
+}
+
+/// A hash algorithm that combines the characters at chosen positions.
+///
+/// The `ElcAlgorithm` always uses the first and last `elc` characters. This
+/// algorithm takes any positions instead, such as the first and third
+/// characters for h1 and the last one for h2. The characters are combined the
+/// same way, with the alphabet of an `ElcAlgorithm`. The word length can be
+/// mixed into the row index too, like with `ElcWithLength`.
+#[derive(Debug, Clone)]
+pub struct PositionAlgorithm {
+    /// The algorithm whose alphabet converts characters to values.
+    elc_algorithm: ElcAlgorithm,
 
-    /// The number of values that can be represented by a letter from the word.
-    num_vals: usize,
+    /// The positions of the characters combined into the row index.
+    h1_positions: Vec<Position>,
+
+    /// The positions of the characters combined into the column index.
+    h2_positions: Vec<Position>,
+
+    /// One more than the length of the longest word that can be hashed, when
+    /// the length is mixed into the row index.
+    max_len: Option<usize>,
 }
 
-impl ElcAlgorithm {
-    /// Creates a new ElcAlgorithm.
+impl PositionAlgorithm {
+    /// Creates a new PositionAlgorithm for the letters 'A' to 'Z'.
     ///
     /// # Parameters
-    /// * `elc` - The number of characters to use from the beginning and end of a word.
-    /// * `num_vals` - The number of values that can be represented by a letter from the word.
+    /// * `h1_positions` - The positions of the characters of the row index.
+    /// * `h2_positions` - The positions of the characters of the column index.
     #[must_use]
-    pub fn new(elc: usize, num_vals: usize) -> Self {
-        Self { elc, num_vals }
+    pub fn new(
+        h1_positions: impl IntoIterator<Item = Position>,
+        h2_positions: impl IntoIterator<Item = Position>,
+    ) -> Self {
+        Self {
+            elc_algorithm: ElcAlgorithm::default(),
+            h1_positions: h1_positions.into_iter().collect(),
+            h2_positions: h2_positions.into_iter().collect(),
+            max_len: None,
+        }
     }
 
-    /// Converts a character to an index.
+    /// Mixes the word length into the row index, which becomes
+    /// `h1 * max_len + len`.
     ///
     /// # Parameters
-    /// * `c` - A character.
-    ///
-    /// # Returns
-    /// An index.
-    fn char_to_index(c: char) -> usize {
-        assert!(c.is_ascii_uppercase());
-        (c as usize) - ('A' as usize)
+    /// * `max_len` - One more than the length of the longest word that can be hashed.
+    #[must_use]
+    pub fn with_length(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
     }
 
-    /// Step to the next index level for each elc position.
+    /// Creates a new PositionAlgorithm with a custom alphabet.
     ///
     /// # Parameters
-    /// * `acc` - The accumulated value.
-    /// * `x` - The current value.
+    /// * `h1_positions` - The positions of the characters of the row index.
+    /// * `h2_positions` - The positions of the characters of the column index.
+    /// * `alphabet` - The characters that may appear at the positions. Repeats are ignored.
     ///
-    /// # Returns
-    /// The next value.
-    fn step(&self, acc: usize, x: usize) -> usize {
-        (acc * self.num_vals) + x
+    /// # Errors
+    /// Will return `Err` if `alphabet` is empty.
+    pub fn with_alphabet(
+        h1_positions: impl IntoIterator<Item = Position>,
+        h2_positions: impl IntoIterator<Item = Position>,
+        alphabet: impl IntoIterator<Item = char>,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            elc_algorithm: ElcAlgorithm::with_alphabet(1, alphabet)?,
+            ..Self::new(h1_positions, h2_positions)
+        })
     }
-}
 
-impl HashAlgorithm for ElcAlgorithm {
-    /// Hash function h1 that is used internally to generate row indices.
-    /// The hash value is generated by taking the first `elc` characters of the word.
+    /// Creates a new PositionAlgorithm with an alphabet inferred from a word
+    /// list.
+    ///
+    /// Only the characters that appear at the positions are given values.
     ///
     /// # Parameters
-    /// * `word` - A word to be hashed.
+    /// * `h1_positions` - The positions of the characters of the row index.
+    /// * `h2_positions` - The positions of the characters of the column index.
+    /// * `word_list` - The word list the algorithm will be used on.
+    #[must_use]
+    pub fn from_word_list<W: Borrow<str>>(
+        h1_positions: impl IntoIterator<Item = Position>,
+        h2_positions: impl IntoIterator<Item = Position>,
+        word_list: &WordList<W>,
+    ) -> Self {
+        let algorithm = Self::new(h1_positions, h2_positions);
+        let alphabet: BTreeSet<char> = word_list
+            .iter()
+            .flat_map(|word| {
+                let chars: Vec<char> = word.borrow().chars().collect();
+                algorithm
+                    .h1_positions
+                    .iter()
+                    .chain(&algorithm.h2_positions)
+                    .filter_map(|position| position.char_of(&chars))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Self {
+            elc_algorithm: ElcAlgorithm {
+                elc_h1: 1,
+                elc_h2: 1,
+                num_vals: alphabet.len().max(1),
+                ascii: alphabet.iter().all(char::is_ascii),
+                alphabet: Some(alphabet.into_iter().collect()),
+            },
+            ..algorithm
+        }
+    }
+
+    /// Gets the positions of the characters of the row index.
     ///
     /// # Returns
-    /// A hash value.
+    /// The positions, in the order they are combined.
+    #[must_use]
+    pub fn get_h1_positions(&self) -> &[Position] {
+        &self.h1_positions
+    }
+
+    /// Gets the positions of the characters of the column index.
     ///
-    /// # Errors
-    /// Will return `Err` if `word` is not a valid word.
-    fn h1(&self, word: &str) -> Result<usize, Error> {
-        if word.len() < self.elc {
-            return Err(Error::new(Kind::ElcAlgorithmError(format!(
-                "Expected word length ({word_len}) to be greater than or equal to elc ({elc}).",
-                word_len = word.len(),
-                elc = self.elc
-            ))));
-        }
+    /// # Returns
+    /// The positions, in the order they are combined.
+    #[must_use]
+    pub fn get_h2_positions(&self) -> &[Position] {
+        &self.h2_positions
+    }
 
-        // Check that the first `elc` characters are uppercase letters.
-        if word.chars().take(self.elc).all(|c| c.is_ascii_uppercase()) {
-            // Convert the first `elc` characters to an index.
-            let x: usize = word
-                .chars()
-                .take(self.elc)
-                .map(ElcAlgorithm::char_to_index)
-                .fold(0, |acc, x| self.step(acc, x));
-            Ok(x)
-        } else {
-            Err(Error::new(Kind::ElcAlgorithmError(format!(
-                "Unexpected character(s) encountered ({word}) in indices (0..{elc}).",
-                word = word,
-                elc = self.elc
-            ))))
-        }
+    /// Gets one more than the length of the longest word that can be hashed.
+    ///
+    /// # Returns
+    /// The multiplier of the row index, or `None` if the length is not used.
+    #[must_use]
+    pub fn get_max_len(&self) -> Option<usize> {
+        self.max_len
     }
 
-    /// A string representation of the h1 function.
-    /// The string representation can be used to implement the h1 hash function in another language.
+    /// Gets the number of values that can be represented by a character.
     ///
     /// # Returns
-    /// A string representation of the h1 function.
-    fn h1_as_text(&self) -> String {
-        format!(
-            "\n        val = 0
-        for x in word_letters[0..{elc}]:
-            val += (val * {num_vals}) + ord(x) - ord('A')",
-            elc = self.elc - 1,
-            num_vals = self.num_vals
-        )
+    /// The radix used to combine characters.
+    #[must_use]
+    pub fn get_num_vals(&self) -> usize {
+        self.elc_algorithm.num_vals
     }
 
-    /// Hash function h2 that is used internally to generate column indices.
-    /// The hash value is generated by taking the last `elc` characters of the word.
+    /// Gets the alphabet inferred from a word list or supplied by the user.
+    ///
+    /// # Returns
+    /// The characters in value order, or `None` if the letters 'A' to 'Z' are used.
+    #[must_use]
+    pub fn get_alphabet(&self) -> Option<&[char]> {
+        self.elc_algorithm.get_alphabet()
+    }
+
+    /// Combines the characters of a word at some positions into an index.
     ///
     /// # Parameters
     /// * `word` - A word to be hashed.
+    /// * `positions` - The positions of the characters to combine.
     ///
     /// # Returns
-    /// A hash value.
-    fn h2(&self, word: &str) -> Result<usize, Error> {
-        if word.len() < self.elc {
+    /// An index.
+    ///
+    /// # Errors
+    /// Will return `Err` if `word` is too short for a position, or if a
+    /// character is not part of the alphabet.
+    fn combine(&self, word: &str, positions: &[Position]) -> Result<usize, Error> {
+        let chars: Vec<char> = word.chars().collect();
+        let elc = &self.elc_algorithm;
+        positions.iter().try_fold(0, |acc, position| {
+            let c = position.char_of(&chars).ok_or_else(|| {
+                Error::new(Kind::ElcAlgorithmError(format!(
+                    "Expected word length ({}) to be greater than or equal to {}.",
+                    chars.len(),
+                    position.min_len()
+                )))
+            })?;
+            let x = elc.char_to_index(c).ok_or_else(|| {
+                Error::new(Kind::ElcAlgorithmError(format!(
+                    "Unexpected character '{c}' encountered ({word}) at {position:?}."
+                )))
+            })?;
+            Ok(elc.step(acc, x))
+        })
+    }
+
+    /// A pseudo code representation of combining the characters at some positions.
+    ///
+    /// # Parameters
+    /// * `positions` - The positions of the characters to combine.
+    ///
+    /// # Returns
+    /// A string representation of the hash function.
+    fn as_text(&self, positions: &[Position]) -> String {
+        let (alphabet, index) = self.elc_algorithm.index_as_text();
+        format!(
+            "{alphabet}\n        val = 0
+        for x in [{letters}]:
+            val = (val * {num_vals}) + {index}",
+            letters = positions
+                .iter()
+                .map(|position| position.as_text())
+                .collect::<Vec<_>>()
+                .join(", "),
+            num_vals = self.elc_algorithm.num_vals
+        )
+    }
+}
+
+impl HashAlgorithm for PositionAlgorithm {
+    fn h1(&self, word: &str) -> Result<usize, Error> {
+        let val = self.combine(word, &self.h1_positions)?;
+        let Some(max_len) = self.max_len else {
+            return Ok(val);
+        };
+        let len = word.chars().count();
+        if len >= max_len {
             return Err(Error::new(Kind::ElcAlgorithmError(format!(
-                "Expected word length ({word_len}) to be greater than or equal to elc ({elc}).",
-                word_len = word.len(),
-                elc = self.elc
+                "Word ({word}) is longer than {} characters.",
+                max_len - 1
             ))));
         }
+        Ok(val * max_len + len)
+    }
 
-        // Check that the last `elc` characters are uppercase letters.
-        if word.chars().take(self.elc).all(|c| c.is_ascii_uppercase()) {
-            // Convert the last `elc` characters to an index.
-            let x: usize = word
-                .chars()
-                .rev()
-                .take(self.elc)
-                .map(ElcAlgorithm::char_to_index)
-                .fold(0, |acc, x| self.step(acc, x));
-            Ok(x)
-        } else {
-            Err(Error::new(Kind::ElcAlgorithmError(format!(
-                "Unexpected character(s) encountered ({word}) in indices (0..{elc}).",
-                word = word,
-                elc = self.elc
-            ))))
+    fn h2(&self, word: &str) -> Result<usize, Error> {
+        self.combine(word, &self.h2_positions)
+    }
+
+    fn h1_as_text(&self) -> String {
+        match self.max_len {
+            Some(max_len) => format!(
+                "{}\n        val = (val * {max_len}) + len(word_letters)",
+                self.as_text(&self.h1_positions)
+            ),
+            None => self.as_text(&self.h1_positions),
         }
     }
 
-    /// A string representation of the h2 function.
-    /// The string representation can be used to implement the h2 hash function in another language.
-    ///
-    /// # Returns
-    /// A string representation of the h2 function.
     fn h2_as_text(&self) -> String {
-        format!(
-            "\n        val = 0
-        for x in word_letters[-1..-{elc}]:
-            val += (val * {num_vals}) + ord(x) - ord('A')",
-            elc = self.elc,
-            num_vals = self.num_vals
+        self.as_text(&self.h2_positions)
+    }
+
+    fn h1_expr(&self) -> Option<Expr> {
+        let val = self
+            .elc_algorithm
+            .combine_expr(self.h1_positions.iter().copied());
+        Some(match self.max_len {
+            Some(max_len) => val * Expr::Const(max_len) + Expr::Len,
+            None => val,
+        })
+    }
+
+    fn h2_expr(&self) -> Option<Expr> {
+        Some(
+            self.elc_algorithm
+                .combine_expr(self.h2_positions.iter().copied()),
         )
     }
+
+    fn name(&self) -> &'static str {
+        "position"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        let positions = |positions: &[Position]| {
+            ParamValue::List(
+                positions
+                    .iter()
+                    .map(|position| {
+                        ParamValue::from(match position {
+                            Position::Start(i) => format!("start {i}"),
+                            Position::End(i) => format!("end {i}"),
+                        })
+                    })
+                    .collect(),
+            )
+        };
+        let mut parameters = vec![
+            ("h1_positions", positions(&self.h1_positions)),
+            ("h2_positions", positions(&self.h2_positions)),
+        ];
+        if let Some(max_len) = self.max_len {
+            parameters.push(("max_len", ParamValue::from(max_len)));
+        }
+        parameters.extend(self.elc_algorithm.alphabet_parameters());
+        parameters
+    }
 }
 
 impl Default for ElcAlgorithm {
     /// Creates a new instance of the ElcAlgorithm with the default values.
     fn default() -> Self {
         Self {
-            elc: 1,
+            elc_h1: 1,
+            elc_h2: 1,
             num_vals: 26,
+            alphabet: None,
+            ascii: true,
         }
     }
 }
@@ -173,16 +1123,18 @@ impl Default for ElcAlgorithm {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::CaseFold;
 
     #[test]
     fn elc_algorithm_unit_test() {
-        assert_eq!(ElcAlgorithm::char_to_index('A'), 0);
-        assert_eq!(ElcAlgorithm::char_to_index('B'), 1);
-        assert_eq!(ElcAlgorithm::char_to_index('Z'), 25);
-
-        // ----
         let hash_algorithm: ElcAlgorithm = ElcAlgorithm::default();
 
+        assert_eq!(hash_algorithm.char_to_index('A'), Some(0));
+        assert_eq!(hash_algorithm.char_to_index('B'), Some(1));
+        assert_eq!(hash_algorithm.char_to_index('Z'), Some(25));
+        assert_eq!(hash_algorithm.char_to_index('a'), None);
+        assert_eq!(hash_algorithm.get_alphabet(), None);
+
         assert_eq!(hash_algorithm.h1("A").unwrap(), 0);
         assert_eq!(hash_algorithm.h1("B").unwrap(), 1);
         assert_eq!(hash_algorithm.h1("Z").unwrap(), 25);
@@ -232,7 +1184,7 @@ mod tests {
                 );
             }
             _ => panic!("Unexpected error type."),
-        };
+        }
 
         match hash_algorithm.h1("aA").unwrap_err().kind() {
             Kind::ElcAlgorithmError(msg) => {
@@ -242,7 +1194,7 @@ mod tests {
                 );
             }
             _ => panic!("Unexpected error type."),
-        };
+        }
 
         match hash_algorithm.h2("A").unwrap_err().kind() {
             Kind::ElcAlgorithmError(msg) => {
@@ -252,7 +1204,7 @@ mod tests {
                 );
             }
             _ => panic!("Unexpected error type."),
-        };
+        }
 
         match hash_algorithm.h2("aA").unwrap_err().kind() {
             Kind::ElcAlgorithmError(msg) => {
@@ -262,11 +1214,364 @@ mod tests {
                 );
             }
             _ => panic!("Unexpected error type."),
-        };
+        }
 
         assert_ne!(hash_algorithm.h1_as_text().len(), 0);
         assert_ne!(hash_algorithm.h2_as_text().len(), 0);
 
         println!("{hash_algorithm:?}");
     }
+
+    #[test]
+    fn elc_algorithm_alphabet_unit_test() {
+        let word_list: WordList = ["ZOO", "ZAP", "PIZZA"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+
+        let hash_algorithm = ElcAlgorithm::from_word_list(1, &word_list);
+        assert_eq!(
+            hash_algorithm.get_alphabet(),
+            Some(&['A', 'O', 'P', 'Z'][..])
+        );
+        assert_eq!(hash_algorithm.get_elc(), 1);
+        assert_eq!(hash_algorithm.get_num_vals(), 4);
+        assert_eq!(hash_algorithm.h1("ZOO").unwrap(), 3);
+        assert_eq!(hash_algorithm.h2("ZOO").unwrap(), 1);
+        assert_eq!(hash_algorithm.h1("PIZZA").unwrap(), 2);
+        assert_eq!(hash_algorithm.h2("PIZZA").unwrap(), 0);
+        assert!(hash_algorithm.h1("BOO").is_err());
+        assert!(hash_algorithm.h1_as_text().contains("alphabet = \"AOPZ\""));
+        assert!(hash_algorithm.h2_as_text().contains("alphabet.index(x)"));
+
+        let hash_algorithm = ElcAlgorithm::from_word_list(2, &word_list);
+        assert_eq!(
+            hash_algorithm.get_alphabet(),
+            Some(&['A', 'I', 'O', 'P', 'Z'][..])
+        );
+        assert_eq!(hash_algorithm.h1("ZA").unwrap(), 20);
+        assert!(hash_algorithm.is_ascii());
+    }
+
+    #[test]
+    fn elc_algorithm_ascii_unit_test() {
+        let hash_algorithm = ElcAlgorithm::new(2, 26);
+        assert!(hash_algorithm.is_ascii());
+        assert_eq!(hash_algorithm.h1("ABCDEFGHIJ").unwrap(), 1);
+        assert_eq!(hash_algorithm.h2("ABCDEFGHIJ").unwrap(), 9 * 26 + 8);
+        assert!(hash_algorithm.h1("\u{c9}A").is_err());
+        assert!(hash_algorithm.h2("A\u{c9}").is_err());
+        assert!(hash_algorithm.h2("\u{c9}AB").is_ok());
+
+        let word_list: WordList = ["\u{c9}T\u{c9}", "ABC"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_algorithm = ElcAlgorithm::from_word_list(1, &word_list);
+        assert!(!hash_algorithm.is_ascii());
+        assert_eq!(hash_algorithm.h1("\u{c9}T\u{c9}").unwrap(), 2);
+        assert_eq!(hash_algorithm.h2("\u{c9}T\u{c9}").unwrap(), 2);
+        assert_eq!(hash_algorithm.h2("ABC").unwrap(), 1);
+
+        // Lengths are counted in characters, not bytes.
+        let word_list: WordList = ["\u{3b1}\u{3b2}", "\u{3b2}\u{3b1}"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_algorithm = ElcAlgorithm::from_word_list(2, &word_list);
+        assert_eq!(hash_algorithm.h1("\u{3b2}\u{3b1}").unwrap(), 2);
+        assert_eq!(hash_algorithm.h2("\u{3b2}\u{3b1}").unwrap(), 1);
+        match hash_algorithm.h1("\u{3b1}").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(
+                    msg,
+                    "Expected word length (1) to be greater than or equal to elc (2)."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+    }
+
+    #[test]
+    fn elc_algorithm_charset_unit_test() {
+        let hash_algorithm = ElcAlgorithm::with_charset(1, Charset::Uppercase).unwrap();
+        assert_eq!(hash_algorithm.get_alphabet(), None);
+        assert_eq!(hash_algorithm.get_num_vals(), 26);
+
+        assert!(ElcAlgorithm::with_charset(1, Charset::Unicode).is_err());
+
+        let hash_algorithm = ElcAlgorithm::with_charset(2, Charset::Identifier).unwrap();
+        assert_eq!(hash_algorithm.get_num_vals(), 37);
+        assert!(hash_algorithm.is_ascii());
+        assert_eq!(hash_algorithm.char_to_index('0'), Some(0));
+        assert_eq!(hash_algorithm.char_to_index('A'), Some(10));
+        assert_eq!(hash_algorithm.char_to_index('_'), Some(36));
+        assert_eq!(hash_algorithm.char_to_index('a'), None);
+        assert_eq!(hash_algorithm.h1("INT8").unwrap(), 18 * 37 + 23);
+        assert_eq!(hash_algorithm.h2("INT8").unwrap(), 8 * 37 + 29);
+        assert_eq!(hash_algorithm.h1("_A").unwrap(), 36 * 37 + 10);
+        assert!(hash_algorithm.h1("A-").is_err());
+        assert!(hash_algorithm.h1_as_text().contains("alphabet.index(x)"));
+    }
+
+    #[test]
+    fn elc_algorithm_custom_alphabet_unit_test() {
+        let hash_algorithm = ElcAlgorithm::with_alphabet(1, "=<>!=".chars()).unwrap();
+        assert_eq!(
+            hash_algorithm.get_alphabet(),
+            Some(&['!', '<', '=', '>'][..])
+        );
+        assert_eq!(hash_algorithm.get_num_vals(), 4);
+        assert!(hash_algorithm.is_ascii());
+        assert_eq!(hash_algorithm.h1("<=").unwrap(), 1);
+        assert_eq!(hash_algorithm.h2("<=").unwrap(), 2);
+
+        let word_list: WordList = ["==", "!=", "<=", ">="]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        assert!(hash_algorithm.check_word_list(&word_list).is_ok());
+
+        let word_list: WordList = ["==", "+="]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        match hash_algorithm
+            .check_word_list(&word_list)
+            .unwrap_err()
+            .kind()
+        {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(
+                    msg,
+                    "Unexpected character(s) encountered (+=) in indices (0..1)."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+
+        let hash_algorithm = ElcAlgorithm::with_alphabet(1, "\\\"".chars()).unwrap();
+        assert!(hash_algorithm.h1_as_text().contains(r#"alphabet = "\"\\""#));
+
+        match ElcAlgorithm::with_alphabet(1, "".chars())
+            .unwrap_err()
+            .kind()
+        {
+            Kind::ElcAlgorithmError(msg) => assert_eq!(msg, "Empty alphabet."),
+            _ => panic!("Unexpected error type."),
+        }
+    }
+
+    #[test]
+    fn byte_elc_algorithm_unit_test() {
+        let hash_algorithm = ByteElcAlgorithm::new(2, 26);
+        assert_eq!(hash_algorithm.get_elc_algorithm().get_elc(), 2);
+        assert_eq!(hash_algorithm.h1(b"BA").unwrap(), 26);
+        assert_eq!(hash_algorithm.h2(b"BA").unwrap(), 1);
+        match hash_algorithm.h1(b"A").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(
+                    msg,
+                    "Expected word length (1) to be greater than or equal to elc (2)."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        match hash_algorithm.h2(b"A\xff").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(
+                    msg,
+                    "Unexpected character(s) encountered (A\\xff) in indices (0..2)."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+
+        let word_list: ByteWordList = [b"\xff\x01".to_vec(), b"\x01\x80".to_vec()]
+            .into_iter()
+            .collect();
+        let hash_algorithm = ByteElcAlgorithm::from_word_list(1, &word_list);
+        assert_eq!(
+            hash_algorithm.get_elc_algorithm().get_alphabet(),
+            Some(&['\u{1}', '\u{80}', '\u{ff}'][..])
+        );
+        assert_eq!(hash_algorithm.h1(b"\xff\x01").unwrap(), 2);
+        assert_eq!(hash_algorithm.h2(b"\x01\x80").unwrap(), 1);
+
+        let hash_algorithm = ByteElcAlgorithm::with_alphabet(1, [0x80, 0x01]).unwrap();
+        assert_eq!(hash_algorithm.h1(b"\x80").unwrap(), 1);
+        assert!(ByteElcAlgorithm::with_alphabet(1, []).is_err());
+        assert_ne!(hash_algorithm.h1_as_text().len(), 0);
+        assert_ne!(hash_algorithm.h2_as_text().len(), 0);
+    }
+
+    #[test]
+    fn elc_with_length_unit_test() {
+        let hash_algorithm = ElcWithLength::new(ElcAlgorithm::default(), 8);
+        assert_eq!(hash_algorithm.get_max_len(), 8);
+        assert_eq!(hash_algorithm.get_elc_algorithm().get_elc(), 1);
+        assert_eq!(hash_algorithm.h2("DOWNTO").unwrap(), 14);
+        assert_eq!(hash_algorithm.h1("DO").unwrap(), 3 * 8 + 2);
+        assert_eq!(hash_algorithm.h1("DOWNTO").unwrap(), 3 * 8 + 6);
+        match hash_algorithm.h1("ABCDEFGH").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(msg, "Word (ABCDEFGH) is longer than 7 characters.");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        assert!(hash_algorithm.h1("dO").is_err());
+        assert!(hash_algorithm
+            .h1_as_text()
+            .ends_with("(val * 8) + len(word_letters)"));
+
+        let word_list: WordList = ["DO", "DOWNTO", "TO"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let hash_algorithm = ElcWithLength::from_word_list(1, &word_list);
+        assert_eq!(hash_algorithm.get_max_len(), 7);
+        assert_eq!(hash_algorithm.get_elc_algorithm().get_num_vals(), 3);
+    }
+
+    #[test]
+    fn position_algorithm_unit_test() {
+        // The first two and last two characters, like an elc of 2.
+        let hash_algorithm = PositionAlgorithm::new(
+            [Position::Start(0), Position::Start(1)],
+            [Position::End(0), Position::End(1)],
+        );
+        let elc_algorithm = ElcAlgorithm::new(2, 26);
+        for word in ["AB", "BEGIN", "ZOO"] {
+            assert_eq!(
+                hash_algorithm.h1(word).unwrap(),
+                elc_algorithm.h1(word).unwrap()
+            );
+            assert_eq!(
+                hash_algorithm.h2(word).unwrap(),
+                elc_algorithm.h2(word).unwrap()
+            );
+        }
+        assert_eq!(hash_algorithm.get_num_vals(), 26);
+        assert_eq!(hash_algorithm.get_alphabet(), None);
+        assert!(hash_algorithm
+            .h1_as_text()
+            .contains("for x in [word_letters[0], word_letters[1]]:"));
+        assert!(hash_algorithm
+            .h2_as_text()
+            .contains("for x in [word_letters[-1], word_letters[-2]]:"));
+
+        let hash_algorithm =
+            PositionAlgorithm::new([Position::Start(0), Position::Start(2)], [Position::End(0)]);
+        assert_eq!(hash_algorithm.get_h1_positions().len(), 2);
+        assert_eq!(hash_algorithm.get_h2_positions(), &[Position::End(0)]);
+        assert_eq!(hash_algorithm.h1("ABC").unwrap(), 2);
+        assert_eq!(hash_algorithm.h2("ABC").unwrap(), 2);
+        match hash_algorithm.h1("AB").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(
+                    msg,
+                    "Expected word length (2) to be greater than or equal to 3."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        match hash_algorithm.h1("ABc").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(
+                    msg,
+                    "Unexpected character 'c' encountered (ABc) at Start(2)."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+
+        let word_list: WordList = ["DO", "DOWNTO", "TO"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let hash_algorithm =
+            PositionAlgorithm::from_word_list([Position::Start(1)], [Position::End(1)], &word_list);
+        assert_eq!(hash_algorithm.get_alphabet(), Some(&['D', 'O', 'T'][..]));
+        assert_eq!(hash_algorithm.h1("DOWNTO").unwrap(), 1);
+        assert_eq!(hash_algorithm.h2("DOWNTO").unwrap(), 2);
+        assert!(PositionAlgorithm::with_alphabet([], [], []).is_err());
+
+        let hash_algorithm = hash_algorithm.with_length(7);
+        assert_eq!(hash_algorithm.get_max_len(), Some(7));
+        assert_eq!(hash_algorithm.h1("DOWNTO").unwrap(), 7 + 6);
+        assert_eq!(hash_algorithm.h2("DOWNTO").unwrap(), 2);
+        assert!(hash_algorithm.h1("DOWNTOO").is_err());
+        assert!(hash_algorithm
+            .h1_as_text()
+            .ends_with("(val * 7) + len(word_letters)"));
+    }
+
+    #[test]
+    fn split_elc_unit_test() {
+        let hash_algorithm = ElcAlgorithm::new(1, 26).with_elcs(1, 3);
+        assert_eq!(hash_algorithm.get_elc_h1(), 1);
+        assert_eq!(hash_algorithm.get_elc_h2(), 3);
+        assert_eq!(hash_algorithm.get_elc(), 3);
+        assert_eq!(hash_algorithm.h1("BEGIN").unwrap(), 1);
+        assert_eq!(hash_algorithm.h2("BEGIN").unwrap(), (13 * 26 + 8) * 26 + 6);
+        match hash_algorithm.h1("AB").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(
+                    msg,
+                    "Expected word length (2) to be greater than or equal to elc (3)."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        match hash_algorithm.h2("BEGiN").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(msg) => {
+                assert_eq!(
+                    msg,
+                    "Unexpected character(s) encountered (BEGiN) in indices (0..3)."
+                );
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        assert!(hash_algorithm.h1_as_text().contains("word_letters[0..0]"));
+        assert!(hash_algorithm.h2_as_text().contains("word_letters[-1..-3]"));
+
+        let word_list: WordList = ["BEGIN", "END"].into_iter().map(String::from).collect();
+        let hash_algorithm = ElcAlgorithm::from_word_list_with_elcs(1, 2, &word_list);
+        assert_eq!(
+            hash_algorithm.get_alphabet(),
+            Some(&['B', 'D', 'E', 'I', 'N'][..])
+        );
+        assert!(hash_algorithm.check_word_list(&word_list).is_ok());
+    }
+
+    #[test]
+    fn hash_pair_unit_test() {
+        let word_list: WordList = ["BEGIN", "\u{e9}T\u{c9}"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        for hash_algorithm in [
+            ElcAlgorithm::new(2, 26).with_elcs(1, 2),
+            ElcAlgorithm::from_word_list(2, &word_list),
+        ] {
+            for word in word_list.iter().filter(|w| hash_algorithm.h1(w).is_ok()) {
+                assert_eq!(
+                    hash_algorithm.hash_pair(word).unwrap(),
+                    (
+                        hash_algorithm.h1(word).unwrap(),
+                        hash_algorithm.h2(word).unwrap()
+                    )
+                );
+            }
+            assert!(hash_algorithm.hash_pair("A").is_err());
+        }
+
+        let hash_algorithm = CaseFold::new(ElcAlgorithm::default());
+        assert_eq!(
+            hash_algorithm.hash_pair("begin").unwrap(),
+            ElcAlgorithm::default().hash_pair("BEGIN").unwrap()
+        );
+        let hash_algorithm: Box<dyn HashAlgorithm> = Box::new(ElcAlgorithm::default());
+        assert_eq!(hash_algorithm.hash_pair("BEGIN").unwrap(), (1, 13));
+    }
 }
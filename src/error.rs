@@ -1,3 +1,4 @@
+use crate::{PackStats, VerifyReport};
 use std::fmt;
 
 /// An error that can occur in this library.
@@ -39,11 +40,74 @@ pub enum Kind {
     /// An error returned while creating the 2D array.
     TwoDArrayError(String),
 
+    /// Two different words landed on the same cell of the 2D array.
+    TwoDArrayCollision {
+        /// The word that was in the cell first.
+        word_a: String,
+
+        /// The word that collided with it.
+        word_b: String,
+
+        /// The row index of the cell.
+        row: usize,
+
+        /// The column index of the cell.
+        col: usize,
+    },
+
     /// An error returned while creating the 1D packed array.
     OneDPackedArrayError(String),
 
+    /// A row of the 2D array could not be placed into the packed array without
+    /// overlapping the rows already placed.
+    PackingFailed {
+        /// The index of the row that could not be placed.
+        row_index: usize,
+
+        /// The number of rows that were placed.
+        rows_placed: usize,
+
+        /// The column indices of the row that could not be placed, the number
+        /// of them is the size of the row.
+        cols: Vec<usize>,
+
+        /// Whether each entry of the packed array was used when packing
+        /// stopped. Empty when the packing strategy does not report it.
+        occupancy: Vec<bool>,
+    },
+
     /// An error returned while creating the ELC algorithm.
     ElcAlgorithmError(String),
+
+    /// An error returned while creating the Cichelli algorithm.
+    CichelliAlgorithmError(String),
+
+    /// An error returned while building a CHM hash.
+    ChmError(String),
+
+    /// An error returned while generating code.
+    CodegenError(String),
+
+    /// An error returned while rebuilding a hash algorithm from its name and
+    /// parameters.
+    SpecError(String),
+
+    /// The generated hash failed verification. Carries the report of the
+    /// collisions, gaps and out of range hash values that were found.
+    VerifyFailed(VerifyReport),
+
+    /// Packing the 1D array took longer than the allowed time budget.
+    /// Carries the statistics of the packing progress made so far.
+    TimeBudgetExceeded(PackStats),
+
+    /// Packing the 1D array needed more placement attempts than allowed.
+    ProbeBudgetExceeded {
+        /// The index of the row that could not be placed within the budget.
+        row_index: usize,
+
+        /// The statistics of the packing progress made so far.
+        stats: PackStats,
+    },
 }
 
 impl fmt::Display for Error {
@@ -52,8 +116,44 @@ impl fmt::Display for Error {
             Kind::HashError(s) => write!(f, "Hash Error: {s}"),
             Kind::WordListError(s) => write!(f, "Word List Error: {s}"),
             Kind::TwoDArrayError(s) => write!(f, "2D Array Error: {s}"),
+            Kind::TwoDArrayCollision {
+                word_a,
+                word_b,
+                row,
+                col,
+            } => write!(
+                f,
+                "2D Array Collision: {word_a} === {word_b} at row {row}, column {col}"
+            ),
             Kind::OneDPackedArrayError(s) => write!(f, "1D Packed Array Error: {s}"),
+            Kind::PackingFailed {
+                row_index,
+                rows_placed,
+                cols,
+                occupancy,
+            } => write!(
+                f,
+                "Packing Failed: row {row_index} of {} columns {cols:?} does not fit ({rows_placed} rows placed, {} of {} entries used)",
+                cols.len(),
+                occupancy.iter().filter(|used| **used).count(),
+                occupancy.len()
+            ),
             Kind::ElcAlgorithmError(s) => write!(f, "ELC Algorithm Error: {s}"),
+            Kind::CichelliAlgorithmError(s) => write!(f, "Cichelli Algorithm Error: {s}"),
+            Kind::ChmError(s) => write!(f, "CHM Error: {s}"),
+            Kind::CodegenError(s) => write!(f, "Codegen Error: {s}"),
+            Kind::SpecError(s) => write!(f, "Spec Error: {s}"),
+            Kind::VerifyFailed(r) => write!(f, "Verify Failed: {r}"),
+            Kind::TimeBudgetExceeded(s) => write!(
+                f,
+                "Time Budget Exceeded: placed {} of {} rows in {:?} ({} probes)",
+                s.rows_placed, s.num_rows, s.elapsed, s.probes
+            ),
+            Kind::ProbeBudgetExceeded { row_index, stats } => write!(
+                f,
+                "Probe Budget Exceeded: row {row_index} not placed after {} probes ({} of {} rows placed)",
+                stats.probes, stats.rows_placed, stats.num_rows
+            ),
         }
     }
 }
@@ -61,6 +161,7 @@ impl fmt::Display for Error {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::WordList;
 
     #[test]
     fn error_unit_test() {
@@ -98,5 +199,81 @@ mod tests {
             _ => panic!("Unexpected Kind: {e}"),
         }
         println!("{e}");
+
+        let e: Error = Error::new(Kind::CodegenError(String::from("idk")));
+        match e.kind() {
+            Kind::CodegenError(s) => assert!(s == "idk"),
+            _ => panic!("Unexpected Kind: {e}"),
+        }
+        println!("{e}");
+
+        let e: Error = Error::new(Kind::SpecError(String::from("idk")));
+        match e.kind() {
+            Kind::SpecError(s) => assert!(s == "idk"),
+            _ => panic!("Unexpected Kind: {e}"),
+        }
+        assert_eq!(e.to_string(), "Spec Error: idk");
+
+        let e: Error = Error::new(Kind::TimeBudgetExceeded(PackStats::default()));
+        match e.kind() {
+            Kind::TimeBudgetExceeded(s) => assert_eq!(*s, PackStats::default()),
+            _ => panic!("Unexpected Kind: {e}"),
+        }
+        println!("{e}");
+
+        let word_list: WordList = ["A", "B"].into_iter().map(String::from).collect();
+        let report = VerifyReport::new::<str, String>(&word_list, vec![0, 0], false, 0, 2);
+        let e: Error = Error::new(Kind::VerifyFailed(report));
+        match e.kind() {
+            Kind::VerifyFailed(r) => assert_eq!(r.get_collisions(), [(0, 1)]),
+            _ => panic!("Unexpected Kind: {e}"),
+        }
+        assert_eq!(
+            e.to_string(),
+            "Verify Failed: 1 collisions [words 0 and 1 at 0], 1 gaps [1], 0 out of range []"
+        );
+
+        let e: Error = Error::new(Kind::TwoDArrayCollision {
+            word_a: String::from("ADD"),
+            word_b: String::from("AND"),
+            row: 0,
+            col: 3,
+        });
+        match e.kind() {
+            Kind::TwoDArrayCollision { word_b, col, .. } => {
+                assert_eq!(word_b, "AND");
+                assert_eq!(*col, 3);
+            }
+            _ => panic!("Unexpected Kind: {e}"),
+        }
+        assert_eq!(
+            e.to_string(),
+            "2D Array Collision: ADD === AND at row 0, column 3"
+        );
+
+        let e: Error = Error::new(Kind::PackingFailed {
+            row_index: 7,
+            rows_placed: 2,
+            cols: vec![0, 2],
+            occupancy: vec![true, false, true, true],
+        });
+        match e.kind() {
+            Kind::PackingFailed { row_index, .. } => assert_eq!(*row_index, 7),
+            _ => panic!("Unexpected Kind: {e}"),
+        }
+        assert_eq!(
+            e.to_string(),
+            "Packing Failed: row 7 of 2 columns [0, 2] does not fit (2 rows placed, 3 of 4 entries used)"
+        );
+
+        let e: Error = Error::new(Kind::ProbeBudgetExceeded {
+            row_index: 3,
+            stats: PackStats::default(),
+        });
+        match e.kind() {
+            Kind::ProbeBudgetExceeded { row_index, .. } => assert_eq!(*row_index, 3),
+            _ => panic!("Unexpected Kind: {e}"),
+        }
+        println!("{e}");
     }
 }
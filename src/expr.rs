@@ -0,0 +1,147 @@
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use crate::Position;
+
+/// An expression computing h1 or h2 from the characters of a word, see
+/// `HashAlgorithm::h1_expr`.
+///
+/// Unlike the pseudo code of `h1_as_text` the expression can be walked, so an
+/// emitter for another language can translate each node faithfully. Every
+/// value is a non-negative integer. Algorithms hashing byte strings treat each
+/// byte as the character with the same value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// A constant.
+    Const(usize),
+
+    /// The number of characters in the word.
+    Len,
+
+    /// The code point of the character at a position.
+    Char(Position),
+
+    /// The value paired with a character, `values[i]` where `keys[i]` is the
+    /// character the inner expression gives. Words with any other character
+    /// cannot be hashed.
+    Lookup {
+        /// The characters, in increasing order.
+        keys: Vec<char>,
+
+        /// The value of each character.
+        values: Vec<usize>,
+
+        /// The expression giving the character.
+        of: Box<Expr>,
+    },
+
+    /// The sum of two expressions.
+    Add(Box<Expr>, Box<Expr>),
+
+    /// The first expression minus the second, which is never larger.
+    Sub(Box<Expr>, Box<Expr>),
+
+    /// The product of two expressions.
+    Mul(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Evaluates the expression for a word.
+    ///
+    /// # Parameters
+    /// * `word` - The characters of the word.
+    ///
+    /// # Returns
+    /// The value, or None if the word is too short for a position, has a
+    /// character missing from a lookup, or the value is out of range.
+    #[must_use]
+    pub fn eval(&self, word: &[char]) -> Option<usize> {
+        match self {
+            Expr::Const(value) => Some(*value),
+            Expr::Len => Some(word.len()),
+            Expr::Char(position) => usize::try_from(u32::from(position.char_of(word)?)).ok(),
+            Expr::Lookup { keys, values, of } => {
+                let c = char::from_u32(u32::try_from(of.eval(word)?).ok()?)?;
+                values.get(keys.binary_search(&c).ok()?).copied()
+            }
+            Expr::Add(a, b) => a.eval(word)?.checked_add(b.eval(word)?),
+            Expr::Sub(a, b) => a.eval(word)?.checked_sub(b.eval(word)?),
+            Expr::Mul(a, b) => a.eval(word)?.checked_mul(b.eval(word)?),
+        }
+    }
+}
+
+impl Add for Expr {
+    type Output = Expr;
+
+    fn add(self, rhs: Expr) -> Expr {
+        Expr::Add(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Sub for Expr {
+    type Output = Expr;
+
+    fn sub(self, rhs: Expr) -> Expr {
+        Expr::Sub(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, rhs: Expr) -> Expr {
+        Expr::Mul(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Writes the expression with every operation in parentheses, characters
+    /// as `word[i]` counted from the start and `word[-i]` from the end, and
+    /// lookups as `lookup(of, "keys", [values])`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Const(value) => write!(f, "{value}"),
+            Expr::Len => write!(f, "len(word)"),
+            Expr::Char(Position::Start(i)) => write!(f, "word[{i}]"),
+            Expr::Char(Position::End(i)) => write!(f, "word[-{}]", i + 1),
+            Expr::Lookup { keys, values, of } => write!(
+                f,
+                "lookup({of}, {:?}, {values:?})",
+                keys.iter().collect::<String>()
+            ),
+            Expr::Add(a, b) => write!(f, "({a} + {b})"),
+            Expr::Sub(a, b) => write!(f, "({a} - {b})"),
+            Expr::Mul(a, b) => write!(f, "({a} * {b})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expr_unit_test() {
+        let expr = (Expr::Char(Position::Start(0)) - Expr::Const(65)) * Expr::Const(26)
+            + Expr::Lookup {
+                keys: vec!['A', 'E'],
+                values: vec![7, 9],
+                of: Box::new(Expr::Char(Position::End(0))),
+            }
+            + Expr::Len;
+        assert_eq!(
+            expr.to_string(),
+            "((((word[0] - 65) * 26) + lookup(word[-1], \"AE\", [7, 9])) + len(word))"
+        );
+        let word: Vec<char> = "BEE".chars().collect();
+        assert_eq!(expr.eval(&word), Some(26 + 9 + 3));
+        let word: Vec<char> = "BEB".chars().collect();
+        assert_eq!(expr.eval(&word), None);
+        assert_eq!(expr.eval(&[]), None);
+        assert_eq!(
+            (Expr::Const(1) - Expr::Char(Position::Start(0))).eval(&['A']),
+            None
+        );
+    }
+}
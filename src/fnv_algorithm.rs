@@ -0,0 +1,235 @@
+use crate::{Error, HashAlgorithm, ParamValue};
+
+/// The offset basis of the 64 bit FNV-1a hash.
+pub(crate) const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The prime of the 64 bit FNV-1a hash.
+pub(crate) const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Hashes bytes with the 64 bit FNV-1a hash.
+///
+/// # Parameters
+/// * `start` - The value the hash starts from, `FNV_OFFSET` for plain FNV-1a.
+/// * `bytes` - The bytes to be hashed.
+///
+/// # Returns
+/// The hash value.
+pub(crate) fn fnv1a(start: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(start, |val, b| {
+        (val ^ u64::from(*b)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Reduces a hash value into a range.
+///
+/// # Parameters
+/// * `val` - A hash value.
+/// * `range` - The number of values, at least 1.
+///
+/// # Returns
+/// `val` modulo `range`.
+pub(crate) fn reduce(val: u64, range: usize) -> usize {
+    usize::try_from(val % u64::try_from(range).unwrap_or(u64::MAX)).unwrap_or(0)
+}
+
+/// A hash algorithm built on the 64 bit FNV-1a hash of the whole word.
+///
+/// The row index is the FNV-1a hash of the bytes of the word modulo the number
+/// of rows, the column index is the quotient modulo the number of columns.
+/// Every byte of the word counts, so word lists whose prefixes and suffixes
+/// collide heavily are spread as well as any other.
+#[derive(Debug, Clone)]
+pub struct FnvAlgorithm {
+    /// The modulus of the row index.
+    num_rows: usize,
+
+    /// The modulus of the column index.
+    num_cols: usize,
+}
+
+impl FnvAlgorithm {
+    /// Creates a new FnvAlgorithm.
+    ///
+    /// # Parameters
+    /// * `num_rows` - The modulus of the row index, at least 1.
+    /// * `num_cols` - The modulus of the column index, at least 1.
+    #[must_use]
+    pub fn new(num_rows: usize, num_cols: usize) -> Self {
+        Self {
+            num_rows: num_rows.max(1),
+            num_cols: num_cols.max(1),
+        }
+    }
+
+    /// Gets the modulus of the row index.
+    ///
+    /// # Returns
+    /// The range of h1.
+    #[must_use]
+    pub fn get_num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Gets the modulus of the column index.
+    ///
+    /// # Returns
+    /// The range of h2.
+    #[must_use]
+    pub fn get_num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// A pseudo code representation of the FNV-1a hash of the word.
+    ///
+    /// # Returns
+    /// A string representation of the hash, leaving it in `val`.
+    fn fnv_as_text() -> String {
+        format!(
+            "\n        val = {FNV_OFFSET:#x}
+        for x in bytes(word_letters):
+            val = ((val ^ x) * {FNV_PRIME:#x}) & 0xffffffffffffffff"
+        )
+    }
+}
+
+impl HashAlgorithm for FnvAlgorithm {
+    /// Hash function h1 that is used internally to generate row indices.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    fn h1(&self, word: &str) -> Result<usize, Error> {
+        self.h1(word.as_bytes())
+    }
+
+    /// A string representation of the h1 function.
+    ///
+    /// # Returns
+    /// A string representation of the h1 function.
+    fn h1_as_text(&self) -> String {
+        <Self as HashAlgorithm<[u8]>>::h1_as_text(self)
+    }
+
+    /// Hash function h2 that is used internally to generate column indices.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    fn h2(&self, word: &str) -> Result<usize, Error> {
+        self.h2(word.as_bytes())
+    }
+
+    /// Hashes a word with both h1 and h2, computing the FNV-1a hash only once.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// The row and column indices.
+    fn hash_pair(&self, word: &str) -> Result<(usize, usize), Error> {
+        self.hash_pair(word.as_bytes())
+    }
+
+    /// A string representation of the h2 function.
+    ///
+    /// # Returns
+    /// A string representation of the h2 function.
+    fn h2_as_text(&self) -> String {
+        <Self as HashAlgorithm<[u8]>>::h2_as_text(self)
+    }
+
+    fn name(&self) -> &'static str {
+        <Self as HashAlgorithm<[u8]>>::name(self)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        <Self as HashAlgorithm<[u8]>>::parameters(self)
+    }
+}
+
+impl HashAlgorithm<[u8]> for FnvAlgorithm {
+    fn h1(&self, word: &[u8]) -> Result<usize, Error> {
+        Ok(reduce(fnv1a(FNV_OFFSET, word), self.num_rows))
+    }
+
+    fn h2(&self, word: &[u8]) -> Result<usize, Error> {
+        let rows = u64::try_from(self.num_rows).unwrap_or(u64::MAX);
+        Ok(reduce(fnv1a(FNV_OFFSET, word) / rows, self.num_cols))
+    }
+
+    fn hash_pair(&self, word: &[u8]) -> Result<(usize, usize), Error> {
+        let val = fnv1a(FNV_OFFSET, word);
+        let rows = u64::try_from(self.num_rows).unwrap_or(u64::MAX);
+        Ok((
+            reduce(val, self.num_rows),
+            reduce(val / rows, self.num_cols),
+        ))
+    }
+
+    fn h1_as_text(&self) -> String {
+        format!(
+            "{}\n        val = val % {}",
+            Self::fnv_as_text(),
+            self.num_rows
+        )
+    }
+
+    fn h2_as_text(&self) -> String {
+        format!(
+            "{}\n        val = (val // {}) % {}",
+            Self::fnv_as_text(),
+            self.num_rows,
+            self.num_cols
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "fnv"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("num_rows", ParamValue::from(self.num_rows)),
+            ("num_cols", ParamValue::from(self.num_cols)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv_algorithm_unit_test() {
+        // Published FNV-1a test vectors.
+        assert_eq!(fnv1a(FNV_OFFSET, b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(FNV_OFFSET, b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(FNV_OFFSET, b"foobar"), 0x8594_4171_f739_67e8);
+
+        let hash_algorithm = FnvAlgorithm::new(0, 1 << 16);
+        println!("{hash_algorithm:?}");
+        assert_eq!(hash_algorithm.get_num_rows(), 1);
+        assert_eq!(hash_algorithm.get_num_cols(), 1 << 16);
+        assert_eq!(hash_algorithm.h1("a").unwrap(), 0);
+        assert_eq!(hash_algorithm.h2("a").unwrap(), 0xec8c);
+
+        let hash_algorithm = FnvAlgorithm::new(1 << 16, 1 << 16);
+        assert_eq!(hash_algorithm.h1("a").unwrap(), 0xec8c);
+        assert_eq!(hash_algorithm.h2("a").unwrap(), 0x8601);
+        assert_eq!(
+            hash_algorithm.h2("a").unwrap(),
+            hash_algorithm.h2(&b"a"[..]).unwrap()
+        );
+
+        let text = HashAlgorithm::<str>::h1_as_text(&hash_algorithm);
+        assert!(text.contains("0xcbf29ce484222325"));
+        assert!(text.contains("0x100000001b3"));
+        assert!(text.ends_with("val = val % 65536"));
+        assert!(HashAlgorithm::<str>::h2_as_text(&hash_algorithm)
+            .ends_with("val = (val // 65536) % 65536"));
+    }
+}
@@ -0,0 +1,145 @@
+use std::iter::successors;
+use std::ops::Range;
+
+/// The number of slots tracked by each word of the bit vector.
+const BITS: usize = u64::BITS as usize;
+
+/// Tracks the unused indices of the packed array.
+///
+/// Each index is one bit of a fixed bit vector, set while the index is unused,
+/// so membership checks and updates are O(1) and searching for the next unused
+/// index skips a whole word of used indices at a time.
+#[derive(Debug, Clone)]
+pub struct FreeSlots {
+    /// One bit per index, set when the index is unused.
+    words: Vec<u64>,
+
+    /// The number of indices tracked.
+    len: usize,
+}
+
+impl FreeSlots {
+    /// Creates a tracker with every index unused.
+    ///
+    /// # Parameters
+    /// * `len` - The number of indices to track.
+    pub fn new(len: usize) -> Self {
+        let mut words = vec![u64::MAX; len.div_ceil(BITS)];
+        if let Some(last) = words.last_mut() {
+            let used_bits = len % BITS;
+            if used_bits != 0 {
+                *last = (1 << used_bits) - 1;
+            }
+        }
+        Self { words, len }
+    }
+
+    /// Tests whether an index is unused.
+    ///
+    /// # Parameters
+    /// * `index` - The index to test.
+    ///
+    /// # Returns
+    /// True if the index is unused. Indices out of range are never unused.
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.len && self.words[index / BITS] & (1 << (index % BITS)) != 0
+    }
+
+    /// Marks an index as unused.
+    ///
+    /// # Parameters
+    /// * `index` - The index to mark.
+    pub fn insert(&mut self, index: usize) {
+        self.words[index / BITS] |= 1 << (index % BITS);
+    }
+
+    /// Marks an index as used.
+    ///
+    /// # Parameters
+    /// * `index` - The index to mark.
+    pub fn remove(&mut self, index: usize) {
+        self.words[index / BITS] &= !(1 << (index % BITS));
+    }
+
+    /// Finds the smallest unused index.
+    ///
+    /// # Returns
+    /// The smallest unused index, or `None` if every index is used.
+    pub fn first(&self) -> Option<usize> {
+        self.next(0)
+    }
+
+    /// Finds the smallest unused index at or after a given index.
+    ///
+    /// # Parameters
+    /// * `from` - The index to start searching at.
+    ///
+    /// # Returns
+    /// The smallest unused index that is at least `from`, or `None` if there is
+    /// none.
+    pub fn next(&self, from: usize) -> Option<usize> {
+        if from >= self.len {
+            return None;
+        }
+        let mut word_index = from / BITS;
+        let mut word = self.words[word_index] & (u64::MAX << (from % BITS));
+        loop {
+            if word != 0 {
+                return Some(word_index * BITS + word.trailing_zeros() as usize);
+            }
+            word_index += 1;
+            word = *self.words.get(word_index)?;
+        }
+    }
+
+    /// Iterates over the unused indices in a range.
+    ///
+    /// # Parameters
+    /// * `range` - The indices to iterate over.
+    ///
+    /// # Returns
+    /// The unused indices in `range`, in increasing order.
+    pub fn range(&self, range: Range<usize>) -> impl Iterator<Item = usize> + '_ {
+        successors(self.next(range.start), move |i| self.next(i + 1))
+            .take_while(move |i| *i < range.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_slots_unit_test() {
+        let mut free_slots = FreeSlots::new(130);
+        println!("{free_slots:?}");
+        assert_eq!(free_slots.first(), Some(0));
+        assert!(free_slots.contains(129));
+        assert!(!free_slots.contains(130));
+        assert_eq!(free_slots.range(0..130).count(), 130);
+
+        for i in 0..128 {
+            free_slots.remove(i);
+        }
+        assert!(!free_slots.contains(5));
+        assert_eq!(free_slots.first(), Some(128));
+        assert_eq!(free_slots.next(129), Some(129));
+        assert_eq!(free_slots.next(130), None);
+
+        free_slots.insert(5);
+        free_slots.insert(64);
+        assert!(free_slots.contains(5));
+        assert_eq!(free_slots.first(), Some(5));
+        assert_eq!(free_slots.next(6), Some(64));
+        assert_eq!(free_slots.range(5..129).collect::<Vec<_>>(), [5, 64, 128]);
+        assert_eq!(free_slots.range(6..64).count(), 0);
+
+        let free_slots = FreeSlots::new(0);
+        assert_eq!(free_slots.first(), None);
+        assert_eq!(free_slots.range(0..0).count(), 0);
+
+        let free_slots = FreeSlots::new(64);
+        assert_eq!(free_slots.next(63), Some(63));
+        assert_eq!(free_slots.next(64), None);
+    }
+}
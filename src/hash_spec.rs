@@ -0,0 +1,589 @@
+//! A machine readable description of a generated hash, see `HashData::to_json`.
+
+use std::fmt::{Display, Write};
+
+use crate::{
+    CichelliAlgorithm, ElcAlgorithm, ElcWithLength, Error, FnvAlgorithm, HashAlgorithm, HashData,
+    Key, Kind, Position, PositionAlgorithm, SeededAlgorithm,
+};
+
+/// The version of the document written by `HashData::to_json`. It is raised
+/// whenever a field is removed or changes meaning; new fields may be added
+/// without raising it.
+pub const JSON_FORMAT_VERSION: u32 = 1;
+
+/// The value of a parameter of a hash algorithm, see
+/// `HashAlgorithm::parameters`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamValue {
+    /// A whole number.
+    Number(u64),
+
+    /// A flag.
+    Bool(bool),
+
+    /// A string, such as the characters of an alphabet.
+    Text(String),
+
+    /// A list of values.
+    List(Vec<ParamValue>),
+}
+
+impl From<usize> for ParamValue {
+    fn from(value: usize) -> Self {
+        ParamValue::Number(u64::try_from(value).unwrap_or(u64::MAX))
+    }
+}
+
+impl From<u64> for ParamValue {
+    fn from(value: u64) -> Self {
+        ParamValue::Number(value)
+    }
+}
+
+impl From<bool> for ParamValue {
+    fn from(value: bool) -> Self {
+        ParamValue::Bool(value)
+    }
+}
+
+impl From<String> for ParamValue {
+    fn from(value: String) -> Self {
+        ParamValue::Text(value)
+    }
+}
+
+impl ParamValue {
+    /// Gets the value as a number.
+    ///
+    /// # Returns
+    /// The number, or None if the value is not a number.
+    #[must_use]
+    pub fn as_number(&self) -> Option<u64> {
+        match self {
+            ParamValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a flag.
+    ///
+    /// # Returns
+    /// The flag, or None if the value is not a flag.
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ParamValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a string.
+    ///
+    /// # Returns
+    /// The string, or None if the value is not a string.
+    #[must_use]
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            ParamValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a list.
+    ///
+    /// # Returns
+    /// The values of the list, or None if the value is not a list.
+    #[must_use]
+    pub fn as_list(&self) -> Option<&[ParamValue]> {
+        match self {
+            ParamValue::List(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Gets the items of a list, where a single value is a list of one item.
+    ///
+    /// # Returns
+    /// The values of a list, or the value itself.
+    fn items(&self) -> &[ParamValue] {
+        match self {
+            ParamValue::List(values) => values,
+            value => std::slice::from_ref(value),
+        }
+    }
+
+    /// Parses a value written as text, such as on a command line: a whole
+    /// number, `true` or `false`, a comma separated list of such values, or
+    /// otherwise a string. A string made only of digits is read as a number.
+    ///
+    /// # Parameters
+    /// * `text` - The text.
+    ///
+    /// # Returns
+    /// The value.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        if text.contains(',') {
+            return ParamValue::List(text.split(',').map(ParamValue::parse).collect());
+        }
+        match text {
+            "true" => ParamValue::Bool(true),
+            "false" => ParamValue::Bool(false),
+            _ => text
+                .parse()
+                .map_or_else(|_| ParamValue::Text(text.to_string()), ParamValue::Number),
+        }
+    }
+
+    /// Writes the value as JSON.
+    ///
+    /// # Returns
+    /// The JSON value.
+    fn to_json(&self) -> String {
+        match self {
+            ParamValue::Number(n) => n.to_string(),
+            ParamValue::Bool(b) => b.to_string(),
+            ParamValue::Text(text) => string(text),
+            ParamValue::List(values) => format!(
+                "[{}]",
+                values
+                    .iter()
+                    .map(ParamValue::to_json)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+/// Rebuilds a hash algorithm from the name and parameters it reported, see
+/// `HashAlgorithm::name` and `HashAlgorithm::parameters`, such as those read
+/// back from `HashData::to_json`.
+///
+/// The ELC, ELC with length, position, FNV, seeded and Cichelli algorithms can
+/// be rebuilt. Case folding is not a parameter, wrap the algorithm in a
+/// `CaseFold` when the saved hash folds case. A list parameter given a single
+/// value is read as a list of one value. To select an algorithm by name with
+/// defaults for missing parameters, see `AlgorithmRegistry`.
+///
+/// # Parameters
+/// * `name` - The name of the algorithm.
+/// * `parameters` - The name and value of each parameter.
+///
+/// # Returns
+/// The algorithm, which hashes every word the same as the algorithm that
+/// reported the name and parameters.
+///
+/// # Errors
+/// Will return `Err` if no algorithm has the name, such as for closures and
+/// custom algorithms, or a parameter is missing or invalid.
+pub fn algorithm_from_spec<N: AsRef<str>>(
+    name: &str,
+    parameters: &[(N, ParamValue)],
+) -> Result<Box<dyn HashAlgorithm>, Error> {
+    let spec = Spec { name, parameters };
+    Ok(match name {
+        "elc" => Box::new(spec.elc_algorithm()?),
+        "elc_with_length" => Box::new(ElcWithLength::new(
+            spec.elc_algorithm()?,
+            spec.number("max_len")?,
+        )),
+        "position" => {
+            let h1_positions = spec.positions("h1_positions")?;
+            let h2_positions = spec.positions("h2_positions")?;
+            let algorithm = match spec.get("alphabet") {
+                Some(_) => {
+                    PositionAlgorithm::with_alphabet(h1_positions, h2_positions, spec.alphabet()?)?
+                }
+                None => PositionAlgorithm::new(h1_positions, h2_positions),
+            };
+            match spec.get("max_len") {
+                Some(_) => Box::new(algorithm.with_length(spec.number("max_len")?)),
+                None => Box::new(algorithm),
+            }
+        }
+        "fnv" => Box::new(FnvAlgorithm::new(
+            spec.number("num_rows")?,
+            spec.number("num_cols")?,
+        )),
+        "seeded" => Box::new(SeededAlgorithm::new(
+            spec.get("seed")
+                .and_then(ParamValue::as_number)
+                .ok_or_else(|| spec.invalid("seed"))?,
+            spec.number("num_rows")?,
+            spec.number("num_cols")?,
+        )),
+        "cichelli" => {
+            let letters = spec.alphabet_of("letters")?;
+            let weights = spec
+                .get("weights")
+                .map(ParamValue::items)
+                .filter(|weights| weights.len() == letters.len())
+                .and_then(|weights| {
+                    weights
+                        .iter()
+                        .map(|weight| weight.as_number().and_then(|n| usize::try_from(n).ok()))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| spec.invalid("weights"))?;
+            Box::new(CichelliAlgorithm::new(letters.into_iter().zip(weights)))
+        }
+        _ => {
+            return Err(Error::new(Kind::SpecError(format!(
+                "Unknown hash algorithm '{name}'."
+            ))))
+        }
+    })
+}
+
+/// The name and parameters of a hash algorithm being rebuilt.
+struct Spec<'a, N> {
+    /// The name of the algorithm.
+    name: &'a str,
+
+    /// The name and value of each parameter.
+    parameters: &'a [(N, ParamValue)],
+}
+
+impl<N: AsRef<str>> Spec<'_, N> {
+    /// Gets a parameter.
+    ///
+    /// # Parameters
+    /// * `key` - The name of the parameter.
+    ///
+    /// # Returns
+    /// The value, or None if the parameter is missing.
+    fn get(&self, key: &str) -> Option<&ParamValue> {
+        self.parameters
+            .iter()
+            .find(|(name, _)| name.as_ref() == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Builds the error of a missing or invalid parameter.
+    ///
+    /// # Parameters
+    /// * `key` - The name of the parameter.
+    ///
+    /// # Returns
+    /// The error.
+    fn invalid(&self, key: &str) -> Error {
+        Error::new(Kind::SpecError(format!(
+            "Missing or invalid parameter '{key}' of the {} algorithm.",
+            self.name
+        )))
+    }
+
+    /// Gets a number parameter.
+    ///
+    /// # Parameters
+    /// * `key` - The name of the parameter.
+    ///
+    /// # Errors
+    /// Will return `Err` if the parameter is missing or not a number.
+    fn number(&self, key: &str) -> Result<usize, Error> {
+        self.get(key)
+            .and_then(ParamValue::as_number)
+            .and_then(|n| usize::try_from(n).ok())
+            .ok_or_else(|| self.invalid(key))
+    }
+
+    /// Gets the characters of a string parameter.
+    ///
+    /// # Parameters
+    /// * `key` - The name of the parameter.
+    ///
+    /// # Errors
+    /// Will return `Err` if the parameter is missing or not a string.
+    fn alphabet_of(&self, key: &str) -> Result<Vec<char>, Error> {
+        self.get(key)
+            .and_then(ParamValue::as_text)
+            .map(|text| text.chars().collect())
+            .ok_or_else(|| self.invalid(key))
+    }
+
+    /// Gets the `alphabet` parameter.
+    ///
+    /// # Errors
+    /// Will return `Err` if the parameter is missing or not a string.
+    fn alphabet(&self) -> Result<Vec<char>, Error> {
+        self.alphabet_of("alphabet")
+    }
+
+    /// Gets a list of positions, each written as `start i` or `end i`.
+    ///
+    /// # Parameters
+    /// * `key` - The name of the parameter.
+    ///
+    /// # Errors
+    /// Will return `Err` if the parameter is missing or not a list of positions.
+    fn positions(&self, key: &str) -> Result<Vec<Position>, Error> {
+        self.get(key)
+            .map(ParamValue::items)
+            .and_then(|positions| {
+                positions
+                    .iter()
+                    .map(|position| {
+                        let (end, i) = position.as_text()?.split_once(' ')?;
+                        let i = i.parse().ok()?;
+                        match end {
+                            "start" => Some(Position::Start(i)),
+                            "end" => Some(Position::End(i)),
+                            _ => None,
+                        }
+                    })
+                    .collect()
+            })
+            .ok_or_else(|| self.invalid(key))
+    }
+
+    /// Rebuilds an `ElcAlgorithm` from the `elc_h1`, `elc_h2`, `num_vals` and
+    /// `alphabet` parameters.
+    ///
+    /// # Errors
+    /// Will return `Err` if a parameter is missing or invalid.
+    fn elc_algorithm(&self) -> Result<ElcAlgorithm, Error> {
+        let elc_h1 = self.number("elc_h1")?;
+        let elc_h2 = self.number("elc_h2")?;
+        let algorithm = match self.get("alphabet") {
+            Some(_) => ElcAlgorithm::with_alphabet(elc_h1, self.alphabet()?)?,
+            None => ElcAlgorithm::new(elc_h1, self.number("num_vals")?),
+        };
+        Ok(algorithm.with_elcs(elc_h1, elc_h2))
+    }
+}
+
+/// Writes a hash as a JSON document.
+///
+/// # Parameters
+/// * `hash_data` - The hash.
+///
+/// # Returns
+/// The document, see `HashData::to_json` for its fields.
+pub(crate) fn to_json<K: Key + ?Sized>(hash_data: &HashData<K>) -> String {
+    let rlt = hash_data.as_fn.get_rlt();
+    let hash_algorithm = hash_data.as_fn.get_hash_algorithm();
+
+    let parameters = hash_algorithm
+        .parameters()
+        .iter()
+        .map(|(name, value)| (*name, value.to_json()))
+        .collect::<Vec<_>>();
+    let algorithm = object(&[
+        ("name", string(hash_algorithm.name())),
+        ("parameters", object(&parameters)),
+        ("folds_case", hash_algorithm.folds_case().to_string()),
+        ("h1", string(&hash_algorithm.h1_as_text())),
+        ("h2", string(&hash_algorithm.h2_as_text())),
+    ]);
+
+    let mut fields = vec![
+        ("format_version", JSON_FORMAT_VERSION.to_string()),
+        ("algorithm", algorithm),
+    ];
+    if let Some(chm) = rlt.get_chm() {
+        fields.push(("backend", string("chm")));
+        fields.push((
+            "chm",
+            object(&[
+                ("vertex_values", array(chm.get_values())),
+                ("keys", array(chm.get_keys())),
+                ("stride", chm.get_stride().to_string()),
+                ("part_len", chm.get_part_len().to_string()),
+            ]),
+        ));
+    } else {
+        let backend = if rlt.get_multipliers().is_some() {
+            "chd"
+        } else {
+            "packed"
+        };
+        fields.push(("backend", string(backend)));
+
+        let mut table = vec![
+            ("element_type", string(rlt.element_type().name())),
+            ("values", array(rlt.get_values())),
+        ];
+        if let Some(rows) = rlt.get_rows() {
+            table.push(("rows", array(rows)));
+        }
+        if let Some(two_level) = rlt.get_two_level() {
+            table.push((
+                "two_level",
+                object(&[
+                    ("block_bits", two_level.get_block_bits().to_string()),
+                    ("first_level", array(two_level.get_first_level())),
+                    ("blocks", array(two_level.get_blocks())),
+                ]),
+            ));
+        }
+        if let Some(multipliers) = rlt.get_multipliers() {
+            table.push(("multipliers", array(multipliers)));
+        }
+        fields.push(("row_lookup_table", object(&table)));
+    }
+    fields.push(("table_len", rlt.get_num_entries().to_string()));
+    fields.push(("hash_base", rlt.get_hash_base().to_string()));
+    fields.push(("max_word_len", hash_data.max_word_len.to_string()));
+    fields.push(("hash_function", string(&hash_data.as_string)));
+    object(&fields)
+}
+
+/// Writes a JSON object.
+///
+/// # Parameters
+/// * `fields` - The name and JSON value of each field, in order.
+///
+/// # Returns
+/// The object.
+fn object(fields: &[(&str, String)]) -> String {
+    format!(
+        "{{{}}}",
+        fields
+            .iter()
+            .map(|(name, value)| format!("{}:{value}", string(name)))
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+/// Writes a JSON array of numbers.
+///
+/// # Parameters
+/// * `values` - The numbers.
+///
+/// # Returns
+/// The array.
+fn array<T: Display>(values: &[T]) -> String {
+    format!(
+        "[{}]",
+        values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+/// Writes a JSON string, escaping quotes, backslashes and control characters.
+///
+/// # Parameters
+/// * `text` - The string.
+///
+/// # Returns
+/// The quoted string.
+pub(crate) fn string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", u32::from(c));
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::CaseFold;
+
+    #[test]
+    fn algorithm_from_spec_unit_test() {
+        let algorithms: Vec<Box<dyn HashAlgorithm>> = vec![
+            Box::new(ElcAlgorithm::new(2, 30).with_elcs(1, 2)),
+            Box::new(ElcAlgorithm::with_alphabet(1, "ABCDEZ".chars()).unwrap()),
+            Box::new(ElcWithLength::new(ElcAlgorithm::default(), 9)),
+            Box::new(
+                PositionAlgorithm::with_alphabet(
+                    [Position::Start(1), Position::End(0)],
+                    [Position::End(2)],
+                    "XYZ".chars(),
+                )
+                .unwrap()
+                .with_length(7),
+            ),
+            Box::new(PositionAlgorithm::new(
+                [Position::Start(0)],
+                [Position::End(0)],
+            )),
+            Box::new(FnvAlgorithm::new(32, 256)),
+            Box::new(SeededAlgorithm::new(u64::MAX, 4, 8)),
+            Box::new(CichelliAlgorithm::new([('A', 1), ('B', 3)])),
+            Box::new(CaseFold::new(FnvAlgorithm::new(3, 5))),
+        ];
+        for algorithm in &algorithms {
+            let rebuilt = algorithm_from_spec(algorithm.name(), &algorithm.parameters()).unwrap();
+            assert_eq!(rebuilt.name(), algorithm.name());
+            assert_eq!(rebuilt.parameters(), algorithm.parameters());
+            for word in ["AB", "BA", "XYZZY", "ZEBRA"] {
+                assert_eq!(rebuilt.h1(word).ok(), algorithm.h1(word).ok());
+                assert_eq!(rebuilt.h2(word).ok(), algorithm.h2(word).ok());
+            }
+        }
+
+        let Err(e) = algorithm_from_spec::<&str>("closure", &[]) else {
+            panic!("closures cannot be rebuilt");
+        };
+        assert_eq!(
+            e.to_string(),
+            "Spec Error: Unknown hash algorithm 'closure'."
+        );
+        let Err(e) = algorithm_from_spec("fnv", &[("num_rows", ParamValue::from(true))]) else {
+            panic!("num_rows is not a number");
+        };
+        assert_eq!(
+            e.to_string(),
+            "Spec Error: Missing or invalid parameter 'num_rows' of the fnv algorithm."
+        );
+        let parameters = [(
+            "h1_positions".to_string(),
+            ParamValue::List(vec![ParamValue::from("middle 1".to_string())]),
+        )];
+        assert!(algorithm_from_spec("position", &parameters).is_err());
+    }
+
+    #[test]
+    fn hash_spec_unit_test() {
+        assert_eq!(string("a \"b\"\\\n\u{1}"), "\"a \\\"b\\\"\\\\\\n\\u0001\"");
+        assert_eq!(array::<isize>(&[]), "[]");
+        assert_eq!(array(&[1, -2]), "[1,-2]");
+        assert_eq!(
+            object(&[("a", "1".to_string()), ("b", string("x"))]),
+            "{\"a\":1,\"b\":\"x\"}"
+        );
+        assert_eq!(ParamValue::parse("12"), ParamValue::Number(12));
+        assert_eq!(ParamValue::parse("false"), ParamValue::Bool(false));
+        assert_eq!(
+            ParamValue::parse("start 1"),
+            ParamValue::Text("start 1".to_string())
+        );
+        assert_eq!(
+            ParamValue::parse("1,x"),
+            ParamValue::List(vec![
+                ParamValue::Number(1),
+                ParamValue::Text("x".to_string())
+            ])
+        );
+        assert_eq!(
+            ParamValue::List(vec![
+                ParamValue::from(3_usize),
+                ParamValue::from(true),
+                ParamValue::from("AB".to_string()),
+            ])
+            .to_json(),
+            "[3,true,\"AB\"]"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+
+/// The steps of hashing a word, see `HashData::explain`.
+///
+/// Each field is a value the pseudo code of the hash computes, so a port of
+/// the pseudo code to another language can be checked step by step. Displays
+/// as one line per step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashTrace {
+    /// The row index, the value of h1.
+    pub row_index: usize,
+
+    /// The column index, the value of h2.
+    pub col_index: usize,
+
+    /// The entry of `row_lookup_table` read for the row. The row index itself
+    /// unless the table is sparse or compressed into two levels.
+    pub rlt_index: usize,
+
+    /// The value of the `row_lookup_table` entry.
+    pub rlt_value: isize,
+
+    /// The `row_multiplier` of the row, when the hash has multipliers.
+    pub multiplier: Option<usize>,
+
+    /// The column index reduced modulo `modulus` and then multiplied by the
+    /// multiplier, which leaves the hash value the same as the pseudo code's
+    /// `row_multiplier[row_index] * col_index`. The column index itself without
+    /// multipliers.
+    pub scaled_col_index: usize,
+
+    /// The table value plus the scaled column index.
+    pub sum: isize,
+
+    /// The number of hash values the sum is reduced modulo.
+    pub modulus: usize,
+
+    /// The mask the sum is reduced with instead, when the table was rounded up
+    /// to a power of two, see `Options::power_of_two_table`.
+    pub mask: Option<usize>,
+
+    /// The smallest hash value, see `Options::hash_base`.
+    pub hash_base: usize,
+
+    /// The hash value, the sum modulo `modulus`, or masked with `mask`, plus
+    /// `hash_base`.
+    pub hash_value: usize,
+}
+
+impl fmt::Display for HashTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "row_index = {}", self.row_index)?;
+        writeln!(f, "col_index = {}", self.col_index)?;
+        if self.rlt_index != self.row_index {
+            writeln!(f, "rlt_index = {}", self.rlt_index)?;
+        }
+        writeln!(
+            f,
+            "row_lookup_table[{}] = {}",
+            self.rlt_index, self.rlt_value
+        )?;
+        if let Some(multiplier) = self.multiplier {
+            writeln!(
+                f,
+                "scaled_col_index = ({} % {}) * {multiplier} = {}",
+                self.col_index, self.modulus, self.scaled_col_index
+            )?;
+        }
+        writeln!(
+            f,
+            "sum = {} + {} = {}",
+            self.rlt_value, self.scaled_col_index, self.sum
+        )?;
+        write!(f, "hash_value = {} ", self.sum)?;
+        match self.mask {
+            Some(mask) => write!(f, "& {mask}")?,
+            None => write!(f, "% {}", self.modulus)?,
+        }
+        if self.hash_base != 0 {
+            write!(f, " + {}", self.hash_base)?;
+        }
+        write!(f, " = {}", self.hash_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_trace_unit_test() {
+        let trace = HashTrace {
+            row_index: 4,
+            col_index: 17,
+            rlt_index: 4,
+            rlt_value: -3,
+            multiplier: None,
+            scaled_col_index: 17,
+            sum: 14,
+            modulus: 8,
+            mask: None,
+            hash_base: 0,
+            hash_value: 6,
+        };
+        assert_eq!(
+            trace.to_string(),
+            "row_index = 4\n\
+             col_index = 17\n\
+             row_lookup_table[4] = -3\n\
+             sum = -3 + 17 = 14\n\
+             hash_value = 14 % 8 = 6"
+        );
+
+        let trace = HashTrace {
+            rlt_index: 1,
+            multiplier: Some(3),
+            scaled_col_index: 3,
+            sum: 0,
+            hash_value: 0,
+            ..trace
+        };
+        assert_eq!(
+            trace.to_string(),
+            "row_index = 4\n\
+             col_index = 17\n\
+             rlt_index = 1\n\
+             row_lookup_table[1] = -3\n\
+             scaled_col_index = (17 % 8) * 3 = 3\n\
+             sum = -3 + 3 = 0\n\
+             hash_value = 0 % 8 = 0"
+        );
+
+        let trace = HashTrace {
+            hash_base: 1,
+            hash_value: 1,
+            ..trace
+        };
+        assert!(trace.to_string().ends_with("\nhash_value = 0 % 8 + 1 = 1"));
+
+        let trace = HashTrace {
+            mask: Some(7),
+            hash_base: 0,
+            hash_value: 0,
+            ..trace
+        };
+        assert!(trace.to_string().ends_with("\nhash_value = 0 & 7 = 0"));
+    }
+}
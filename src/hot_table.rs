@@ -0,0 +1,176 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+
+use crate::{Error, Kind, MsmpMap, Options, WordList};
+
+/// Owns the current `MsmpMap` of a long running service and replaces it when
+/// the word list changes.
+///
+/// A reload regenerates the map on a background thread, using the options the
+/// table was created with, so budgets bound how long a reload can take. Readers
+/// keep using the current map until the new one is ready, and then it is swapped
+/// in atomically. A failed reload leaves the current map in place.
+pub struct HotTable<V> {
+    /// The current map.
+    current: Arc<RwLock<Arc<MsmpMap<V>>>>,
+
+    /// The options used to regenerate the map.
+    options: Options,
+
+    /// The number of the latest reload. Only the latest reload may swap its map in.
+    generation: Arc<AtomicU64>,
+
+    /// The latest reload, if it has not been waited on.
+    pending: Option<JoinHandle<Result<(), Error>>>,
+}
+
+impl<V> fmt::Debug for HotTable<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HotTable")
+            .field("options", &self.options)
+            .field("generation", &self.generation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V: Send + Sync + 'static> HotTable<V> {
+    /// Creates a new hot table.
+    ///
+    /// # Parameters
+    /// * `map` - The initial map.
+    /// * `options` - The options used to regenerate the map on reload.
+    #[must_use]
+    pub fn new(map: MsmpMap<V>, options: Options) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(map))),
+            options,
+            generation: Arc::new(AtomicU64::new(0)),
+            pending: None,
+        }
+    }
+
+    /// Gets the current map.
+    ///
+    /// The returned map stays valid, and unchanged, even if a reload swaps in a
+    /// new map while it is being used.
+    ///
+    /// # Returns
+    /// The current map.
+    #[must_use]
+    pub fn current(&self) -> Arc<MsmpMap<V>> {
+        match self.current.read() {
+            Ok(current) => Arc::clone(&current),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+        }
+    }
+
+    /// Starts regenerating the map for a new word list in the background.
+    ///
+    /// The `elc` value is chosen automatically, see `MsmpMap::auto`. A reload
+    /// supersedes any reload still in progress; the superseded map is never
+    /// swapped in.
+    ///
+    /// # Parameters
+    /// * `word_list` - The new keys of the map.
+    /// * `values` - One value per word, in word list order.
+    pub fn reload(&mut self, word_list: WordList, values: Vec<V>) {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let latest = Arc::clone(&self.generation);
+        let current = Arc::clone(&self.current);
+        let options = self.options.clone();
+        self.pending = Some(thread::spawn(move || {
+            let map = MsmpMap::auto(&word_list, values, &options)?;
+            let mut current = match current.write() {
+                Ok(current) => current,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            if latest.load(Ordering::SeqCst) == generation {
+                *current = Arc::new(map);
+            }
+            Ok(())
+        }));
+    }
+
+    /// Returns true if the latest reload is still running.
+    ///
+    /// # Returns
+    /// True if a reload is in progress.
+    #[must_use]
+    pub fn is_reloading(&self) -> bool {
+        self.pending
+            .as_ref()
+            .is_some_and(|pending| !pending.is_finished())
+    }
+
+    /// Waits for the latest reload to finish.
+    ///
+    /// # Returns
+    /// `Ok(())` if there was no reload to wait on, or if it succeeded.
+    ///
+    /// # Errors
+    /// Will return `Err` with the reason the latest reload failed. The current
+    /// map is unchanged in that case.
+    pub fn wait(&mut self) -> Result<(), Error> {
+        match self.pending.take() {
+            Some(pending) => pending.join().unwrap_or_else(|_| {
+                Err(Error::new(Kind::HashError(
+                    "Reload thread panicked.".to_string(),
+                )))
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ElcAlgorithm;
+
+    fn word_list(words: &[&str]) -> WordList {
+        words.iter().map(|w| (*w).to_string()).collect()
+    }
+
+    #[test]
+    fn hot_table_unit_test() {
+        let options = Options::default();
+        let map = MsmpMap::new(
+            &word_list(&["AND", "BEGIN"]),
+            vec![1, 2],
+            ElcAlgorithm::default(),
+            &options,
+        )
+        .unwrap();
+        let mut table = HotTable::new(map, options);
+        println!("{table:?}");
+        assert!(!table.is_reloading());
+        assert!(table.wait().is_ok());
+
+        let before = table.current();
+        table.reload(
+            word_list(&["AND", "BEGIN", "CHAR", "END"]),
+            vec![1, 2, 3, 4],
+        );
+        table.wait().unwrap();
+        assert_eq!(before.get("CHAR"), None);
+        assert_eq!(table.current().get("CHAR"), Some(&3));
+        assert_eq!(table.current().len(), 4);
+
+        // A failed reload keeps the current map.
+        table.reload(word_list(&["AB", "ABAB"]), vec![1, 2]);
+        match table.wait().unwrap_err().kind() {
+            Kind::HashError(_) => {}
+            _ => panic!("Unexpected error type."),
+        }
+        assert_eq!(table.current().get("CHAR"), Some(&3));
+
+        // Only the latest reload is swapped in.
+        table.reload(word_list(&["ELSE", "EOF"]), vec![5, 6]);
+        table.reload(word_list(&["CONST", "ENTER"]), vec![7, 8]);
+        table.wait().unwrap();
+        assert_eq!(table.current().get("ELSE"), None);
+        assert_eq!(table.current().get("ENTER"), Some(&8));
+    }
+}
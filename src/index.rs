@@ -0,0 +1,75 @@
+//! The integer type used to store word and column indices internally.
+//!
+//! The 2D array and the packed array hold one index per word. With the
+//! `compact-index` feature they are stored as `u32`, which halves their memory
+//! and limits word lists, and column indices, to `u32::MAX` entries.
+
+/// The integer type used to store word and column indices.
+#[cfg(feature = "compact-index")]
+pub type Index = u32;
+
+/// The integer type used to store word and column indices.
+#[cfg(not(feature = "compact-index"))]
+pub type Index = usize;
+
+/// Converts a value to the stored index type.
+///
+/// # Parameters
+/// * `value` - The value to convert.
+///
+/// # Returns
+/// The value as an index, or `None` if it does not fit.
+#[cfg(feature = "compact-index")]
+pub fn to_index(value: usize) -> Option<Index> {
+    Index::try_from(value).ok()
+}
+
+/// Converts a value to the stored index type.
+///
+/// # Parameters
+/// * `value` - The value to convert.
+///
+/// # Returns
+/// The value as an index, or `None` if it does not fit.
+#[cfg(not(feature = "compact-index"))]
+#[allow(clippy::unnecessary_wraps)]
+pub fn to_index(value: usize) -> Option<Index> {
+    Some(value)
+}
+
+/// Converts a stored index back to a `usize`.
+///
+/// # Parameters
+/// * `index` - The index to convert.
+///
+/// # Returns
+/// The index as a `usize`.
+#[cfg(feature = "compact-index")]
+pub fn from_index(index: Index) -> usize {
+    usize::try_from(index).unwrap_or(usize::MAX)
+}
+
+/// Converts a stored index back to a `usize`.
+///
+/// # Parameters
+/// * `index` - The index to convert.
+///
+/// # Returns
+/// The index as a `usize`.
+#[cfg(not(feature = "compact-index"))]
+pub fn from_index(index: Index) -> usize {
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_unit_test() {
+        assert_eq!(to_index(42), Some(42));
+        assert_eq!(from_index(42), 42);
+        #[cfg(feature = "compact-index")]
+        assert_eq!(to_index(1 << 32), None);
+    }
+}
@@ -0,0 +1,132 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::codegen::quote;
+use crate::MaybeSync;
+
+/// A type of key that can be hashed, such as `str` for words or `[u8]` for
+/// byte strings that are not valid UTF-8.
+///
+/// Hash algorithms compute row and column indices from a key, the rest of the
+/// generation only needs to compare, copy and print keys. User types only need
+/// `Ord`, `Clone`, `Default` and `Debug` to be keys, every method has a default.
+/// With the `parallel` feature keys also have to be `Sync`, see `MaybeSync`.
+pub trait Key:
+    Ord + fmt::Debug + MaybeSync + ToOwned<Owned: Ord + Clone + Default + fmt::Debug>
+{
+    /// Converts the ASCII letters of the key to uppercase, only allocating
+    /// when it has lowercase letters.
+    ///
+    /// # Returns
+    /// The key with its ASCII letters in uppercase. By default the key is
+    /// returned unchanged.
+    fn fold_case(&self) -> Cow<'_, Self> {
+        Cow::Borrowed(self)
+    }
+
+    /// Gets the length of the key, in bytes.
+    ///
+    /// # Returns
+    /// The length of the key, 0 by default.
+    fn key_len(&self) -> usize {
+        0
+    }
+
+    /// Gets a readable form of the key for messages.
+    ///
+    /// # Returns
+    /// The key as text, its `Debug` form by default.
+    fn to_text(&self) -> Cow<'_, str> {
+        Cow::Owned(format!("{self:?}"))
+    }
+
+    /// Gets the key as a pseudo code literal.
+    ///
+    /// # Returns
+    /// The key as a literal, its `Debug` form by default.
+    fn to_literal(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl Key for str {
+    fn fold_case(&self) -> Cow<'_, Self> {
+        if self.bytes().any(|b| b.is_ascii_lowercase()) {
+            Cow::Owned(self.to_ascii_uppercase())
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+
+    fn key_len(&self) -> usize {
+        self.len()
+    }
+
+    fn to_text(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+
+    fn to_literal(&self) -> String {
+        quote(self)
+    }
+}
+
+impl Key for [u8] {
+    fn fold_case(&self) -> Cow<'_, Self> {
+        if self.iter().any(u8::is_ascii_lowercase) {
+            Cow::Owned(self.to_ascii_uppercase())
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+
+    fn key_len(&self) -> usize {
+        self.len()
+    }
+
+    fn to_text(&self) -> Cow<'_, str> {
+        Cow::Owned(self.escape_ascii().to_string())
+    }
+
+    fn to_literal(&self) -> String {
+        format!("b\"{}\"", self.escape_ascii())
+    }
+}
+
+/// Gets the form of a key that is compared when looking for repeats.
+///
+/// # Parameters
+/// * `key` - The key to compare.
+/// * `fold_case` - Whether case is ignored.
+///
+/// # Returns
+/// The key, folded to uppercase when `fold_case` is set.
+pub(crate) fn compare_key<K: Key + ?Sized>(key: &K, fold_case: bool) -> Cow<'_, K> {
+    if fold_case {
+        key.fold_case()
+    } else {
+        Cow::Borrowed(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_unit_test() {
+        assert!(matches!("BEGIN".fold_case(), Cow::Borrowed("BEGIN")));
+        assert_eq!("Begin".fold_case(), "BEGIN");
+        assert_eq!(compare_key("Begin", false), "Begin");
+        assert_eq!(compare_key("Begin", true), "BEGIN");
+        assert_eq!("BEGIN".key_len(), 5);
+        assert_eq!("A\"B".to_literal(), "\"A\\\"B\"");
+
+        let bytes: &[u8] = b"\xffab";
+        assert_eq!(bytes.fold_case().as_ref(), b"\xffAB");
+        assert_eq!(compare_key(bytes, false).as_ref(), bytes);
+        assert_eq!(bytes.key_len(), 3);
+        assert_eq!(bytes.to_text(), "\\xffab");
+        assert_eq!(bytes.to_literal(), "b\"\\xffab\"");
+    }
+}
@@ -0,0 +1,20 @@
+//! A marker for types that are shared between threads while generating a hash.
+//!
+//! With the `parallel` feature the words are hashed concurrently, so hash
+//! algorithms and keys have to be `Sync`. Without it every type qualifies.
+
+/// A type that is `Sync` when the `parallel` feature is enabled. Every `Sync`
+/// type implements it.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "parallel")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// A type that is `Sync` when the `parallel` feature is enabled. Every type
+/// implements it.
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "parallel"))]
+impl<T: ?Sized> MaybeSync for T {}
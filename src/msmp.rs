@@ -7,29 +7,116 @@
 #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
 #![warn(clippy::all, clippy::pedantic)]
-#![warn(clippy::all)]
 #![allow(clippy::doc_markdown)]
 
-use std::collections::BTreeSet;
+use std::borrow::Borrow;
 use std::fmt;
+use std::sync::Arc;
 
-pub use elc_algorithm::ElcAlgorithm;
+// Only used by the integration tests, and by the binary built with the `cli`
+// feature.
+#[cfg(any(test, feature = "cli"))]
+use serde_json as _;
+
+pub use case_fold::CaseFold;
+pub use cichelli_algorithm::CichelliAlgorithm;
+pub use closure_algorithm::ClosureAlgorithm;
+pub use codegen::{
+    c_recognizer, c_recognizer_self_test, c_recognizers, hash_fn_text, hash_fn_variants,
+    rust_recognizer, rust_recognizer_self_test, test_vectors, value_table_from_pairs,
+    value_table_scaffold, Language, Profile, ProfileVariants, VectorFormat,
+};
+pub use elc_algorithm::{
+    ByteElcAlgorithm, ElcAlgorithm, ElcWithLength, Position, PositionAlgorithm,
+};
 pub use error::{Error, Kind};
-pub use word_list::WordList;
+pub use expr::Expr;
+pub use fnv_algorithm::FnvAlgorithm;
+pub use hash_spec::{algorithm_from_spec, ParamValue, JSON_FORMAT_VERSION};
+pub use hash_trace::HashTrace;
+pub use hot_table::HotTable;
+pub use key::Key;
+pub use maybe_sync::MaybeSync;
+pub use msmp_fn::MsmpFn;
+pub use msmp_map::{MsmpMap, PerfectHashMap};
+pub use one_d_packed_array::{OneDPackedArray, PackEvent, PackStats};
+pub use options::{Backend, Options, RowTieBreak, Verification, MAX_HASH_BASE};
+#[cfg(feature = "heuristics")]
+pub use packing_strategy::Annealing;
+pub use packing_strategy::{Backtracking, FirstFit, PackContext, PackingStrategy};
+pub use perfect_hash_set::PerfectHashSet;
+pub use permutation::Permutation;
+pub use registry::{AlgorithmConstructor, AlgorithmRegistry, AlgorithmSearch};
+pub use rlt::{Rlt, RltElementType};
+pub use search::{
+    diagnose_collisions, find_distinguishing_positions, generate_hash_auto,
+    generate_hash_auto_with_options, generate_hash_cascade, generate_hash_seeded, AutoHash,
+    CascadeResult, CollisionDiagnosis, SeededHash,
+};
+pub use seeded_algorithm::SeededAlgorithm;
+pub use two_d_array::{analyze_collisions, Collision};
+pub use two_level_rlt::TwoLevelRlt;
+pub use verify_report::VerifyReport;
+pub use word_list::{
+    ByteWordList, Charset, DuplicatePolicy, ValidationPolicy, WordList, WordListBuilder,
+    WordListProfile,
+};
 
-use one_d_packed_array::OneDPackedArray;
-use rlt::Rlt;
-use two_d_array::{Row, RowSizeIterator, TwoDArray};
+use chm::MIX_AS_TEXT;
+use free_slots::FreeSlots;
+use index::{from_index, to_index, Index};
+use key::compare_key;
+#[cfg(feature = "internals")]
+pub use packing_diagram::{failure_diagram, packing_diagram};
+#[cfg(feature = "internals")]
+pub use two_d_array::{Row, RowSizeIterator, TwoDArray};
+#[cfg(not(feature = "internals"))]
+use two_d_array::{Row, TwoDArray};
 
+pub mod analysis;
+mod case_fold;
+mod chm;
+mod cichelli_algorithm;
+mod closure_algorithm;
+mod codegen;
 mod elc_algorithm;
 mod error;
+mod expr;
+mod fnv_algorithm;
+mod free_slots;
+mod hash_spec;
+mod hash_trace;
+mod hot_table;
+mod index;
+mod key;
+mod maybe_sync;
+mod msmp_fn;
+mod msmp_map;
 mod one_d_packed_array;
+mod options;
+#[cfg(feature = "internals")]
+mod packing_diagram;
+pub mod packing_strategy;
+mod perfect_hash_set;
+mod permutation;
+#[cfg(feature = "presets")]
+pub mod presets;
+mod registry;
 mod rlt;
+pub mod search;
+mod seeded_algorithm;
 mod two_d_array;
+mod two_level_rlt;
+mod verify_report;
 mod word_list;
 
 /// A trait for a hash algorithm.
-pub trait HashAlgorithm {
+///
+/// The key type `K` is what is hashed, `str` for words, `[u8]` for byte
+/// strings, or any type implementing `Key`. `ElcAlgorithm` hashes `str`. With
+/// the `parallel` feature words are hashed concurrently, so the algorithm has
+/// to be `Sync`, see `MaybeSync`.
+pub trait HashAlgorithm<K: ?Sized = str>: MaybeSync {
     /// Hash function h1 that is used internally to generate row indices.
     ///
     /// # Parameters
@@ -40,7 +127,7 @@ pub trait HashAlgorithm {
     ///
     /// # Errors
     /// Will return `Err` if `word` is not a valid word.
-    fn h1(&self, word: &str) -> Result<usize, Error>;
+    fn h1(&self, word: &K) -> Result<usize, Error>;
 
     /// Hash function h2 that is used internally to generate column indices.
     ///
@@ -52,7 +139,7 @@ pub trait HashAlgorithm {
     ///
     /// # Errors
     /// Will return `Err` if `word` is not a valid word.
-    fn h2(&self, word: &str) -> Result<usize, Error>;
+    fn h2(&self, word: &K) -> Result<usize, Error>;
 
     /// A representation of the hash function h1 as a string of pseudo code.
     ///
@@ -65,22 +152,197 @@ pub trait HashAlgorithm {
     /// # Returns
     /// A string representation of the h2 hash function.
     fn h2_as_text(&self) -> String;
+
+    /// Whether the algorithm ignores the case of ASCII letters, so that words
+    /// that differ only in case hash to the same value.
+    ///
+    /// # Returns
+    /// True if the algorithm folds case. The default is false.
+    fn folds_case(&self) -> bool {
+        false
+    }
+
+    /// An expression computing h1, for emitters that translate the hash to
+    /// another language node by node rather than from the pseudo code. When
+    /// the algorithm `folds_case` the expression is of the folded word.
+    ///
+    /// # Returns
+    /// The expression, or None if the algorithm cannot be described by an
+    /// `Expr`. The default is None.
+    fn h1_expr(&self) -> Option<Expr> {
+        None
+    }
+
+    /// An expression computing h2, see `h1_expr`.
+    ///
+    /// # Returns
+    /// The expression, or None if the algorithm cannot be described by an
+    /// `Expr`. The default is None.
+    fn h2_expr(&self) -> Option<Expr> {
+        None
+    }
+
+    /// The name of the algorithm, for machine readable descriptions of the
+    /// hash such as `HashData::to_json`.
+    ///
+    /// # Returns
+    /// The name. The default is "custom".
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    /// The parameters the algorithm was created with, for machine readable
+    /// descriptions of the hash such as `HashData::to_json`.
+    ///
+    /// # Returns
+    /// The name and value of each parameter. The default is none.
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        Vec::new()
+    }
+
+    /// Hashes a word with both h1 and h2.
+    ///
+    /// Implementations that scan the word for both indices can override this
+    /// to do so in one pass. Generation and the generated hash function call
+    /// this rather than `h1` and `h2`.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// The row and column indices, the same as `h1` and `h2` would return.
+    ///
+    /// # Errors
+    /// Will return `Err` if `word` is not a valid word.
+    fn hash_pair(&self, word: &K) -> Result<(usize, usize), Error> {
+        Ok((self.h1(word)?, self.h2(word)?))
+    }
+}
+
+impl<K: ?Sized, T: HashAlgorithm<K> + ?Sized> HashAlgorithm<K> for Box<T> {
+    fn h1(&self, word: &K) -> Result<usize, Error> {
+        (**self).h1(word)
+    }
+
+    fn h2(&self, word: &K) -> Result<usize, Error> {
+        (**self).h2(word)
+    }
+
+    fn h1_as_text(&self) -> String {
+        (**self).h1_as_text()
+    }
+
+    fn h2_as_text(&self) -> String {
+        (**self).h2_as_text()
+    }
+
+    fn folds_case(&self) -> bool {
+        (**self).folds_case()
+    }
+
+    fn h1_expr(&self) -> Option<Expr> {
+        (**self).h1_expr()
+    }
+
+    fn h2_expr(&self) -> Option<Expr> {
+        (**self).h2_expr()
+    }
+
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        (**self).parameters()
+    }
+
+    fn hash_pair(&self, word: &K) -> Result<(usize, usize), Error> {
+        (**self).hash_pair(word)
+    }
+}
+
+impl<K: ?Sized, T: HashAlgorithm<K> + ?Sized> HashAlgorithm<K> for Arc<T>
+where
+    Arc<T>: MaybeSync,
+{
+    fn h1(&self, word: &K) -> Result<usize, Error> {
+        (**self).h1(word)
+    }
+
+    fn h2(&self, word: &K) -> Result<usize, Error> {
+        (**self).h2(word)
+    }
+
+    fn h1_as_text(&self) -> String {
+        (**self).h1_as_text()
+    }
+
+    fn h2_as_text(&self) -> String {
+        (**self).h2_as_text()
+    }
+
+    fn folds_case(&self) -> bool {
+        (**self).folds_case()
+    }
+
+    fn h1_expr(&self) -> Option<Expr> {
+        (**self).h1_expr()
+    }
+
+    fn h2_expr(&self) -> Option<Expr> {
+        (**self).h2_expr()
+    }
+
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        (**self).parameters()
+    }
+
+    fn hash_pair(&self, word: &K) -> Result<(usize, usize), Error> {
+        (**self).hash_pair(word)
+    }
 }
 
 ///  A closure that takes a word and returns a hash value.
-pub struct HashClosure {
+pub struct HashClosure<K: ?Sized = str> {
     /// A closure that takes a word and returns a hash value.
-    pub cls: Box<dyn Fn(&str) -> usize>,
+    pub cls: Box<dyn Fn(&K) -> usize>,
 }
 
-impl fmt::Debug for HashClosure {
+impl<K: ?Sized> fmt::Debug for HashClosure<K> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "HashClosure")
     }
 }
 
-impl HashClosure {
-    fn new(cls: impl Fn(&str) -> usize + 'static) -> Self {
+impl<K: ?Sized> HashClosure<K> {
+    fn new(cls: impl Fn(&K) -> usize + 'static) -> Self {
+        Self { cls: Box::new(cls) }
+    }
+}
+
+/// A function that takes a word and returns its hash value, or `None` for words
+/// that are not in the word list.
+pub type CheckedHashFn<K = str> = dyn Fn(&K) -> Option<usize>;
+
+/// A closure that takes a word and returns its hash value, or `None` for words
+/// that are not in the word list.
+pub struct CheckedHashClosure<K: ?Sized = str> {
+    /// A closure that takes a word and returns a hash value, see `MsmpFn::find`.
+    pub cls: Box<CheckedHashFn<K>>,
+}
+
+impl<K: ?Sized> fmt::Debug for CheckedHashClosure<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CheckedHashClosure")
+    }
+}
+
+impl<K: ?Sized> CheckedHashClosure<K> {
+    fn new(cls: impl Fn(&K) -> Option<usize> + 'static) -> Self {
         Self { cls: Box::new(cls) }
     }
 }
@@ -88,20 +350,145 @@ impl HashClosure {
 /// A struct containing a string representation of the hash function and a
 /// closure that takes a word and returns a hash value.
 #[derive(Debug)]
-pub struct HashData {
+pub struct HashData<K: Key + ?Sized = str> {
     /// A string representation of the hash function.
     /// This is a pseudo code representation of the msmp hash function. It can be
     /// used to help implement the msmp hash function in other languages.
     pub as_string: String,
 
     /// A closure that takes a word and returns a hash value.
-    pub as_closure: HashClosure,
+    pub as_closure: HashClosure<K>,
+
+    /// A closure that takes a word and returns a hash value, or `None` for words
+    /// that are not in the word list. Words that can be hashed are only rejected
+    /// when the keys were stored, see `Options::store_keys`.
+    pub as_checked_closure: CheckedHashClosure<K>,
+
+    /// The hash function. Unlike `as_closure` it can be cloned, reports the
+    /// table size and can report words that cannot be hashed.
+    pub as_fn: MsmpFn<K>,
+
+    /// Links each word's index in the word list to its hash value.
+    pub permutation: Permutation,
+
+    /// The packed array. Each position, which is also a hash value less the
+    /// hash base, holds the 1 based index of the word placed there.
+    pub packed_array: OneDPackedArray,
+
+    /// The length in bytes of the longest word in the word list. Longer words
+    /// can not be in the word list, and emitted code can use it to bound loops
+    /// and buffers.
+    pub max_word_len: usize,
+
+    /// The number of hash values. This is the number of distinct words, unless
+    /// the table was packed with slack or rounded up to a power of two, see
+    /// `Options::slack_percent` and `Options::power_of_two_table`.
+    pub table_len: usize,
+}
+
+impl<K: Key + ?Sized> HashData<K> {
+    /// Hashes a word, reporting words that cannot be hashed. Unlike
+    /// `as_closure` an invalid word is not silently given a hash value.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    ///
+    /// # Errors
+    /// Will return `Err` if `word` cannot be hashed, see `MsmpFn::try_hash`.
+    pub fn try_hash(&self, word: &K) -> Result<usize, Error> {
+        self.as_fn.try_hash(word)
+    }
+
+    /// Traces the hashing of a word step by step: the row and column index,
+    /// the row lookup table entry read, the sum and the modulo. Useful for
+    /// checking a port of the pseudo code to another language value by value.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// The steps of hashing the word, see `HashTrace`.
+    ///
+    /// # Errors
+    /// Will return `Err` if `word` cannot be hashed, see `MsmpFn::explain`.
+    pub fn explain(&self, word: &K) -> Result<HashTrace, Error> {
+        self.as_fn.explain(word)
+    }
+
+    /// Describes the hash as a JSON document, for tools that generate code in
+    /// languages without an emitter. Unlike the pseudo code of `as_string`
+    /// every table and parameter is a separate field.
+    ///
+    /// The document is an object with the fields:
+    /// * `format_version` - `JSON_FORMAT_VERSION`.
+    /// * `algorithm` - The `name` and `parameters` of the hash algorithm,
+    ///   whether it `folds_case`, and the pseudo code of `h1` and `h2`.
+    /// * `backend` - `packed`, `chd` or `chm`.
+    /// * `row_lookup_table` - Except for `chm`, the `element_type` and `values`
+    ///   of the table, and when present its sparse `rows`, its `two_level`
+    ///   form and the `multipliers` of `chd`.
+    /// * `chm` - For `chm` only, the `vertex_values`, `keys`, `stride` and
+    ///   `part_len` of the hash of the cells.
+    /// * `table_len` - The number of hash values.
+    /// * `hash_base` - The smallest hash value, see `Options::hash_base`.
+    /// * `max_word_len` - The length in bytes of the longest word.
+    /// * `hash_function` - The pseudo code of `as_string`.
+    ///
+    /// # Returns
+    /// The document.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        hash_spec::to_json(self)
+    }
+
+    /// Looks up the position of a word in the word list the hash was generated
+    /// from, so that payloads can be attached to words without a parallel array
+    /// laid out by hash value.
+    ///
+    /// Words that were not in the word list are rejected when the keys were
+    /// stored, see `Options::store_keys` and `MsmpFn::find`. Otherwise, like the
+    /// hash itself, a word that was not in the word list may return the
+    /// position of another word.
+    ///
+    /// # Parameters
+    /// * `word` - The word to look up.
+    ///
+    /// # Returns
+    /// The 0 based index of `word` in the word list, the first occurrence when
+    /// repeated words are allowed. `None` if `word` cannot be hashed, hashes
+    /// to a position without a word, or is known not to be in the word list.
+    #[must_use]
+    pub fn lookup(&self, word: &K) -> Option<usize> {
+        let hash_value = self.as_fn.find(word)?;
+        self.permutation
+            .word_of_hash(hash_value.checked_sub(self.as_fn.get_rlt().get_hash_base())?)
+    }
+
+    /// Gets the word that hashes to a hash value.
+    ///
+    /// Only available when the keys were stored, see `Options::store_keys`.
+    ///
+    /// # Parameters
+    /// * `hash_value` - The hash value.
+    ///
+    /// # Returns
+    /// The word with the hash value, or `None` if the keys were not stored,
+    /// `hash_value` is out of range or no word has it.
+    #[must_use]
+    pub fn word_at(&self, hash_value: usize) -> Option<&K> {
+        let slot = hash_value.checked_sub(self.as_fn.get_rlt().get_hash_base())?;
+        self.permutation.word_of_hash(slot)?;
+        Some(self.as_fn.get_keys()?.get(slot)?.borrow())
+    }
 }
 
 /// Generates a msmp hash function from a word list.
 ///
 /// # Parameters
-/// * `word_list` - A word list.
+/// * `word_list` - A word list, of owned `String`s or borrowed `&str`s.
 /// * `hash_algorithm` - A hash algorithm.
 ///
 /// # Returns
@@ -110,31 +497,296 @@ pub struct HashData {
 ///
 /// # Errors
 /// Will return `Err` if `word_list` fails to resolve to a hash function.
-pub fn generate_hash(
-    word_list: &WordList,
+pub fn generate_hash<W: Borrow<str>>(
+    word_list: &WordList<W>,
+    hash_algorithm: impl HashAlgorithm + 'static,
+) -> Result<HashData, Error> {
+    generate_hash_with_options(word_list, hash_algorithm, &Options::default())
+}
+
+/// Generates a msmp hash function from a word list using the given options.
+///
+/// # Parameters
+/// * `word_list` - A word list, of owned `String`s or borrowed `&str`s.
+/// * `hash_algorithm` - A hash algorithm.
+/// * `options` - Options controlling the generation.
+///
+/// # Returns
+/// A struct containing a string representation of the hash function and a
+/// closure that takes a word and returns a hash value.
+///
+/// # Errors
+/// Will return `Err` if `word_list` fails to resolve to a hash function, or if
+/// a limit set in `options` is exceeded.
+pub fn generate_hash_with_options<W: Borrow<str>>(
+    word_list: &WordList<W>,
     hash_algorithm: impl HashAlgorithm + 'static,
+    options: &Options,
 ) -> Result<HashData, Error> {
-    match word_list.is_valid() {
-        Ok(_) => {
-            let two_d_array: TwoDArray = TwoDArray::new(word_list, &hash_algorithm)?;
+    let policy = options.get_validation_policy();
+    word_list
+        .is_valid_under(&policy.fold_case(policy.get_fold_case() || hash_algorithm.folds_case()))?;
+    if policy.get_fold_case() && !hash_algorithm.folds_case() {
+        build(word_list, CaseFold::new(hash_algorithm), options)
+    } else {
+        build(word_list, hash_algorithm, options)
+    }
+}
 
-            let one_d_packed_array: OneDPackedArray = OneDPackedArray::new(&two_d_array)?;
+/// Generates a msmp hash function from a list of byte strings.
+///
+/// # Parameters
+/// * `word_list` - A list of byte strings, which need not be valid UTF-8.
+/// * `hash_algorithm` - A hash algorithm for byte strings.
+///
+/// # Returns
+/// A struct containing a string representation of the hash function and a
+/// closure that takes a byte string and returns a hash value.
+///
+/// # Errors
+/// Will return `Err` if `word_list` fails to resolve to a hash function.
+pub fn generate_byte_hash(
+    word_list: &ByteWordList,
+    hash_algorithm: impl HashAlgorithm<[u8]> + 'static,
+) -> Result<HashData<[u8]>, Error> {
+    generate_byte_hash_with_options(word_list, hash_algorithm, &Options::default())
+}
 
-            //println!("{two_d_array:?}");
+/// Generates a msmp hash function from a list of byte strings using the given
+/// options.
+///
+/// # Parameters
+/// * `word_list` - A list of byte strings, which need not be valid UTF-8.
+/// * `hash_algorithm` - A hash algorithm for byte strings.
+/// * `options` - Options controlling the generation. Only the duplicate and
+///   case handling of the validation policy are used, any byte may appear in
+///   a key.
+///
+/// # Returns
+/// A struct containing a string representation of the hash function and a
+/// closure that takes a byte string and returns a hash value.
+///
+/// # Errors
+/// Will return `Err` if `word_list` fails to resolve to a hash function, or if
+/// a limit set in `options` is exceeded.
+pub fn generate_byte_hash_with_options(
+    word_list: &ByteWordList,
+    hash_algorithm: impl HashAlgorithm<[u8]> + 'static,
+    options: &Options,
+) -> Result<HashData<[u8]>, Error> {
+    generate_key_hash_with_options(word_list, hash_algorithm, options)
+}
 
-            //println!("{one_d_packed_array:?}");
+/// Generates a msmp hash function from a list of keys of any type.
+///
+/// # Parameters
+/// * `word_list` - A list of keys.
+/// * `hash_algorithm` - A hash algorithm for the keys.
+///
+/// # Returns
+/// A struct containing a string representation of the hash function and a
+/// closure that takes a key and returns a hash value.
+///
+/// # Errors
+/// Will return `Err` if `word_list` fails to resolve to a hash function.
+pub fn generate_key_hash<K: Key + ?Sized + 'static>(
+    word_list: &WordList<K::Owned>,
+    hash_algorithm: impl HashAlgorithm<K> + 'static,
+) -> Result<HashData<K>, Error> {
+    generate_key_hash_with_options(word_list, hash_algorithm, &Options::default())
+}
 
-            verify(word_list, one_d_packed_array.get_rlt(), &hash_algorithm)?;
+/// Generates a msmp hash function from a list of keys of any type using the
+/// given options.
+///
+/// Keys are only checked for repeats, the hash algorithm decides which keys
+/// it accepts.
+///
+/// # Parameters
+/// * `word_list` - A list of keys.
+/// * `hash_algorithm` - A hash algorithm for the keys.
+/// * `options` - Options controlling the generation. Only the duplicate and
+///   case handling of the validation policy are used.
+///
+/// # Returns
+/// A struct containing a string representation of the hash function and a
+/// closure that takes a key and returns a hash value.
+///
+/// # Errors
+/// Will return `Err` if `word_list` fails to resolve to a hash function, or if
+/// a limit set in `options` is exceeded.
+pub fn generate_key_hash_with_options<K: Key + ?Sized + 'static>(
+    word_list: &WordList<K::Owned>,
+    hash_algorithm: impl HashAlgorithm<K> + 'static,
+    options: &Options,
+) -> Result<HashData<K>, Error> {
+    let policy = options.get_validation_policy();
+    let fold_case = policy.get_fold_case() || hash_algorithm.folds_case();
+    word_list.validate_keys::<K>(policy.get_duplicate_policy(), fold_case)?;
+    if policy.get_fold_case() && !hash_algorithm.folds_case() {
+        build(word_list, CaseFold::new(hash_algorithm), options)
+    } else {
+        build(word_list, hash_algorithm, options)
+    }
+}
+
+/// Generates a msmp hash function from a validated list of keys.
+///
+/// # Parameters
+/// * `word_list` - A list of keys.
+/// * `hash_algorithm` - A hash algorithm.
+/// * `options` - Options controlling the generation.
+///
+/// # Returns
+/// A struct containing a string representation of the hash function and a
+/// closure that takes a key and returns a hash value.
+///
+/// # Errors
+/// Will return `Err` if `word_list` fails to resolve to a hash function, or if
+/// a limit set in `options` is exceeded.
+fn build<K: Key + ?Sized + 'static, W: Borrow<K>>(
+    word_list: &WordList<W>,
+    hash_algorithm: impl HashAlgorithm<K> + 'static,
+    options: &Options,
+) -> Result<HashData<K>, Error> {
+    let one_d_packed_array = pack_words(word_list, &hash_algorithm, options)?;
+    Ok(assemble(
+        word_list,
+        hash_algorithm,
+        options,
+        one_d_packed_array,
+    ))
+}
 
-            Ok(HashData {
-                as_string: text(one_d_packed_array.get_rlt(), &hash_algorithm),
-                as_closure: HashClosure::new(move |a| {
-                    hash(a, one_d_packed_array.get_rlt(), &hash_algorithm)
-                }),
-            })
-        }
-        Err(e) => Err(e),
+/// Packs a validated list of keys, the costly part of generating a hash. The
+/// packed array can be sent to another thread, unlike the `HashData` that
+/// `assemble` builds from it.
+///
+/// # Parameters
+/// * `word_list` - A list of keys.
+/// * `hash_algorithm` - A hash algorithm.
+/// * `options` - Options controlling the generation.
+///
+/// # Returns
+/// The packed array of the hash.
+///
+/// # Errors
+/// Will return `Err` if `word_list` fails to resolve to a hash function, or if
+/// a limit set in `options` is exceeded.
+pub(crate) fn pack_words<K: Key + ?Sized, W: Borrow<K>>(
+    word_list: &WordList<W>,
+    hash_algorithm: &impl HashAlgorithm<K>,
+    options: &Options,
+) -> Result<OneDPackedArray, Error> {
+    let two_d_array: TwoDArray = phase("two_d_array", || {
+        TwoDArray::new(word_list, hash_algorithm, options)
+    })?;
+
+    let one_d_packed_array: OneDPackedArray =
+        phase("pack", || OneDPackedArray::pack(&two_d_array, options))?;
+    check_hash_base(options.get_hash_base(), one_d_packed_array.len())?;
+
+    if options.get_verification().is_enabled() {
+        phase("verify", || {
+            verify_pairs(
+                word_list,
+                one_d_packed_array.get_rlt(),
+                two_d_array.get_hash_pairs(),
+                hash_algorithm.folds_case(),
+            )
+        })?;
     }
+    Ok(one_d_packed_array)
+}
+
+/// Builds a hash from the packed array `pack_words` made of a list of keys.
+///
+/// # Parameters
+/// * `word_list` - The list of keys given to `pack_words`.
+/// * `hash_algorithm` - The hash algorithm given to `pack_words`.
+/// * `options` - The options given to `pack_words`.
+/// * `one_d_packed_array` - The packed array of the keys.
+///
+/// # Returns
+/// A struct containing a string representation of the hash function and a
+/// closure that takes a key and returns a hash value.
+pub(crate) fn assemble<K: Key + ?Sized + 'static, W: Borrow<K>>(
+    word_list: &WordList<W>,
+    hash_algorithm: impl HashAlgorithm<K> + 'static,
+    options: &Options,
+    one_d_packed_array: OneDPackedArray,
+) -> HashData<K> {
+    let fold_case = hash_algorithm.folds_case();
+    let permutation = Permutation::new::<K, W>(one_d_packed_array.as_slice(), word_list, fold_case);
+    let keys: Option<Vec<K::Owned>> = options.get_store_keys().then(|| {
+        permutation
+            .words_in_hash_order()
+            .iter()
+            .map(|w| w.map_or_else(K::Owned::default, |w| word_list[w].borrow().to_owned()))
+            .collect()
+    });
+
+    let as_string = text(
+        one_d_packed_array.get_rlt(),
+        &hash_algorithm,
+        keys.as_deref(),
+    );
+    let mut as_fn = MsmpFn::new(one_d_packed_array.get_rlt().clone(), hash_algorithm);
+    if let Some(keys) = keys {
+        as_fn = as_fn.with_keys(keys);
+    }
+    let closure_fn = as_fn.clone();
+    let checked_fn = as_fn.clone();
+
+    HashData {
+        as_string,
+        as_closure: HashClosure::new(move |a| closure_fn.hash(a)),
+        as_checked_closure: CheckedHashClosure::new(move |a| checked_fn.find(a)),
+        as_fn,
+        permutation,
+        max_word_len: word_list
+            .iter()
+            .map(|word| word.borrow().key_len())
+            .max()
+            .unwrap_or(0),
+        table_len: one_d_packed_array.len(),
+        packed_array: one_d_packed_array,
+    }
+}
+
+/// Checks that the hash values from a base fit in a `usize` and that the base
+/// is at most `MAX_HASH_BASE`.
+///
+/// # Parameters
+/// * `hash_base` - The smallest hash value.
+/// * `table_len` - The number of hash values.
+///
+/// # Errors
+/// Will return `Err` if the base is too large.
+fn check_hash_base(hash_base: usize, table_len: usize) -> Result<(), Error> {
+    if hash_base > MAX_HASH_BASE || hash_base.checked_add(table_len).is_none() {
+        return Err(Error::new(Kind::HashError(format!(
+            "The hash base {hash_base} is larger than {MAX_HASH_BASE}."
+        ))));
+    }
+    Ok(())
+}
+
+/// Runs a phase of the hash generation. With the `tracing` feature the phase
+/// runs in a span named after it.
+///
+/// # Parameters
+/// * `name` - The name of the phase.
+/// * `f` - The phase.
+///
+/// # Returns
+/// The result of the phase.
+fn phase<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("phase", name).entered();
+    #[cfg(not(feature = "tracing"))]
+    let _ = name;
+    f()
 }
 
 /// Implements the closure returned to the generate_hash caller.
@@ -146,12 +798,118 @@ pub fn generate_hash(
 ///
 /// # Returns
 /// A hash value.
-fn hash(word: &str, rlt: &Rlt, hash_algorithm: &dyn HashAlgorithm) -> usize {
-    let row_index = hash_algorithm.h1(word).unwrap_or(0);
-    let col_index = hash_algorithm.h2(word).unwrap_or(0);
+fn hash<K: ?Sized>(word: &K, rlt: &Rlt, hash_algorithm: &dyn HashAlgorithm<K>) -> usize {
+    let (row_index, col_index) = hash_algorithm.hash_pair(word).unwrap_or((0, 0));
+    hash_cell(row_index, col_index, rlt)
+}
+
+/// Hashes a cell of the 2D array, given the row and column index of a word.
+///
+/// # Parameters
+/// * `row_index` - The row index of the word.
+/// * `col_index` - The column index of the word.
+/// * `rlt` - A row lookup table.
+///
+/// # Returns
+/// A hash value.
+fn hash_cell(row_index: usize, col_index: usize, rlt: &Rlt) -> usize {
+    if let Some(chm) = rlt.get_chm() {
+        return chm.hash(row_index, col_index) + rlt.get_hash_base();
+    }
+    let col_index = rlt.scale(row_index, col_index);
     let rlt_val = rlt.get(row_index).unwrap_or(&0);
     let tmp = usize::try_from(rlt_val + isize::try_from(col_index).unwrap_or(0)).unwrap_or(0);
-    tmp % rlt.get_num_entries()
+    rlt.reduce(tmp) + rlt.get_hash_base()
+}
+
+/// Implements `MsmpFn::try_hash`.
+///
+/// # Parameters
+/// * `word` - A word to be hashed.
+/// * `rlt` - A row lookup table.
+/// * `hash_algorithm` - A hash algorithm.
+///
+/// # Returns
+/// A hash value.
+///
+/// # Errors
+/// Will return `Err` if the hash algorithm rejects `word`, or if `word`
+/// indexes outside of the row lookup table or outside of its row's bounds.
+fn try_hash<K: ?Sized>(
+    word: &K,
+    rlt: &Rlt,
+    hash_algorithm: &dyn HashAlgorithm<K>,
+) -> Result<usize, Error> {
+    if let Some(chm) = rlt.get_chm() {
+        let (row_index, col_index) = hash_algorithm.hash_pair(word)?;
+        return Ok(chm.hash(row_index, col_index) + rlt.get_hash_base());
+    }
+    explain(word, rlt, hash_algorithm).map(|trace| trace.hash_value)
+}
+
+/// Implements `MsmpFn::explain`.
+///
+/// # Parameters
+/// * `word` - A word to be hashed.
+/// * `rlt` - A row lookup table.
+/// * `hash_algorithm` - A hash algorithm.
+///
+/// # Returns
+/// The steps of hashing the word.
+///
+/// # Errors
+/// Will return `Err` if the hash algorithm rejects `word`, if `word` indexes
+/// outside of the row lookup table or outside of its row's bounds, or if the
+/// hash was built by the CHM backend, which has no row lookup table.
+fn explain<K: ?Sized>(
+    word: &K,
+    rlt: &Rlt,
+    hash_algorithm: &dyn HashAlgorithm<K>,
+) -> Result<HashTrace, Error> {
+    let (row_index, col_index) = hash_algorithm.hash_pair(word)?;
+    if rlt.get_chm().is_some() {
+        return Err(Error::new(Kind::HashError(
+            "A CHM hash has no row lookup table to explain.".to_string(),
+        )));
+    }
+    let (Some(rlt_index), Some(&rlt_value)) = (rlt.table_index(row_index), rlt.get(row_index))
+    else {
+        return Err(Error::new(Kind::HashError(format!(
+            "Row index {row_index} is out of range."
+        ))));
+    };
+    let out_of_range = || {
+        Error::new(Kind::HashError(format!(
+            "Column index {col_index} is out of range for row {row_index}."
+        )))
+    };
+    let in_bounds = rlt
+        .get_col_bounds(row_index)
+        .is_none_or(|(min_col, max_col)| (min_col..=max_col).contains(&col_index));
+    if !in_bounds {
+        return Err(out_of_range());
+    }
+    let scaled_col_index = rlt.scale(row_index, col_index);
+    let sum = isize::try_from(scaled_col_index)
+        .ok()
+        .and_then(|scaled| rlt_value.checked_add(scaled))
+        .ok_or_else(out_of_range)?;
+    let modulus = rlt.get_num_entries();
+    let hash_base = rlt.get_hash_base();
+    let hash_value = rlt.reduce(usize::try_from(sum).map_err(|_| out_of_range())?) + hash_base;
+    Ok(HashTrace {
+        row_index,
+        col_index,
+        rlt_index,
+        rlt_value,
+        multiplier: rlt.get_multiplier(row_index),
+        scaled_col_index,
+        sum,
+        modulus,
+        mask: rlt.get_mask(),
+        hash_base,
+        hash_value,
+    })
 }
 
 /// Generates a string representation of the hash function.
@@ -163,74 +921,195 @@ fn hash(word: &str, rlt: &Rlt, hash_algorithm: &dyn HashAlgorithm) -> usize {
 /// # Parameters
 /// * `rlt` - A row lookup table.
 /// * `hash_algorithm` - A hash algorithm.
+/// * `keys` - The word at each hash value, when words that are not in the word
+///   list should be rejected.
 ///
 /// # Returns
 /// A string representation of the hash function.
-fn text(rlt: &Rlt, hash_algorithm: &dyn HashAlgorithm) -> String {
+fn text<K: Key + ?Sized>(
+    rlt: &Rlt,
+    hash_algorithm: &dyn HashAlgorithm<K>,
+    keys: Option<&[K::Owned]>,
+) -> String {
+    let (bounds_tables, bounds_check) = match rlt
+        .get_min_cols_as_text()
+        .zip(rlt.get_max_cols_as_text())
+    {
+        Some((min_cols, max_cols)) => (
+            format!(
+                "row_min_col = [{min_cols}]\n\
+                     row_max_col = [{max_cols}]\n"
+            ),
+            "if col_index < row_min_col[row_index] or col_index > row_max_col[row_index]:\n    \
+                 not_found\n",
+        ),
+        None => (String::new(), ""),
+    };
+    let (rows_table, rows_lookup) = match rlt.get_rows_as_text() {
+        Some(rows) => (
+            format!("row_keys = [{rows}]\n"),
+            "row_index = binary_search(row_keys, row_index)\n\
+             if row_index == -1:\n    \
+                 not_found\n",
+        ),
+        None => (String::new(), ""),
+    };
+    let fold_case = hash_algorithm.folds_case();
+    let (key_table, key_check) = match keys {
+        Some(keys) => (
+            format!(
+                "key_table = [{}]\n",
+                keys.iter()
+                    .map(|key| compare_key(key.borrow(), fold_case).to_literal())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            if fold_case {
+                "if upper(word) != key_table[hash_value]:\n    not_found\n"
+            } else {
+                "if word != key_table[hash_value]:\n    not_found\n"
+            },
+        ),
+        None => (String::new(), ""),
+    };
+    let add_base = match rlt.get_hash_base() {
+        0 => String::new(),
+        hash_base => format!("hash_value = hash_value + {hash_base}\n"),
+    };
+    let (tables, hash_value) = if let Some(chm) = rlt.get_chm() {
+        (
+            format!(
+                "vertex_values = [{}]\n{MIX_AS_TEXT}",
+                chm.get_values_as_text()
+            ),
+            chm.hash_as_text(),
+        )
+    } else {
+        let (multiplier_table, scaled_col) = match rlt.get_multipliers_as_text() {
+            Some(multipliers) => (
+                format!("row_multiplier = [{multipliers}]\n"),
+                "row_multiplier[row_index] * col_index",
+            ),
+            None => (String::new(), "col_index"),
+        };
+        let (rlt_table, rlt_value) = match rlt.get_two_level() {
+            Some(two_level) => (
+                format!(
+                    "row_block = [{}]\nrow_lookup_table = [{}]\n",
+                    two_level.get_first_level_as_text(),
+                    two_level.get_blocks_as_text()
+                ),
+                two_level.lookup_as_text(),
+            ),
+            None => (
+                format!("row_lookup_table = [{}]\n", rlt.get_as_text()),
+                String::from("row_lookup_table[row_index]"),
+            ),
+        };
+        (
+            format!("{rlt_table}{multiplier_table}"),
+            format!(
+                "hash_value = ({rlt_value} + {scaled_col}) {}\n",
+                rlt.get_reduce_as_text()
+            ),
+        )
+    };
     let rv = format!(
-        "row_lookup_table = [{rlt}]\n\
+        "{rows_table}\
+         {tables}\
+         {bounds_tables}\
+         {key_table}\
          row_index = {h1}\n\
          col_index = {h2}\n\
-         hash_value = (row_lookup_table[row_index] + col_index) % {len}\n",
-        rlt = rlt.get_as_text(),
+         {rows_lookup}\
+         {bounds_check}\
+         {hash_value}\
+         {key_check}\
+         {add_base}",
         h1 = hash_algorithm.h1_as_text(),
         h2 = hash_algorithm.h2_as_text(),
-        len = rlt.get_num_entries()
     );
     rv
 }
 
-/// Verifies that the hash function is valid for the given word list.
+/// Verifies a hash function against a word list, without regenerating it.
 ///
-/// This is done by generating a hash value for each word in the word list
-/// and checking that there are no collisions and that the hash values are
-/// in the range [0, len(word_list)-1).
-/// This function is used to verify that the hash function generated by
-/// generate_hash is valid.
-/// This function is not intended to be called directly by the caller.
-/// It is called by generate_hash.
+/// Lets a row lookup table that was edited by hand, or rebuilt from a saved
+/// form, be checked before it is used. The table of a generated hash is
+/// available from `MsmpFn::get_rlt`.
 ///
 /// # Parameters
 /// * `word_list` - A word list.
 /// * `rlt` - A row lookup table.
-/// * `hash_algorithm` - A hash algorithm.
+/// * `hash_algorithm` - The hash algorithm the table was built with.
+///
+/// # Returns
+/// The hash value of each word, and any collisions, gaps or out of range hash
+/// values found.
+///
+/// # Errors
+/// Will return `Err` if a word cannot be hashed, see `MsmpFn::try_hash`.
+pub fn verify<K: Key + ?Sized, W: Borrow<K>>(
+    word_list: &WordList<W>,
+    rlt: &Rlt,
+    hash_algorithm: &impl HashAlgorithm<K>,
+) -> Result<VerifyReport, Error> {
+    let hash_values = word_list
+        .iter()
+        .map(|word| try_hash(word.borrow(), rlt, hash_algorithm))
+        .collect::<Result<Vec<usize>, Error>>()?;
+    Ok(VerifyReport::new::<K, W>(
+        word_list,
+        hash_values,
+        hash_algorithm.folds_case(),
+        rlt.get_hash_base(),
+        rlt.get_num_entries(),
+    ))
+}
+
+/// Verifies a freshly packed hash function against its word list.
+///
+/// The row and column index of each word are the ones computed while building
+/// the 2D array, so the words are not hashed a second time.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `rlt` - A row lookup table.
+/// * `hash_pairs` - The row and column index of each word of `word_list`.
+/// * `fold_case` - Whether the hash algorithm folds case.
 ///
 /// # Returns
 /// `Ok(())` if the hash function is valid.
 ///
 /// # Errors
-/// Will return `Err` if a collision is detected or if the hash values are
-/// not in the range [0, len(word_list)).
-fn verify(
-    word_list: &WordList,
+/// Will return `Err` with a `VerifyReport` of the offending words if a
+/// collision or an out of range hash value is detected. Every hash value is
+/// below the table length, so a table without collisions that was packed
+/// without slack has no gaps either.
+fn verify_pairs<K: Key + ?Sized, W: Borrow<K>>(
+    word_list: &WordList<W>,
     rlt: &Rlt,
-    hash_algorithm: &dyn HashAlgorithm,
+    hash_pairs: &[(usize, usize)],
+    fold_case: bool,
 ) -> Result<(), Error> {
-    let w_it = word_list.list.iter();
-    let mut hash_results = BTreeSet::new();
-    for word in w_it {
-        let hash_result = hash(word, rlt, hash_algorithm);
-        println!("{word} -> {hash_result}");
-        if hash_results.contains(&hash_result) {
-            return Err(Error::new(Kind::HashError(
-                "Collision detected while verifying the hash.".to_string(),
-            )));
-        }
-        hash_results.insert(hash_result);
-    }
-
-    let h_it = hash_results.iter();
-    for (i, hash_result) in h_it.enumerate() {
-        if *hash_result != i {
-            return Err(Error::new(Kind::HashError(
-                "Unexpected gap found in index list.".to_string(),
-            )));
-        }
-        if *hash_result >= word_list.list.len() {
-            return Err(Error::new(Kind::HashError(
-                "Hash value is out of range.".to_string(),
-            )));
-        }
+    let hash_values: Vec<usize> = hash_pairs
+        .iter()
+        .map(|(row_index, col_index)| hash_cell(*row_index, *col_index, rlt))
+        .collect();
+    #[cfg(feature = "tracing")]
+    for (word, hash_value) in word_list.iter().zip(&hash_values) {
+        tracing::trace!(word = %word.borrow().to_text(), hash_value, "verified");
+    }
+    let report = VerifyReport::new::<K, W>(
+        word_list,
+        hash_values,
+        fold_case,
+        rlt.get_hash_base(),
+        rlt.get_num_entries(),
+    );
+    if !report.is_perfect() {
+        return Err(Error::new(Kind::VerifyFailed(report)));
     }
+
     Ok(())
 }
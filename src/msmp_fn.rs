@@ -0,0 +1,227 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::key::compare_key;
+use crate::{explain, hash, try_hash, Error, HashAlgorithm, HashTrace, Key, Rlt};
+
+/// A generated msmp hash function.
+///
+/// Holds the row lookup table and the hash algorithm the table was generated
+/// with. Cloning is cheap, the clones share the table and the algorithm.
+pub struct MsmpFn<K: Key + ?Sized = str> {
+    /// The row lookup table.
+    rlt: Rc<Rlt>,
+
+    /// The hash algorithm used to generate the row and column indices.
+    hash_algorithm: Rc<dyn HashAlgorithm<K>>,
+
+    /// The word at each hash value, empty for hash values without a word. Only
+    /// stored when requested with `Options::store_keys`.
+    keys: Option<Rc<[K::Owned]>>,
+}
+
+impl<K: Key + ?Sized> Clone for MsmpFn<K> {
+    fn clone(&self) -> Self {
+        Self {
+            rlt: Rc::clone(&self.rlt),
+            hash_algorithm: Rc::clone(&self.hash_algorithm),
+            keys: self.keys.clone(),
+        }
+    }
+}
+
+impl<K: Key + ?Sized> fmt::Debug for MsmpFn<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MsmpFn")
+            .field("rlt", &self.rlt)
+            .field("keys", &self.keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<K: Key + ?Sized> MsmpFn<K> {
+    /// Creates a new hash function.
+    ///
+    /// Together with `Rlt::with_values` and `algorithm_from_spec` this rebuilds
+    /// a saved hash, such as one written by `HashData::to_json`. The table is
+    /// not checked, use `verify` to confirm it still hashes the word list.
+    ///
+    /// # Parameters
+    /// * `rlt` - The row lookup table.
+    /// * `hash_algorithm` - The hash algorithm the table was generated with.
+    #[must_use]
+    pub fn new(rlt: Rlt, hash_algorithm: impl HashAlgorithm<K> + 'static) -> Self {
+        Self {
+            rlt: Rc::new(rlt),
+            hash_algorithm: Rc::new(hash_algorithm),
+            keys: None,
+        }
+    }
+
+    /// Stores the word at each hash value, so that words not in the word list
+    /// can be rejected.
+    ///
+    /// # Parameters
+    /// * `keys` - The word at each hash value, empty for hash values without a word.
+    ///
+    /// # Returns
+    /// The hash function with the keys stored.
+    pub(crate) fn with_keys(mut self, keys: Vec<K::Owned>) -> Self {
+        self.keys = Some(keys.into());
+        self
+    }
+
+    /// Gets the word stored at each hash value.
+    ///
+    /// # Returns
+    /// The word at each hash value, or `None` if the keys were not stored.
+    #[must_use]
+    pub fn get_keys(&self) -> Option<&[K::Owned]> {
+        self.keys.as_deref()
+    }
+
+    /// Gets the row lookup table.
+    ///
+    /// # Returns
+    /// The row lookup table.
+    #[must_use]
+    pub fn get_rlt(&self) -> &Rlt {
+        &self.rlt
+    }
+
+    /// Gets the hash algorithm the table was generated with.
+    ///
+    /// # Returns
+    /// The hash algorithm.
+    pub(crate) fn get_hash_algorithm(&self) -> &dyn HashAlgorithm<K> {
+        self.hash_algorithm.as_ref()
+    }
+
+    /// Hashes a word.
+    ///
+    /// Words that were not in the word list still hash to some value in the
+    /// range of the table. Words the hash algorithm rejects hash to an
+    /// unspecified value, use `try_hash` to detect them.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    #[must_use]
+    pub fn hash(&self, word: &K) -> usize {
+        hash(word, &self.rlt, self.hash_algorithm.as_ref())
+    }
+
+    /// Hashes a word, reporting words that cannot be hashed.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// A hash value.
+    ///
+    /// # Errors
+    /// Will return `Err` if the hash algorithm rejects `word`, or if `word`
+    /// indexes outside of the row lookup table. When row bounds were recorded,
+    /// words whose column index is outside of their row's bounds are rejected too.
+    pub fn try_hash(&self, word: &K) -> Result<usize, Error> {
+        try_hash(word, &self.rlt, self.hash_algorithm.as_ref())
+    }
+
+    /// Traces the hashing of a word step by step.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// The steps of hashing the word, see `HashTrace`. Its `hash_value` is the
+    /// value `try_hash` returns.
+    ///
+    /// # Errors
+    /// Will return `Err` for the words `try_hash` rejects, and for every word
+    /// when the hash was built by the CHM backend, which has no row lookup
+    /// table.
+    pub fn explain(&self, word: &K) -> Result<HashTrace, Error> {
+        explain(word, &self.rlt, self.hash_algorithm.as_ref())
+    }
+
+    /// Hashes a word, rejecting words that were not in the word list.
+    ///
+    /// Membership can only be confirmed when the keys were stored, see
+    /// `Options::store_keys`. Otherwise only words that cannot be hashed are
+    /// rejected, like with `try_hash`.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be hashed.
+    ///
+    /// # Returns
+    /// The hash value of `word`, or `None` if `word` is not in the word list.
+    #[must_use]
+    pub fn find(&self, word: &K) -> Option<usize> {
+        let hash_value = self.try_hash(word).ok()?;
+        let slot = hash_value.checked_sub(self.rlt.get_hash_base())?;
+        let fold_case = self.hash_algorithm.folds_case();
+        match &self.keys {
+            Some(keys)
+                if compare_key(keys.get(slot)?.borrow(), fold_case)
+                    != compare_key(word, fold_case) =>
+            {
+                None
+            }
+            _ => Some(hash_value),
+        }
+    }
+
+    /// Gets the size of the table the hash values index into.
+    ///
+    /// # Returns
+    /// The number of distinct hash values, which start at
+    /// `Rlt::get_hash_base`.
+    #[must_use]
+    pub fn table_size(&self) -> usize {
+        self.rlt.get_num_entries()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ElcAlgorithm, Kind};
+
+    #[test]
+    fn msmp_fn_unit_test() {
+        let rlt = Rlt::with_values(vec![0, 1], 3);
+
+        let f = MsmpFn::new(rlt, ElcAlgorithm::default());
+        assert_eq!(f.table_size(), 3);
+        assert_eq!(f.hash("AA"), 0);
+        assert_eq!(f.hash("BA"), 1);
+        assert_eq!(f.hash("BB"), 2);
+        assert_eq!(f.try_hash("BB").unwrap(), 2);
+
+        let g = f.clone();
+        assert_eq!(g.hash("BA"), f.hash("BA"));
+        println!("{g:?}");
+
+        match f.try_hash("ZA").unwrap_err().kind() {
+            Kind::HashError(s) => assert_eq!(s, "Row index 25 is out of range."),
+            _ => panic!("Unexpected error type."),
+        }
+        match f.try_hash("bA").unwrap_err().kind() {
+            Kind::ElcAlgorithmError(_) => {}
+            _ => panic!("Unexpected error type."),
+        }
+
+        assert_eq!(f.get_keys(), None);
+        assert_eq!(f.find("AB"), Some(1));
+        assert_eq!(f.find("ZA"), None);
+        let f = f.with_keys(vec!["AA".to_string(), "BA".to_string(), String::new()]);
+        assert_eq!(f.get_keys().map(<[String]>::len), Some(3));
+        assert_eq!(f.find("AA"), Some(0));
+        assert_eq!(f.find("BA"), Some(1));
+        assert_eq!(f.find("AB"), None);
+        assert_eq!(f.find("BB"), None);
+    }
+}
@@ -0,0 +1,340 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::codegen::check_word_list;
+use crate::search::auto_elc_algorithm;
+use crate::{
+    generate_hash_with_options, try_hash, Error, HashAlgorithm, HashData, Kind, Options, Rlt,
+    WordList,
+};
+
+/// A read only map from the words of a word list to values, indexed by a
+/// generated msmp hash.
+///
+/// Unlike `HashData` the map can be sent to and shared between threads, so it
+/// can be generated in the background.
+pub struct MsmpMap<V> {
+    /// The row lookup table.
+    rlt: Rlt,
+
+    /// The hash algorithm the table was generated with.
+    hash_algorithm: Arc<dyn HashAlgorithm + Send + Sync>,
+
+    /// The words, indexed by hash value less the hash base. Used to confirm a
+    /// match. Empty for hash values without a word, which only exist when the
+    /// table was packed with slack or rounded up to a power of two.
+    keys: Vec<String>,
+
+    /// The values, indexed like `keys`. `None` for hash values without a word.
+    values: Vec<Option<V>>,
+
+    /// The number of distinct words, counted when the map is built.
+    len: usize,
+}
+
+/// A map from words to values with O(1), collision free lookups. Another name
+/// for `MsmpMap`, matching `PerfectHashSet`.
+pub type PerfectHashMap<V> = MsmpMap<V>;
+
+impl<V: fmt::Debug> fmt::Debug for MsmpMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MsmpMap")
+            .field("rlt", &self.rlt)
+            .field("keys", &self.keys)
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V> MsmpMap<V> {
+    /// Creates a new map by generating a hash for the word list.
+    ///
+    /// # Parameters
+    /// * `word_list` - The keys of the map.
+    /// * `values` - One value per word, in word list order. When repeated words
+    ///   are allowed, the value of the first occurrence is kept.
+    /// * `hash_algorithm` - A hash algorithm.
+    /// * `options` - Options controlling the generation.
+    ///
+    /// # Returns
+    /// A new map.
+    ///
+    /// # Errors
+    /// Will return `Err` if the number of values does not match the number of
+    /// words, or if `word_list` fails to resolve to a hash function.
+    pub fn new(
+        word_list: &WordList,
+        values: Vec<V>,
+        hash_algorithm: impl HashAlgorithm + Send + Sync + 'static,
+        options: &Options,
+    ) -> Result<Self, Error> {
+        let hash_algorithm: Arc<dyn HashAlgorithm + Send + Sync> = Arc::new(hash_algorithm);
+        check_len(word_list, &values)?;
+        let hash_data =
+            generate_hash_with_options(word_list, Arc::clone(&hash_algorithm), options)?;
+        Self::from_parts(word_list, values, hash_algorithm, &hash_data)
+    }
+
+    /// Creates a new map, choosing the `elc` value automatically.
+    ///
+    /// See `generate_hash_auto_with_options`.
+    ///
+    /// # Parameters
+    /// * `word_list` - The keys of the map.
+    /// * `values` - One value per word, in word list order.
+    /// * `options` - Options controlling the generation.
+    ///
+    /// # Returns
+    /// A new map.
+    ///
+    /// # Errors
+    /// Will return `Err` if the number of values does not match the number of
+    /// words, or if no `elc` value produces a hash.
+    pub fn auto(word_list: &WordList, values: Vec<V>, options: &Options) -> Result<Self, Error> {
+        check_len(word_list, &values)?;
+        let (hash_algorithm, hash_data) = auto_elc_algorithm(word_list, options)?;
+        Self::from_parts(word_list, values, Arc::new(hash_algorithm), &hash_data)
+    }
+
+    /// Creates a new map from word and value pairs, choosing the `elc` value
+    /// automatically.
+    ///
+    /// # Parameters
+    /// * `pairs` - The keys of the map, each with its value.
+    /// * `options` - Options controlling the generation.
+    ///
+    /// # Returns
+    /// A new map.
+    ///
+    /// # Errors
+    /// Will return `Err` if the words are invalid, or if no `elc` value produces
+    /// a hash.
+    pub fn from_pairs(
+        pairs: impl IntoIterator<Item = (String, V)>,
+        options: &Options,
+    ) -> Result<Self, Error> {
+        let (list, values): (Vec<String>, Vec<V>) = pairs.into_iter().unzip();
+        Self::auto(&WordList { list }, values, options)
+    }
+
+    /// Assembles a map from a generated hash.
+    ///
+    /// # Parameters
+    /// * `word_list` - The keys of the map.
+    /// * `values` - One value per word, in word list order.
+    /// * `hash_algorithm` - The hash algorithm `hash_data` was generated with.
+    /// * `hash_data` - The hash generated from `word_list`.
+    ///
+    /// # Returns
+    /// A new map.
+    ///
+    /// # Errors
+    /// Will return `Err` if the number of values does not match the number of
+    /// words, or if `hash_data` was not generated from `word_list`.
+    fn from_parts(
+        word_list: &WordList,
+        values: Vec<V>,
+        hash_algorithm: Arc<dyn HashAlgorithm + Send + Sync>,
+        hash_data: &HashData,
+    ) -> Result<Self, Error> {
+        check_len(word_list, &values)?;
+        check_word_list(word_list, hash_data)?;
+        let order = hash_data.permutation.words_in_hash_order();
+        let mut slots: Vec<Option<V>> = values.into_iter().map(Some).collect();
+        Ok(Self {
+            rlt: hash_data.as_fn.get_rlt().clone(),
+            hash_algorithm,
+            keys: order
+                .iter()
+                .map(|w| w.map_or_else(String::new, |w| word_list[w].clone()))
+                .collect(),
+            values: order
+                .iter()
+                .map(|w| w.and_then(|w| slots[w].take()))
+                .collect(),
+            len: order.iter().flatten().count(),
+        })
+    }
+
+    /// Gets the value of a word.
+    ///
+    /// # Parameters
+    /// * `word` - The word to look up.
+    ///
+    /// # Returns
+    /// The value of `word`, or `None` if `word` is not a key of the map.
+    #[must_use]
+    pub fn get(&self, word: &str) -> Option<&V> {
+        let hash_value = try_hash(word, &self.rlt, self.hash_algorithm.as_ref()).ok()?;
+        let slot = hash_value.checked_sub(self.rlt.get_hash_base())?;
+        if self.keys.get(slot)? != word {
+            return None;
+        }
+        self.values.get(slot)?.as_ref()
+    }
+
+    /// Gets the number of keys.
+    ///
+    /// # Returns
+    /// The number of distinct words in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the map has no keys.
+    ///
+    /// # Returns
+    /// True if the map is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<V> TryFrom<Vec<(String, V)>> for MsmpMap<V> {
+    type Error = Error;
+
+    /// Creates a map from word and value pairs with the default options, see
+    /// `MsmpMap::from_pairs`.
+    ///
+    /// # Errors
+    /// Will return `Err` if the words are invalid, or if no `elc` value produces
+    /// a hash.
+    fn try_from(pairs: Vec<(String, V)>) -> Result<Self, Error> {
+        Self::from_pairs(pairs, &Options::default())
+    }
+}
+
+/// Checks that there is one value per word.
+///
+/// # Parameters
+/// * `word_list` - The keys of the map.
+/// * `values` - The values of the map.
+///
+/// # Errors
+/// Will return `Err` if the number of values does not match the number of words.
+fn check_len<V>(word_list: &WordList, values: &[V]) -> Result<(), Error> {
+    if values.len() != word_list.len() {
+        return Err(Error::new(Kind::WordListError(format!(
+            "Expected {} values, found {}.",
+            word_list.len(),
+            values.len()
+        ))));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DuplicatePolicy, ElcAlgorithm, PerfectHashMap};
+
+    #[test]
+    fn msmp_map_unit_test() {
+        let word_list: WordList = ["AND", "BEGIN", "CHAR", "END", "BEGIN"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let options = Options::new().duplicate_policy(DuplicatePolicy::Allow);
+        let map = MsmpMap::new(
+            &word_list,
+            vec![1, 2, 3, 4, 5],
+            ElcAlgorithm::default(),
+            &options,
+        )
+        .unwrap();
+        println!("{map:?}");
+        assert_eq!(map.len(), 4);
+        assert!(!map.is_empty());
+        assert_eq!(map.get("AND"), Some(&1));
+        assert_eq!(map.get("BEGIN"), Some(&2));
+        assert_eq!(map.get("CHAR"), Some(&3));
+        assert_eq!(map.get("END"), Some(&4));
+        assert_eq!(map.get("ELSE"), None);
+        assert_eq!(map.get("and"), None);
+
+        let map = MsmpMap::auto(&word_list, vec!["a"; 5], &options).unwrap();
+        assert_eq!(map.get("CHAR"), Some(&"a"));
+
+        match MsmpMap::auto(&word_list, vec![1], &options) {
+            Ok(_) => panic!("Value count mismatch not detected."),
+            Err(e) => match e.kind() {
+                Kind::WordListError(s) => assert_eq!(s, "Expected 5 values, found 1."),
+                _ => panic!("Unexpected error type."),
+            },
+        }
+
+        let options = options.slack_percent(50);
+        let map = MsmpMap::new(
+            &word_list,
+            vec![1, 2, 3, 4, 5],
+            ElcAlgorithm::default(),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get("END"), Some(&4));
+        assert_eq!(map.get(""), None);
+    }
+
+    #[test]
+    fn msmp_map_pairs_unit_test() {
+        let pairs: Vec<(String, u8)> = [("ELSE", 1), ("END", 2), ("ENTER", 3), ("EOF", 4)]
+            .iter()
+            .map(|(w, v)| ((*w).to_string(), *v))
+            .collect();
+        let map = PerfectHashMap::try_from(pairs.clone()).unwrap();
+        assert_eq!(map.len(), 4);
+        for (word, value) in &pairs {
+            assert_eq!(map.get(word), Some(value));
+        }
+        assert_eq!(map.get("EXIT"), None);
+
+        let options = Options::new().slack_percent(50);
+        let map = MsmpMap::from_pairs(pairs, &options).unwrap();
+        assert_eq!(map.get("ENTER"), Some(&3));
+        assert_eq!(map.len(), 4);
+
+        let pairs = vec![("END".to_string(), 1), ("end".to_string(), 2)];
+        match MsmpMap::try_from(pairs) {
+            Ok(_) => panic!("Invalid word not detected."),
+            Err(e) => match e.kind() {
+                Kind::WordListError(_) => {}
+                _ => panic!("Unexpected error type."),
+            },
+        }
+    }
+
+    #[test]
+    fn msmp_map_from_parts_unit_test() {
+        let word_list: WordList = ["AND", "BEGIN", "CHAR", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_algorithm: Arc<dyn HashAlgorithm + Send + Sync> =
+            Arc::new(ElcAlgorithm::default());
+        let hash_data = generate_hash_with_options(
+            &word_list,
+            Arc::clone(&hash_algorithm),
+            &Options::default(),
+        )
+        .unwrap();
+        let other: WordList = ["AND", "BEGIN"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        match MsmpMap::from_parts(&other, vec![1, 2], hash_algorithm, &hash_data) {
+            Ok(_) => panic!("Word list mismatch not detected."),
+            Err(e) => match e.kind() {
+                Kind::CodegenError(s) => {
+                    assert_eq!(
+                        s,
+                        "The hash was generated from 4 words, the word list holds 2."
+                    );
+                }
+                _ => panic!("Unexpected error type."),
+            },
+        }
+    }
+}
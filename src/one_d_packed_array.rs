@@ -1,185 +1,1313 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
 use std::convert::TryFrom;
 use std::iter::zip;
+use std::ops::Range;
+use std::time::{Duration, Instant};
 
-use crate::{Error, Kind, Rlt, Row, RowSizeIterator, TwoDArray};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::chm::Chm;
+use crate::{
+    from_index, Backend, Error, FreeSlots, Index, Kind, Options, PackContext, Rlt, Row, TwoDArray,
+};
+
+/// The number of candidate offsets searched between budget checks.
+#[cfg(not(feature = "parallel"))]
+const PROBE_CHUNK: usize = 1;
+
+/// The number of candidate offsets searched concurrently between budget checks.
+#[cfg(feature = "parallel")]
+const PROBE_CHUNK: usize = 1024;
 
 /// A one dimensional packed array.
 #[derive(Debug)]
 pub struct OneDPackedArray {
     /// The packed array.
-    array: Vec<usize>,
+    array: Vec<Index>,
+
+    /// The row lookup table. The row lookup table is used to find the index of the first element in
+    /// the array for a given row.
+    rlt: Rlt,
+
+    /// The row index and offset of each row, in the order the rows were placed.
+    placements: Vec<(usize, isize)>,
+
+    /// Every offset tried while packing, when `Options::record_pack_events` is set.
+    events: Vec<PackEvent>,
+}
+
+/// Statistics about the progress of packing a 2D array into a 1D array.
+///
+/// The time budget is checked before each batch of candidate offsets is probed, so
+/// the statistics of a `Kind::TimeBudgetExceeded` error count only the rows placed and
+/// the offsets probed before the budget ran out. A zero budget stops before the first
+/// probe, with nothing placed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackStats {
+    /// The number of rows placed into the packed array.
+    pub rows_placed: usize,
+
+    /// The number of rows in the 2D array.
+    pub num_rows: usize,
+
+    /// The number of offsets tried, over all rows.
+    pub probes: usize,
+
+    /// The time spent packing.
+    pub elapsed: Duration,
+}
+
+/// An attempt to place a row at an offset, recorded while packing when
+/// `Options::record_pack_events` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackEvent {
+    /// The index of the row in the 2D array.
+    pub row_index: usize,
+
+    /// The offset tried.
+    pub offset: isize,
+
+    /// The first index of the packed array, in column order, that a value of the
+    /// row landed on while already in use, or None if the row was placed at the
+    /// offset.
+    pub conflict: Option<usize>,
+}
+
+/// The bookkeeping of a packing in progress.
+#[derive(Debug)]
+pub(crate) struct Progress {
+    /// When the packing started.
+    pub(crate) start: Instant,
+
+    /// The statistics of the packing so far.
+    pub(crate) stats: PackStats,
+
+    /// The offsets tried so far, if they are recorded.
+    pub(crate) events: Option<Vec<PackEvent>>,
+}
+
+/// Computes the greatest common divisor of two numbers.
+///
+/// # Parameters
+/// * `a` - A number.
+/// * `b` - A number.
+///
+/// # Returns
+/// The greatest common divisor, `a` if `b` is 0.
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// A small seeded pseudo random number generator used to permute the row order.
+#[derive(Debug)]
+struct SplitMix64 {
+    /// The generator state.
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a new generator.
+    ///
+    /// # Parameters
+    /// * `seed` - The seed. The same seed always produces the same sequence.
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generates the next value.
+    ///
+    /// # Returns
+    /// A pseudo random value.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a value in the range `[0, n)`.
+    ///
+    /// # Parameters
+    /// * `n` - The end of the range, at least 1.
+    ///
+    /// # Returns
+    /// A pseudo random value below `n`.
+    fn below(&mut self, n: usize) -> usize {
+        usize::try_from(self.next() % (n as u64)).unwrap_or(0)
+    }
+
+    /// Generates a value in the range `[0, 1]`.
+    ///
+    /// # Returns
+    /// A pseudo random fraction.
+    #[cfg(feature = "heuristics")]
+    fn fraction(&mut self) -> f64 {
+        f64::from(u32::try_from(self.next() >> 32).unwrap_or(0)) / f64::from(u32::MAX)
+    }
+
+    /// Shuffles a slice in place.
+    ///
+    /// # Parameters
+    /// * `items` - The slice to shuffle.
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+impl OneDPackedArray {
+    /// Packs a 2D array with the backend chosen in `options`.
+    ///
+    /// # Parameters
+    /// * `two_d_array` - The 2D array to pack.
+    /// * `options` - The options controlling the packing.
+    ///
+    /// # Returns
+    /// A new one dimensional packed array.
+    ///
+    /// # Errors
+    /// Will return `Err` if the backend fails to place the rows, or if packing
+    /// exceeds a limit given in `options`.
+    pub fn pack(two_d_array: &TwoDArray, options: &Options) -> Result<Self, Error> {
+        let packed = match options.get_backend() {
+            Backend::Packed => Self::new(two_d_array, options),
+            Backend::Chd => Self::chd(two_d_array, options),
+            Backend::Chm => Self::chm(two_d_array),
+        }?;
+        let mut rlt = packed.rlt.with_hash_base(options.get_hash_base());
+        if options.get_power_of_two_table() && rlt.get_chm().is_none() {
+            rlt = rlt.with_mask();
+        }
+        Ok(Self { rlt, ..packed })
+    }
+
+    /// Computes the number of entries of the packed array: the number of
+    /// words, plus the slack, rounded up to a power of two when requested.
+    ///
+    /// A table that is not minimal anyway is grown further while two columns
+    /// of a row land on the same index, which no offset can fix. Otherwise a
+    /// word list that packs minimally could fail to pack with slack.
+    ///
+    /// # Parameters
+    /// * `order` - The rows to place.
+    /// * `num_words` - The number of words.
+    /// * `options` - The options giving the slack and the rounding.
+    ///
+    /// # Returns
+    /// The number of entries, the number of hash values.
+    ///
+    /// # Errors
+    /// Will return `Err` if the number of entries overflows.
+    fn table_len(
+        order: &[(usize, &Row)],
+        num_words: usize,
+        options: &Options,
+    ) -> Result<usize, Error> {
+        let slack_percent = options.get_slack_percent();
+        let power_of_two = options.get_power_of_two_table();
+        let too_large = || {
+            Error::new(Kind::OneDPackedArrayError(format!(
+                "The packed array of {num_words} words with {slack_percent}% slack is too large."
+            )))
+        };
+        let round = |len: usize| {
+            if power_of_two {
+                len.checked_next_power_of_two()
+            } else {
+                Some(len)
+            }
+        };
+        let mut table_len = num_words
+            .checked_mul(slack_percent)
+            .and_then(|slack| num_words.checked_add(slack.div_ceil(100)))
+            .and_then(round)
+            .ok_or_else(too_large)?;
+        if table_len > num_words {
+            while order.iter().any(|(_, row)| Self::aliases(row, table_len)) {
+                table_len = table_len
+                    .checked_add(1)
+                    .and_then(round)
+                    .ok_or_else(too_large)?;
+            }
+        }
+        Ok(table_len)
+    }
+
+    /// Checks whether two columns of a row land on the same index of the
+    /// packed array, whatever the row's offset.
+    ///
+    /// # Parameters
+    /// * `row` - The row.
+    /// * `table_len` - The number of entries in the packed array.
+    ///
+    /// # Returns
+    /// True if two columns of `row` are congruent modulo `table_len`.
+    fn aliases(row: &Row, table_len: usize) -> bool {
+        let cols = row.get_col_indices();
+        let distinct: BTreeSet<usize> = cols.iter().map(|c| c % table_len.max(1)).collect();
+        distinct.len() != cols.len()
+    }
+
+    /// Create a new one dimensional packed array.
+    /// The array is created by packing the values in the 2D array into a one dimensional array.
+    /// The array is packed by placing the values in the row into the array in order.
+    ///
+    /// # Parameters
+    /// * `two_d_array` - The 2D array to pack.
+    /// * `options` - The options controlling the packing.
+    ///
+    /// # Returns
+    /// A new one dimensional packed array.
+    ///
+    /// # Errors
+    /// Will return `Err` if the array cannot be packed, or if packing exceeds the
+    /// time budget given in `options`.
+    pub(crate) fn new(two_d_array: &TwoDArray, options: &Options) -> Result<Self, Error> {
+        let mut progress = Progress {
+            start: Instant::now(),
+            stats: PackStats {
+                num_rows: two_d_array.get_num_rows(),
+                ..PackStats::default()
+            },
+            events: options.get_record_pack_events().then(Vec::new),
+        };
+
+        // * Rows are placed biggest first.
+        let mut order: Vec<(usize, &Row)> = two_d_array.rows_by_size().collect();
+
+        let table_len = Self::table_len(&order, two_d_array.get_num_entries(), options)?;
+        Self::check_feasible(&order, table_len)?;
+        let rlt_len = two_d_array.get_last_row_index() + 1;
+        let mut rng = SplitMix64::new(options.get_order_seed());
+        let mut retries = 0;
+        loop {
+            let mut ctx = PackContext::new(&order, table_len, rlt_len, options, &mut progress);
+            let packed = options
+                .get_packing_strategy()
+                .pack(&mut ctx)
+                .and_then(|placed| Self::from_offsets(&ctx, &placed));
+            match packed {
+                Err(e)
+                    if retries < options.get_order_retries()
+                        && matches!(
+                            e.kind(),
+                            Kind::OneDPackedArrayError(_) | Kind::PackingFailed { .. }
+                        ) =>
+                {
+                    retries += 1;
+                    progress.stats.rows_placed = 0;
+                    if retries == 1 {
+                        // Keep biggest first, but break size ties the other way.
+                        order.reverse();
+                        order.sort_by_key(|(_, row)| Reverse(row.len()));
+                    } else {
+                        rng.shuffle(&mut order);
+                    }
+                }
+                rv => {
+                    return rv.map(|mut packed| {
+                        packed.events = progress.events.take().unwrap_or_default();
+                        packed
+                    })
+                }
+            }
+        }
+    }
+
+    /// Checks the rows for conflicts that no offsets can avoid, before any
+    /// packing is attempted.
+    ///
+    /// A row fits nowhere if two of its columns are congruent modulo the table
+    /// length, which includes any row with more columns than the table has
+    /// entries. Two rows fit together only if some difference of their offsets
+    /// keeps every pair of columns apart. Each pair of columns rules out one
+    /// difference, so only pairs of rows whose sizes multiply to at least the
+    /// table length can rule out all of them.
+    ///
+    /// # Parameters
+    /// * `order` - The rows to place, biggest first.
+    /// * `table_len` - The number of entries in the packed array.
+    ///
+    /// # Errors
+    /// Will return `Err` if a row, or a pair of rows, cannot be placed at any
+    /// offsets.
+    fn check_feasible(order: &[(usize, &Row)], table_len: usize) -> Result<(), Error> {
+        let infeasible = |(row_index, row): &(usize, &Row)| {
+            Error::new(Kind::PackingFailed {
+                row_index: *row_index,
+                rows_placed: 0,
+                cols: row.get_col_indices(),
+                occupancy: vec![false; table_len],
+            })
+        };
+        let cols: Vec<Vec<usize>> = order
+            .iter()
+            .map(|(_, row)| {
+                row.get_col_indices()
+                    .iter()
+                    .map(|c| c % table_len.max(1))
+                    .collect()
+            })
+            .collect();
+        if let Some(entry) = order.iter().find(|(_, row)| Self::aliases(row, table_len)) {
+            return Err(infeasible(entry));
+        }
+
+        let mut ruled_out = vec![false; table_len];
+        for (i, a) in cols.iter().enumerate() {
+            for (j, b) in cols.iter().enumerate().skip(i + 1) {
+                if a.len() * b.len() < table_len {
+                    // The rows are biggest first, so no later row can do it either.
+                    break;
+                }
+                ruled_out.fill(false);
+                for c_a in a {
+                    for c_b in b {
+                        ruled_out[(c_b + table_len - c_a) % table_len] = true;
+                    }
+                }
+                if ruled_out.iter().all(|r| *r) {
+                    return Err(infeasible(&order[j]));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a new one dimensional packed array with the CHD (compress, hash,
+    /// displace) construction.
+    ///
+    /// Each row of the 2D array is a bucket. Buckets are placed biggest first, each
+    /// at the first displacement, a multiplier and an offset, that sends every
+    /// column index `c` of the bucket to an unused index `(multiplier * c + offset)
+    /// % table_len`. Only multipliers coprime with the table length are tried, so
+    /// that distinct columns of a bucket stay distinct. Trying multipliers besides
+    /// 1 gives each bucket many more places to go than a plain offset, which keeps
+    /// the placement from getting stuck on large word lists.
+    ///
+    /// # Parameters
+    /// * `two_d_array` - The 2D array to pack.
+    /// * `options` - The options controlling the packing.
+    ///
+    /// # Returns
+    /// A new one dimensional packed array, whose row lookup table holds the
+    /// multipliers as well as the offsets.
+    ///
+    /// # Errors
+    /// Will return `Err` if a bucket cannot be placed with any displacement, or if
+    /// packing exceeds a budget given in `options`.
+    pub(crate) fn chd(two_d_array: &TwoDArray, options: &Options) -> Result<Self, Error> {
+        let mut progress = Progress {
+            start: Instant::now(),
+            stats: PackStats {
+                num_rows: two_d_array.get_num_rows(),
+                ..PackStats::default()
+            },
+            events: None,
+        };
+
+        let order: Vec<(usize, &Row)> = two_d_array.rows_by_size().collect();
+
+        let table_len = Self::table_len(&order, two_d_array.get_num_entries(), options)?;
+        let rlt_len = two_d_array.get_last_row_index() + 1;
+        let mut ctx = PackContext::new(&order, table_len, rlt_len, options, &mut progress);
+        let (mut self_, mut unused_array_indices) = Self::empty(&ctx);
+        let multipliers: Vec<usize> = (1..table_len.max(2))
+            .filter(|m| gcd(*m, table_len) == 1)
+            .collect();
+        let mut offsets = vec![0; rlt_len];
+        let mut row_multipliers = vec![1; rlt_len];
+        let mut col_bounds = vec![(1, 0); rlt_len];
+
+        for (k, (row_index, row)) in order.iter().enumerate() {
+            let col_indices = row.get_col_indices();
+            let Some(first_col_index) = col_indices.first() else {
+                continue;
+            };
+            // Columns that wrap onto each other collide with any displacement.
+            if Self::aliases(row, table_len) {
+                return Err(ctx.unable_to_pack());
+            }
+            let mut displacement = None;
+            'search: for multiplier in &multipliers {
+                ctx.probe(k)?;
+                let scaled: Vec<usize> = col_indices
+                    .iter()
+                    .map(|c| c % table_len * multiplier % table_len)
+                    .collect();
+                // Try the offsets that put the first column on an unused index.
+                let first = first_col_index % table_len * multiplier % table_len;
+                for slot in unused_array_indices.range(0..table_len) {
+                    let offset = (slot + table_len - first) % table_len;
+                    if scaled
+                        .iter()
+                        .all(|c| unused_array_indices.contains((c + offset) % table_len))
+                    {
+                        displacement = Some((*multiplier, offset, scaled));
+                        break 'search;
+                    }
+                }
+            }
+            let Some((multiplier, offset, scaled)) = displacement else {
+                return Err(ctx.unable_to_pack_with(
+                    (0..table_len)
+                        .map(|i| !unused_array_indices.contains(i))
+                        .collect(),
+                ));
+            };
+            for (c, v) in zip(scaled, row.get_col_values()) {
+                let i = (c + offset) % table_len;
+                unused_array_indices.remove(i);
+                self_.array[i] = v;
+            }
+            let Ok(offset) = isize::try_from(offset) else {
+                return Err(Error::new(Kind::OneDPackedArrayError(
+                    "Unexpected index overflow".to_string(),
+                )));
+            };
+            offsets[*row_index] = offset;
+            row_multipliers[*row_index] = multiplier;
+            self_.placements.push((*row_index, offset));
+            if let Some(last_col_index) = col_indices.last() {
+                col_bounds[*row_index] = (*first_col_index, *last_col_index);
+            }
+            ctx.set_rows_placed(k + 1);
+        }
+        let mut rlt = Rlt::with_values(offsets, table_len).with_multipliers(row_multipliers);
+        if options.get_row_bounds() {
+            rlt = rlt.with_col_bounds(col_bounds);
+        }
+        self_.rlt = Self::finish_rlt(rlt, &order, options);
+        Ok(self_)
+    }
+
+    /// Create a new one dimensional packed array with the order preserving CHM
+    /// construction.
+    ///
+    /// Instead of shifting rows, every cell of the 2D array is hashed with a
+    /// `Chm` built so that words keep the order of the word list. The packed array
+    /// then holds the words in order, and the row lookup table holds the `Chm`.
+    ///
+    /// # Parameters
+    /// * `two_d_array` - The 2D array to pack.
+    ///
+    /// # Returns
+    /// A new one dimensional packed array.
+    ///
+    /// # Errors
+    /// Will return `Err` if the hypergraph of the cells cannot be peeled.
+    pub(crate) fn chm(two_d_array: &TwoDArray) -> Result<Self, Error> {
+        let mut cells: Vec<(Index, usize, usize)> = Vec::new();
+        for (row_index, row) in two_d_array.rows() {
+            for (col_index, value) in zip(row.get_col_indices(), row.get_col_values()) {
+                cells.push((value, row_index, col_index));
+            }
+        }
+        cells.sort_unstable();
+
+        let chm = Chm::new(
+            &cells
+                .iter()
+                .map(|(_, row_index, col_index)| (*row_index, *col_index))
+                .collect::<Vec<_>>(),
+        )?;
+        let rlt = Rlt::with_values(vec![0; two_d_array.get_last_row_index() + 1], cells.len())
+            .with_chm(chm);
+        Ok(Self {
+            array: cells.iter().map(|(value, _, _)| *value).collect(),
+            rlt,
+            placements: Vec::new(),
+            events: Vec::new(),
+        })
+    }
+
+    /// Create an empty packed array.
+    ///
+    /// # Parameters
+    /// * `ctx` - The packing the array is for.
+    ///
+    /// # Returns
+    /// A packed array without any rows placed, along with its unused indices.
+    fn empty(ctx: &PackContext) -> (Self, FreeSlots) {
+        let self_ = OneDPackedArray {
+            array: vec![0; ctx.table_len()],
+            // Set once every row is placed, see `record`.
+            rlt: Rlt::with_values(Vec::new(), 0),
+            placements: Vec::new(),
+            events: Vec::new(),
+        };
+        (self_, FreeSlots::new(ctx.table_len()))
+    }
+
+    /// Create the packed array from the offsets chosen by a packing strategy.
+    ///
+    /// # Parameters
+    /// * `ctx` - The packing the offsets were chosen for.
+    /// * `placed` - The offset of each row of `ctx`.
+    ///
+    /// # Returns
+    /// A new one dimensional packed array.
+    ///
+    /// # Errors
+    /// Will return `Err` if an offset is missing or not allowed, see
+    /// `PackContext::allowed_offsets`, if it shifts a row below index 0, or if
+    /// two values land on the same index.
+    fn from_offsets(ctx: &PackContext, placed: &[isize]) -> Result<Self, Error> {
+        let (mut self_, mut unused_array_indices) = Self::empty(ctx);
+        if placed.len() != ctx.order.len() {
+            return Err(Error::new(Kind::OneDPackedArrayError(format!(
+                "Packing strategy placed {} of {} rows.",
+                placed.len(),
+                ctx.order.len()
+            ))));
+        }
+        for (i, ((row_index, row), rlt_value)) in zip(ctx.order, placed).enumerate() {
+            // Strategies may return any offset, so check that every column
+            // stays at a valid index before the row is adjusted.
+            let shifted = |col: &usize| {
+                isize::try_from(*col)
+                    .ok()
+                    .and_then(|col| col.checked_add(*rlt_value))
+            };
+            let cols = &ctx.rows()[i];
+            if !matches!(cols.first().and_then(shifted), Some(first) if first >= 0)
+                || cols.last().and_then(shifted).is_none()
+            {
+                return Err(Error::new(Kind::OneDPackedArrayError(format!(
+                    "Packing strategy shifted row {row_index} by {rlt_value}, outside of the packed array."
+                ))));
+            }
+            if ctx
+                .allowed_offsets(i)
+                .is_some_and(|allowed| !allowed.contains(rlt_value))
+            {
+                return Err(Error::new(Kind::OneDPackedArrayError(format!(
+                    "Packing strategy shifted row {row_index} by {rlt_value}, which is not allowed."
+                ))));
+            }
+            if !self_.fits(&unused_array_indices, row, *rlt_value) {
+                return Err(Error::new(Kind::OneDPackedArrayError(
+                    "Packing strategy placed overlapping rows.".to_string(),
+                )));
+            }
+            self_.insert(&mut unused_array_indices, row, *rlt_value);
+        }
+        self_.record(ctx.order, placed, ctx.rlt_len, ctx.options);
+        Ok(self_)
+    }
+
+    /// Place each row, biggest first, at the first offset it fits at.
+    ///
+    /// # Parameters
+    /// * `ctx` - The rows to place and the budget to place them in.
+    ///
+    /// # Returns
+    /// The offset of each row.
+    ///
+    /// # Errors
+    /// Will return `Err` if a row does not fit at any offset, or if packing exceeds
+    /// a budget given in the options.
+    pub(crate) fn first_fit(ctx: &mut PackContext) -> Result<Vec<isize>, Error> {
+        let (mut self_, mut unused_array_indices) = Self::empty(ctx);
+        let mut placed: Vec<isize> = Vec::new();
+
+        // * Loop through all rows containing one or more values.
+        for (i, (row_index, row)) in ctx.order.iter().copied().enumerate() {
+            let allowed = ctx.allowed_offsets(i);
+            if let Some(candidates) =
+                self_.candidate_offsets(&unused_array_indices, row, allowed)?
+            {
+                let rlt_value = self_.place_row(
+                    &mut unused_array_indices,
+                    row_index,
+                    row,
+                    candidates,
+                    ctx.options,
+                    ctx.progress,
+                )?;
+                ctx.progress.stats.rows_placed += 1;
+                placed.push(rlt_value);
+            } else {
+                placed.push(0);
+            }
+        }
+        Ok(placed)
+    }
+
+    /// Place each row, backtracking when a row cannot be placed.
+    ///
+    /// Instead of giving up when a row does not fit, the previously placed row is removed
+    /// and re-placed at its next fitting offset. This searches every combination of
+    /// offsets, so it only fails when no minimal packing exists for the row order, or
+    /// when a budget given in the options is exceeded.
+    ///
+    /// # Parameters
+    /// * `ctx` - The rows to place and the budget to place them in.
+    ///
+    /// # Returns
+    /// The offset of each row.
+    ///
+    /// # Errors
+    /// Will return `Err` if the rows cannot be packed in this order, or if packing
+    /// exceeds a budget given in the options.
+    pub(crate) fn backtracking(ctx: &mut PackContext) -> Result<Vec<isize>, Error> {
+        let (mut self_, mut unused_array_indices) = Self::empty(ctx);
+
+        // The offset and the end of the candidate offsets of each placed row.
+        let mut placed: Vec<(isize, isize)> = Vec::new();
+        let mut resume: Option<Range<isize>> = None;
+
+        while let Some((row_index, row)) = ctx.order.get(placed.len()).copied() {
+            let candidates = match resume.take() {
+                Some(candidates) => candidates,
+                None => self_
+                    .candidate_offsets(
+                        &unused_array_indices,
+                        row,
+                        ctx.allowed_offsets(placed.len()),
+                    )?
+                    .unwrap_or(0..1),
+            };
+            let end = candidates.end;
+            match self_.place_row(
+                &mut unused_array_indices,
+                row_index,
+                row,
+                candidates,
+                ctx.options,
+                ctx.progress,
+            ) {
+                Ok(rlt_value) => {
+                    ctx.progress.stats.rows_placed += 1;
+                    placed.push((rlt_value, end));
+                }
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        Kind::OneDPackedArrayError(_) | Kind::PackingFailed { .. }
+                    ) =>
+                {
+                    // Move the previous row on to its next offset.
+                    let Some((rlt_value, end)) = placed.pop() else {
+                        return Err(e);
+                    };
+                    let (_, prior_row) = ctx.order[placed.len()];
+                    self_.remove(&mut unused_array_indices, prior_row, rlt_value);
+                    ctx.progress.stats.rows_placed -= 1;
+                    resume = Some(rlt_value + 1..end);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(placed.into_iter().map(|(rlt_value, _)| rlt_value).collect())
+    }
+
+    /// Place the rows by simulated annealing.
+    ///
+    /// The cost of a placement is the number of values that overlap another value. Every
+    /// row starts at a random offset, then random rows are moved to random offsets. Moves
+    /// that lower the cost are always kept, moves that raise it are kept with a probability
+    /// that shrinks as the temperature drops, which lets the search climb out of local
+    /// minima early on.
+    ///
+    /// # Parameters
+    /// * `ctx` - The rows to place and the budget to place them in.
+    /// * `seed` - The seed of the random moves.
+    /// * `iterations` - The number of moves to try.
+    ///
+    /// # Returns
+    /// The offset of each row.
+    ///
+    /// # Errors
+    /// Will return `Err` if no overlap free placement is found within `iterations` moves,
+    /// or if packing exceeds a budget given in the options.
+    #[cfg(feature = "heuristics")]
+    pub(crate) fn annealing(
+        ctx: &mut PackContext,
+        seed: u64,
+        iterations: usize,
+    ) -> Result<Vec<isize>, Error> {
+        const START_TEMPERATURE: f64 = 2.0;
+        const END_TEMPERATURE: f64 = 0.01;
+
+        let num_entries = ctx.table_len();
+        let cols = ctx.rows().to_vec();
+        let allowed: Vec<Option<Range<isize>>> =
+            (0..cols.len()).map(|r| ctx.allowed_offsets(r)).collect();
+        if allowed.iter().flatten().any(Range::is_empty) {
+            return Err(ctx.unable_to_pack());
+        }
+        let mut rng = SplitMix64::new(seed);
+        // Offsets are drawn from the allowed offsets of the row, if restricted.
+        let draw = |rng: &mut SplitMix64, r: usize| match &allowed[r] {
+            Some(allowed) => {
+                let len = usize::try_from(allowed.end - allowed.start).unwrap_or(usize::MAX);
+                allowed.start + isize::try_from(rng.below(len)).unwrap_or(0)
+            }
+            None => isize::try_from(rng.below(num_entries.max(1))).unwrap_or(0),
+        };
+        let index = |c: &usize, offset: isize| {
+            let (c, n) = (isize::try_from(*c), isize::try_from(num_entries));
+            match (c, n) {
+                (Ok(c), Ok(n)) => usize::try_from((c + offset).rem_euclid(n)).unwrap_or(0),
+                _ => 0,
+            }
+        };
+
+        // The number of values placed at each index, and the number of overlaps.
+        let mut counts = vec![0_usize; num_entries];
+        let mut cost = 0;
+        let mut offsets: Vec<isize> = Vec::with_capacity(cols.len());
+        for (r, row_cols) in cols.iter().enumerate() {
+            let offset = draw(&mut rng, r);
+            for c in row_cols {
+                let i = index(c, offset);
+                cost += usize::from(counts[i] > 0);
+                counts[i] += 1;
+            }
+            offsets.push(offset);
+        }
+
+        let cooling = (END_TEMPERATURE / START_TEMPERATURE)
+            .powf(1.0 / f64::from(u32::try_from(iterations.max(1)).unwrap_or(u32::MAX)));
+        let mut temperature = START_TEMPERATURE;
+        let mut iteration = 0;
+        while cost > 0 && iteration < iterations && !cols.is_empty() {
+            let r = rng.below(cols.len());
+            ctx.probe(r)?;
+            iteration += 1;
+
+            let from = offsets[r];
+            let to = draw(&mut rng, r);
+            let mut new_cost = cost;
+            for c in &cols[r] {
+                let i = index(c, from);
+                counts[i] -= 1;
+                new_cost -= usize::from(counts[i] > 0);
+            }
+            for c in &cols[r] {
+                let i = index(c, to);
+                new_cost += usize::from(counts[i] > 0);
+                counts[i] += 1;
+            }
+
+            let accept = new_cost <= cost || {
+                let worse = f64::from(u32::try_from(new_cost - cost).unwrap_or(u32::MAX));
+                rng.fraction() < (-worse / temperature).exp()
+            };
+            if accept {
+                offsets[r] = to;
+                cost = new_cost;
+            } else {
+                for c in &cols[r] {
+                    counts[index(c, to)] -= 1;
+                }
+                for c in &cols[r] {
+                    counts[index(c, from)] += 1;
+                }
+            }
+            temperature *= cooling;
+        }
+
+        if cost > 0 {
+            return Err(ctx.unable_to_pack_with(counts.iter().map(|n| *n > 0).collect()));
+        }
+        ctx.set_rows_placed(cols.len());
+        Ok(offsets)
+    }
+
+    /// Compute the offsets a row may be placed at.
+    ///
+    /// # Parameters
+    /// * `unused_array_indices` - The set of unused indices in the packed array.
+    /// * `row` - The row to place.
+    /// * `allowed` - The offsets the row may be placed at, see
+    ///   `PackContext::allowed_offsets`.
+    ///
+    /// # Returns
+    /// The candidate offsets, or `None` if the row is empty.
+    ///
+    /// # Errors
+    /// Will return `Err` if an index overflows or if the packed array is already full.
+    fn candidate_offsets(
+        &self,
+        unused_array_indices: &FreeSlots,
+        row: &Row,
+        allowed: Option<Range<isize>>,
+    ) -> Result<Option<Range<isize>>, Error> {
+        let col_indices = row.get_col_indices();
+        let Some(fist_col_index) = col_indices.first() else {
+            return Ok(None);
+        };
+        let Ok(rlt_seed) = isize::try_from(*fist_col_index) else {
+            return Err(Error::new(Kind::OneDPackedArrayError(
+                "Unexpected empty row found".to_string(),
+            )));
+        };
+        let Some(first_array_unused_index) = unused_array_indices.first() else {
+            return Err(Error::new(Kind::OneDPackedArrayError(
+                "Unexpected no unused index found".to_string(),
+            )));
+        };
+        let Ok(first_array_unused_index) = isize::try_from(first_array_unused_index) else {
+            return Err(Error::new(Kind::OneDPackedArrayError(
+                "Unexpected index overflow".to_string(),
+            )));
+        };
+        let Ok(num_entries) = isize::try_from(self.array.len()) else {
+            return Err(Error::new(Kind::OneDPackedArrayError(
+                "Unexpected num entries overflow".to_string(),
+            )));
+        };
+
+        // Offsets at or beyond this would place the row's first value
+        // outside of the packed array, see `table_len`.
+        let end_rlt_value = num_entries - rlt_seed;
+        let mut candidates = -rlt_seed + first_array_unused_index..end_rlt_value;
+        if let Some(allowed) = allowed {
+            candidates.start = candidates.start.max(allowed.start);
+            candidates.end = candidates.end.min(allowed.end).max(candidates.start);
+        }
+        Ok(Some(candidates))
+    }
+
+    /// Record the offsets the rows were placed at in the row lookup table.
+    ///
+    /// # Parameters
+    /// * `order` - The rows of the 2D array, in the order they were placed.
+    /// * `placed` - The offset each row in `order` was placed at.
+    /// * `rlt_len` - The number of entries in the row lookup table.
+    /// * `options` - The options controlling whether row bounds are recorded.
+    fn record(
+        &mut self,
+        order: &[(usize, &Row)],
+        placed: &[isize],
+        rlt_len: usize,
+        options: &Options,
+    ) {
+        let mut values = vec![0; rlt_len];
+        let mut col_bounds = vec![(1, 0); rlt_len];
+        for ((row_index, row), rlt_value) in zip(order, placed) {
+            let col_indices = row.get_col_indices();
+            if let (Some(fist_col_index), Some(last_col_index)) =
+                (col_indices.first(), col_indices.last())
+            {
+                // Record how much the row was shifted in the row lookup table.
+                values[*row_index] = *rlt_value;
+                self.placements.push((*row_index, *rlt_value));
+                col_bounds[*row_index] = (*fist_col_index, *last_col_index);
+            }
+        }
+        let mut rlt = Rlt::with_values(values, self.array.len());
+        if options.get_row_bounds() {
+            rlt = rlt.with_col_bounds(col_bounds);
+        }
+        self.rlt = Self::finish_rlt(rlt, order, options);
+    }
+
+    /// Completes the row lookup table once every row is recorded, normalizing
+    /// it or making it sparse as the options request.
+    ///
+    /// # Parameters
+    /// * `rlt` - The row lookup table.
+    /// * `order` - The rows of the 2D array.
+    /// * `options` - The options controlling the form of the row lookup table.
+    ///
+    /// # Returns
+    /// The row lookup table in the requested form.
+    fn finish_rlt(mut rlt: Rlt, order: &[(usize, &Row)], options: &Options) -> Rlt {
+        if options.get_normalize_rlt() {
+            rlt = rlt.into_normalized();
+        }
+        if options.get_sparse_rlt() {
+            rlt = rlt.into_sparse(
+                order
+                    .iter()
+                    .filter(|(_, row)| !row.is_empty())
+                    .map(|(row_index, _)| *row_index),
+            );
+        }
+        if options.get_two_level_rlt() {
+            rlt = rlt.into_two_level();
+        }
+        rlt
+    }
+
+    /// Get the row lookup table for the packed array.
+    /// The row lookup table is used to find the index of the first element in the array for a given
+    /// row.
+    ///
+    /// # Returns
+    /// The row lookup table.
+    #[must_use]
+    pub fn get_rlt(&self) -> &Rlt {
+        &self.rlt
+    }
+
+    /// Get where each row of the 2D array was placed.
+    ///
+    /// The CHM backend hashes cells instead of placing rows, so it has no
+    /// placements.
+    ///
+    /// # Returns
+    /// The row index and offset of each row, in the order the rows were
+    /// placed. A row's column `c` landed at index `(offset + c) % len()`, with
+    /// the column scaled by the row's multiplier for the CHD backend.
+    #[must_use]
+    pub fn placements(&self) -> &[(usize, isize)] {
+        &self.placements
+    }
+
+    /// Get every offset tried while packing, in the order they were tried.
+    ///
+    /// Offsets are only recorded when `Options::record_pack_events` is set, and
+    /// only by the `FirstFit` and `Backtracking` strategies of the packed
+    /// backend. The attempts of row orders that were retried, see
+    /// `Options::order_retries`, come first.
+    ///
+    /// # Returns
+    /// The attempts to place a row, empty if none were recorded.
+    #[must_use]
+    pub fn pack_events(&self) -> &[PackEvent] {
+        &self.events
+    }
+
+    /// Get the packed array.
+    /// Each position holds the 1 based index of the word placed there.
+    ///
+    /// # Returns
+    /// The packed array.
+    pub(crate) fn as_slice(&self) -> &[Index] {
+        &self.array
+    }
+
+    /// Get the word placed at a position of the packed array.
+    ///
+    /// The position of a word is its hash value, so a value table laid out like
+    /// the packed array can be indexed directly by hash value.
+    ///
+    /// # Parameters
+    /// * `index` - The position in the packed array.
+    ///
+    /// # Returns
+    /// The 1 based index in the word list of the word placed at `index`, 0 if no
+    /// word was placed there, or `None` if `index` is out of range.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<usize> {
+        self.array.get(index).map(|w| from_index(*w))
+    }
+
+    /// Iterates over the packed array.
+    ///
+    /// # Returns
+    /// The 1 based word index at each position, 0 for unused positions, in
+    /// position order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.array.iter().map(|w| from_index(*w))
+    }
+
+    /// Get the number of positions in the packed array.
+    ///
+    /// # Returns
+    /// The number of positions, which is the table size of the hash.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.array.len()
+    }
 
-    /// The row lookup table. The row lookup table is used to find the index of the first element in
-    /// the array for a given row.
-    rlt: Rlt,
-}
+    /// Is the packed array empty.
+    ///
+    /// # Returns
+    /// True if the packed array is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
 
-impl OneDPackedArray {
-    /// Create a new one dimensional packed array.
-    /// The array is created by packing the values in the 2D array into a one dimensional array.
-    /// The array is packed by placing the values in the row into the array in order.
+    /// Place a row into the packed array.
+    ///
+    /// The row is shifted through the candidate offsets until it can be inserted without
+    /// overlapping any existing values. Offsets that put the row's first column on a used
+    /// index are skipped without being tried.
     ///
     /// # Parameters
-    /// * `two_d_array` - The 2D array to pack.
+    /// * `unused_array_indices` - The set of unused indices in the packed array.
+    /// * `row_index` - The index of the row in the 2D array.
+    /// * `row` - The row to place.
+    /// * `candidates` - The offsets to try, in order.
+    /// * `options` - The options holding the packing budgets.
+    /// * `progress` - The progress of the packing so far.
     ///
     /// # Returns
-    /// A new one dimensional packed array.
+    /// The offset the row was placed at.
     ///
     /// # Errors
-    /// Will return `Err` if the array cannot be packed.
-    pub fn new(two_d_array: &TwoDArray) -> Result<Self, Error> {
-        let mut self_ = OneDPackedArray {
-            array: vec![0; two_d_array.get_num_entries()],
-            rlt: Rlt::new(two_d_array.get_last_row_index() + 1),
-        };
-        let mut rlt_wrk: BTreeMap<usize, isize> = BTreeMap::new();
+    /// Will return `Err` if the row does not fit at any candidate offset, or if a budget
+    /// is exceeded.
+    fn place_row(
+        &mut self,
+        unused_array_indices: &mut FreeSlots,
+        row_index: usize,
+        row: &Row,
+        candidates: Range<isize>,
+        options: &Options,
+        progress: &mut Progress,
+    ) -> Result<isize, Error> {
+        let mut rlt_value = candidates.start;
+        let mut row_probes: usize = 0;
+        let mut chunk: Vec<isize> = Vec::with_capacity(PROBE_CHUNK);
+        loop {
+            if let Some(max_duration) = options.get_max_duration() {
+                progress.stats.elapsed = progress.start.elapsed();
+                if progress.stats.elapsed > max_duration {
+                    return Err(Error::new(Kind::TimeBudgetExceeded(progress.stats.clone())));
+                }
+            }
 
-        let mut unused_array_indices: BTreeSet<usize> =
-            (0..two_d_array.get_num_entries()).collect();
+            let budget = OneDPackedArray::probe_budget(options, row_probes, progress.stats.probes);
+            OneDPackedArray::next_candidates(
+                unused_array_indices,
+                row,
+                rlt_value..candidates.end,
+                budget,
+                &mut chunk,
+            );
+            let found = self.find_offset(unused_array_indices, row, &chunk);
+            let tried = found.map_or(chunk.len(), |i| i + 1);
+            row_probes += tried;
+            progress.stats.probes += tried;
+            if let Some(events) = &mut progress.events {
+                events.extend(chunk[..tried].iter().map(|offset| PackEvent {
+                    row_index,
+                    offset: *offset,
+                    conflict: self.conflict(unused_array_indices, row, *offset),
+                }));
+            }
 
-        // * Loop through all rows containing one or more values.
-        let mut it = RowSizeIterator::new(two_d_array);
-        while let Some((row_index, row)) = it.next_biggest() {
-            let col_indices = row.get_col_indices();
-            if let Some(fist_col_index) = col_indices.first() {
-                if let Ok(rlt_seed) = isize::try_from(*fist_col_index) {
-                    if let Some(first_array_unused_index) = unused_array_indices.first() {
-                        if let Ok(first_array_unused_index) =
-                            isize::try_from(*first_array_unused_index)
-                        {
-                            let mut rlt_value = -rlt_seed + first_array_unused_index;
-
-                            // Try to insert the row into the packed array. Keep shifting the row index
-                            // until it can be inserted.
-                            while self_.not_inserted(&mut unused_array_indices, row, rlt_value) {
-                                rlt_value += 1; // todo step to next unused index
-                                if let Ok(num_entries) =
-                                    isize::try_from(two_d_array.get_num_entries())
-                                {
-                                    if rlt_value + rlt_seed >= num_entries {
-                                        return Err(Error::new(Kind::OneDPackedArrayError(
-                                            "unable to minimally pack array".to_string(),
-                                        )));
-                                    }
-                                } else {
-                                    return Err(Error::new(Kind::OneDPackedArrayError(
-                                        "Unexpected num entries overflow".to_string(),
-                                    )));
-                                }
-                            }
-
-                            // Record how much the row was shifted in a row lookup map.
-                            rlt_wrk.insert(row_index, rlt_value);
-                        } else {
-                            return Err(Error::new(Kind::OneDPackedArrayError(
-                                "Unexpected index overflow".to_string(),
-                            )));
-                        }
-                    } else {
-                        return Err(Error::new(Kind::OneDPackedArrayError(
-                            "Unexpected no unused index found".to_string(),
-                        )));
-                    }
-                } else {
-                    return Err(Error::new(Kind::OneDPackedArrayError(
-                        "Unexpected empty row found".to_string(),
-                    )));
-                }
+            if let Some(found) = found {
+                self.insert(unused_array_indices, row, chunk[found]);
+                return Ok(chunk[found]);
             }
-        }
 
-        // Convert the row lookup map into a row lookup table.
-        let it = rlt_wrk.iter();
-        for (row_index, rlt_value) in it {
-            self_.rlt.insert(*row_index, *rlt_value);
+            rlt_value = chunk.last().map_or(candidates.end, |last| last + 1);
+            if rlt_value >= candidates.end {
+                return Err(Error::new(Kind::PackingFailed {
+                    row_index,
+                    rows_placed: progress.stats.rows_placed,
+                    cols: row.get_col_indices(),
+                    occupancy: (0..self.array.len())
+                        .map(|i| !unused_array_indices.contains(i))
+                        .collect(),
+                }));
+            }
+            if options
+                .get_row_probe_limit()
+                .is_some_and(|m| row_probes >= m)
+                || options
+                    .get_total_probe_limit()
+                    .is_some_and(|m| progress.stats.probes >= m)
+            {
+                progress.stats.elapsed = progress.start.elapsed();
+                return Err(Error::new(Kind::ProbeBudgetExceeded {
+                    row_index,
+                    stats: progress.stats.clone(),
+                }));
+            }
         }
-        self_.rlt.set_num_entries(self_.array.len());
-
-        Ok(self_)
     }
 
-    /// Get the row lookup table for the packed array.
-    /// The row lookup table is used to find the index of the first element in the array for a given
-    /// row.
+    /// The number of offsets that may be tried before the next budget check.
+    ///
+    /// The serial search checks after every offset. The parallel search
+    /// evaluates a chunk of offsets concurrently between checks.
+    ///
+    /// # Parameters
+    /// * `options` - The options holding the probe limits.
+    /// * `row_probes` - The number of offsets already tried for the current row.
+    /// * `total_probes` - The number of offsets already tried for all rows.
     ///
     /// # Returns
-    /// The row lookup table.
-    pub fn get_rlt(&self) -> &Rlt {
-        &self.rlt
+    /// The number of offsets to try, at least one.
+    fn probe_budget(options: &Options, row_probes: usize, total_probes: usize) -> usize {
+        let row_budget = options
+            .get_row_probe_limit()
+            .map_or(usize::MAX, |m| m.saturating_sub(row_probes));
+        let total_budget = options
+            .get_total_probe_limit()
+            .map_or(usize::MAX, |m| m.saturating_sub(total_probes));
+        PROBE_CHUNK.min(row_budget).min(total_budget).max(1)
     }
 
-    /// Is the packed array empty.
-    /// Only used for testing.
+    /// Collect the next offsets worth trying for a row.
     ///
-    /// # Returns
-    /// True if the packed array is empty.
-    #[cfg(test)]
-    pub fn is_empty(&self) -> bool {
-        self.array.is_empty()
+    /// A row can only fit at an offset that puts its first column on an unused
+    /// index, so rather than stepping through every offset the search jumps from
+    /// one unused index to the next.
+    ///
+    /// # Parameters
+    /// * `unused_array_indices` - The set of unused indices in the packed array.
+    /// * `row` - The row to place.
+    /// * `candidates` - The remaining offsets.
+    /// * `budget` - The maximum number of offsets to collect.
+    /// * `chunk` - Replaced by the offsets in `candidates` that put the row's
+    ///   first column on an unused index, in order, at most `budget` of them.
+    ///   Reused from probe to probe so the search does not allocate.
+    fn next_candidates(
+        unused_array_indices: &FreeSlots,
+        row: &Row,
+        candidates: Range<isize>,
+        budget: usize,
+        chunk: &mut Vec<isize>,
+    ) {
+        chunk.clear();
+        let Some(first_col) = row.iter().next().and_then(|(c, _)| isize::try_from(c).ok()) else {
+            chunk.extend(candidates.take(budget));
+            return;
+        };
+        let to_index = |rlt_value: isize| usize::try_from(rlt_value + first_col).unwrap_or(0);
+        let (start, end) = (to_index(candidates.start), to_index(candidates.end));
+        chunk.extend(
+            unused_array_indices
+                .range(start..end)
+                .take(budget)
+                .filter_map(|i| isize::try_from(i).ok())
+                .map(|i| i - first_col),
+        );
     }
 
-    /// Attempt to insert a row into the packed array.
-    ///
-    /// The row is inserted into the packed array if it can be inserted without overlapping any
-    /// existing values.
-    /// The row is inserted into the packed array by placing the values in the row into the array in
-    /// order.
-    /// The row is shifted by the given amount before being inserted.
+    /// Find the first offset at which a row can be inserted into the packed array.
     ///
     /// # Parameters
     /// * `unused_array_indices` - The set of unused indices in the packed array.
     /// * `row` - The row to insert.
-    /// * `rlt_value` - The amount to shift the row by before inserting.
+    /// * `candidates` - The offsets to try, in order.
     ///
     /// # Returns
-    /// True if the row cannot be inserted without overlapping an existing value.
-    /// False if the row can be inserted without overlapping an existing value.
-    fn not_inserted(
-        &mut self,
-        unused_array_indices: &mut BTreeSet<usize>,
+    /// The position in `candidates` of the first offset at which the row fits, if
+    /// any.
+    #[cfg(not(feature = "parallel"))]
+    fn find_offset(
+        &self,
+        unused_array_indices: &FreeSlots,
         row: &Row,
-        rlt_value: isize,
-    ) -> bool {
-        let col_indices = row.get_col_indices();
-
-        // Adjust the rows's column indices by the row lookup table value.
-        let adj_col_indices: Vec<usize> = col_indices
+        candidates: &[isize],
+    ) -> Option<usize> {
+        candidates
             .iter()
-            .map(|x| OneDPackedArray::adjust_index(*x, rlt_value, self.array.len()))
-            .collect::<BTreeSet<_>>()
-            .into_iter()
-            .collect();
+            .position(|rlt_value| self.fits(unused_array_indices, row, *rlt_value))
+    }
 
-        // Sanity check the adjusted column indices.
-        if adj_col_indices.len() != col_indices.len() {
-            return true;
-        }
+    /// Find the first offset at which a row can be inserted into the packed array.
+    ///
+    /// The candidate offsets are evaluated concurrently, but the lowest fitting
+    /// offset always wins so the result is identical to the serial search.
+    ///
+    /// # Parameters
+    /// * `unused_array_indices` - The set of unused indices in the packed array.
+    /// * `row` - The row to insert.
+    /// * `candidates` - The offsets to try, in order.
+    ///
+    /// # Returns
+    /// The position in `candidates` of the first offset at which the row fits, if
+    /// any.
+    #[cfg(feature = "parallel")]
+    fn find_offset(
+        &self,
+        unused_array_indices: &FreeSlots,
+        row: &Row,
+        candidates: &[isize],
+    ) -> Option<usize> {
+        candidates
+            .par_iter()
+            .position_first(|rlt_value| self.fits(unused_array_indices, row, *rlt_value))
+    }
 
+    /// Check whether a row can be inserted into the packed array.
+    ///
+    /// The adjusted column indices are computed on the fly, this is the hot path
+    /// of packing. Rows are checked by `check_feasible` before packing, so no
+    /// two columns of a row land on the same index.
+    ///
+    /// # Parameters
+    /// * `unused_array_indices` - The set of unused indices in the packed array.
+    /// * `row` - The row to insert.
+    /// * `rlt_value` - The amount to shift the row by before inserting.
+    ///
+    /// # Returns
+    /// True if the row can be inserted without overlapping an existing value.
+    fn fits(&self, unused_array_indices: &FreeSlots, row: &Row, rlt_value: isize) -> bool {
         // Check if any of the adjusted column indices are already in use.
-        {
-            let it = adj_col_indices.iter();
-            for i in it {
-                if !unused_array_indices.contains(i) {
-                    return true;
-                }
-            }
-        }
+        self.conflict(unused_array_indices, row, rlt_value)
+            .is_none()
+    }
 
-        // Remove the adjusted column indices from the set of unused indices.
-        let it = adj_col_indices.iter();
-        for i in it {
-            unused_array_indices.remove(i);
-        }
+    /// Find where a row would overlap an existing value of the packed array.
+    ///
+    /// # Parameters
+    /// * `unused_array_indices` - The set of unused indices in the packed array.
+    /// * `row` - The row to insert.
+    /// * `rlt_value` - The amount to shift the row by before inserting.
+    ///
+    /// # Returns
+    /// The first adjusted column index, in column order, that is already in use,
+    /// or None if the row can be inserted.
+    fn conflict(
+        &self,
+        unused_array_indices: &FreeSlots,
+        row: &Row,
+        rlt_value: isize,
+    ) -> Option<usize> {
+        row.iter()
+            .map(|(i, _)| OneDPackedArray::adjust_index(i, rlt_value, self.array.len()))
+            .find(|adj_i| !unused_array_indices.contains(*adj_i))
+    }
 
+    /// Insert a row into the packed array.
+    ///
+    /// The row is inserted into the packed array by placing the values in the row into the array in
+    /// order.
+    /// The row is shifted by the given amount before being inserted. The caller must have checked
+    /// that the row `fits` at this offset.
+    ///
+    /// # Parameters
+    /// * `unused_array_indices` - The set of unused indices in the packed array.
+    /// * `row` - The row to insert.
+    /// * `rlt_value` - The amount to shift the row by before inserting.
+    fn insert(&mut self, unused_array_indices: &mut FreeSlots, row: &Row, rlt_value: isize) {
         // Insert the row's values into the packed array at the adjusted column indices.
-        let col_values = row.get_col_values();
-        let it = zip(col_values.iter(), adj_col_indices.iter());
-        for (v, adj_i) in it {
-            self.array[*adj_i] = *v;
+        // Each value is adjusted along with its own column index, since columns that
+        // wrap around the end of the array land before the ones that do not.
+        for (i, v) in row.iter() {
+            let adj_i = OneDPackedArray::adjust_index(i, rlt_value, self.array.len());
+            unused_array_indices.remove(adj_i);
+            self.array[adj_i] = v;
         }
+    }
 
-        false
+    /// Remove a row from the packed array.
+    ///
+    /// Undoes an `insert` of the row at the same offset.
+    ///
+    /// # Parameters
+    /// * `unused_array_indices` - The set of unused indices in the packed array.
+    /// * `row` - The row to remove.
+    /// * `rlt_value` - The amount the row was shifted by when it was inserted.
+    fn remove(&mut self, unused_array_indices: &mut FreeSlots, row: &Row, rlt_value: isize) {
+        for (i, _) in row.iter() {
+            let adj_i = OneDPackedArray::adjust_index(i, rlt_value, self.array.len());
+            self.array[adj_i] = 0;
+            unused_array_indices.insert(adj_i);
+        }
     }
 
     /// Adjust an index by the given amount.
@@ -215,27 +1343,92 @@ impl OneDPackedArray {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ElcAlgorithm, WordList};
-
-    #[test]
-    fn one_d_packed_array_unit_test() {
-        let hash_algorithm: ElcAlgorithm = ElcAlgorithm::default();
+    #[cfg(feature = "heuristics")]
+    use crate::Annealing;
+    use crate::{Backend, Backtracking, ElcAlgorithm, HashAlgorithm, WordList};
 
+    fn axx_word_list() -> WordList {
         let mut word_list = WordList::new();
         word_list.push("AXXA");
         word_list.push("AXXC");
         word_list.push("AXXD");
         word_list.push("BXXA");
         word_list.push("BXXC");
+        word_list
+    }
+
+    /// Checks that every word can be found in the packed array at its hash value.
+    fn assert_packs(words: &[&str], odpa: &OneDPackedArray) {
+        let hash_algorithm = ElcAlgorithm::default();
+        let mut values: Vec<usize> = odpa
+            .as_slice()
+            .iter()
+            .map(|v| from_index(*v))
+            .filter(|v| *v != 0)
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, (1..=words.len()).collect::<Vec<_>>());
+        for (i, word) in words.iter().enumerate() {
+            let row = hash_algorithm.h1(word).unwrap();
+            let col = odpa.rlt.scale(row, hash_algorithm.h2(word).unwrap());
+            let rlt_value = *odpa.rlt.get(row).unwrap();
+            let index = OneDPackedArray::adjust_index(col, rlt_value, odpa.as_slice().len());
+            assert_eq!(from_index(odpa.as_slice()[index]), i + 1);
+            assert!(odpa.placements().contains(&(row, rlt_value)));
+        }
+    }
+
+    #[test]
+    fn one_d_packed_array_slack_unit_test() {
+        let hash_algorithm = ElcAlgorithm::default();
+        let mut word_list = axx_word_list();
+        word_list.push("BXXZ");
+        let words: Vec<&str> = word_list.iter().map(String::as_str).collect();
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+
+        // 6 words with 50% slack get 9 entries, which is enough room for rows
+        // that can not be packed minimally.
+        match OneDPackedArray::new(&tda, &Options::new().slack_percent(50)) {
+            Ok(odpa) => {
+                assert_eq!(odpa.as_slice().len(), 9);
+                assert_eq!(odpa.rlt.get_num_entries(), 9);
+                assert_packs(&words, &odpa);
+            }
+            Err(e) => panic!("Unexpected packing failure. {e}"),
+        }
 
-        if let Ok(tda) = TwoDArray::new(&word_list, &hash_algorithm) {
-            if let Ok(odpa) = OneDPackedArray::new(&tda) {
+        // The slack is rounded up to a whole entry.
+        let odpa = OneDPackedArray::new(&tda, &Options::new().slack_percent(1)).unwrap();
+        assert_eq!(odpa.as_slice().len(), 7);
+        assert_packs(&words, &odpa);
+    }
+
+    #[test]
+    fn one_d_packed_array_unit_test() {
+        let hash_algorithm: ElcAlgorithm = ElcAlgorithm::default();
+
+        let mut word_list = axx_word_list();
+
+        if let Ok(tda) = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()) {
+            if let Ok(odpa) = OneDPackedArray::new(&tda, &Options::default()) {
                 println!("{odpa:?}");
-                assert_eq!(odpa.array, vec![1, 4, 2, 3, 5]);
+                assert_eq!(odpa.as_slice(), &[1, 5, 2, 3, 4]);
                 assert_eq!(odpa.rlt.get(0), Some(&0));
                 assert_eq!(odpa.rlt.get(1), Some(&4));
                 assert_eq!(odpa.array.len(), 5);
+                assert_eq!(odpa.len(), 5);
                 assert!(!odpa.is_empty());
+                assert_eq!(odpa.get(1), Some(5));
+                assert_eq!(odpa.get(5), None);
+                assert_eq!(odpa.iter().collect::<Vec<_>>(), vec![1, 5, 2, 3, 4]);
+                assert_eq!(odpa.rlt.get_col_bounds(0), None);
+            } else {
+                panic!("Unable to create OneDPackedArray");
+            }
+
+            if let Ok(odpa) = OneDPackedArray::new(&tda, &Options::new().row_bounds(true)) {
+                assert_eq!(odpa.rlt.get_col_bounds(0), Some((0, 3)));
+                assert_eq!(odpa.rlt.get_col_bounds(1), Some((0, 2)));
             } else {
                 panic!("Unable to create OneDPackedArray");
             }
@@ -244,13 +1437,11 @@ mod tests {
         }
 
         word_list.push("BXXZ");
-        if let Ok(tda) = TwoDArray::new(&word_list, &hash_algorithm) {
-            match OneDPackedArray::new(&tda) {
+        if let Ok(tda) = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()) {
+            match OneDPackedArray::new(&tda, &Options::default()) {
                 Ok(_) => panic!("Should not be able to create OneDPackedArray"),
                 Err(e) => match e.kind() {
-                    Kind::OneDPackedArrayError(s) => {
-                        assert_eq!(s, "unable to minimally pack array");
-                    }
+                    Kind::PackingFailed { .. } => {}
                     _ => panic!("Unexpected error type"),
                 },
             }
@@ -262,18 +1453,425 @@ mod tests {
         word_list2.push("WORD");
         word_list2.push("WORF");
 
-        if let Ok(tda) = TwoDArray::new(&word_list2, &hash_algorithm) {
-            match OneDPackedArray::new(&tda) {
+        if let Ok(tda) = TwoDArray::new(&word_list2, &hash_algorithm, &Options::default()) {
+            match OneDPackedArray::new(&tda, &Options::default()) {
                 Ok(_) => panic!("Should not be able to create OneDPackedArray"),
                 Err(e) => match e.kind() {
-                    Kind::OneDPackedArrayError(s) => {
-                        assert_eq!(s, "unable to minimally pack array");
+                    Kind::PackingFailed { .. } => {}
+                    _ => panic!("Unexpected error type"),
+                },
+            }
+        } else {
+            panic!("Unable to create TwoDArray");
+        }
+    }
+
+    #[test]
+    fn one_d_packed_array_wraparound_unit_test() {
+        let hash_algorithm: ElcAlgorithm = ElcAlgorithm::default();
+        let word_list = axx_word_list();
+
+        // Row B is shifted by 4, so its column 2 wraps around to index 1, before its
+        // column 0 at index 4. Every word must still find its own value.
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        let odpa = OneDPackedArray::new(&tda, &Options::default()).unwrap();
+        for (i, word) in ["AXXA", "AXXC", "AXXD", "BXXA", "BXXC"].iter().enumerate() {
+            let row = hash_algorithm.h1(word).unwrap();
+            let col = hash_algorithm.h2(word).unwrap();
+            let rlt_value = *odpa.rlt.get(row).unwrap();
+            let index = OneDPackedArray::adjust_index(col, rlt_value, odpa.array.len());
+            assert_eq!(from_index(odpa.as_slice()[index]), i + 1, "{word}");
+        }
+    }
+
+    #[test]
+    fn one_d_packed_array_budget_unit_test() {
+        let hash_algorithm: ElcAlgorithm = ElcAlgorithm::default();
+        let word_list = axx_word_list();
+
+        if let Ok(tda) = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()) {
+            let options = Options::new().max_duration(Duration::ZERO);
+            match OneDPackedArray::new(&tda, &options) {
+                Ok(_) => panic!("Time budget should have been exceeded"),
+                Err(e) => match e.kind() {
+                    Kind::TimeBudgetExceeded(stats) => {
+                        assert_eq!(stats.rows_placed, 0);
+                        assert_eq!(stats.num_rows, 2);
+                        assert_eq!(stats.probes, 0);
+                    }
+                    _ => panic!("Unexpected error type"),
+                },
+            }
+        } else {
+            panic!("Unable to create TwoDArray");
+        }
+
+        if let Ok(tda) = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()) {
+            // Row 1 fits at the second offset that puts its first column on an
+            // unused index. Offsets landing on used indices are not probed.
+            let options = Options::new().row_probe_limit(1);
+            match OneDPackedArray::new(&tda, &options) {
+                Ok(_) => panic!("Row probe budget should have been exceeded"),
+                Err(e) => match e.kind() {
+                    Kind::ProbeBudgetExceeded { row_index, stats } => {
+                        assert_eq!(*row_index, 1);
+                        assert_eq!(stats.rows_placed, 1);
+                        assert_eq!(stats.probes, 2);
+                    }
+                    _ => panic!("Unexpected error type"),
+                },
+            }
+            assert!(OneDPackedArray::new(&tda, &Options::new().row_probe_limit(2)).is_ok());
+
+            let options = Options::new().total_probe_limit(2);
+            match OneDPackedArray::new(&tda, &options) {
+                Ok(_) => panic!("Total probe budget should have been exceeded"),
+                Err(e) => match e.kind() {
+                    Kind::ProbeBudgetExceeded { row_index, stats } => {
+                        assert_eq!(*row_index, 1);
+                        assert_eq!(stats.probes, 2);
                     }
                     _ => panic!("Unexpected error type"),
                 },
             }
+
+            let options = Options::new().row_probe_limit(4).total_probe_limit(5);
+            if let Err(e) = OneDPackedArray::new(&tda, &options) {
+                panic!("Unexpected probe budget failure. {e}");
+            }
         } else {
             panic!("Unable to create TwoDArray");
         }
     }
+
+    #[test]
+    fn one_d_packed_array_order_retry_unit_test() {
+        let hash_algorithm = ElcAlgorithm::default();
+        for (words, order_retries) in [
+            (["AXD", "BXA", "BXD", "BXE", "CXC", "CXD", "EXC", "EXF"], 1),
+            (["AXA", "AXF", "BXA", "BXD", "BXE", "DXB", "EXD", "EXE"], 5),
+        ] {
+            let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+            let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+
+            match OneDPackedArray::new(&tda, &Options::default()) {
+                Ok(_) => panic!("Biggest first order should not have packed"),
+                Err(e) => match e.kind() {
+                    Kind::PackingFailed { .. } => {}
+                    _ => panic!("Unexpected error type"),
+                },
+            }
+
+            let options = Options::new().order_retries(order_retries);
+            match OneDPackedArray::new(&tda, &options) {
+                Ok(odpa) => {
+                    let mut values: Vec<usize> =
+                        odpa.as_slice().iter().map(|v| from_index(*v)).collect();
+                    values.sort_unstable();
+                    assert_eq!(values, (1..=words.len()).collect::<Vec<_>>());
+                }
+                Err(e) => panic!("Unexpected packing failure. {e}"),
+            }
+        }
+
+        let mut rng = SplitMix64::new(1);
+        let mut items: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut items);
+        let mut sorted = items.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+        let mut rng = SplitMix64::new(1);
+        let mut again: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut again);
+        assert_eq!(items, again);
+    }
+
+    #[test]
+    fn one_d_packed_array_feasibility_unit_test() {
+        let hash_algorithm = ElcAlgorithm::default();
+
+        // Columns 3 and 5 of row W are congruent modulo 2.
+        let word_list: WordList = ["WORD", "WORF"].iter().map(|w| (*w).to_string()).collect();
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        match OneDPackedArray::new(&tda, &Options::default())
+            .unwrap_err()
+            .kind()
+        {
+            Kind::PackingFailed {
+                row_index,
+                rows_placed,
+                cols,
+                occupancy,
+            } => {
+                assert_eq!((*row_index, *rows_placed), (22, 0));
+                assert_eq!(cols, &[3, 5]);
+                assert_eq!(occupancy, &[false, false]);
+            }
+            _ => panic!("Unexpected error type"),
+        }
+
+        // Rows {0, 2, 3} and {0, 2, 25} rule out every offset difference in a
+        // table of 6 entries.
+        let mut word_list = axx_word_list();
+        word_list.push("BXXZ");
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        let options = Options::new().packing_strategy(Backtracking);
+        match OneDPackedArray::new(&tda, &options).unwrap_err().kind() {
+            Kind::PackingFailed { row_index, .. } => assert_eq!(*row_index, 1),
+            _ => panic!("Unexpected error type"),
+        }
+
+        // With slack the same rows fit.
+        assert!(OneDPackedArray::new(&tda, &options.slack_percent(50)).is_ok());
+    }
+
+    #[test]
+    fn one_d_packed_array_backtrack_unit_test() {
+        let hash_algorithm = ElcAlgorithm::default();
+        let options = Options::new().packing_strategy(Backtracking);
+
+        // First fit places rows B and C, then row E does not fit.
+        let word_list: WordList = ["AXD", "BXA", "BXD", "BXE", "CXC", "CXD", "EXC", "EXF"]
+            .iter()
+            .map(|w| (*w).to_string())
+            .collect();
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        let e = OneDPackedArray::new(&tda, &Options::default()).unwrap_err();
+        match e.kind() {
+            Kind::PackingFailed {
+                rows_placed,
+                cols,
+                occupancy,
+                ..
+            } => {
+                assert_eq!(*rows_placed, 2);
+                assert_eq!(cols, &[2, 5]);
+                assert_eq!(occupancy.iter().filter(|used| **used).count(), 5);
+            }
+            _ => panic!("Unexpected error type"),
+        }
+        assert_eq!(
+            e.to_string(),
+            "Packing Failed: row 4 of 2 columns [2, 5] does not fit (2 rows placed, 5 of 8 entries used)"
+        );
+
+        for words in [
+            ["AXD", "BXA", "BXD", "BXE", "CXC", "CXD", "EXC", "EXF"],
+            ["AXA", "AXF", "BXA", "BXD", "BXE", "DXB", "EXD", "EXE"],
+        ] {
+            let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+            let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+            assert!(OneDPackedArray::new(&tda, &Options::default()).is_err());
+
+            match OneDPackedArray::new(&tda, &options.clone().row_bounds(true)) {
+                Ok(odpa) => {
+                    assert_packs(&words, &odpa);
+                    assert!(odpa.rlt.get_col_bounds(0).is_some());
+                }
+                Err(e) => panic!("Unexpected packing failure. {e}"),
+            }
+        }
+
+        // Rows {0, 2, 3} and {0, 2, 25} can not share a table of 6 entries at any
+        // offsets, which is found before backtracking starts.
+        let mut word_list = axx_word_list();
+        word_list.push("BXXZ");
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        match OneDPackedArray::new(&tda, &options) {
+            Ok(_) => panic!("Should not be able to create OneDPackedArray"),
+            Err(e) => match e.kind() {
+                Kind::PackingFailed { .. } => {}
+                _ => panic!("Unexpected error type"),
+            },
+        }
+
+        // The rows of a list that only packs after backtracking pass the
+        // feasibility checks, so the budget is what stops the search.
+        let words = ["AXD", "BXA", "BXD", "BXE", "CXC", "CXD", "EXC", "EXF"];
+        let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        let options = options.total_probe_limit(5);
+        match OneDPackedArray::new(&tda, &options) {
+            Ok(_) => panic!("Total probe budget should have been exceeded"),
+            Err(e) => match e.kind() {
+                Kind::ProbeBudgetExceeded { .. } => {}
+                _ => panic!("Unexpected error type"),
+            },
+        }
+    }
+
+    #[test]
+    fn one_d_packed_array_chd_unit_test() {
+        let hash_algorithm = ElcAlgorithm::default();
+        let options = Options::new().backend(Backend::Chd);
+        for words in [
+            ["AXD", "BXA", "BXD", "BXE", "CXC", "CXD", "EXC", "EXF"],
+            ["AXA", "AXF", "BXA", "BXD", "BXE", "DXB", "EXD", "EXE"],
+        ] {
+            let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+            let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+            assert!(OneDPackedArray::new(&tda, &Options::default()).is_err());
+
+            match OneDPackedArray::chd(&tda, &options.clone().row_bounds(true)) {
+                Ok(odpa) => {
+                    assert_packs(&words, &odpa);
+                    assert!(odpa.rlt.get_col_bounds(0).is_some());
+                    assert!(odpa.rlt.get_multipliers_as_text().is_some());
+                }
+                Err(e) => panic!("Unexpected packing failure. {e}"),
+            }
+        }
+
+        // Columns 3 and 9 of a row wrap onto each other in a table of 6 entries.
+        let word_list: WordList = ["AXXA", "AXXD", "AXXJ", "BXXA", "CXXA", "DXXA"]
+            .iter()
+            .map(|w| (*w).to_string())
+            .collect();
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        match OneDPackedArray::chd(&tda, &options) {
+            Ok(_) => panic!("Should not be able to create OneDPackedArray"),
+            Err(e) => match e.kind() {
+                Kind::PackingFailed { .. } => {}
+                _ => panic!("Unexpected error type"),
+            },
+        }
+
+        let mut word_list = axx_word_list();
+        word_list.push("BXXZ");
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        match OneDPackedArray::chd(&tda, &options.clone().total_probe_limit(1)) {
+            Ok(_) => panic!("Total probe budget should have been exceeded"),
+            Err(e) => match e.kind() {
+                Kind::ProbeBudgetExceeded { .. } => {}
+                _ => panic!("Unexpected error type"),
+            },
+        }
+    }
+
+    #[test]
+    fn one_d_packed_array_row_shift_unit_test() {
+        /// Shifts the second row by 4.
+        #[derive(Debug)]
+        struct Shifted;
+        impl crate::PackingStrategy for Shifted {
+            fn pack(&self, ctx: &mut PackContext) -> Result<Vec<isize>, Error> {
+                Ok(vec![0, 4][..ctx.rows().len()].to_vec())
+            }
+        }
+
+        let hash_algorithm = ElcAlgorithm::default();
+        let word_list = axx_word_list();
+        let words: Vec<&str> = word_list.iter().map(String::as_str).collect();
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        // The shift of each word's row, and its column shifted before the modulo.
+        let shifts = |odpa: &OneDPackedArray| -> Vec<(isize, isize)> {
+            words
+                .iter()
+                .map(|word| {
+                    let row = hash_algorithm.h1(word).unwrap();
+                    let col = odpa.rlt.scale(row, hash_algorithm.h2(word).unwrap());
+                    let rlt_value = *odpa.rlt.get(row).unwrap();
+                    (rlt_value, isize::try_from(col).unwrap() + rlt_value)
+                })
+                .collect()
+        };
+
+        let num_entries = isize::try_from(word_list.len()).unwrap();
+        let packing_failed = |options: &Options| {
+            OneDPackedArray::new(&tda, options)
+                .is_err_and(|e| matches!(e.kind(), Kind::PackingFailed { .. }))
+        };
+
+        // First fit only packs the list minimally by wrapping a row around.
+        let odpa = OneDPackedArray::new(&tda, &Options::new()).unwrap();
+        assert!(shifts(&odpa).iter().any(|(_, col)| *col >= num_entries));
+        assert!(packing_failed(&Options::new().wraparound(false)));
+        let options = Options::new()
+            .wraparound(false)
+            .packing_strategy(Backtracking);
+        let odpa = OneDPackedArray::new(&tda, &options).unwrap();
+        assert_packs(&words, &odpa);
+        assert!(shifts(&odpa)
+            .iter()
+            .all(|(_, col)| (0..num_entries).contains(col)));
+
+        assert!(packing_failed(&Options::new().max_row_shift(3)));
+        let odpa = OneDPackedArray::new(&tda, &Options::new().max_row_shift(4)).unwrap();
+        assert_packs(&words, &odpa);
+        let options = Options::new()
+            .max_row_shift(1)
+            .packing_strategy(Backtracking);
+        let odpa = OneDPackedArray::new(&tda, &options).unwrap();
+        assert_packs(&words, &odpa);
+        assert!(shifts(&odpa).iter().all(|(shift, _)| shift.abs() <= 1));
+        assert!(packing_failed(
+            &Options::new()
+                .max_row_shift(0)
+                .packing_strategy(Backtracking)
+        ));
+
+        // Offsets chosen by a strategy are checked too.
+        let options = Options::new().max_row_shift(1).packing_strategy(Shifted);
+        match OneDPackedArray::new(&tda, &options) {
+            Ok(_) => panic!("Disallowed shift not detected."),
+            Err(e) => assert_eq!(
+                e.to_string(),
+                "1D Packed Array Error: Packing strategy shifted row 1 by 4, which is not \
+                 allowed."
+            ),
+        }
+    }
+
+    #[cfg(feature = "heuristics")]
+    #[test]
+    fn one_d_packed_array_annealing_unit_test() {
+        let hash_algorithm = ElcAlgorithm::default();
+        let options = Options::new().packing_strategy(Annealing {
+            seed: 1,
+            iterations: 10_000,
+        });
+        for words in [
+            ["AXD", "BXA", "BXD", "BXE", "CXC", "CXD", "EXC", "EXF"],
+            ["AXA", "AXF", "BXA", "BXD", "BXE", "DXB", "EXD", "EXE"],
+        ] {
+            let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+            let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+            match OneDPackedArray::new(&tda, &options) {
+                Ok(odpa) => assert_packs(&words, &odpa),
+                Err(e) => panic!("Unexpected packing failure. {e}"),
+            }
+        }
+
+        let mut word_list = axx_word_list();
+        word_list.push("BXXZ");
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        match OneDPackedArray::new(&tda, &options) {
+            Ok(_) => panic!("Should not be able to create OneDPackedArray"),
+            Err(e) => match e.kind() {
+                Kind::PackingFailed { .. } => {}
+                _ => panic!("Unexpected error type"),
+            },
+        }
+
+        // Annealing draws the offsets of each row from its allowed offsets.
+        let word_list = axx_word_list();
+        let words: Vec<&str> = word_list.iter().map(String::as_str).collect();
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        let odpa = OneDPackedArray::new(&tda, &options.clone().wraparound(false)).unwrap();
+        assert_packs(&words, &odpa);
+        assert_eq!((odpa.rlt.get(0), odpa.rlt.get(1)), (Some(&1), Some(&0)));
+
+        // The rows of a list that first fit cannot pack pass the feasibility
+        // checks, so the budget is what stops the search.
+        let words = ["AXD", "BXA", "BXD", "BXE", "CXC", "CXD", "EXC", "EXF"];
+        let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+        let tda = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        match OneDPackedArray::new(&tda, &options.total_probe_limit(10)) {
+            Ok(_) => panic!("Total probe budget should have been exceeded"),
+            Err(e) => match e.kind() {
+                Kind::ProbeBudgetExceeded { stats, .. } => assert_eq!(stats.probes, 10),
+                _ => panic!("Unexpected error type"),
+            },
+        }
+    }
 }
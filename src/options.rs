@@ -0,0 +1,831 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{Charset, DuplicatePolicy, FirstFit, PackingStrategy, ValidationPolicy};
+
+/// The largest smallest hash value, see `Options::hash_base`. Tables indexed by
+/// the hash value hold an unused entry for every hash value below the base, so
+/// the base is kept small.
+pub const MAX_HASH_BASE: usize = 1 << 16;
+
+/// The construction used to turn the 2D array into a hash table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Rows are shifted into the packed array by the packing strategy. This is
+    /// the default.
+    #[default]
+    Packed,
+
+    /// Rows are buckets placed with the CHD (compress, hash, displace)
+    /// construction, each at a multiplier and an offset. The packing strategy is
+    /// not used. Scales to much larger word lists than the packing strategies.
+    Chd,
+
+    /// Each cell of the 2D array is an edge of a 3-hypergraph, which is peeled
+    /// to build an order preserving CHM hash: words hash to their position in
+    /// the word list. Never leaves slack and does not use the packing strategy,
+    /// the standard construction for dictionary sized word lists.
+    Chm,
+}
+
+/// When a generated hash is verified against the word list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Verification {
+    /// Every generated hash is verified. This is the default.
+    #[default]
+    Always,
+
+    /// Generated hashes are only verified in builds with debug assertions.
+    DebugOnly,
+
+    /// Generated hashes are never verified. Only for word lists and options
+    /// that are known to produce a valid hash, such as when regenerating a
+    /// table whose inputs have not changed. A hash that would have failed
+    /// verification is returned as if it were valid.
+    Never,
+}
+
+impl Verification {
+    /// Returns true if a generated hash is to be verified in this build.
+    ///
+    /// # Returns
+    /// True if the hash is verified.
+    #[must_use]
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Verification::Always => true,
+            Verification::DebugOnly => cfg!(debug_assertions),
+            Verification::Never => false,
+        }
+    }
+}
+
+/// How rows of the same size are ordered for packing.
+///
+/// Rows are packed biggest first. The order of rows of the same size changes
+/// which offsets are free when each row is placed, so another policy can pack
+/// a 2D array that fails with the default one. Every policy gives the same
+/// order for the same 2D array.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RowTieBreak {
+    /// Rows with a lower row index come first. This is the default.
+    #[default]
+    RowIndexAscending,
+
+    /// Rows with a higher row index come first.
+    RowIndexDescending,
+
+    /// Rows with a lower first column index come first, then by row index.
+    FirstColumn,
+
+    /// Rows are shuffled by a seeded mix of their row index. The same seed
+    /// always gives the same order.
+    Seeded(u64),
+}
+
+/// Options that control how a hash is generated.
+///
+/// The defaults reproduce the behavior of `generate_hash`.
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Options {
+    /// The wall-clock time budget for packing the 2D array.
+    max_duration: Option<Duration>,
+
+    /// The maximum number of placement attempts for a single row.
+    row_probe_limit: Option<usize>,
+
+    /// The maximum number of placement attempts for all rows combined.
+    total_probe_limit: Option<usize>,
+
+    /// The largest distance a row may be shifted by.
+    max_row_shift: Option<usize>,
+
+    /// Whether rows may not wrap around the end of the packed array.
+    no_wraparound: bool,
+
+    /// Whether to record and emit the column bounds of each row.
+    row_bounds: bool,
+
+    /// Whether automatically chosen algorithms infer their alphabet from the word list.
+    infer_alphabet: bool,
+
+    /// The rules the word list must follow.
+    validation_policy: ValidationPolicy,
+
+    /// The number of times packing is retried with a different row order.
+    order_retries: usize,
+
+    /// The seed used to permute the row order on retries.
+    order_seed: u64,
+
+    /// How rows of the same size are ordered for packing.
+    row_tie_break: RowTieBreak,
+
+    /// How rows are placed into the packed array, `FirstFit` when `None`.
+    packing_strategy: Option<Arc<dyn PackingStrategy>>,
+
+    /// The extra space allocated in the packed array, as a percentage of the
+    /// number of words.
+    slack_percent: usize,
+
+    /// Whether the packed array is rounded up to a power of two entries.
+    power_of_two_table: bool,
+
+    /// Whether the generated hash stores the word at each hash value.
+    store_keys: bool,
+
+    /// The construction used to turn the 2D array into a hash table.
+    backend: Backend,
+
+    /// When the generated hash is verified against the word list.
+    verification: Verification,
+
+    /// Whether the row lookup table is reduced to non-negative values.
+    normalize_rlt: bool,
+
+    /// Whether the row lookup table only keeps the rows that are used.
+    sparse_rlt: bool,
+
+    /// Whether the row lookup table is emitted compressed into two levels.
+    two_level_rlt: bool,
+
+    /// Whether every offset tried while packing is recorded.
+    record_pack_events: bool,
+
+    /// The smallest hash value.
+    hash_base: usize,
+}
+
+impl Options {
+    /// Creates a new set of options with the default values.
+    #[must_use]
+    pub fn new() -> Self {
+        Options::default()
+    }
+
+    /// Sets the wall-clock time budget for packing the 2D array.
+    ///
+    /// The budget is checked before each batch of candidate offsets is probed.
+    ///
+    /// # Parameters
+    /// * `max_duration` - The maximum time packing is allowed to take.
+    #[must_use]
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Gets the wall-clock time budget for packing the 2D array.
+    ///
+    /// # Returns
+    /// The time budget, or `None` if packing is not time limited.
+    #[must_use]
+    pub fn get_max_duration(&self) -> Option<Duration> {
+        self.max_duration
+    }
+
+    /// Sets the maximum number of placement attempts for a single row.
+    ///
+    /// # Parameters
+    /// * `row_probe_limit` - The maximum number of shifts tried for any one row.
+    #[must_use]
+    pub fn row_probe_limit(mut self, row_probe_limit: usize) -> Self {
+        self.row_probe_limit = Some(row_probe_limit);
+        self
+    }
+
+    /// Gets the maximum number of placement attempts for a single row.
+    ///
+    /// # Returns
+    /// The per row probe budget, or `None` if it is unlimited.
+    #[must_use]
+    pub fn get_row_probe_limit(&self) -> Option<usize> {
+        self.row_probe_limit
+    }
+
+    /// Sets the maximum number of placement attempts for all rows combined.
+    ///
+    /// # Parameters
+    /// * `total_probe_limit` - The maximum number of shifts tried overall.
+    #[must_use]
+    pub fn total_probe_limit(mut self, total_probe_limit: usize) -> Self {
+        self.total_probe_limit = Some(total_probe_limit);
+        self
+    }
+
+    /// Gets the maximum number of placement attempts for all rows combined.
+    ///
+    /// # Returns
+    /// The total probe budget, or `None` if it is unlimited.
+    #[must_use]
+    pub fn get_total_probe_limit(&self) -> Option<usize> {
+        self.total_probe_limit
+    }
+
+    /// Sets the largest distance a row may be shifted by when it is packed,
+    /// which bounds the values of the row lookup table. Rows that cannot be
+    /// placed within the distance fail the packing, see `slack_percent` for
+    /// growing the table instead. The CHD and CHM backends ignore it.
+    ///
+    /// # Parameters
+    /// * `max_row_shift` - The largest row lookup table value, either way.
+    #[must_use]
+    pub fn max_row_shift(mut self, max_row_shift: usize) -> Self {
+        self.max_row_shift = Some(max_row_shift);
+        self
+    }
+
+    /// Gets the largest distance a row may be shifted by when it is packed.
+    ///
+    /// # Returns
+    /// The largest shift, or `None` if it is unlimited.
+    #[must_use]
+    pub fn get_max_row_shift(&self) -> Option<usize> {
+        self.max_row_shift
+    }
+
+    /// Sets whether a shifted row may wrap around the end of the packed array,
+    /// its last columns landing at its start. Without wraparound every word
+    /// hashes to `rlt[row] + col` before the modulo, so a table can be emitted
+    /// without it. Rows that cannot be placed without wrapping fail the
+    /// packing, see `slack_percent` for growing the table instead. The CHD
+    /// and CHM backends ignore it.
+    ///
+    /// # Parameters
+    /// * `wraparound` - True, the default, to let rows wrap around.
+    #[must_use]
+    pub fn wraparound(mut self, wraparound: bool) -> Self {
+        self.no_wraparound = !wraparound;
+        self
+    }
+
+    /// Gets whether a shifted row may wrap around the end of the packed array.
+    ///
+    /// # Returns
+    /// True if rows may wrap around.
+    #[must_use]
+    pub fn get_wraparound(&self) -> bool {
+        !self.no_wraparound
+    }
+
+    /// Sets whether to record and emit the column bounds of each row.
+    ///
+    /// With row bounds the generated hash rejects words whose column index is
+    /// outside of the range of columns used by their row, before indexing.
+    ///
+    /// # Parameters
+    /// * `row_bounds` - True to record and emit the row bounds.
+    #[must_use]
+    pub fn row_bounds(mut self, row_bounds: bool) -> Self {
+        self.row_bounds = row_bounds;
+        self
+    }
+
+    /// Gets whether to record and emit the column bounds of each row.
+    ///
+    /// # Returns
+    /// True if row bounds are recorded and emitted.
+    #[must_use]
+    pub fn get_row_bounds(&self) -> bool {
+        self.row_bounds
+    }
+
+    /// Sets whether automatically chosen algorithms infer their alphabet from the
+    /// word list.
+    ///
+    /// When set, `generate_hash_auto_with_options` sizes `num_vals` to the
+    /// characters actually used by the word list instead of the 26 letters.
+    ///
+    /// # Parameters
+    /// * `infer_alphabet` - True to infer the alphabet.
+    #[must_use]
+    pub fn infer_alphabet(mut self, infer_alphabet: bool) -> Self {
+        self.infer_alphabet = infer_alphabet;
+        self
+    }
+
+    /// Gets whether automatically chosen algorithms infer their alphabet from the
+    /// word list.
+    ///
+    /// # Returns
+    /// True if the alphabet is inferred.
+    #[must_use]
+    pub fn get_infer_alphabet(&self) -> bool {
+        self.infer_alphabet
+    }
+
+    /// Sets how repeated words in the word list are handled.
+    ///
+    /// # Parameters
+    /// * `duplicate_policy` - The policy for repeated words.
+    #[must_use]
+    pub fn duplicate_policy(mut self, duplicate_policy: DuplicatePolicy) -> Self {
+        self.validation_policy = self.validation_policy.duplicate_policy(duplicate_policy);
+        self
+    }
+
+    /// Gets how repeated words in the word list are handled.
+    ///
+    /// # Returns
+    /// The policy for repeated words.
+    #[must_use]
+    pub fn get_duplicate_policy(&self) -> DuplicatePolicy {
+        self.validation_policy.get_duplicate_policy()
+    }
+
+    /// Sets the characters words may be made of.
+    ///
+    /// Automatically chosen algorithms use the whole character set as their
+    /// alphabet, unless the alphabet is inferred.
+    ///
+    /// # Parameters
+    /// * `charset` - The character set of the words.
+    #[must_use]
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.validation_policy = self.validation_policy.charset(charset);
+        self
+    }
+
+    /// Gets the characters words may be made of.
+    ///
+    /// # Returns
+    /// The character set of the words.
+    #[must_use]
+    pub fn get_charset(&self) -> Charset {
+        self.validation_policy.get_charset()
+    }
+
+    /// Sets the rules the word list must follow.
+    ///
+    /// This replaces the duplicate policy and the character set set so far.
+    ///
+    /// # Parameters
+    /// * `validation_policy` - The rules the words must follow.
+    #[must_use]
+    pub fn validation_policy(mut self, validation_policy: ValidationPolicy) -> Self {
+        self.validation_policy = validation_policy;
+        self
+    }
+
+    /// Gets the rules the word list must follow.
+    ///
+    /// # Returns
+    /// The validation policy.
+    #[must_use]
+    pub fn get_validation_policy(&self) -> ValidationPolicy {
+        self.validation_policy
+    }
+
+    /// Sets the number of times packing is retried with a different row order.
+    ///
+    /// Rows are placed biggest first. When that fails to minimally pack, the
+    /// first retry breaks ties between rows of the same size the other way, and
+    /// later retries place the rows in a random order drawn from the seed.
+    /// Probe and time budgets are shared by all attempts.
+    ///
+    /// # Parameters
+    /// * `order_retries` - The number of retries.
+    #[must_use]
+    pub fn order_retries(mut self, order_retries: usize) -> Self {
+        self.order_retries = order_retries;
+        self
+    }
+
+    /// Gets the number of times packing is retried with a different row order.
+    ///
+    /// # Returns
+    /// The number of retries.
+    #[must_use]
+    pub fn get_order_retries(&self) -> usize {
+        self.order_retries
+    }
+
+    /// Sets the seed used to permute the row order on retries.
+    ///
+    /// # Parameters
+    /// * `order_seed` - The seed. The same seed always gives the same orders.
+    #[must_use]
+    pub fn order_seed(mut self, order_seed: u64) -> Self {
+        self.order_seed = order_seed;
+        self
+    }
+
+    /// Gets the seed used to permute the row order on retries.
+    ///
+    /// # Returns
+    /// The seed.
+    #[must_use]
+    pub fn get_order_seed(&self) -> u64 {
+        self.order_seed
+    }
+
+    /// Sets how rows of the same size are ordered for packing.
+    ///
+    /// A retry that breaks ties the other way, see `order_retries`, reverses
+    /// this order.
+    ///
+    /// # Parameters
+    /// * `row_tie_break` - The tie-break policy.
+    #[must_use]
+    pub fn row_tie_break(mut self, row_tie_break: RowTieBreak) -> Self {
+        self.row_tie_break = row_tie_break;
+        self
+    }
+
+    /// Gets how rows of the same size are ordered for packing.
+    ///
+    /// # Returns
+    /// The tie-break policy.
+    #[must_use]
+    pub fn get_row_tie_break(&self) -> RowTieBreak {
+        self.row_tie_break
+    }
+
+    /// Sets how rows are placed into the packed array.
+    ///
+    /// Strategies other than `FirstFit` can succeed on word
+    /// lists that first fit fails on, but can take much longer, so they are
+    /// best combined with a time or probe budget.
+    ///
+    /// # Parameters
+    /// * `packing_strategy` - The packing strategy.
+    #[must_use]
+    pub fn packing_strategy(mut self, packing_strategy: impl PackingStrategy + 'static) -> Self {
+        self.packing_strategy = Some(Arc::new(packing_strategy));
+        self
+    }
+
+    /// Gets how rows are placed into the packed array.
+    ///
+    /// # Returns
+    /// The packing strategy.
+    #[must_use]
+    pub fn get_packing_strategy(&self) -> &dyn PackingStrategy {
+        match &self.packing_strategy {
+            Some(packing_strategy) => packing_strategy.as_ref(),
+            None => &FirstFit,
+        }
+    }
+
+    /// Sets the extra space allocated in the packed array.
+    ///
+    /// With slack the packed array has `ceil(n * (100 + slack_percent) / 100)`
+    /// entries for `n` words, so the hash is no longer minimal, but word lists
+    /// that cannot be minimally packed may fit. Some hash values then have no
+    /// word, see `HashData::table_len`. The table grows past that length when
+    /// two columns of a row would land on the same index, which no offset can
+    /// avoid, and packing fails if the length overflows.
+    ///
+    /// # Parameters
+    /// * `slack_percent` - The extra space as a percentage of the number of
+    ///   words. 0 packs minimally.
+    #[must_use]
+    pub fn slack_percent(mut self, slack_percent: usize) -> Self {
+        self.slack_percent = slack_percent;
+        self
+    }
+
+    /// Gets the extra space allocated in the packed array.
+    ///
+    /// # Returns
+    /// The extra space as a percentage of the number of words.
+    #[must_use]
+    pub fn get_slack_percent(&self) -> usize {
+        self.slack_percent
+    }
+
+    /// Sets whether the packed array is rounded up to a power of two entries.
+    ///
+    /// Hash values are then reduced with a mask, `& (len - 1)`, instead of a
+    /// modulo, `% len`, which is slow on targets without a fast divide. The
+    /// pseudo code, the emitted code and the closures all use the mask, see
+    /// `Rlt::with_mask`. The extra entries make the hash non minimal, like
+    /// `slack_percent`, which is applied first. Has no effect with
+    /// `Backend::Chm`.
+    ///
+    /// # Parameters
+    /// * `power_of_two_table` - True to round the table up to a power of two.
+    #[must_use]
+    pub fn power_of_two_table(mut self, power_of_two_table: bool) -> Self {
+        self.power_of_two_table = power_of_two_table;
+        self
+    }
+
+    /// Gets whether the packed array is rounded up to a power of two entries.
+    ///
+    /// # Returns
+    /// True if the table is rounded up to a power of two.
+    #[must_use]
+    pub fn get_power_of_two_table(&self) -> bool {
+        self.power_of_two_table
+    }
+
+    /// Sets whether the generated hash stores the word at each hash value.
+    ///
+    /// Stored keys let `MsmpFn::find`, `HashData::as_checked_closure` and the
+    /// emitted pseudo code reject words that are not in the word list, and let
+    /// `HashData::word_at` map hash values back to words, at the cost of keeping
+    /// a copy of the word list.
+    ///
+    /// # Parameters
+    /// * `store_keys` - True to store the keys.
+    #[must_use]
+    pub fn store_keys(mut self, store_keys: bool) -> Self {
+        self.store_keys = store_keys;
+        self
+    }
+
+    /// Gets whether the generated hash stores the word at each hash value.
+    ///
+    /// # Returns
+    /// True if the keys are stored.
+    #[must_use]
+    pub fn get_store_keys(&self) -> bool {
+        self.store_keys
+    }
+
+    /// Sets the construction used to turn the 2D array into a hash table.
+    ///
+    /// # Parameters
+    /// * `backend` - The backend.
+    #[must_use]
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Gets the construction used to turn the 2D array into a hash table.
+    ///
+    /// # Returns
+    /// The backend.
+    #[must_use]
+    pub fn get_backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Sets when the generated hash is verified against the word list.
+    ///
+    /// Verification hashes every word once more and checks for collisions.
+    /// Skipping it is only safe for trusted inputs, see `Verification::Never`.
+    ///
+    /// # Parameters
+    /// * `verification` - When to verify.
+    #[must_use]
+    pub fn verification(mut self, verification: Verification) -> Self {
+        self.verification = verification;
+        self
+    }
+
+    /// Gets when the generated hash is verified against the word list.
+    ///
+    /// # Returns
+    /// When to verify.
+    #[must_use]
+    pub fn get_verification(&self) -> Verification {
+        self.verification
+    }
+
+    /// Sets whether the row lookup table is reduced to non-negative values.
+    ///
+    /// Packing can shift a row to a negative offset. Normalizing adds the table
+    /// length to such offsets, which gives the same hash values, so the table
+    /// can be emitted in languages with unsigned array types and the hash can
+    /// be computed with unsigned arithmetic, see `Rlt::into_normalized`.
+    /// Rows normalized this way wrap around the end of the packed array, and
+    /// `max_row_shift` and `wraparound` only limit the offsets before they are
+    /// normalized.
+    ///
+    /// # Parameters
+    /// * `normalize_rlt` - True to normalize the row lookup table.
+    #[must_use]
+    pub fn normalize_rlt(mut self, normalize_rlt: bool) -> Self {
+        self.normalize_rlt = normalize_rlt;
+        self
+    }
+
+    /// Gets whether the row lookup table is reduced to non-negative values.
+    ///
+    /// # Returns
+    /// True if the row lookup table is normalized.
+    #[must_use]
+    pub fn get_normalize_rlt(&self) -> bool {
+        self.normalize_rlt
+    }
+
+    /// Sets whether the row lookup table only keeps the rows that are used.
+    ///
+    /// The row lookup table has an entry for every possible row index, which
+    /// grows quickly with `elc` even when few rows are used. A sparse table
+    /// keeps the used row indices in a sorted `row_keys` table next to their
+    /// values, and finds a row by a binary search, see `Rlt::into_sparse`.
+    ///
+    /// # Parameters
+    /// * `sparse_rlt` - True to keep only the used rows.
+    #[must_use]
+    pub fn sparse_rlt(mut self, sparse_rlt: bool) -> Self {
+        self.sparse_rlt = sparse_rlt;
+        self
+    }
+
+    /// Gets whether the row lookup table only keeps the rows that are used.
+    ///
+    /// # Returns
+    /// True if the row lookup table is sparse.
+    #[must_use]
+    pub fn get_sparse_rlt(&self) -> bool {
+        self.sparse_rlt
+    }
+
+    /// Sets whether the row lookup table is emitted compressed into two levels.
+    ///
+    /// The row index is split into a block and a position in the block, and
+    /// identical blocks are emitted once, which shrinks tables of large radices
+    /// where most rows are unused, see `TwoLevelRlt`. Has no effect together
+    /// with `sparse_rlt`.
+    ///
+    /// # Parameters
+    /// * `two_level_rlt` - True to compress the row lookup table.
+    #[must_use]
+    pub fn two_level_rlt(mut self, two_level_rlt: bool) -> Self {
+        self.two_level_rlt = two_level_rlt;
+        self
+    }
+
+    /// Gets whether the row lookup table is emitted compressed into two levels.
+    ///
+    /// # Returns
+    /// True if the row lookup table is compressed.
+    #[must_use]
+    pub fn get_two_level_rlt(&self) -> bool {
+        self.two_level_rlt
+    }
+
+    /// Sets whether every offset tried while packing is recorded.
+    ///
+    /// Each attempt to place a row is kept as a `PackEvent`, with the index the
+    /// row conflicted at, and returned with the packed array, see
+    /// `OneDPackedArray::pack_events`. Meant for studying how the packer
+    /// behaves on a word list. Recording slows packing down and the list grows
+    /// with every probe, so it is off by default.
+    ///
+    /// # Parameters
+    /// * `record_pack_events` - True to record the attempts.
+    #[must_use]
+    pub fn record_pack_events(mut self, record_pack_events: bool) -> Self {
+        self.record_pack_events = record_pack_events;
+        self
+    }
+
+    /// Gets whether every offset tried while packing is recorded.
+    ///
+    /// # Returns
+    /// True if the attempts are recorded.
+    #[must_use]
+    pub fn get_record_pack_events(&self) -> bool {
+        self.record_pack_events
+    }
+
+    /// Sets the smallest hash value.
+    ///
+    /// Hash values run from 0 by default. A base of 1 gives 1 based hash
+    /// values, for targets whose tables are indexed from 1. The base is added
+    /// to the hash values returned, the pseudo code and the emitted code, and
+    /// emitted tables indexed by the hash value start at the base, see
+    /// `Rlt::with_hash_base`. Generation fails for a base above
+    /// `MAX_HASH_BASE`.
+    ///
+    /// # Parameters
+    /// * `hash_base` - The smallest hash value.
+    #[must_use]
+    pub fn hash_base(mut self, hash_base: usize) -> Self {
+        self.hash_base = hash_base;
+        self
+    }
+
+    /// Gets the smallest hash value.
+    ///
+    /// # Returns
+    /// The smallest hash value, 0 by default.
+    #[must_use]
+    pub fn get_hash_base(&self) -> usize {
+        self.hash_base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Backtracking;
+
+    #[test]
+    fn options_unit_test() {
+        let options = Options::new();
+        assert_eq!(options.get_max_duration(), None);
+        assert_eq!(options.get_row_probe_limit(), None);
+        assert_eq!(options.get_total_probe_limit(), None);
+        assert_eq!(options.get_max_row_shift(), None);
+        assert!(options.get_wraparound());
+        assert!(!options.get_row_bounds());
+        assert!(!options.get_infer_alphabet());
+        assert_eq!(options.get_duplicate_policy(), DuplicatePolicy::Reject);
+        assert_eq!(options.get_order_retries(), 0);
+        assert_eq!(options.get_order_seed(), 0);
+        assert_eq!(options.get_row_tie_break(), RowTieBreak::RowIndexAscending);
+        assert_eq!(format!("{:?}", options.get_packing_strategy()), "FirstFit");
+        assert_eq!(options.get_slack_percent(), 0);
+        assert!(!options.get_power_of_two_table());
+        assert!(!options.get_store_keys());
+        assert_eq!(options.get_backend(), Backend::Packed);
+        assert_eq!(options.get_verification(), Verification::Always);
+        assert!(!options.get_normalize_rlt());
+        assert!(!options.get_sparse_rlt());
+        assert!(!options.get_two_level_rlt());
+        assert!(!options.get_record_pack_events());
+        assert_eq!(options.get_hash_base(), 0);
+
+        let options = options.max_duration(Duration::from_millis(5));
+        assert_eq!(options.get_max_duration(), Some(Duration::from_millis(5)));
+
+        let options = options.row_probe_limit(10).total_probe_limit(100);
+        assert_eq!(options.get_row_probe_limit(), Some(10));
+        assert_eq!(options.get_total_probe_limit(), Some(100));
+
+        let options = options.max_row_shift(4).wraparound(false);
+        assert_eq!(options.get_max_row_shift(), Some(4));
+        assert!(!options.get_wraparound());
+
+        let options = options.row_bounds(true);
+        assert!(options.get_row_bounds());
+
+        let options = options.infer_alphabet(true);
+        assert!(options.get_infer_alphabet());
+
+        let options = options.duplicate_policy(DuplicatePolicy::Allow);
+        assert_eq!(options.get_duplicate_policy(), DuplicatePolicy::Allow);
+
+        let options = options.charset(Charset::Identifier);
+        assert_eq!(
+            options.get_validation_policy(),
+            ValidationPolicy::new()
+                .duplicate_policy(DuplicatePolicy::Allow)
+                .charset(Charset::Identifier)
+        );
+        let options = options.validation_policy(ValidationPolicy::new().max_len(8));
+        assert_eq!(options.get_validation_policy().get_max_len(), Some(8));
+        assert_eq!(options.get_duplicate_policy(), DuplicatePolicy::Reject);
+        assert_eq!(options.get_charset(), Charset::Uppercase);
+
+        let options = options.order_retries(3).order_seed(42);
+        assert_eq!(options.get_order_retries(), 3);
+        assert_eq!(options.get_order_seed(), 42);
+
+        let options = options.packing_strategy(Backtracking);
+        assert_eq!(
+            format!("{:?}", options.get_packing_strategy()),
+            "Backtracking"
+        );
+
+        let options = options.slack_percent(5);
+        assert_eq!(options.get_slack_percent(), 5);
+
+        let options = options.power_of_two_table(true);
+        assert!(options.get_power_of_two_table());
+
+        let options = options.store_keys(true);
+        assert!(options.get_store_keys());
+
+        let options = options.backend(Backend::Chd);
+        assert_eq!(options.get_backend(), Backend::Chd);
+        let options = options.backend(Backend::Chm);
+        assert_eq!(options.get_backend(), Backend::Chm);
+
+        let options = options.verification(Verification::DebugOnly);
+        assert_eq!(options.get_verification(), Verification::DebugOnly);
+        assert_eq!(
+            options.get_verification().is_enabled(),
+            cfg!(debug_assertions)
+        );
+        assert!(!Verification::Never.is_enabled());
+        assert!(Verification::Always.is_enabled());
+
+        let options = options.normalize_rlt(true);
+        assert!(options.get_normalize_rlt());
+
+        let options = options.sparse_rlt(true);
+        assert!(options.get_sparse_rlt());
+
+        let options = options.two_level_rlt(true);
+        assert!(options.get_two_level_rlt());
+
+        let options = options.record_pack_events(true);
+        assert!(options.get_record_pack_events());
+
+        let options = options.hash_base(1);
+        assert_eq!(options.get_hash_base(), 1);
+
+        let options = options.row_tie_break(RowTieBreak::Seeded(7));
+        assert_eq!(options.get_row_tie_break(), RowTieBreak::Seeded(7));
+        println!("{options:?}");
+    }
+}
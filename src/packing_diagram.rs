@@ -0,0 +1,189 @@
+//! ASCII diagrams of how the rows of the 2D array were packed.
+//!
+//! Each line of a diagram is one row, drawn across the packed array: `#` marks
+//! an entry the row placed a word at and `.` any other entry. A ruler of the
+//! last digit of each entry index runs above the rows and the entries used by
+//! all rows are summed up below them, so gaps and crowded stretches stand out.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::{from_index, Error, Kind, OneDPackedArray, TwoDArray};
+
+/// Draws the rows of a 2D array where they were placed in the packed array.
+///
+/// # Parameters
+/// * `two_d_array` - The 2D array that was packed.
+/// * `packed` - The packed array built from `two_d_array`.
+///
+/// # Returns
+/// The diagram, one line per row in the order the rows were placed. The CHM
+/// backend places no rows, so only the used entries are drawn for it.
+#[must_use]
+pub fn packing_diagram(two_d_array: &TwoDArray, packed: &OneDPackedArray) -> String {
+    let positions: BTreeMap<usize, usize> = packed
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| *word != 0)
+        .map(|(position, word)| (word, position))
+        .collect();
+    let width = label_width(two_d_array.get_last_row_index());
+
+    let mut text = ruler(width, packed.len());
+    for (row_index, offset) in packed.placements() {
+        let mut line = vec!['.'; packed.len()];
+        if let Some(row) = two_d_array.row(*row_index) {
+            for (_, word) in row.iter() {
+                if let Some(position) = positions.get(&from_index(word)) {
+                    line[*position] = '#';
+                }
+            }
+        }
+        let line: String = line.into_iter().collect();
+        let _ = writeln!(text, "row {row_index:>width$}  {line}  offset {offset}");
+    }
+    let used: Vec<bool> = packed.iter().map(|word| word != 0).collect();
+    let _ = write!(
+        text,
+        "{:width$}  {}",
+        "used",
+        marks(&used),
+        width = width + 4
+    );
+    text
+}
+
+/// Draws why packing failed: the entries used when it stopped, and the row that
+/// did not fit laid out from its first column.
+///
+/// # Parameters
+/// * `error` - An error returned while packing.
+///
+/// # Returns
+/// The diagram, or None if `error` is not a `Kind::PackingFailed` error or the
+/// packing strategy did not report which entries were used.
+#[must_use]
+pub fn failure_diagram(error: &Error) -> Option<String> {
+    let Kind::PackingFailed {
+        row_index,
+        cols,
+        occupancy,
+        ..
+    } = error.kind()
+    else {
+        return None;
+    };
+    if occupancy.is_empty() {
+        return None;
+    }
+    let width = label_width(*row_index);
+    let mut row = vec![false; cols.iter().max().map_or(0, |max_col| max_col + 1)];
+    for col in cols {
+        row[*col] = true;
+    }
+
+    let mut text = ruler(width, occupancy.len());
+    let _ = writeln!(
+        text,
+        "{:width$}  {}",
+        "used",
+        marks(occupancy),
+        width = width + 4
+    );
+    let _ = write!(
+        text,
+        "row {row_index:>width$}  {}  does not fit",
+        marks(&row)
+    );
+    Some(text)
+}
+
+/// Gets the width of the row indices printed in a diagram.
+///
+/// # Parameters
+/// * `last_row_index` - The largest row index printed.
+///
+/// # Returns
+/// The number of digits in `last_row_index`.
+fn label_width(last_row_index: usize) -> usize {
+    last_row_index.to_string().len()
+}
+
+/// Draws the ruler above the rows of a diagram.
+///
+/// # Parameters
+/// * `width` - The width of the row indices.
+/// * `len` - The number of entries in the packed array.
+///
+/// # Returns
+/// The ruler line, ending with a new line.
+fn ruler(width: usize, len: usize) -> String {
+    let digits: String = (0..len)
+        .map(|i| char::from_digit(u32::try_from(i % 10).unwrap_or(0), 10).unwrap_or('0'))
+        .collect();
+    format!("{:width$}  {digits}\n", "", width = width + 4)
+}
+
+/// Draws used entries as `#` and unused entries as `.`.
+///
+/// # Parameters
+/// * `used` - Whether each entry is used.
+///
+/// # Returns
+/// The entries as a string.
+fn marks(used: &[bool]) -> String {
+    used.iter()
+        .map(|used| if *used { '#' } else { '.' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Options;
+
+    #[test]
+    fn packing_diagram_unit_test() {
+        // The first and last letter of AND, BEGIN, CHAR, CONST, ELSE, END, ENTER
+        // and EOF.
+        let pairs = [
+            (0, 3),
+            (1, 13),
+            (2, 17),
+            (2, 19),
+            (4, 4),
+            (4, 3),
+            (4, 17),
+            (4, 5),
+        ];
+        let options = Options::default();
+        let two_d_array = TwoDArray::from_pairs(&pairs, pairs.len(), &options).unwrap();
+        let packed = OneDPackedArray::pack(&two_d_array, &options).unwrap();
+        assert_eq!(
+            packing_diagram(&two_d_array, &packed),
+            "       01234567\n\
+             row 4  ###...#.  offset -3\n\
+             row 2  ...#.#..  offset -14\n\
+             row 0  ....#...  offset 1\n\
+             row 1  .......#  offset -6\n\
+             used   ########"
+        );
+
+        let e = Error::new(Kind::PackingFailed {
+            row_index: 12,
+            rows_placed: 3,
+            cols: vec![0, 2],
+            occupancy: vec![true, false, true, true],
+        });
+        assert_eq!(
+            failure_diagram(&e).unwrap(),
+            "        0123\n\
+             used    #.##\n\
+             row 12  #.#  does not fit"
+        );
+        assert_eq!(
+            failure_diagram(&Error::new(Kind::HashError(String::new()))),
+            None
+        );
+    }
+}
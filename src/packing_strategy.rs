@@ -0,0 +1,413 @@
+//! Strategies for placing the rows of the 2D array into the packed array.
+//!
+//! The rows of the 2D array are packed into a 1D array with one entry per
+//! word. A strategy chooses an offset for every row, so that a value in column
+//! `c` of a row lands at index `(c + offset) % table_len`, without two values
+//! landing on the same index. Implement `PackingStrategy` to try a packer of
+//! your own, and select it with `Options::packing_strategy`.
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::one_d_packed_array::{OneDPackedArray, Progress};
+use crate::{Error, Kind, Options, PackStats, Row};
+
+/// Chooses the offset of every row of the 2D array.
+pub trait PackingStrategy: fmt::Debug + Send + Sync {
+    /// Chooses the offset of every row.
+    ///
+    /// # Parameters
+    /// * `ctx` - The rows to place and the budget to place them in.
+    ///
+    /// # Returns
+    /// The offset of each row of `ctx.rows()`, in the same order.
+    ///
+    /// # Errors
+    /// Will return `Err` if the rows cannot be placed without overlapping, see
+    /// `PackContext::unable_to_pack`, or with the error of `PackContext::probe`
+    /// if a budget is exceeded.
+    fn pack(&self, ctx: &mut PackContext) -> Result<Vec<isize>, Error>;
+}
+
+/// The rows to place, and the budget to place them in.
+#[derive(Debug)]
+pub struct PackContext<'a> {
+    /// The row index and row of each row to place, biggest first unless the
+    /// order is being retried.
+    pub(crate) order: &'a [(usize, &'a Row)],
+
+    /// The column indices of each row in `order`.
+    cols: Vec<Vec<usize>>,
+
+    /// The number of entries in the packed array.
+    table_len: usize,
+
+    /// The number of entries in the row lookup table.
+    pub(crate) rlt_len: usize,
+
+    /// The options holding the budgets.
+    pub(crate) options: &'a Options,
+
+    /// The progress of the packing so far.
+    pub(crate) progress: &'a mut Progress,
+
+    /// The number of placement attempts made for each row in `order`.
+    row_probes: Vec<usize>,
+}
+
+impl<'a> PackContext<'a> {
+    /// Creates a new context.
+    ///
+    /// # Parameters
+    /// * `order` - The rows to place.
+    /// * `table_len` - The number of entries in the packed array.
+    /// * `rlt_len` - The number of entries in the row lookup table.
+    /// * `options` - The options holding the budgets.
+    /// * `progress` - The progress of the packing so far.
+    pub(crate) fn new(
+        order: &'a [(usize, &'a Row)],
+        table_len: usize,
+        rlt_len: usize,
+        options: &'a Options,
+        progress: &'a mut Progress,
+    ) -> Self {
+        Self {
+            order,
+            cols: order.iter().map(|(_, row)| row.get_col_indices()).collect(),
+            table_len,
+            rlt_len,
+            options,
+            progress,
+            row_probes: vec![0; order.len()],
+        }
+    }
+
+    /// Gets the rows to place.
+    ///
+    /// # Returns
+    /// The sorted column indices used by each row.
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<usize>] {
+        &self.cols
+    }
+
+    /// Gets the number of entries in the packed array.
+    ///
+    /// # Returns
+    /// The table length that offsets wrap around.
+    #[must_use]
+    pub fn table_len(&self) -> usize {
+        self.table_len
+    }
+
+    /// Gets the offsets a row may be placed at when they are restricted by
+    /// `Options::max_row_shift` or `Options::wraparound`. A row is never
+    /// shifted below index 0, and without wraparound never past the end of the
+    /// packed array.
+    ///
+    /// # Parameters
+    /// * `row` - The index into `rows()` of the row.
+    ///
+    /// # Returns
+    /// The allowed offsets, possibly none, or `None` if every offset is
+    /// allowed.
+    #[must_use]
+    pub fn allowed_offsets(&self, row: usize) -> Option<Range<isize>> {
+        let max_row_shift = self.options.get_max_row_shift();
+        let wraparound = self.options.get_wraparound();
+        if max_row_shift.is_none() && wraparound {
+            return None;
+        }
+        let cols = self.cols.get(row)?;
+        let to_isize = |value: usize| isize::try_from(value).unwrap_or(isize::MAX);
+        let (first, last) = (to_isize(*cols.first()?), to_isize(*cols.last()?));
+
+        let mut start = -first;
+        let mut end = isize::MAX;
+        if let Some(max_row_shift) = max_row_shift.map(to_isize) {
+            start = start.max(-max_row_shift);
+            end = max_row_shift.saturating_add(1);
+        }
+        if !wraparound {
+            end = end.min(to_isize(self.table_len) - last);
+        }
+        Some(start..end.max(start))
+    }
+
+    /// Records an attempt to place a row, checking the budgets first.
+    ///
+    /// # Parameters
+    /// * `row` - The index into `rows()` of the row being placed.
+    ///
+    /// # Errors
+    /// Will return `Err` if the time budget or a probe budget is exceeded.
+    pub fn probe(&mut self, row: usize) -> Result<(), Error> {
+        if let Some(max_duration) = self.options.get_max_duration() {
+            self.progress.stats.elapsed = self.progress.start.elapsed();
+            if self.progress.stats.elapsed > max_duration {
+                return Err(Error::new(Kind::TimeBudgetExceeded(
+                    self.progress.stats.clone(),
+                )));
+            }
+        }
+        if self
+            .options
+            .get_row_probe_limit()
+            .is_some_and(|m| self.row_probes[row] >= m)
+            || self
+                .options
+                .get_total_probe_limit()
+                .is_some_and(|m| self.progress.stats.probes >= m)
+        {
+            self.progress.stats.elapsed = self.progress.start.elapsed();
+            return Err(Error::new(Kind::ProbeBudgetExceeded {
+                row_index: self.order[row].0,
+                stats: self.progress.stats.clone(),
+            }));
+        }
+        self.row_probes[row] += 1;
+        self.progress.stats.probes += 1;
+        Ok(())
+    }
+
+    /// Records how many rows are currently placed, for the statistics reported
+    /// when a budget is exceeded.
+    ///
+    /// # Parameters
+    /// * `rows_placed` - The number of rows placed.
+    pub fn set_rows_placed(&mut self, rows_placed: usize) {
+        self.progress.stats.rows_placed = rows_placed;
+    }
+
+    /// Gets the statistics of the packing so far.
+    ///
+    /// # Returns
+    /// The packing statistics.
+    #[must_use]
+    pub fn stats(&self) -> PackStats {
+        PackStats {
+            elapsed: self.progress.start.elapsed(),
+            ..self.progress.stats.clone()
+        }
+    }
+
+    /// The error to return when the rows cannot be placed without overlapping.
+    ///
+    /// The row that does not fit is taken to be the one after the rows placed,
+    /// see `set_rows_placed`. The occupancy of the packed array is not
+    /// reported, see `unable_to_pack_with`.
+    ///
+    /// # Returns
+    /// The error.
+    #[must_use]
+    pub fn unable_to_pack(&self) -> Error {
+        self.unable_to_pack_with(Vec::new())
+    }
+
+    /// The error to return when the rows cannot be placed without overlapping,
+    /// reporting which entries of the packed array were used when packing
+    /// stopped.
+    ///
+    /// # Parameters
+    /// * `occupancy` - Whether each entry of the packed array is used.
+    ///
+    /// # Returns
+    /// The error.
+    #[must_use]
+    pub fn unable_to_pack_with(&self, occupancy: Vec<bool>) -> Error {
+        let rows_placed = self.progress.stats.rows_placed;
+        Error::new(Kind::PackingFailed {
+            row_index: self
+                .order
+                .get(rows_placed)
+                .map_or(0, |(row_index, _)| *row_index),
+            rows_placed,
+            cols: self
+                .order
+                .get(rows_placed)
+                .map_or_else(Vec::new, |(_, row)| row.get_col_indices()),
+            occupancy,
+        })
+    }
+}
+
+/// Each row, biggest first, is placed at the first offset it fits at. Fails as
+/// soon as a row does not fit anywhere. This is the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FirstFit;
+
+impl PackingStrategy for FirstFit {
+    fn pack(&self, ctx: &mut PackContext) -> Result<Vec<isize>, Error> {
+        OneDPackedArray::first_fit(ctx)
+    }
+}
+
+/// Like `FirstFit`, but when a row does not fit the previously placed row is
+/// re-placed at its next fitting offset. Only fails once every combination of
+/// offsets has been tried.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Backtracking;
+
+impl PackingStrategy for Backtracking {
+    fn pack(&self, ctx: &mut PackContext) -> Result<Vec<isize>, Error> {
+        OneDPackedArray::backtracking(ctx)
+    }
+}
+
+/// Simulated annealing. All rows are placed at random offsets, and then single
+/// rows are moved to random offsets, accepting moves that add overlaps with a
+/// probability that decreases as the search cools.
+///
+/// The acceptance probability and the cooling schedule are computed with
+/// `f64::exp` and `f64::powf`, whose results may differ in the last bit between
+/// platforms. Unlike the other strategies, a packing found by annealing is only
+/// reproducible on the same platform.
+#[cfg(feature = "heuristics")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Annealing {
+    /// The seed of the random moves. The same seed always gives the same result.
+    pub seed: u64,
+
+    /// The number of moves tried before giving up.
+    pub iterations: usize,
+}
+
+#[cfg(feature = "heuristics")]
+impl PackingStrategy for Annealing {
+    fn pack(&self, ctx: &mut PackContext) -> Result<Vec<isize>, Error> {
+        OneDPackedArray::annealing(ctx, self.seed, self.iterations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_hash_with_options, ElcAlgorithm, WordList};
+
+    /// Places every row at the offset that puts its first column at the next free index.
+    #[derive(Debug)]
+    struct Consecutive;
+
+    impl PackingStrategy for Consecutive {
+        fn pack(&self, ctx: &mut PackContext) -> Result<Vec<isize>, Error> {
+            let mut next = 0;
+            let mut offsets = Vec::new();
+            for (row, cols) in ctx.rows().to_vec().iter().enumerate() {
+                ctx.probe(row)?;
+                if cols.windows(2).any(|w| w[1] != w[0] + 1) {
+                    return Err(ctx.unable_to_pack());
+                }
+                offsets.push(next - isize::try_from(cols[0]).unwrap_or(0));
+                next += isize::try_from(cols.len()).unwrap_or(0);
+                ctx.set_rows_placed(row + 1);
+            }
+            Ok(offsets)
+        }
+    }
+
+    /// Places every row at the same offset.
+    #[derive(Debug)]
+    struct Fixed(isize);
+
+    impl PackingStrategy for Fixed {
+        fn pack(&self, ctx: &mut PackContext) -> Result<Vec<isize>, Error> {
+            Ok(vec![self.0; ctx.rows().len()])
+        }
+    }
+
+    #[test]
+    fn packing_strategy_unit_test() {
+        let word_list: WordList = ["AB", "AC", "BD", "CA"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+
+        let options = Options::new().packing_strategy(Consecutive);
+        let hash_data =
+            generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+        assert_eq!(hash_data.as_fn.table_size(), 4);
+
+        // Offset 0 happens to fit these rows.
+        let options = Options::new().packing_strategy(Fixed(0));
+        assert!(generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).is_ok());
+
+        let word_list: WordList = ["AB", "BB"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+            Ok(_) => panic!("Overlapping rows not detected."),
+            Err(e) => match e.kind() {
+                Kind::OneDPackedArrayError(s) => {
+                    assert_eq!(s, "Packing strategy placed overlapping rows.");
+                }
+                _ => panic!("Unexpected error type."),
+            },
+        }
+
+        let word_list: WordList = ["AB", "AD"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let options = Options::new().packing_strategy(Consecutive);
+        match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+            Ok(_) => panic!("Should not be able to pack."),
+            Err(e) => match e.kind() {
+                Kind::PackingFailed { rows_placed, .. } => assert_eq!(*rows_placed, 0),
+                _ => panic!("Unexpected error type."),
+            },
+        }
+
+        let word_list: WordList = ["AB", "AC", "BD", "CA"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let options = Options::new()
+            .packing_strategy(Consecutive)
+            .total_probe_limit(0);
+        match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+            Ok(_) => panic!("Total probe budget should have been exceeded"),
+            Err(e) => match e.kind() {
+                Kind::ProbeBudgetExceeded { stats, .. } => assert_eq!(stats.probes, 0),
+                _ => panic!("Unexpected error type."),
+            },
+        }
+    }
+
+    #[test]
+    fn packing_strategy_offsets_unit_test() {
+        let word_list: WordList = ["AB", "AC", "BD", "CA"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+
+        for (offset, options) in [
+            (-100, Options::new()),
+            (isize::MAX, Options::new()),
+            (-1, Options::new().wraparound(false)),
+        ] {
+            let options = options.packing_strategy(Fixed(offset));
+            match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+                Ok(_) => panic!("Offset {offset} not rejected."),
+                Err(e) => match e.kind() {
+                    Kind::OneDPackedArrayError(s) => assert!(
+                        s.ends_with(&format!("by {offset}, outside of the packed array.")),
+                        "{s}"
+                    ),
+                    _ => panic!("Unexpected error type."),
+                },
+            }
+        }
+
+        let options = Options::new()
+            .wraparound(false)
+            .packing_strategy(Fixed(100));
+        match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+            Ok(_) => panic!("Offset past the end not rejected."),
+            Err(e) => match e.kind() {
+                Kind::OneDPackedArrayError(s) => assert!(s.ends_with("which is not allowed.")),
+                _ => panic!("Unexpected error type."),
+            },
+        }
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::search::auto_elc_algorithm;
+use crate::{Error, MsmpFn, Options, WordList};
+
+/// A set of words that owns its word list along with a minimal perfect hash
+/// generated for it.
+///
+/// The `elc` value of the hash is chosen automatically, see
+/// `generate_hash_auto`. The words are stored with their hash values, so
+/// `contains` rejects every word that is not in the set.
+///
+/// With the `serde` feature the set serializes as its list of words, and the
+/// hash is generated again when it is deserialized.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "Vec<String>", into = "Vec<String>")
+)]
+pub struct PerfectHashSet {
+    /// The hash of the words, with the words stored.
+    hash: MsmpFn,
+
+    /// The words, in word list order.
+    words: Vec<String>,
+}
+
+impl PerfectHashSet {
+    /// Tests whether a word is in the set.
+    ///
+    /// # Parameters
+    /// * `word` - The word to test.
+    ///
+    /// # Returns
+    /// True if `word` is in the set.
+    #[must_use]
+    pub fn contains(&self, word: &str) -> bool {
+        self.hash.find(word).is_some()
+    }
+
+    /// Gets the hash value of a word in the set.
+    ///
+    /// # Parameters
+    /// * `word` - The word to hash.
+    ///
+    /// # Returns
+    /// The hash value of `word`, or `None` if `word` is not in the set.
+    #[must_use]
+    pub fn hash(&self, word: &str) -> Option<usize> {
+        self.hash.find(word)
+    }
+
+    /// Gets the number of words in the set.
+    ///
+    /// # Returns
+    /// The number of words.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns true if the set has no words.
+    ///
+    /// # Returns
+    /// True if the set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Iterates over the words of the set.
+    ///
+    /// # Returns
+    /// The words, in the order of the word list the set was created from.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+}
+
+impl TryFrom<WordList> for PerfectHashSet {
+    type Error = Error;
+
+    /// Creates a set by generating a hash for a word list.
+    ///
+    /// # Errors
+    /// Will return `Err` if the word list is invalid, or if no `elc` value
+    /// produces a hash for it.
+    fn try_from(word_list: WordList) -> Result<Self, Error> {
+        let options = Options::new().store_keys(true);
+        let (_, hash_data) = auto_elc_algorithm(&word_list, &options)?;
+        Ok(Self {
+            hash: hash_data.as_fn,
+            words: word_list.into_iter().collect(),
+        })
+    }
+}
+
+impl TryFrom<Vec<String>> for PerfectHashSet {
+    type Error = Error;
+
+    /// Creates a set by generating a hash for a list of words.
+    ///
+    /// # Errors
+    /// Will return `Err` if the words are invalid, or if no `elc` value produces
+    /// a hash for them.
+    fn try_from(words: Vec<String>) -> Result<Self, Error> {
+        Self::try_from(WordList { list: words })
+    }
+}
+
+impl From<PerfectHashSet> for Vec<String> {
+    /// Takes the words of a set, in word list order.
+    fn from(set: PerfectHashSet) -> Self {
+        set.words
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Kind;
+
+    fn word_list(words: &[&str]) -> WordList {
+        words.iter().map(|w| (*w).to_string()).collect()
+    }
+
+    #[test]
+    fn perfect_hash_set_unit_test() {
+        let words = [
+            "AND", "BEGIN", "CHAR", "CONST", "ELSE", "END", "ENTER", "EOF",
+        ];
+        let set = PerfectHashSet::try_from(word_list(&words)).unwrap();
+        println!("{set:?}");
+        assert_eq!(set.len(), 8);
+        assert!(!set.is_empty());
+        assert_eq!(set.iter().collect::<Vec<_>>(), words);
+        for word in words {
+            assert!(set.contains(word));
+        }
+        assert!(!set.contains("CHAZ"));
+        assert!(!set.contains("end"));
+        assert!(!set.contains(""));
+
+        let mut hash_values: Vec<usize> = words.iter().filter_map(|w| set.hash(w)).collect();
+        hash_values.sort_unstable();
+        assert_eq!(hash_values, (0..8).collect::<Vec<_>>());
+
+        let words: Vec<String> = set.clone().into();
+        let copy = PerfectHashSet::try_from(words).unwrap();
+        assert_eq!(copy.hash("ELSE"), set.hash("ELSE"));
+
+        match PerfectHashSet::try_from(word_list(&["AND", "AND"])) {
+            Ok(_) => panic!("Duplicate not detected."),
+            Err(e) => match e.kind() {
+                Kind::WordListError(_) => {}
+                _ => panic!("Unexpected error type."),
+            },
+        }
+    }
+}
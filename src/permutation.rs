@@ -0,0 +1,183 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::BTreeMap;
+
+use crate::key::compare_key;
+use crate::{from_index, Index, Key, WordList};
+
+/// Links the index spaces involved in a generated hash.
+///
+/// There are three index spaces:
+/// * the word index, the 0 based position of a word in the `WordList`,
+/// * the packed array position, where the word was placed in the 1D packed array,
+/// * the hash value, what the generated hash function returns for the word.
+///
+/// The hash values used here are the packed array positions, which start at 0.
+/// The hash function returns them plus the hash base, see `Options::hash_base`,
+/// so a value table indexed by hash value less the base is laid out exactly like
+/// the packed array. Note that the packed array itself stores 1 based word
+/// indices, whereas the word indices used here are 0 based like the `WordList`.
+///
+/// When repeated words are allowed, every occurrence of a word has the same
+/// hash value, and the hash value maps back to the first occurrence. When the
+/// table was packed with slack or rounded up to a power of two, see
+/// `Options::slack_percent` and `Options::power_of_two_table`, some hash values
+/// have no word at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permutation {
+    /// The hash value of each word, indexed by word index.
+    word_to_hash: Vec<usize>,
+
+    /// The word index for each hash value, `None` for unused hash values.
+    hash_to_word: Vec<Option<usize>>,
+}
+
+impl Permutation {
+    /// Creates a permutation from a packed array.
+    ///
+    /// # Parameters
+    /// * `packed_array` - The 1 based word index stored at each packed array position,
+    ///   0 for unused positions.
+    /// * `word_list` - The word list the packed array was built from.
+    /// * `fold_case` - Whether words that differ only in case are the same key.
+    pub(crate) fn new<K: Key + ?Sized, W: Borrow<K>>(
+        packed_array: &[Index],
+        word_list: &WordList<W>,
+        fold_case: bool,
+    ) -> Self {
+        let hash_to_word: Vec<Option<usize>> = packed_array
+            .iter()
+            .map(|w| from_index(*w).checked_sub(1))
+            .collect();
+        let hash_by_word: BTreeMap<Cow<K>, usize> = hash_to_word
+            .iter()
+            .enumerate()
+            .filter_map(|(hash_value, word_index)| {
+                word_index.map(|w| (compare_key(word_list[w].borrow(), fold_case), hash_value))
+            })
+            .collect();
+        let word_to_hash = word_list
+            .iter()
+            .map(|word| {
+                hash_by_word
+                    .get(&compare_key(word.borrow(), fold_case))
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .collect();
+        Self {
+            word_to_hash,
+            hash_to_word,
+        }
+    }
+
+    /// Gets the number of hash values.
+    ///
+    /// # Returns
+    /// The number of hash values, which is the number of distinct words unless
+    /// the table was packed with slack or rounded up to a power of two.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.hash_to_word.len()
+    }
+
+    /// Returns true if there are no hash values.
+    ///
+    /// # Returns
+    /// True if there are no hash values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hash_to_word.is_empty()
+    }
+
+    /// Gets the number of words in the word list the permutation was built from.
+    ///
+    /// # Returns
+    /// The number of words, counting repeated words.
+    #[must_use]
+    pub fn num_words(&self) -> usize {
+        self.word_to_hash.len()
+    }
+
+    /// Gets the hash value of a word.
+    ///
+    /// # Parameters
+    /// * `word_index` - The 0 based index of the word in the word list.
+    ///
+    /// # Returns
+    /// The hash value, or None if the word index is out of range.
+    #[must_use]
+    pub fn hash_of_word(&self, word_index: usize) -> Option<usize> {
+        self.word_to_hash.get(word_index).copied()
+    }
+
+    /// Gets the word that hashes to a value.
+    ///
+    /// # Parameters
+    /// * `hash_value` - A hash value.
+    ///
+    /// # Returns
+    /// The 0 based index of the word in the word list, or None if the hash value is
+    /// out of range.
+    #[must_use]
+    pub fn word_of_hash(&self, hash_value: usize) -> Option<usize> {
+        self.hash_to_word.get(hash_value).copied().flatten()
+    }
+
+    /// Gets the packed array position of a word.
+    ///
+    /// # Parameters
+    /// * `word_index` - The 0 based index of the word in the word list.
+    ///
+    /// # Returns
+    /// The packed array position, or None if the word index is out of range.
+    #[must_use]
+    pub fn position_of_word(&self, word_index: usize) -> Option<usize> {
+        self.hash_of_word(word_index)
+    }
+
+    /// Gets the word indices in hash value order.
+    ///
+    /// # Returns
+    /// The 0 based word index for each hash value, `None` for unused hash values.
+    #[must_use]
+    pub fn words_in_hash_order(&self) -> &[Option<usize>] {
+        &self.hash_to_word
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutation_unit_test() {
+        let word_list: WordList = ["A", "B", "C", "B"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let p = Permutation::new::<str, _>(&[2, 3, 1], &word_list, false);
+        assert_eq!(p.len(), 3);
+        assert!(!p.is_empty());
+        assert_eq!(p.hash_of_word(0), Some(2));
+        assert_eq!(p.hash_of_word(1), Some(0));
+        assert_eq!(p.hash_of_word(2), Some(1));
+        assert_eq!(p.hash_of_word(3), Some(0));
+        assert_eq!(p.hash_of_word(4), None);
+        assert_eq!(p.word_of_hash(0), Some(1));
+        assert_eq!(p.word_of_hash(3), None);
+        assert_eq!(p.position_of_word(0), Some(2));
+        assert_eq!(p.words_in_hash_order(), &[Some(1), Some(2), Some(0)]);
+        println!("{p:?}");
+
+        let p = Permutation::new::<str, _>(&[0, 2, 3, 0, 1], &word_list, false);
+        assert_eq!(p.len(), 5);
+        assert_eq!(p.hash_of_word(0), Some(4));
+        assert_eq!(p.hash_of_word(3), Some(1));
+        assert_eq!(p.word_of_hash(0), None);
+        assert_eq!(p.word_of_hash(2), Some(2));
+        assert_eq!(
+            p.words_in_hash_order(),
+            &[None, Some(1), Some(2), None, Some(0)]
+        );
+    }
+}
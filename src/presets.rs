@@ -0,0 +1,182 @@
+//! Preset word lists with pre-tuned hash configurations.
+//!
+//! Each preset pairs a commonly used word list with an `ElcAlgorithm`
+//! configuration known to produce a minimal perfect hash for it, so the table
+//! can be generated with one call.
+
+use crate::{generate_hash, ElcAlgorithm, Error, HashData, WordList};
+
+/// A word list together with a hash configuration that is known to work for it.
+#[derive(Debug, Clone, Copy)]
+pub struct Preset {
+    /// The words of the preset.
+    words: &'static [&'static str],
+
+    /// The number of characters to use from the beginning and end of a word.
+    elc: usize,
+
+    /// The number of values that can be represented by a letter from the word.
+    num_vals: usize,
+}
+
+impl Preset {
+    /// Gets the words of the preset.
+    ///
+    /// # Returns
+    /// The words of the preset.
+    #[must_use]
+    pub fn words(&self) -> &'static [&'static str] {
+        self.words
+    }
+
+    /// Creates a word list from the words of the preset.
+    ///
+    /// # Returns
+    /// A new word list.
+    #[must_use]
+    pub fn word_list(&self) -> WordList {
+        self.words.iter().map(|w| (*w).to_string()).collect()
+    }
+
+    /// Creates the hash algorithm tuned for the preset.
+    ///
+    /// # Returns
+    /// A new hash algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> ElcAlgorithm {
+        ElcAlgorithm::new(self.elc, self.num_vals)
+    }
+
+    /// Generates the hash for the preset.
+    ///
+    /// # Returns
+    /// A struct containing a string representation of the hash function and a
+    /// closure that takes a word and returns a hash value.
+    ///
+    /// # Errors
+    /// Will return `Err` if the hash cannot be generated. This does not happen for
+    /// the shipped presets.
+    pub fn generate(&self) -> Result<HashData, Error> {
+        generate_hash(&self.word_list(), self.algorithm())
+    }
+}
+
+/// The HTTP request methods.
+#[must_use]
+pub fn http_methods() -> Preset {
+    Preset {
+        words: HTTP_METHODS,
+        elc: 2,
+        num_vals: 26,
+    }
+}
+
+/// Commonly used SQL keywords.
+#[must_use]
+pub fn sql_keywords() -> Preset {
+    Preset {
+        words: SQL_KEYWORDS,
+        elc: 2,
+        num_vals: 26,
+    }
+}
+
+/// The ISO 3166-1 alpha-2 country codes.
+#[must_use]
+pub fn iso_country_codes() -> Preset {
+    Preset {
+        words: ISO_COUNTRY_CODES,
+        elc: 2,
+        num_vals: 26,
+    }
+}
+
+/// The ISO 4217 codes of commonly traded currencies.
+#[must_use]
+pub fn iso_currency_codes() -> Preset {
+    Preset {
+        words: ISO_CURRENCY_CODES,
+        elc: 2,
+        num_vals: 26,
+    }
+}
+
+#[rustfmt::skip]
+const HTTP_METHODS: &[&str] = &[
+    "GET", "HEAD", "POST", "PUT", "DELETE", "CONNECT", "OPTIONS", "TRACE", "PATCH",
+];
+
+#[rustfmt::skip]
+const SQL_KEYWORDS: &[&str] = &[
+    "ADD", "ALL", "ALTER", "AND", "ANY", "AS", "ASC", "BETWEEN",
+    "BY", "CASE", "CHECK", "COLUMN", "CONSTRAINT", "CREATE", "DATABASE", "DEFAULT",
+    "DELETE", "DESC", "DISTINCT", "DROP", "ELSE", "END", "EXISTS", "FOREIGN",
+    "FROM", "FULL", "GROUP", "HAVING", "IN", "INDEX", "INNER", "INSERT",
+    "INTO", "IS", "JOIN", "KEY", "LEFT", "LIKE", "LIMIT", "NOT",
+    "NULL", "ON", "OR", "ORDER", "OUTER", "PRIMARY", "REFERENCES", "RIGHT",
+    "SELECT", "SET", "TABLE", "THEN", "TOP", "TRUNCATE", "UNION", "UNIQUE",
+    "UPDATE", "VALUES", "VIEW", "WHERE", "WITH",
+];
+
+#[rustfmt::skip]
+const ISO_COUNTRY_CODES: &[&str] = &[
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT",
+    "AU", "AW", "AX", "AZ", "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI",
+    "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS", "BT", "BV", "BW", "BY",
+    "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM",
+    "DO", "DZ", "EC", "EE", "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK",
+    "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF", "GG", "GH", "GI", "GL",
+    "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR",
+    "IS", "IT", "JE", "JM", "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN",
+    "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC", "LI", "LK", "LR", "LS",
+    "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW",
+    "MX", "MY", "MZ", "NA", "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP",
+    "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG", "PH", "PK", "PL", "PM",
+    "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM",
+    "SN", "SO", "SR", "SS", "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF",
+    "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO", "TR", "TT", "TV", "TW",
+    "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+#[rustfmt::skip]
+const ISO_CURRENCY_CODES: &[&str] = &[
+    "AED", "AFN", "ALL", "AMD", "ARS", "AUD", "BRL", "CAD", "CHF", "CLP", "CNY", "COP",
+    "CZK", "DKK", "EGP", "EUR", "GBP", "HKD", "HUF", "IDR", "ILS", "INR", "ISK", "JPY",
+    "KES", "KRW", "MXN", "MYR", "NOK", "NZD", "PEN", "PHP", "PKR", "PLN", "RON", "RUB",
+    "SAR", "SEK", "SGD", "THB", "TRY", "TWD", "UAH", "USD", "VND", "ZAR",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_unit_test() {
+        for preset in [
+            http_methods(),
+            sql_keywords(),
+            iso_country_codes(),
+            iso_currency_codes(),
+        ] {
+            let word_list = preset.word_list();
+            assert_eq!(word_list.len(), preset.words().len());
+            match preset.generate() {
+                Ok(hash) => {
+                    let mut seen = vec![false; word_list.len()];
+                    for word in preset.words() {
+                        let hash_value = hash.as_fn.hash(word);
+                        assert!(!seen[hash_value]);
+                        seen[hash_value] = true;
+                    }
+                }
+                Err(e) => panic!("Preset failed to generate. {e}"),
+            }
+        }
+        println!("{:?}", http_methods());
+    }
+}
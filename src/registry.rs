@@ -0,0 +1,404 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::{
+    algorithm_from_spec, find_distinguishing_positions, generate_hash_seeded, CichelliAlgorithm,
+    ElcWithLength, Error, HashAlgorithm, Kind, Options, ParamValue, WordList,
+};
+
+/// Builds a hash algorithm from its parameters, see
+/// `AlgorithmRegistry::register`.
+pub type AlgorithmConstructor =
+    dyn Fn(&[(String, ParamValue)]) -> Result<Box<dyn HashAlgorithm>, Error>;
+
+/// Searches a word list for the parameters of a hash algorithm, see
+/// `AlgorithmRegistry::register_search`.
+pub type AlgorithmSearch = dyn Fn(&WordList) -> Result<Box<dyn HashAlgorithm>, Error>;
+
+/// The number of seeds the `seeded` search tries.
+const SEARCH_SEEDS: u64 = 1000;
+
+/// The parameters of the ELC algorithm. `elc` sets both `elc_h1` and `elc_h2`.
+const ELC_PARAMETERS: &[&str] = &["elc", "elc_h1", "elc_h2", "num_vals", "alphabet"];
+
+/// The parameters of the ELC algorithm with the word length.
+const ELC_WITH_LENGTH_PARAMETERS: &[&str] =
+    &["elc", "elc_h1", "elc_h2", "num_vals", "alphabet", "max_len"];
+
+/// The parameters of the position algorithm.
+const POSITION_PARAMETERS: &[&str] = &["h1_positions", "h2_positions", "max_len", "alphabet"];
+
+/// The parameters of the FNV algorithm.
+const FNV_PARAMETERS: &[&str] = &["num_rows", "num_cols"];
+
+/// The parameters of the seeded algorithm.
+const SEEDED_PARAMETERS: &[&str] = &["seed", "num_rows", "num_cols"];
+
+/// The parameters of the Cichelli algorithm.
+const CICHELLI_PARAMETERS: &[&str] = &["letters", "weights"];
+
+/// Hash algorithms addressable by name, so that command lines and
+/// configuration files can select an algorithm at run time, such as
+/// `--algorithm elc --param elc=2`.
+///
+/// `new` registers the built in algorithms:
+/// * `elc` - `ElcAlgorithm`, with `elc` (default 1), or `elc_h1` and `elc_h2`,
+///   and `num_vals` (default 26) or an `alphabet`.
+/// * `elc+len`, also `elc_with_length` - `ElcWithLength`, with the parameters
+///   of `elc` and `max_len`.
+/// * `position` - `PositionAlgorithm`, with `h1_positions` and `h2_positions`
+///   written as `start i` or `end i`, and optionally `max_len` and `alphabet`.
+/// * `fnv` - `FnvAlgorithm`, with `num_rows` and `num_cols`.
+/// * `seeded` - `SeededAlgorithm`, with `seed`, `num_rows` and `num_cols`.
+/// * `cichelli` - `CichelliAlgorithm`, with the `letters` and their `weights`.
+///
+/// Except for `elc` and `fnv` the parameters depend on the words, so `build_for`
+/// searches the word list for them when none are given: `elc+len` uses
+/// `ElcWithLength::from_word_list`, `position` uses
+/// `find_distinguishing_positions`, `seeded` uses `generate_hash_seeded` and
+/// `cichelli` uses `CichelliAlgorithm::from_word_list`.
+///
+/// Further algorithms are added with `register` and `register_search`.
+pub struct AlgorithmRegistry {
+    /// The constructor of each algorithm, by name.
+    constructors: BTreeMap<String, Box<AlgorithmConstructor>>,
+
+    /// The search for the parameters of each algorithm that has one, by name.
+    searches: BTreeMap<String, Box<AlgorithmSearch>>,
+}
+
+impl fmt::Debug for AlgorithmRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AlgorithmRegistry")
+            .field("constructors", &self.names().collect::<Vec<_>>())
+            .field("searches", &self.searches.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Default for AlgorithmRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AlgorithmRegistry {
+    /// Creates a registry of the built in algorithms.
+    #[must_use]
+    pub fn new() -> Self {
+        let no_defaults = |_: &mut Vec<(String, ParamValue)>| ();
+        let constructors = [
+            ("elc", builtin("elc", ELC_PARAMETERS, elc_defaults)),
+            (
+                "elc+len",
+                builtin("elc_with_length", ELC_WITH_LENGTH_PARAMETERS, elc_defaults),
+            ),
+            (
+                "elc_with_length",
+                builtin("elc_with_length", ELC_WITH_LENGTH_PARAMETERS, elc_defaults),
+            ),
+            (
+                "position",
+                builtin("position", POSITION_PARAMETERS, no_defaults),
+            ),
+            ("fnv", builtin("fnv", FNV_PARAMETERS, no_defaults)),
+            ("seeded", builtin("seeded", SEEDED_PARAMETERS, no_defaults)),
+            (
+                "cichelli",
+                builtin("cichelli", CICHELLI_PARAMETERS, no_defaults),
+            ),
+        ];
+        let searches: [(&str, Box<AlgorithmSearch>); 5] = [
+            (
+                "elc+len",
+                Box::new(|word_list| Ok(Box::new(ElcWithLength::from_word_list(1, word_list)))),
+            ),
+            (
+                "elc_with_length",
+                Box::new(|word_list| Ok(Box::new(ElcWithLength::from_word_list(1, word_list)))),
+            ),
+            (
+                "position",
+                Box::new(|word_list| Ok(Box::new(find_distinguishing_positions(word_list)?))),
+            ),
+            (
+                "seeded",
+                Box::new(|word_list| {
+                    let seeded = generate_hash_seeded(word_list, &Options::new(), SEARCH_SEEDS)?;
+                    Ok(Box::new(seeded.hash_algorithm))
+                }),
+            ),
+            (
+                "cichelli",
+                Box::new(|word_list| Ok(Box::new(CichelliAlgorithm::from_word_list(word_list)?))),
+            ),
+        ];
+        Self {
+            constructors: constructors
+                .into_iter()
+                .map(|(name, constructor)| (name.to_string(), constructor))
+                .collect(),
+            searches: searches
+                .into_iter()
+                .map(|(name, search)| (name.to_string(), search))
+                .collect(),
+        }
+    }
+
+    /// Adds an algorithm, replacing any algorithm registered with the same name.
+    ///
+    /// # Parameters
+    /// * `name` - The name the algorithm is selected by.
+    /// * `constructor` - Builds the algorithm from its parameters.
+    ///
+    /// # Returns
+    /// The registry with the algorithm added.
+    #[must_use]
+    pub fn register(
+        mut self,
+        name: impl Into<String>,
+        constructor: impl Fn(&[(String, ParamValue)]) -> Result<Box<dyn HashAlgorithm>, Error> + 'static,
+    ) -> Self {
+        self.constructors.insert(name.into(), Box::new(constructor));
+        self
+    }
+
+    /// Adds the search for the parameters of an algorithm, replacing any search
+    /// registered with the same name, see `build_for`.
+    ///
+    /// # Parameters
+    /// * `name` - The name the algorithm is selected by.
+    /// * `search` - Builds the algorithm with parameters suited to a word list.
+    ///
+    /// # Returns
+    /// The registry with the search added.
+    #[must_use]
+    pub fn register_search(
+        mut self,
+        name: impl Into<String>,
+        search: impl Fn(&WordList) -> Result<Box<dyn HashAlgorithm>, Error> + 'static,
+    ) -> Self {
+        self.searches.insert(name.into(), Box::new(search));
+        self
+    }
+
+    /// Gets the names of the registered algorithms.
+    ///
+    /// # Returns
+    /// The names, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+
+    /// Builds an algorithm by name.
+    ///
+    /// # Parameters
+    /// * `name` - The name the algorithm was registered with.
+    /// * `parameters` - The name and value of each parameter.
+    ///
+    /// # Returns
+    /// The algorithm.
+    ///
+    /// # Errors
+    /// Will return `Err` if no algorithm is registered with the name, or the
+    /// constructor rejects the parameters.
+    pub fn build(
+        &self,
+        name: &str,
+        parameters: &[(String, ParamValue)],
+    ) -> Result<Box<dyn HashAlgorithm>, Error> {
+        let constructor = self.constructors.get(name).ok_or_else(|| {
+            Error::new(Kind::SpecError(format!(
+                "Unknown hash algorithm '{name}', expected one of: {}.",
+                self.names().collect::<Vec<_>>().join(", ")
+            )))
+        })?;
+        constructor(parameters)
+    }
+
+    /// Builds an algorithm by name for a word list. When no parameters are
+    /// given and the algorithm has a search, the word list is searched for
+    /// them, see `register_search`.
+    ///
+    /// # Parameters
+    /// * `name` - The name the algorithm was registered with.
+    /// * `parameters` - The name and value of each parameter.
+    /// * `word_list` - The words the algorithm will hash.
+    ///
+    /// # Returns
+    /// The algorithm.
+    ///
+    /// # Errors
+    /// Will return `Err` if no algorithm is registered with the name, the
+    /// search finds no parameters or the constructor rejects the parameters.
+    pub fn build_for(
+        &self,
+        name: &str,
+        parameters: &[(String, ParamValue)],
+        word_list: &WordList,
+    ) -> Result<Box<dyn HashAlgorithm>, Error> {
+        match self.searches.get(name) {
+            Some(search) if parameters.is_empty() => search(word_list),
+            _ => self.build(name, parameters),
+        }
+    }
+}
+
+/// Creates the constructor of a built in algorithm, which rejects unknown
+/// parameters, fills in defaults and rebuilds the algorithm with
+/// `algorithm_from_spec`.
+///
+/// # Parameters
+/// * `name` - The name of the algorithm, see `HashAlgorithm::name`.
+/// * `accepted` - The names of the parameters the algorithm takes.
+/// * `defaults` - Adds the defaults of missing parameters.
+///
+/// # Returns
+/// The constructor.
+fn builtin(
+    name: &'static str,
+    accepted: &'static [&'static str],
+    defaults: fn(&mut Vec<(String, ParamValue)>),
+) -> Box<AlgorithmConstructor> {
+    Box::new(move |parameters| {
+        if let Some((unknown, _)) = parameters
+            .iter()
+            .find(|(key, _)| !accepted.contains(&key.as_str()))
+        {
+            return Err(Error::new(Kind::SpecError(format!(
+                "Unknown parameter '{unknown}' of the {name} algorithm, expected one of: {}.",
+                accepted.join(", ")
+            ))));
+        }
+        let mut parameters = parameters.to_vec();
+        defaults(&mut parameters);
+        algorithm_from_spec(name, &parameters)
+    })
+}
+
+/// Adds the defaults of the ELC parameters: `elc` is 1 and sets `elc_h1` and
+/// `elc_h2` when they are missing, and `num_vals` is 26.
+///
+/// # Parameters
+/// * `parameters` - The parameters given.
+fn elc_defaults(parameters: &mut Vec<(String, ParamValue)>) {
+    let elc = parameters
+        .iter()
+        .position(|(key, _)| key == "elc")
+        .map_or(ParamValue::Number(1), |i| parameters.remove(i).1);
+    for (key, value) in [
+        ("elc_h1", elc.clone()),
+        ("elc_h2", elc),
+        ("num_vals", ParamValue::Number(26)),
+    ] {
+        if parameters.iter().all(|(name, _)| name != key) {
+            parameters.push((key.to_string(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithm_registry_unit_test() {
+        let registry = AlgorithmRegistry::default();
+        println!("{registry:?}");
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            [
+                "cichelli",
+                "elc",
+                "elc+len",
+                "elc_with_length",
+                "fnv",
+                "position",
+                "seeded"
+            ]
+        );
+
+        let param = |key: &str, value: &str| (key.to_string(), ParamValue::parse(value));
+        let elc = registry.build("elc", &[param("elc", "2")]).unwrap();
+        assert_eq!(elc.name(), "elc");
+        assert_eq!(
+            elc.parameters(),
+            [
+                ("elc_h1", ParamValue::Number(2)),
+                ("elc_h2", ParamValue::Number(2)),
+                ("num_vals", ParamValue::Number(26)),
+            ]
+        );
+        assert_eq!(elc.h1("BEGIN").unwrap(), 30);
+
+        let elc = registry.build("elc", &[]).unwrap();
+        assert_eq!(elc.h1("BEGIN").unwrap(), 1);
+
+        let elc_len = registry
+            .build("elc+len", &[param("max_len", "8"), param("elc_h2", "2")])
+            .unwrap();
+        assert_eq!(elc_len.name(), "elc_with_length");
+        assert_eq!(elc_len.h1("BEGIN").unwrap(), 13);
+
+        let position = registry
+            .build(
+                "position",
+                &[
+                    param("h1_positions", "start 0,end 0"),
+                    param("h2_positions", "start 1"),
+                ],
+            )
+            .unwrap();
+        assert_eq!(position.h2("BEGIN").unwrap(), 4);
+
+        let cichelli = registry
+            .build(
+                "cichelli",
+                &[param("letters", "BN"), param("weights", "1,2")],
+            )
+            .unwrap();
+        assert_eq!(cichelli.h2("BEGIN").unwrap(), 8);
+        assert!(registry.build("seeded", &[param("seed", "3")]).is_err());
+
+        let Err(e) = registry.build("elc", &[param("ecl", "2")]) else {
+            panic!("ecl is not a parameter");
+        };
+        assert_eq!(
+            e.to_string(),
+            "Spec Error: Unknown parameter 'ecl' of the elc algorithm, expected one of: \
+             elc, elc_h1, elc_h2, num_vals, alphabet."
+        );
+        let Err(e) = registry.build("md5", &[]) else {
+            panic!("md5 is not registered");
+        };
+        assert!(e.to_string().contains("expected one of: cichelli, elc,"));
+
+        let registry =
+            registry.register("square", |_| Ok(Box::new(crate::FnvAlgorithm::new(2, 2))));
+        assert!(registry.build("square", &[]).is_ok());
+    }
+
+    #[test]
+    fn algorithm_registry_search_unit_test() {
+        let word_list: WordList = ["AND", "BEGIN", "CHAR", "CONST", "ELSE", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let registry = AlgorithmRegistry::new();
+        for name in ["cichelli", "elc+len", "position", "seeded"] {
+            assert!(registry.build(name, &[]).is_err());
+            let hash_algorithm = registry.build_for(name, &[], &word_list).unwrap();
+            assert!(crate::generate_hash(&word_list, hash_algorithm).is_ok());
+        }
+        // Given parameters are used as they are.
+        let param = ("seed".to_string(), ParamValue::Number(3));
+        assert!(registry.build_for("seeded", &[param], &word_list).is_err());
+        assert!(registry.build_for("fnv", &[], &word_list).is_err());
+
+        let registry = registry.register_search("fnv", |word_list| {
+            Ok(Box::new(crate::FnvAlgorithm::new(word_list.len(), 64)))
+        });
+        println!("{registry:?}");
+        assert!(registry.build_for("fnv", &[], &word_list).is_ok());
+    }
+}
@@ -1,27 +1,223 @@
-#[derive(Debug)]
+use std::slice::Iter;
+
+use crate::chm::Chm;
+use crate::TwoLevelRlt;
+
+/// The integer type of the values of a row lookup table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RltElementType {
+    /// A signed 8 bit integer.
+    I8,
+
+    /// A signed 16 bit integer.
+    I16,
+
+    /// A signed 32 bit integer.
+    I32,
+
+    /// A signed 64 bit integer.
+    I64,
+
+    /// An unsigned 8 bit integer.
+    U8,
+
+    /// An unsigned 16 bit integer.
+    U16,
+
+    /// An unsigned 32 bit integer.
+    U32,
+
+    /// An unsigned 64 bit integer.
+    U64,
+}
+
+impl RltElementType {
+    /// Finds the narrowest signed integer type that holds a range of values.
+    ///
+    /// # Parameters
+    /// * `min` - The smallest value.
+    /// * `max` - The largest value.
+    ///
+    /// # Returns
+    /// The integer type.
+    #[must_use]
+    pub fn signed(min: isize, max: isize) -> Self {
+        let fits = |lo: i64, hi: i64| {
+            i64::try_from(min).is_ok_and(|min| min >= lo)
+                && i64::try_from(max).is_ok_and(|max| max <= hi)
+        };
+        if fits(i8::MIN.into(), i8::MAX.into()) {
+            Self::I8
+        } else if fits(i16::MIN.into(), i16::MAX.into()) {
+            Self::I16
+        } else if fits(i32::MIN.into(), i32::MAX.into()) {
+            Self::I32
+        } else {
+            Self::I64
+        }
+    }
+
+    /// Finds the narrowest unsigned integer type that holds a range of values
+    /// starting at 0.
+    ///
+    /// # Parameters
+    /// * `max` - The largest value.
+    ///
+    /// # Returns
+    /// The integer type.
+    #[must_use]
+    pub fn unsigned(max: isize) -> Self {
+        let fits = |hi: u64| u64::try_from(max).is_ok_and(|max| max <= hi);
+        if fits(u8::MAX.into()) {
+            Self::U8
+        } else if fits(u16::MAX.into()) {
+            Self::U16
+        } else if fits(u32::MAX.into()) {
+            Self::U32
+        } else {
+            Self::U64
+        }
+    }
+
+    /// Gets the name of the type, as used by the emitted code.
+    ///
+    /// # Returns
+    /// The name of the type, such as `i8`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+        }
+    }
+
+    /// Gets the name of the type in C, from `stdint.h`.
+    ///
+    /// # Returns
+    /// The name of the type, such as `int8_t`.
+    #[must_use]
+    pub fn c_name(self) -> &'static str {
+        match self {
+            Self::I8 => "int8_t",
+            Self::I16 => "int16_t",
+            Self::I32 => "int32_t",
+            Self::I64 => "int64_t",
+            Self::U8 => "uint8_t",
+            Self::U16 => "uint16_t",
+            Self::U32 => "uint32_t",
+            Self::U64 => "uint64_t",
+        }
+    }
+
+    /// Gets the size of a value of the type.
+    ///
+    /// # Returns
+    /// The number of bytes in a value.
+    #[must_use]
+    pub fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 => 4,
+            Self::I64 | Self::U64 => 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 
 /// The row lookup table (RLT) is used to find the starting index of a row
 /// from the two dimensional array that was packed into a one dimensional array.
 pub struct Rlt {
     /// The row lookup table.
-    /// The index of the table is the row number.
+    /// The index of the table is the row number, or the position of the row
+    /// number in `rows` when the table is sparse.
     /// The value of the table is the starting index of the row in the one dimensional array.
     table: Vec<isize>,
 
     /// This is not the number of entries in the table,
     /// but the number of words in the word list used to create the table.
     num_words: usize,
+
+    /// The smallest and largest column index used by each row of the two
+    /// dimensional array. Only recorded when row bounds are requested. Rows
+    /// without any columns hold the empty range `(1, 0)`.
+    col_bounds: Option<Vec<(usize, usize)>>,
+
+    /// The factor the column index of each row is multiplied by before the
+    /// row's value is added. Only recorded by the CHD backend, the column index
+    /// is used as is otherwise.
+    multipliers: Option<Vec<usize>>,
+
+    /// The hash of the cells of the two dimensional array. Only set by the CHM
+    /// backend, which replaces the row offsets.
+    chm: Option<Chm>,
+
+    /// Whether every value has been reduced into `0..num_words`, see
+    /// `into_normalized`.
+    normalized: bool,
+
+    /// The row numbers kept by a sparse table, in increasing order, see
+    /// `into_sparse`. None when the table holds every row.
+    rows: Option<Vec<usize>>,
+
+    /// The table compressed into two levels for emitting, see
+    /// `into_two_level`.
+    two_level: Option<TwoLevelRlt>,
+
+    /// The value added to every hash value, 0 for 0 based hash values, see
+    /// `with_hash_base`.
+    hash_base: usize,
+
+    /// Whether sums are reduced to hash values with a mask instead of a
+    /// modulo, see `with_mask`.
+    masked: bool,
 }
 
 impl Rlt {
-    /// Creates a new row lookup table.
+    /// Creates a row lookup table from its values.
+    ///
+    /// The table cannot be changed once it is created. Column bounds and
+    /// multipliers are added with `with_col_bounds` and `with_multipliers`.
     ///
     /// # Parameters
-    /// * `size` - The number of entries in the table.
-    pub fn new(size: usize) -> Self {
+    /// * `values` - The starting index of each row, indexed by row index.
+    /// * `num_words` - The number of words in the word list used to create the
+    ///   table, the number of hash values.
+    #[must_use]
+    pub fn with_values(values: Vec<isize>, num_words: usize) -> Self {
         Self {
-            table: vec![0; size],
-            num_words: 0,
+            table: values,
+            num_words,
+            col_bounds: None,
+            multipliers: None,
+            chm: None,
+            normalized: false,
+            rows: None,
+            two_level: None,
+            hash_base: 0,
+            masked: false,
+        }
+    }
+
+    /// Finds where the values of a row are kept.
+    ///
+    /// # Parameters
+    /// * `index` - The index of the row.
+    ///
+    /// # Returns
+    /// The position of the row in the table, or None if a sparse table does not
+    /// keep the row. The index itself when the table is not sparse.
+    fn slot(&self, index: usize) -> Option<usize> {
+        match &self.rows {
+            Some(rows) => rows.binary_search(&index).ok(),
+            None => Some(index),
         }
     }
 
@@ -32,40 +228,481 @@ impl Rlt {
     ///
     /// # Returns
     /// The value at the specified index.
-    /// If the index is out of range, None is returned.
+    /// If the index is out of range, or a sparse table does not keep the row,
+    /// None is returned.
+    #[must_use]
     pub fn get(&self, index: usize) -> Option<&isize> {
-        self.table.get(index)
+        self.table.get(self.slot(index)?)
     }
 
-    /// Sets the value at the specified index.
+    /// Finds which entry of the emitted `row_lookup_table` holds the value of a
+    /// row. The entry is found with `row_keys` when the table is sparse, and
+    /// with `row_block` when it is compressed into two levels.
     ///
     /// # Parameters
-    /// * `index` - The index of the value to set.
-    /// * `value` - The value to set.
-    pub fn insert(&mut self, index: usize, value: isize) {
-        self.table[index] = value;
+    /// * `index` - The index of the row.
+    ///
+    /// # Returns
+    /// The position of the row's value in the emitted table, or None if the
+    /// index is out of range or a sparse table does not keep the row.
+    #[must_use]
+    pub fn table_index(&self, index: usize) -> Option<usize> {
+        let slot = self.slot(index).filter(|&slot| slot < self.table.len())?;
+        match &self.two_level {
+            Some(two_level) => two_level.position(slot),
+            None => Some(slot),
+        }
+    }
+
+    /// Gets all the values of the table.
+    ///
+    /// # Returns
+    /// The starting index of each row, in row order. Only the rows kept are
+    /// included when the table is sparse, see `get_rows`.
+    #[must_use]
+    pub fn get_values(&self) -> &[isize] {
+        &self.table
+    }
+
+    /// Gets the table as a slice, for code generation or serialization.
+    ///
+    /// # Returns
+    /// The starting index of each row, indexed by row index.
+    #[must_use]
+    pub fn as_slice(&self) -> &[isize] {
+        &self.table
+    }
+
+    /// Gets the narrowest integer type that holds every value of the table.
+    ///
+    /// The type is unsigned once the table is normalized, see `into_normalized`.
+    ///
+    /// # Returns
+    /// The integer type of the values.
+    #[must_use]
+    pub fn element_type(&self) -> RltElementType {
+        let max = self.table.iter().copied().max().unwrap_or(0);
+        if self.normalized {
+            RltElementType::unsigned(max)
+        } else {
+            RltElementType::signed(self.table.iter().copied().min().unwrap_or(0), max)
+        }
+    }
+
+    /// Serializes the values of the table, each as a little endian integer of
+    /// the type given by `element_type`.
+    ///
+    /// # Returns
+    /// The values as bytes, `len() * element_type().size()` of them.
+    #[must_use]
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let size = self.element_type().size();
+        let mut bytes = Vec::with_capacity(self.table.len() * size);
+        for value in &self.table {
+            // Two's complement truncation keeps the value, since it fits the type.
+            bytes.extend_from_slice(&i64::try_from(*value).unwrap_or(0).to_le_bytes()[..size]);
+        }
+        bytes
+    }
+
+    /// Iterates over the values of the table.
+    ///
+    /// # Returns
+    /// An iterator over the starting index of each row, in row order.
+    pub fn iter(&self) -> Iter<'_, isize> {
+        self.table.iter()
+    }
+
+    /// Gets the number of entries in the table.
+    ///
+    /// # Returns
+    /// The number of rows kept, not the number of words, see `get_num_entries`.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns true if the table has no entries.
+    ///
+    /// # Returns
+    /// True if the table has no rows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Drops every row but the given ones, so that the table only grows with
+    /// the number of rows used rather than with the range of row indices.
+    ///
+    /// The rows kept are listed in increasing order by `get_rows`, and the
+    /// values, column bounds and multipliers are kept in the same order. A row
+    /// is then found by a binary search of the rows kept.
+    ///
+    /// # Parameters
+    /// * `rows` - The indices of the rows to keep. Indices out of range are
+    ///   ignored.
+    ///
+    /// # Returns
+    /// The sparse table, or the table unchanged if it is already sparse.
+    #[must_use]
+    pub fn into_sparse(mut self, rows: impl IntoIterator<Item = usize>) -> Self {
+        if self.rows.is_some() {
+            return self;
+        }
+        let mut rows: Vec<usize> = rows
+            .into_iter()
+            .filter(|row| *row < self.table.len())
+            .collect();
+        rows.sort_unstable();
+        rows.dedup();
+        self.table = rows.iter().map(|row| self.table[*row]).collect();
+        if let Some(col_bounds) = &mut self.col_bounds {
+            *col_bounds = rows.iter().map(|row| col_bounds[*row]).collect();
+        }
+        if let Some(multipliers) = &mut self.multipliers {
+            *multipliers = rows.iter().map(|row| multipliers[*row]).collect();
+        }
+        self.rows = Some(rows);
+        self
+    }
+
+    /// Compresses the table into two levels, which the emitted hash reads
+    /// instead of the table, see `TwoLevelRlt`.
+    ///
+    /// # Returns
+    /// The table along with its compressed form, or the table unchanged if it
+    /// is sparse, since a sparse table has no runs of unused rows left to share.
+    #[must_use]
+    pub fn into_two_level(mut self) -> Self {
+        if self.rows.is_none() {
+            self.two_level = Some(TwoLevelRlt::new(&self.table));
+        }
+        self
+    }
+
+    /// Gets the table compressed into two levels.
+    ///
+    /// # Returns
+    /// The compressed table, or None if the table was not compressed.
+    #[must_use]
+    pub fn get_two_level(&self) -> Option<&TwoLevelRlt> {
+        self.two_level.as_ref()
+    }
+
+    /// Gets the rows kept by a sparse table.
+    ///
+    /// # Returns
+    /// The indices of the rows kept, in increasing order, or None if the table
+    /// is not sparse.
+    #[must_use]
+    pub fn get_rows(&self) -> Option<&[usize]> {
+        self.rows.as_deref()
+    }
+
+    /// Gets the rows kept by a sparse table as a string.
+    ///
+    /// # Returns
+    /// The indices of the rows kept as a string, or None if the table is not
+    /// sparse.
+    #[must_use]
+    pub fn get_rows_as_text(&self) -> Option<String> {
+        self.rows.as_ref().map(|rows| {
+            rows.iter()
+                .map(|x| format!("{x}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+
+    /// Reduces every value modulo the number of words, so that no value is
+    /// negative.
+    ///
+    /// A row's value plus any of its column indices is never negative, so it
+    /// gives the same hash value modulo the number of words as the reduced
+    /// value does. The reduced table fits unsigned array types and lets the
+    /// hash be computed with unsigned arithmetic.
+    ///
+    /// # Returns
+    /// The normalized table, or the table unchanged if it has no words.
+    #[must_use]
+    pub fn into_normalized(mut self) -> Self {
+        let Ok(num_words) = isize::try_from(self.num_words) else {
+            return self;
+        };
+        if num_words == 0 {
+            return self;
+        }
+        for value in &mut self.table {
+            *value = value.rem_euclid(num_words);
+        }
+        self.normalized = true;
+        if self.two_level.is_some() {
+            self = self.into_two_level();
+        }
+        self
+    }
+
+    /// Gets whether the values have been normalized.
+    ///
+    /// # Returns
+    /// True if every value is in `0..num_words`, see `into_normalized`.
+    #[must_use]
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
     }
 
     /// Gets the number of words in the word list used to create the table.
     ///
     /// # Returns
     /// The number of words in the word list used to create the table.
+    #[must_use]
     pub fn get_num_entries(&self) -> usize {
         self.num_words
     }
 
-    /// Sets the number of words in the word list used to create the table.
+    /// Sets the value added to every hash value, so that the hash values run
+    /// from `hash_base` to `hash_base + get_num_entries() - 1`. A base of 1
+    /// gives 1 based hash values, for tables indexed from 1. Generation
+    /// rejects a base above `MAX_HASH_BASE`, as should callers building a
+    /// table from their own values.
+    ///
+    /// # Parameters
+    /// * `hash_base` - The smallest hash value.
+    ///
+    /// # Returns
+    /// The table with the base set.
+    #[must_use]
+    pub fn with_hash_base(mut self, hash_base: usize) -> Self {
+        self.hash_base = hash_base;
+        self
+    }
+
+    /// Gets the value added to every hash value.
+    ///
+    /// # Returns
+    /// The smallest hash value, 0 unless set with `with_hash_base`.
+    #[must_use]
+    pub fn get_hash_base(&self) -> usize {
+        self.hash_base
+    }
+
+    /// Reduces sums to hash values with a mask of their low bits instead of a
+    /// modulo, which is cheaper on targets without a fast divide. Both give
+    /// the same hash values when the number of hash values is a power of two,
+    /// see `Options::power_of_two_table`.
+    ///
+    /// # Returns
+    /// The table reducing with a mask, or the table unchanged if the number of
+    /// hash values is not a power of two.
+    #[must_use]
+    pub fn with_mask(mut self) -> Self {
+        self.masked = self.num_words.is_power_of_two();
+        self
+    }
+
+    /// Gets the mask sums are reduced with.
+    ///
+    /// # Returns
+    /// The number of hash values minus 1, or None if sums are reduced with a
+    /// modulo.
+    #[must_use]
+    pub fn get_mask(&self) -> Option<usize> {
+        self.masked.then(|| self.num_words - 1)
+    }
+
+    /// Reduces a sum to a hash value, before the hash base is added.
+    ///
+    /// # Parameters
+    /// * `sum` - The row's value plus the scaled column index.
+    ///
+    /// # Returns
+    /// The sum masked with `get_mask`, or modulo the number of hash values.
+    /// A table without hash values reduces every sum to 0.
+    #[must_use]
+    pub fn reduce(&self, sum: usize) -> usize {
+        match self.get_mask() {
+            Some(mask) => sum & mask,
+            None => sum % self.num_words.max(1),
+        }
+    }
+
+    /// Gets the reduction of a sum to a hash value as text.
+    ///
+    /// # Returns
+    /// The operator and operand, such as `% 9` or `& 7`.
+    #[must_use]
+    pub fn get_reduce_as_text(&self) -> String {
+        match self.get_mask() {
+            Some(mask) => format!("& {mask}"),
+            None => format!("% {}", self.num_words),
+        }
+    }
+
+    /// Gets the column bounds of the row at the specified index.
+    ///
+    /// # Parameters
+    /// * `index` - The index of the row.
+    ///
+    /// # Returns
+    /// The smallest and largest column index used by the row.
+    /// If row bounds were not recorded or the index is out of range, None is returned.
+    #[must_use]
+    pub fn get_col_bounds(&self, index: usize) -> Option<(usize, usize)> {
+        self.col_bounds.as_ref()?.get(self.slot(index)?).copied()
+    }
+
+    /// Adds the column bounds of each row, which enables row bounds for the
+    /// table.
+    ///
+    /// # Parameters
+    /// * `col_bounds` - The smallest and largest column index used by each row,
+    ///   indexed like the values. Rows without any columns hold the empty range
+    ///   `(1, 0)`.
+    ///
+    /// # Returns
+    /// The table with row bounds.
+    #[must_use]
+    pub fn with_col_bounds(mut self, col_bounds: Vec<(usize, usize)>) -> Self {
+        self.col_bounds = Some(col_bounds);
+        self
+    }
+
+    /// Adds the multiplier of each row, which enables multipliers for the
+    /// table.
+    ///
+    /// # Parameters
+    /// * `multipliers` - The factor the column index of each row is multiplied
+    ///   by, indexed like the values.
+    ///
+    /// # Returns
+    /// The table with multipliers.
+    #[must_use]
+    pub fn with_multipliers(mut self, multipliers: Vec<usize>) -> Self {
+        self.multipliers = Some(multipliers);
+        self
+    }
+
+    /// Scales a column index by the multiplier of its row.
     ///
     /// # Parameters
-    /// * `num_words` - The number of words in the word list used to create the table.
-    pub fn set_num_entries(&mut self, num_words: usize) {
-        self.num_words = num_words;
+    /// * `index` - The index of the row.
+    /// * `col_index` - The column index.
+    ///
+    /// # Returns
+    /// The column index times the multiplier of the row, reduced modulo the
+    /// number of words. The column index unchanged if multipliers were not
+    /// recorded or the index is out of range.
+    #[must_use]
+    pub fn scale(&self, index: usize, col_index: usize) -> usize {
+        match self
+            .multipliers
+            .as_ref()
+            .zip(self.slot(index))
+            .and_then(|(m, i)| m.get(i))
+        {
+            Some(multiplier) => col_index % self.num_words.max(1) * multiplier,
+            None => col_index,
+        }
+    }
+
+    /// Gets the multiplier of a row.
+    ///
+    /// # Parameters
+    /// * `index` - The index of the row.
+    ///
+    /// # Returns
+    /// The multiplier of the row, or None if multipliers were not recorded or
+    /// the index is out of range.
+    #[must_use]
+    pub fn get_multiplier(&self, index: usize) -> Option<usize> {
+        self.multipliers.as_ref()?.get(self.slot(index)?).copied()
+    }
+
+    /// Gets the multiplier of each row.
+    ///
+    /// # Returns
+    /// The multipliers, indexed like the values, or None if multipliers were
+    /// not recorded.
+    #[must_use]
+    pub fn get_multipliers(&self) -> Option<&[usize]> {
+        self.multipliers.as_deref()
+    }
+
+    /// Gets the multiplier of each row as a string.
+    ///
+    /// # Returns
+    /// The multipliers as a string, or None if multipliers were not recorded.
+    #[must_use]
+    pub fn get_multipliers_as_text(&self) -> Option<String> {
+        self.multipliers.as_ref().map(|multipliers| {
+            multipliers
+                .iter()
+                .map(|x| format!("{x}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+
+    /// Adds the CHM hash replacing the row offsets.
+    ///
+    /// # Parameters
+    /// * `chm` - The hash of the cells of the two dimensional array.
+    ///
+    /// # Returns
+    /// The table hashing with `chm`.
+    pub(crate) fn with_chm(mut self, chm: Chm) -> Self {
+        self.chm = Some(chm);
+        self
+    }
+
+    /// Gets the CHM hash replacing the row offsets.
+    ///
+    /// # Returns
+    /// The hash of the cells, or None if the row offsets are used.
+    pub(crate) fn get_chm(&self) -> Option<&Chm> {
+        self.chm.as_ref()
+    }
+
+    /// Gets the smallest column index of each row as a string.
+    ///
+    /// # Returns
+    /// The smallest column indices as a string, or None if row bounds were not recorded.
+    #[must_use]
+    pub fn get_min_cols_as_text(&self) -> Option<String> {
+        self.col_bounds_as_text(|(min_col, _)| *min_col)
+    }
+
+    /// Gets the largest column index of each row as a string.
+    ///
+    /// # Returns
+    /// The largest column indices as a string, or None if row bounds were not recorded.
+    #[must_use]
+    pub fn get_max_cols_as_text(&self) -> Option<String> {
+        self.col_bounds_as_text(|(_, max_col)| *max_col)
+    }
+
+    /// Formats one side of the column bounds as a string.
+    ///
+    /// # Parameters
+    /// * `side` - Selects the bound to format.
+    ///
+    /// # Returns
+    /// The selected bounds as a string, or None if row bounds were not recorded.
+    fn col_bounds_as_text(&self, side: impl Fn(&(usize, usize)) -> usize) -> Option<String> {
+        self.col_bounds.as_ref().map(|col_bounds| {
+            col_bounds
+                .iter()
+                .map(|x| format!("{}", side(x)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
     }
 
     /// Gets the table as a string.
     ///
     /// # Returns
     /// The row lookup table as a string.
+    #[must_use]
     pub fn get_as_text(&self) -> String {
         self.table
             .iter()
@@ -75,18 +712,22 @@ impl Rlt {
     }
 }
 
+impl<'a> IntoIterator for &'a Rlt {
+    type Item = &'a isize;
+    type IntoIter = Iter<'a, isize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn rlt_unit_test() {
-        let mut rlt = Rlt::new(5);
-        rlt.insert(0, 1);
-        rlt.insert(1, 2);
-        rlt.insert(2, 3);
-        rlt.insert(3, 4);
-        rlt.insert(4, 5);
+        let rlt = Rlt::with_values(vec![1, 2, 3, 4, 5], 10);
 
         assert_eq!(rlt.get(0), Some(&1));
         assert_eq!(rlt.get(1), Some(&2));
@@ -98,11 +739,122 @@ mod tests {
         assert_eq!(rlt.get(7), None);
         assert_eq!(rlt.get(8), None);
         assert_eq!(rlt.get(9), None);
-
-        assert_eq!(rlt.get_num_entries(), 0);
-        rlt.set_num_entries(10);
+        assert_eq!(rlt.table_index(4), Some(4));
+        assert_eq!(rlt.table_index(5), None);
+        assert_eq!(rlt.get_values(), &[1, 2, 3, 4, 5]);
+        assert_eq!(rlt.as_slice(), &[1, 2, 3, 4, 5]);
+        assert_eq!(rlt.iter().sum::<isize>(), 15);
+        assert_eq!((&rlt).into_iter().max(), Some(&5));
+        assert_eq!(rlt.len(), 5);
+        assert!(!rlt.is_empty());
+        assert!(Rlt::with_values(Vec::new(), 0).is_empty());
         assert_eq!(rlt.get_num_entries(), 10);
-
         assert_eq!(rlt.get_as_text(), "1, 2, 3, 4, 5");
+
+        assert_eq!(rlt.get_col_bounds(0), None);
+        assert_eq!(rlt.get_min_cols_as_text(), None);
+        assert_eq!(rlt.scale(1, 13), 13);
+        assert_eq!(rlt.get_multipliers_as_text(), None);
+
+        let rlt = rlt
+            .with_col_bounds(vec![(1, 0), (3, 7), (1, 0), (2, 2), (1, 0)])
+            .with_multipliers(vec![1, 3, 1, 1, 1]);
+        assert_eq!(rlt.get_col_bounds(0), Some((1, 0)));
+        assert_eq!(rlt.get_col_bounds(1), Some((3, 7)));
+        assert_eq!(rlt.get_col_bounds(5), None);
+        assert_eq!(rlt.get_min_cols_as_text().unwrap(), "1, 3, 1, 2, 1");
+        assert_eq!(rlt.get_max_cols_as_text().unwrap(), "0, 7, 0, 2, 0");
+        assert_eq!(rlt.scale(0, 13), 3);
+        assert_eq!(rlt.scale(1, 13), 9);
+        assert_eq!(rlt.scale(5, 13), 13);
+        assert_eq!(rlt.get_multipliers_as_text().unwrap(), "1, 3, 1, 1, 1");
+
+        let rlt = Rlt::with_values(vec![-2, 7, 0], 0).into_normalized();
+        assert!(!rlt.is_normalized());
+        let rlt = Rlt::with_values(vec![-2, 7, 0], 5).into_normalized();
+        assert!(rlt.is_normalized());
+        assert_eq!(rlt.as_slice(), &[3, 2, 0]);
+        assert_eq!(rlt.element_type(), RltElementType::U8);
+        assert_eq!(rlt.to_le_bytes(), [3, 2, 0]);
+
+        let rlt = Rlt::with_values(vec![-2, 0], 2);
+        assert_eq!(rlt.element_type(), RltElementType::I8);
+        assert_eq!(rlt.to_le_bytes(), [0xfe, 0]);
+        let rlt = Rlt::with_values(vec![-2, 300], 2);
+        assert_eq!(rlt.element_type(), RltElementType::I16);
+        assert_eq!(rlt.element_type().size(), 2);
+        assert_eq!(rlt.to_le_bytes(), [0xfe, 0xff, 0x2c, 0x01]);
+
+        assert_eq!(RltElementType::signed(-128, 127), RltElementType::I8);
+        assert_eq!(RltElementType::signed(-129, 0), RltElementType::I16);
+        assert_eq!(RltElementType::signed(0, 40_000).name(), "i32");
+        assert_eq!(RltElementType::signed(0, 3_000_000_000).name(), "i64");
+        assert_eq!(RltElementType::unsigned(255).name(), "u8");
+        assert_eq!(RltElementType::unsigned(256).name(), "u16");
+        assert_eq!(RltElementType::unsigned(256).c_name(), "uint16_t");
+        assert_eq!(RltElementType::signed(-1, 0).c_name(), "int8_t");
+        assert_eq!(RltElementType::unsigned(70_000).name(), "u32");
+
+        let mut values = vec![0; 676];
+        values[3] = -1;
+        values[600] = 4;
+        let mut col_bounds = vec![(1, 0); 676];
+        col_bounds[600] = (2, 9);
+        let mut multipliers = vec![1; 676];
+        multipliers[3] = 5;
+        let rlt = Rlt::with_values(values, 8)
+            .with_col_bounds(col_bounds)
+            .with_multipliers(multipliers);
+        assert_eq!(rlt.get_rows(), None);
+        assert_eq!(rlt.get_rows_as_text(), None);
+        let rlt = rlt.into_sparse([600, 3, 3, 1000]);
+        assert_eq!(rlt.len(), 2);
+        assert_eq!(rlt.get_rows(), Some(&[3, 600][..]));
+        assert_eq!(rlt.get_rows_as_text().unwrap(), "3, 600");
+        assert_eq!(rlt.as_slice(), &[-1, 4]);
+        assert_eq!(rlt.get(600), Some(&4));
+        assert_eq!(rlt.get(4), None);
+        assert_eq!(rlt.table_index(600), Some(1));
+        assert_eq!(rlt.table_index(4), None);
+        assert_eq!(rlt.get_col_bounds(600), Some((2, 9)));
+        assert_eq!(rlt.get_col_bounds(4), None);
+        assert_eq!(rlt.get_min_cols_as_text().unwrap(), "1, 2");
+        assert_eq!(rlt.get_multipliers_as_text().unwrap(), "5, 1");
+        assert_eq!(rlt.get_multiplier(3), Some(5));
+        assert_eq!(rlt.get_multiplier(4), None);
+        assert_eq!(rlt.into_two_level().get_two_level(), None);
+
+        let mut values = vec![0; 676];
+        values[600] = -4;
+        let rlt = Rlt::with_values(values, 8).into_two_level();
+        let two_level = rlt.get_two_level().unwrap();
+        assert!(two_level.size() < 100);
+        assert_eq!(two_level.get(600), Some(&-4));
+        assert_eq!(two_level.get_blocks()[rlt.table_index(600).unwrap()], -4);
+        assert_eq!(rlt.table_index(676), None);
+        // Normalizing keeps the compressed table in step.
+        let rlt = rlt.into_normalized();
+        assert_eq!(rlt.get_two_level().unwrap().get(600), Some(&4));
+    }
+
+    #[test]
+    fn rlt_reduce_unit_test() {
+        let rlt = Rlt::with_values(vec![1, 2, 3, 4, 5], 10);
+        assert_eq!(rlt.get_hash_base(), 0);
+        assert_eq!(rlt.clone().with_hash_base(1).get_hash_base(), 1);
+        assert_eq!(rlt.clone().with_mask().get_mask(), None);
+        assert_eq!(rlt.reduce(13), 3);
+        assert_eq!(rlt.get_reduce_as_text(), "% 10");
+
+        let rlt = Rlt::with_values(vec![1, 2, 3, 4, 5], 8);
+        assert_eq!(rlt.get_mask(), None);
+        let rlt = rlt.with_mask();
+        assert_eq!(rlt.get_mask(), Some(7));
+        assert_eq!(rlt.reduce(13), 5);
+        assert_eq!(rlt.get_reduce_as_text(), "& 7");
+
+        let rlt = Rlt::with_values(vec![1], 0);
+        assert_eq!(rlt.clone().with_mask().get_mask(), None);
+        assert_eq!(rlt.reduce(5), 0);
     }
 }
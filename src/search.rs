@@ -0,0 +1,748 @@
+//! Searches for hash parameters that work for a given word list.
+
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    analyze_collisions, assemble, generate_hash, generate_hash_with_options, pack_words, Collision,
+    ElcAlgorithm, Error, HashAlgorithm, HashData, Kind, Options, Position, PositionAlgorithm,
+    SeededAlgorithm, WordList,
+};
+
+/// The result of searching for a working `elc` value.
+#[derive(Debug)]
+pub struct ElcSearchResult {
+    /// The smallest `elc` value that produced a minimal perfect hash, if any.
+    pub elc: Option<usize>,
+
+    /// The hash generated with `elc`.
+    pub hash_data: Option<HashData>,
+
+    /// The reason each smaller (or every, if none worked) candidate failed, in
+    /// ascending `elc` order.
+    pub failures: Vec<(usize, Error)>,
+}
+
+/// Tries a range of `ElcAlgorithm` configurations.
+///
+/// Every candidate `elc` value is tried with an alphabet inferred from the
+/// word list, see `ElcAlgorithm::from_word_list`. With the `parallel` feature
+/// the candidates are packed concurrently, otherwise they are tried in
+/// ascending order until one works. Either way the smallest candidate that
+/// produces a valid minimal perfect hash wins.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `elcs` - The `elc` values to try.
+///
+/// # Returns
+/// The smallest working `elc` value with its hash, and the failure reasons of
+/// the smaller candidates.
+#[must_use]
+pub fn best_elc(word_list: &WordList, elcs: RangeInclusive<usize>) -> ElcSearchResult {
+    let options = Options::default();
+    let attempt = |elc: usize| {
+        let hash_algorithm = ElcAlgorithm::from_word_list(elc, word_list);
+        let packed = word_list
+            .is_valid_under(&options.get_validation_policy())
+            .and_then(|()| pack_words(word_list, &hash_algorithm, &options));
+        (elc, hash_algorithm, packed)
+    };
+    // The hash data is not thread safe, so only the packing runs concurrently.
+    #[cfg(feature = "parallel")]
+    let attempts: Vec<_> = elcs.into_par_iter().map(attempt).collect();
+    #[cfg(not(feature = "parallel"))]
+    let attempts = elcs.map(attempt);
+
+    let mut rv = ElcSearchResult {
+        elc: None,
+        hash_data: None,
+        failures: Vec::new(),
+    };
+    for (elc, hash_algorithm, packed) in attempts {
+        match packed {
+            Ok(one_d_packed_array) => {
+                rv.elc = Some(elc);
+                rv.hash_data = Some(assemble(
+                    word_list,
+                    hash_algorithm,
+                    &options,
+                    one_d_packed_array,
+                ));
+                break;
+            }
+            Err(e) => rv.failures.push((elc, e)),
+        }
+    }
+    rv
+}
+
+/// A hash generated with automatically chosen parameters.
+#[derive(Debug)]
+pub struct AutoHash {
+    /// The generated hash.
+    pub hash_data: HashData,
+
+    /// The `elc` value that was chosen.
+    pub elc: usize,
+
+    /// The `num_vals` value that was chosen.
+    pub num_vals: usize,
+}
+
+/// Generates a msmp hash function, choosing the `elc` value automatically.
+///
+/// The word list is analyzed to find the smallest `elc` for which the first
+/// and last `elc` characters distinguish every word, since anything smaller
+/// is certain to collide in the 2D array. From there `elc` is escalated until
+/// packing succeeds or the shortest word is exhausted.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+///
+/// # Returns
+/// The hash along with the parameters that were chosen.
+///
+/// # Errors
+/// Will return `Err` if `word_list` is invalid, or with the error of the last
+/// attempt if no `elc` value produces a hash.
+pub fn generate_hash_auto(word_list: &WordList) -> Result<AutoHash, Error> {
+    generate_hash_auto_with_options(word_list, &Options::default())
+}
+
+/// Generates a msmp hash function using the given options, choosing the `elc`
+/// value automatically.
+///
+/// See `generate_hash_auto`. When `options` asks for the alphabet to be
+/// inferred, `num_vals` is chosen from the characters used by the word list.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `options` - Options controlling the generation.
+///
+/// # Returns
+/// The hash along with the parameters that were chosen.
+///
+/// # Errors
+/// Will return `Err` if `word_list` is invalid, or with the error of the last
+/// attempt if no `elc` value produces a hash.
+pub fn generate_hash_auto_with_options(
+    word_list: &WordList,
+    options: &Options,
+) -> Result<AutoHash, Error> {
+    let (hash_algorithm, hash_data) = auto_elc_algorithm(word_list, options)?;
+    Ok(AutoHash {
+        hash_data,
+        elc: hash_algorithm.get_elc(),
+        num_vals: hash_algorithm.get_num_vals(),
+    })
+}
+
+/// Chooses the `elc` value automatically, see `generate_hash_auto_with_options`.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `options` - Options controlling the generation.
+///
+/// # Returns
+/// The chosen algorithm along with the hash generated with it.
+///
+/// # Errors
+/// Will return `Err` if `word_list` is invalid, or with the error of the last
+/// attempt if no `elc` value produces a hash.
+pub(crate) fn auto_elc_algorithm(
+    word_list: &WordList,
+    options: &Options,
+) -> Result<(ElcAlgorithm, HashData), Error> {
+    word_list.is_valid_under(&options.get_validation_policy())?;
+
+    let max_elc = word_list
+        .iter()
+        .map(|word| word.chars().count())
+        .min()
+        .unwrap_or(0);
+    let min_elc = (1..=max_elc)
+        .find(|elc| is_distinguishing(word_list, *elc))
+        .ok_or_else(|| {
+            Error::new(Kind::HashError(format!(
+                "No elc up to {max_elc} distinguishes all words by their ends."
+            )))
+        })?;
+
+    let mut last_error = None;
+    for elc in min_elc..=max_elc {
+        let hash_algorithm = if options.get_infer_alphabet() {
+            ElcAlgorithm::from_word_list(elc, word_list)
+        } else {
+            match ElcAlgorithm::with_charset(elc, options.get_charset()) {
+                Ok(hash_algorithm) => hash_algorithm,
+                // Character sets too large to be an alphabet are inferred.
+                Err(_) => ElcAlgorithm::from_word_list(elc, word_list),
+            }
+        };
+        match generate_hash_with_options(word_list, hash_algorithm.clone(), options) {
+            Ok(hash_data) => return Ok((hash_algorithm, hash_data)),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| Error::new(Kind::HashError("No elc tried.".to_string()))))
+}
+
+/// The result of trying a cascade of hash algorithms.
+#[derive(Debug)]
+pub struct CascadeResult {
+    /// The index of the first algorithm that produced a hash, if any.
+    pub algorithm_index: Option<usize>,
+
+    /// The hash generated with that algorithm.
+    pub hash_data: Option<HashData>,
+
+    /// The index and failure reason of each algorithm that was tried without
+    /// success, in the order they were tried.
+    pub failures: Vec<(usize, Error)>,
+}
+
+/// Generates a msmp hash function, trying each algorithm in turn until one works.
+///
+/// A collision in the 2D array or a failure to pack means a new algorithm
+/// needs to be tried; this automates doing so.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `algorithms` - The algorithms to try, in order.
+///
+/// # Returns
+/// The first successful hash, along with the failure reason of every
+/// algorithm tried before it.
+#[must_use]
+pub fn generate_hash_cascade(
+    word_list: &WordList,
+    algorithms: Vec<Box<dyn HashAlgorithm>>,
+) -> CascadeResult {
+    let mut rv = CascadeResult {
+        algorithm_index: None,
+        hash_data: None,
+        failures: Vec::new(),
+    };
+    for (i, hash_algorithm) in algorithms.into_iter().enumerate() {
+        match generate_hash(word_list, hash_algorithm) {
+            Ok(hash_data) => {
+                rv.algorithm_index = Some(i);
+                rv.hash_data = Some(hash_data);
+                break;
+            }
+            Err(e) => rv.failures.push((i, e)),
+        }
+    }
+    rv
+}
+
+/// A hash generated with a searched seed.
+#[derive(Debug)]
+pub struct SeededHash {
+    /// The generated hash.
+    pub hash_data: HashData,
+
+    /// The algorithm with the seed that was found.
+    pub hash_algorithm: SeededAlgorithm,
+}
+
+/// Generates a msmp hash function with a `SeededAlgorithm`, trying seeds until
+/// one produces a hash.
+///
+/// The 2D array has a row and a column per distinct word, so a collision is
+/// unlikely for any seed and the rows hold few words, which packs easily. This
+/// works on word lists the `ElcAlgorithm` cannot distinguish by their ends.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `options` - Options controlling the generation.
+/// * `max_seeds` - The number of seeds to try, starting from 0.
+///
+/// # Returns
+/// The hash along with the algorithm that produced it.
+///
+/// # Errors
+/// Will return `Err` if `word_list` is invalid, or with the error of the last
+/// attempt if no seed produces a hash.
+pub fn generate_hash_seeded<W: Borrow<str>>(
+    word_list: &WordList<W>,
+    options: &Options,
+    max_seeds: u64,
+) -> Result<SeededHash, Error> {
+    word_list.is_valid_under(&options.get_validation_policy())?;
+
+    let num_words = word_list
+        .iter()
+        .map(Borrow::borrow)
+        .collect::<BTreeSet<&str>>()
+        .len();
+    let mut last_error = None;
+    for seed in 0..max_seeds {
+        let hash_algorithm = SeededAlgorithm::new(seed, num_words, num_words);
+        match generate_hash_with_options(word_list, hash_algorithm.clone(), options) {
+            Ok(hash_data) => {
+                return Ok(SeededHash {
+                    hash_data,
+                    hash_algorithm,
+                })
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| Error::new(Kind::HashError("No seed tried.".to_string()))))
+}
+
+/// Searches for the smallest set of character positions that distinguishes
+/// every word, and builds a `PositionAlgorithm` from it.
+///
+/// Only positions every word has are considered, counted from either end.
+/// Sets are tried by size. For each size the sets of characters alone are
+/// tried first, then the sets mixed with the word length. The first half of
+/// the found positions is combined into the row index and the rest into the
+/// column index, and the alphabet is inferred from the word list.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+///
+/// # Returns
+/// An algorithm for which no two distinct words share both their row and
+/// column index.
+///
+/// # Errors
+/// Will return `Err` if `word_list` is empty, or if even all the positions and
+/// the length together do not distinguish every word.
+pub fn find_distinguishing_positions<W: Borrow<str>>(
+    word_list: &WordList<W>,
+) -> Result<PositionAlgorithm, Error> {
+    let words: Vec<Vec<char>> = word_list
+        .iter()
+        .map(Borrow::borrow)
+        .collect::<BTreeSet<&str>>()
+        .into_iter()
+        .map(|word| word.chars().collect())
+        .collect();
+    let Some(min_len) = words.iter().map(Vec::len).min() else {
+        return Err(Error::new(Kind::HashError("Empty word list.".to_string())));
+    };
+    let max_len = words.iter().map(Vec::len).max().unwrap_or(0);
+    let candidates: Vec<Position> = (0..min_len)
+        .map(Position::Start)
+        .chain((0..min_len).map(Position::End))
+        .collect();
+
+    let distinguishes = |positions: &[usize], use_length: bool| {
+        let mut seen = BTreeSet::new();
+        words.iter().all(|chars| {
+            let key: Vec<char> = positions
+                .iter()
+                .filter_map(|p| candidates[*p].char_of(chars))
+                .collect();
+            seen.insert((key, use_length.then_some(chars.len())))
+        })
+    };
+    let all: Vec<usize> = (0..candidates.len()).collect();
+    if !distinguishes(&all, true) {
+        return Err(Error::new(Kind::HashError(
+            "No character positions distinguish all words.".to_string(),
+        )));
+    }
+
+    for size in 1..=candidates.len() {
+        for use_length in [false, true] {
+            let mut positions: Vec<usize> = (0..size).collect();
+            loop {
+                if distinguishes(&positions, use_length) {
+                    let positions: Vec<Position> =
+                        positions.iter().map(|p| candidates[*p]).collect();
+                    let (h1, h2) = positions.split_at(size / 2);
+                    let hash_algorithm = PositionAlgorithm::from_word_list(
+                        h1.iter().copied(),
+                        h2.iter().copied(),
+                        word_list,
+                    );
+                    return Ok(if use_length {
+                        hash_algorithm.with_length(max_len + 1)
+                    } else {
+                        hash_algorithm
+                    });
+                }
+                if !next_combination(&mut positions, candidates.len()) {
+                    break;
+                }
+            }
+        }
+    }
+    Err(Error::new(Kind::HashError(
+        "No character positions distinguish all words.".to_string(),
+    )))
+}
+
+/// The collisions of a word list in the 2D array, with parameters that avoid
+/// them.
+#[derive(Debug)]
+pub struct CollisionDiagnosis {
+    /// Every pair of words that collide, see `analyze_collisions`.
+    pub collisions: Vec<Collision>,
+
+    /// The smallest `elc` for which the first and last `elc` characters
+    /// distinguish every word, if there are collisions and such an `elc` exists.
+    pub suggested_elc: Option<usize>,
+
+    /// An algorithm combining character positions that distinguish every word,
+    /// only searched for when no `elc` does, see `find_distinguishing_positions`.
+    pub suggested_positions: Option<PositionAlgorithm>,
+}
+
+impl CollisionDiagnosis {
+    /// Describes what to retry with.
+    ///
+    /// # Returns
+    /// A sentence suggesting the parameters to retry with.
+    #[must_use]
+    pub fn advice(&self) -> String {
+        if self.collisions.is_empty() {
+            "No collisions.".to_string()
+        } else if let Some(elc) = self.suggested_elc {
+            format!("Retry with elc={elc}.")
+        } else if let Some(positions) = &self.suggested_positions {
+            format!(
+                "Retry with a PositionAlgorithm using positions {:?} and {:?}.",
+                positions.get_h1_positions(),
+                positions.get_h2_positions()
+            )
+        } else {
+            "No elc or character positions distinguish all words, retry with \
+             generate_hash_seeded."
+                .to_string()
+        }
+    }
+}
+
+/// Finds the collisions of a word list in the 2D array, and the smallest `elc`
+/// or set of character positions that would avoid them.
+///
+/// The colliding words bound the `elc` from below: the first and last `elc`
+/// characters of each colliding pair must differ. From there the smallest `elc`
+/// that distinguishes every word is searched, up to the length of the shortest
+/// word.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `hash_algorithm` - The hash algorithm the collisions occurred with.
+///
+/// # Returns
+/// The collisions, along with the parameters to retry with.
+///
+/// # Errors
+/// Will return `Err` if the hash algorithm rejects a word.
+pub fn diagnose_collisions<W: Borrow<str>>(
+    word_list: &WordList<W>,
+    hash_algorithm: &impl HashAlgorithm,
+) -> Result<CollisionDiagnosis, Error> {
+    let collisions = analyze_collisions(word_list, hash_algorithm)?;
+    let mut rv = CollisionDiagnosis {
+        collisions,
+        suggested_elc: None,
+        suggested_positions: None,
+    };
+    if rv.collisions.is_empty() {
+        return Ok(rv);
+    }
+
+    let min_elc = rv
+        .collisions
+        .iter()
+        .map(|c| pair_elc(word_list[c.first].borrow(), word_list[c.second].borrow()))
+        .max()
+        .unwrap_or(1);
+    let max_elc = word_list
+        .iter()
+        .map(|word| word.borrow().chars().count())
+        .min()
+        .unwrap_or(0);
+    rv.suggested_elc = (min_elc..=max_elc).find(|elc| is_distinguishing(word_list, *elc));
+    if rv.suggested_elc.is_none() {
+        rv.suggested_positions = find_distinguishing_positions(word_list).ok();
+    }
+    Ok(rv)
+}
+
+/// Finds the smallest number of characters taken from each end that tells two
+/// words apart.
+///
+/// # Parameters
+/// * `a` - A word.
+/// * `b` - Another word.
+///
+/// # Returns
+/// The smallest `elc` for which the prefixes or the suffixes of length `elc`
+/// differ.
+fn pair_elc(a: &str, b: &str) -> usize {
+    let same_prefix = a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count();
+    let same_suffix = a
+        .chars()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    same_prefix.min(same_suffix) + 1
+}
+
+/// Advances to the next combination of indices, in lexicographic order.
+///
+/// # Parameters
+/// * `indices` - Increasing indices below `n`, updated in place.
+/// * `n` - The number of indices to choose from.
+///
+/// # Returns
+/// False if `indices` was the last combination.
+fn next_combination(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    let Some(i) = (0..k).rev().find(|i| indices[*i] < n - k + i) else {
+        return false;
+    };
+    indices[i] += 1;
+    for j in i + 1..k {
+        indices[j] = indices[j - 1] + 1;
+    }
+    true
+}
+
+/// Checks whether the first and last `elc` characters distinguish every word.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `elc` - The number of characters to take from each end of a word.
+///
+/// # Returns
+/// True if no two distinct words share both their prefix and suffix of length `elc`.
+fn is_distinguishing<W: Borrow<str>>(word_list: &WordList<W>, elc: usize) -> bool {
+    let words: BTreeSet<&str> = word_list.iter().map(Borrow::borrow).collect();
+    let mut seen = BTreeSet::new();
+    words.into_iter().all(|word| {
+        let prefix: String = word.chars().take(elc).collect();
+        let suffix: String = word.chars().rev().take(elc).collect();
+        seen.insert((prefix, suffix))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_unit_test() {
+        let word_list: WordList = ["ADD", "AND", "BEGIN", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+
+        let result = best_elc(&word_list, 1..=3);
+        println!("{result:?}");
+        assert_eq!(result.elc, Some(2));
+        let hash_data = result.hash_data.as_ref().unwrap();
+        // The alphabet is inferred from the ends of the words.
+        assert!(hash_data.as_string.contains("alphabet = \"ABDEIN\""));
+        let mut hash_values: Vec<usize> =
+            word_list.iter().map(|w| hash_data.as_fn.hash(w)).collect();
+        hash_values.sort_unstable();
+        assert_eq!(hash_values, vec![0, 1, 2, 3]);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].0, 1);
+        match result.failures[0].1.kind() {
+            Kind::TwoDArrayCollision { word_a, word_b, .. } => {
+                assert_eq!((word_a.as_str(), word_b.as_str()), ("ADD", "AND"));
+            }
+            _ => panic!("Unexpected error type."),
+        }
+
+        let result = best_elc(&word_list, 4..=5);
+        assert_eq!(result.elc, None);
+        assert!(result.hash_data.is_none());
+        assert_eq!(result.failures.len(), 2);
+    }
+
+    #[test]
+    fn generate_hash_seeded_unit_test() {
+        let word_list: WordList = ["ABBA", "ACCA", "ADDA", "AXA", "ABA"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+        let seeded = generate_hash_seeded(&word_list, &Options::default(), 100).unwrap();
+        assert_eq!(seeded.hash_algorithm.get_num_rows(), 5);
+        assert_eq!(seeded.hash_algorithm.get_num_cols(), 5);
+        for (i, word) in word_list.iter().enumerate() {
+            assert_eq!(seeded.hash_data.lookup(word), Some(i));
+        }
+
+        match generate_hash_seeded(&word_list, &Options::default(), 0)
+            .unwrap_err()
+            .kind()
+        {
+            Kind::HashError(s) => assert_eq!(s, "No seed tried."),
+            _ => panic!("Unexpected error type."),
+        }
+        assert!(generate_hash_seeded(&WordList::new(), &Options::default(), 100).is_err());
+    }
+
+    #[test]
+    fn generate_hash_auto_unit_test() {
+        let word_list: WordList = ["ADD", "AND", "BEGIN", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        assert!(!is_distinguishing(&word_list, 1));
+        assert!(is_distinguishing(&word_list, 2));
+
+        let auto = generate_hash_auto(&word_list).unwrap();
+        assert_eq!(auto.elc, 2);
+        assert_eq!(auto.num_vals, 26);
+        assert_eq!(auto.hash_data.as_fn.table_size(), 4);
+
+        let options = Options::new().infer_alphabet(true);
+        let auto = generate_hash_auto_with_options(&word_list, &options).unwrap();
+        assert_eq!(auto.elc, 2);
+        assert_eq!(auto.num_vals, 6);
+        assert_eq!(auto.hash_data.as_fn.table_size(), 4);
+
+        let word_list: WordList = ["AB", "ABAB"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        match generate_hash_auto(&word_list).unwrap_err().kind() {
+            Kind::HashError(s) => {
+                assert_eq!(s, "No elc up to 2 distinguishes all words by their ends.");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+    }
+
+    #[test]
+    fn generate_hash_cascade_unit_test() {
+        let word_list: WordList = ["ADD", "AND", "BEGIN", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+
+        let result = generate_hash_cascade(
+            &word_list,
+            vec![
+                Box::new(ElcAlgorithm::new(1, 26)),
+                Box::new(ElcAlgorithm::new(4, 26)),
+                Box::new(ElcAlgorithm::new(2, 26)),
+                Box::new(ElcAlgorithm::new(3, 26)),
+            ],
+        );
+        assert_eq!(result.algorithm_index, Some(2));
+        assert!(result.hash_data.is_some());
+        assert_eq!(result.failures.len(), 2);
+        assert_eq!(result.failures[0].0, 0);
+        assert_eq!(result.failures[1].0, 1);
+        match result.failures[1].1.kind() {
+            Kind::ElcAlgorithmError(_) => {}
+            _ => panic!("Unexpected error type."),
+        }
+
+        let result = generate_hash_cascade(&word_list, Vec::new());
+        assert_eq!(result.algorithm_index, None);
+        assert!(result.failures.is_empty());
+    }
+
+    #[test]
+    fn diagnose_collisions_unit_test() {
+        let word_list: WordList = ["ADD", "AND", "BEGIN", "END"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let diagnosis = diagnose_collisions(&word_list, &ElcAlgorithm::default()).unwrap();
+        println!("{diagnosis:?}");
+        assert_eq!(diagnosis.collisions.len(), 1);
+        assert_eq!(diagnosis.suggested_elc, Some(2));
+        assert!(diagnosis.suggested_positions.is_none());
+        assert_eq!(diagnosis.advice(), "Retry with elc=2.");
+
+        let diagnosis = diagnose_collisions(&word_list, &ElcAlgorithm::new(2, 26)).unwrap();
+        assert!(diagnosis.collisions.is_empty());
+        assert_eq!(diagnosis.suggested_elc, None);
+        assert_eq!(diagnosis.advice(), "No collisions.");
+
+        // The ends of AB and ABAB are the same, only their lengths differ.
+        let word_list: WordList = ["AB", "ABAB"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        assert_eq!(pair_elc("AB", "ABAB"), 3);
+        let diagnosis = diagnose_collisions(&word_list, &ElcAlgorithm::default()).unwrap();
+        assert_eq!(diagnosis.suggested_elc, None);
+        assert!(diagnosis.suggested_positions.is_some());
+        assert!(diagnosis
+            .advice()
+            .starts_with("Retry with a PositionAlgorithm using positions"));
+    }
+
+    #[test]
+    fn find_distinguishing_positions_unit_test() {
+        let word_list: WordList = ["CAT", "COT", "CUT", "CAP", "COP"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_algorithm = find_distinguishing_positions(&word_list).unwrap();
+        assert_eq!(hash_algorithm.get_h1_positions(), &[Position::Start(1)]);
+        assert_eq!(hash_algorithm.get_h2_positions(), &[Position::Start(2)]);
+        assert_eq!(hash_algorithm.get_max_len(), None);
+        assert!(generate_hash(&word_list, hash_algorithm).is_ok());
+
+        // The ends only tell these apart with the length.
+        let word_list: WordList = ["DO", "DOTO", "DOWNTO", "TO"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_algorithm = find_distinguishing_positions(&word_list).unwrap();
+        assert_eq!(hash_algorithm.get_h1_positions(), &[]);
+        assert_eq!(hash_algorithm.get_h2_positions(), &[Position::Start(0)]);
+        assert_eq!(hash_algorithm.get_max_len(), Some(7));
+        assert!(generate_hash(&word_list, hash_algorithm).is_ok());
+
+        let word_list: WordList = ["ABC", "AXC"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let hash_algorithm = find_distinguishing_positions(&word_list).unwrap();
+        assert_eq!(hash_algorithm.get_h2_positions(), &[Position::Start(1)]);
+
+        let word_list: WordList = ["ABCA", "AXCA"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        assert!(find_distinguishing_positions(&word_list).is_ok());
+        let word_list: WordList = ["AB", "ACCCA", "ACDCA"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        match find_distinguishing_positions(&word_list)
+            .unwrap_err()
+            .kind()
+        {
+            Kind::HashError(s) => {
+                assert_eq!(s, "No character positions distinguish all words.");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        assert!(find_distinguishing_positions(&WordList::new()).is_err());
+
+        let mut indices = vec![0, 1];
+        let mut count = 1;
+        while next_combination(&mut indices, 4) {
+            count += 1;
+        }
+        assert_eq!(count, 6);
+        assert_eq!(indices, [2, 3]);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::fnv_algorithm::{fnv1a, reduce, FNV_OFFSET, FNV_PRIME};
+use crate::{Error, HashAlgorithm, ParamValue};
+
+/// The value the seed of h1 is combined with to get the seed of h2.
+const H2_SEED_MIX: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// A hash algorithm built on a seeded hash of the whole word.
+///
+/// The row and column indices are FNV-1a hashes of the bytes of the word,
+/// started from a seed and finished with a xorshift, reduced modulo the
+/// number of rows and columns. Unlike the `ElcAlgorithm` every byte of the
+/// word counts, so any word list can be hashed once a seed without collisions
+/// is found, see `generate_hash_seeded`.
+#[derive(Debug, Clone)]
+pub struct SeededAlgorithm {
+    /// The seed of h1. The seed of h2 is derived from it.
+    seed: u64,
+
+    /// The number of rows the words are spread over.
+    num_rows: usize,
+
+    /// The number of columns the words of a row are spread over.
+    num_cols: usize,
+}
+
+impl SeededAlgorithm {
+    /// Creates a new SeededAlgorithm.
+    ///
+    /// # Parameters
+    /// * `seed` - The seed of the hashes.
+    /// * `num_rows` - The number of rows, at least 1.
+    /// * `num_cols` - The number of columns, at least 1.
+    #[must_use]
+    pub fn new(seed: u64, num_rows: usize, num_cols: usize) -> Self {
+        Self {
+            seed,
+            num_rows: num_rows.max(1),
+            num_cols: num_cols.max(1),
+        }
+    }
+
+    /// Gets the seed of the hashes.
+    ///
+    /// # Returns
+    /// The seed of h1.
+    #[must_use]
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Gets the number of rows the words are spread over.
+    ///
+    /// # Returns
+    /// The range of h1.
+    #[must_use]
+    pub fn get_num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Gets the number of columns the words of a row are spread over.
+    ///
+    /// # Returns
+    /// The range of h2.
+    #[must_use]
+    pub fn get_num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Hashes the bytes of a word into a range.
+    ///
+    /// # Parameters
+    /// * `seed` - The seed to start the hash from.
+    /// * `bytes` - The bytes of the word.
+    /// * `range` - The number of values the hash is reduced to.
+    ///
+    /// # Returns
+    /// A hash value below `range`.
+    fn mix(seed: u64, bytes: &[u8], range: usize) -> usize {
+        let val = fnv1a(seed ^ FNV_OFFSET, bytes);
+        reduce(val ^ (val >> 33), range)
+    }
+
+    /// A pseudo code representation of a seeded hash.
+    ///
+    /// # Parameters
+    /// * `seed` - The seed the hash starts from.
+    /// * `range` - The number of values the hash is reduced to.
+    ///
+    /// # Returns
+    /// A string representation of the hash function.
+    fn as_text(seed: u64, range: usize) -> String {
+        format!(
+            "\n        val = {start:#x}
+        for x in bytes(word_letters):
+            val = ((val ^ x) * {FNV_PRIME:#x}) & 0xffffffffffffffff
+        val = (val ^ (val >> 33)) % {range}",
+            start = seed ^ FNV_OFFSET
+        )
+    }
+}
+
+impl HashAlgorithm for SeededAlgorithm {
+    fn h1(&self, word: &str) -> Result<usize, Error> {
+        self.h1(word.as_bytes())
+    }
+
+    fn h2(&self, word: &str) -> Result<usize, Error> {
+        self.h2(word.as_bytes())
+    }
+
+    fn h1_as_text(&self) -> String {
+        <Self as HashAlgorithm<[u8]>>::h1_as_text(self)
+    }
+
+    fn h2_as_text(&self) -> String {
+        <Self as HashAlgorithm<[u8]>>::h2_as_text(self)
+    }
+
+    fn name(&self) -> &'static str {
+        <Self as HashAlgorithm<[u8]>>::name(self)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        <Self as HashAlgorithm<[u8]>>::parameters(self)
+    }
+}
+
+impl HashAlgorithm<[u8]> for SeededAlgorithm {
+    fn h1(&self, word: &[u8]) -> Result<usize, Error> {
+        Ok(Self::mix(self.seed, word, self.num_rows))
+    }
+
+    fn h2(&self, word: &[u8]) -> Result<usize, Error> {
+        Ok(Self::mix(self.seed ^ H2_SEED_MIX, word, self.num_cols))
+    }
+
+    fn h1_as_text(&self) -> String {
+        Self::as_text(self.seed, self.num_rows)
+    }
+
+    fn h2_as_text(&self) -> String {
+        Self::as_text(self.seed ^ H2_SEED_MIX, self.num_cols)
+    }
+
+    fn name(&self) -> &'static str {
+        "seeded"
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("seed", ParamValue::from(self.seed)),
+            ("num_rows", ParamValue::from(self.num_rows)),
+            ("num_cols", ParamValue::from(self.num_cols)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_algorithm_unit_test() {
+        let hash_algorithm = SeededAlgorithm::new(7, 5, 0);
+        println!("{hash_algorithm:?}");
+        assert_eq!(hash_algorithm.get_seed(), 7);
+        assert_eq!(hash_algorithm.get_num_rows(), 5);
+        assert_eq!(hash_algorithm.get_num_cols(), 1);
+
+        for word in ["", "BEGIN", "begin", "\u{e9}t\u{e9}"] {
+            assert!(hash_algorithm.h1(word).unwrap() < 5);
+            assert_eq!(hash_algorithm.h2(word).unwrap(), 0);
+            assert_eq!(
+                hash_algorithm.h1(word).unwrap(),
+                hash_algorithm.h1(word.as_bytes()).unwrap()
+            );
+        }
+
+        // The seed changes the hashes.
+        let a = SeededAlgorithm::new(1, 1 << 20, 1 << 20);
+        let b = SeededAlgorithm::new(2, 1 << 20, 1 << 20);
+        assert_ne!(
+            (a.h1("BEGIN").unwrap(), a.h2("BEGIN").unwrap()),
+            (b.h1("BEGIN").unwrap(), b.h2("BEGIN").unwrap())
+        );
+        assert_ne!(a.h1("BEGIN").unwrap(), a.h1("BEGIM").unwrap());
+
+        assert!(HashAlgorithm::<str>::h1_as_text(&hash_algorithm).ends_with("% 5"));
+        assert!(HashAlgorithm::<str>::h2_as_text(&hash_algorithm).ends_with("% 1"));
+    }
+}
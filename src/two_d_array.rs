@@ -1,16 +1,27 @@
-use crate::{Error, HashAlgorithm, Kind, WordList};
+use crate::chm::mix;
+use crate::key::compare_key;
+use crate::{
+    from_index, to_index, DuplicatePolicy, Error, HashAlgorithm, Index, Key, Kind, Options,
+    RowTieBreak, WordList,
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::borrow::Borrow;
 use std::cmp::Reverse;
 use std::collections::BTreeMap;
-use std::iter::zip;
+
+/// Rows are stored in a vector indexed by row index when the row index range is
+/// at most this many times the number of used rows.
+const DENSE_ROW_FACTOR: usize = 4;
 
 /// A 2D array that is used to store the indices of the words in the word list.
-/// The array is stored as a BTreeMap of rows. Each row is a BTreeMap of
-/// column indices.
+/// Each row is a vector of column indices, sorted by column. The rows are kept
+/// in a vector indexed by row index when the used row indices are dense, or in
+/// a BTreeMap otherwise.
 #[derive(Debug)]
 pub struct TwoDArray {
     // The rows of the 2D array.
-    // The key is the row index and the value is the row.
-    rows: BTreeMap<usize, Row>, // row index, row
+    rows: Rows,
 
     // The row indices sorted by the number of entries in the row.
     rows_by_size: Vec<usize>,
@@ -23,15 +34,131 @@ pub struct TwoDArray {
 
     // The index of the last row in the array.
     last_row_index: usize,
+
+    // The row and column index of each word, in word list order.
+    hash_pairs: Vec<(usize, usize)>,
 }
 
-/// A row in the 2D array.
+/// The storage of the rows of the 2D array.
 #[derive(Debug)]
+enum Rows {
+    // The rows indexed by row index. Unused row indices hold `None`.
+    Dense(Vec<Option<Row>>),
+
+    // The key is the row index and the value is the row.
+    Sparse(BTreeMap<usize, Row>),
+}
+
+/// Two different words that hash to the same cell of the 2D array, see
+/// `analyze_collisions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collision {
+    /// The 0 based word index of the first word in the cell.
+    pub first: usize,
+
+    /// The 0 based word index of a later word in the same cell.
+    pub second: usize,
+
+    /// The row index of the cell.
+    pub row: usize,
+
+    /// The column index of the cell.
+    pub col: usize,
+}
+
+/// Finds every pair of words that collide in the 2D array.
+///
+/// Building the 2D array stops at the first collision. This reports all of them
+/// in one pass, so a word list or hash algorithm can be fixed in one go. Each
+/// later word in a cell is paired with the first word in the cell. Repeated
+/// words are the same key and do not collide with each other.
+///
+/// # Parameters
+/// * `word_list` - A word list.
+/// * `hash_algorithm` - A hash algorithm.
+///
+/// # Returns
+/// The collisions, in word list order of the later word. Empty when the word
+/// list fits the 2D array.
+///
+/// # Errors
+/// Will return `Err` if the hash algorithm rejects a word.
+pub fn analyze_collisions<K: Key + ?Sized, W: Borrow<K>>(
+    word_list: &WordList<W>,
+    hash_algorithm: &impl HashAlgorithm<K>,
+) -> Result<Vec<Collision>, Error> {
+    let fold_case = hash_algorithm.folds_case();
+    let mut cells: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    let mut collisions = Vec::new();
+    for (word_index, word) in word_list.iter().map(Borrow::borrow).enumerate() {
+        let (row, col) = hash_algorithm.hash_pair(word)?;
+        let first = *cells.entry((row, col)).or_insert(word_index);
+        if compare_key(word_list[first].borrow(), fold_case) != compare_key(word, fold_case) {
+            collisions.push(Collision {
+                first,
+                second: word_index,
+                row,
+                col,
+            });
+        }
+    }
+    Ok(collisions)
+}
+
+/// Hashes every word with the hash algorithm.
+///
+/// # Parameters
+/// * `words` - The words, in word list order.
+/// * `hash_algorithm` - The hash algorithm.
+///
+/// # Returns
+/// The row and column index of each word, in word list order.
+///
+/// # Errors
+/// Will return `Err` if the hash algorithm rejects a word.
+#[cfg(not(feature = "parallel"))]
+fn hash_pairs<K: ?Sized>(
+    words: &[&K],
+    hash_algorithm: &dyn HashAlgorithm<K>,
+) -> Result<Vec<(usize, usize)>, Error> {
+    words
+        .iter()
+        .map(|word| hash_algorithm.hash_pair(word))
+        .collect()
+}
+
+/// Hashes every word with the hash algorithm.
+///
+/// The words are hashed concurrently, but the error of the first rejected word
+/// in word list order is returned, the same as the serial version.
+///
+/// # Parameters
+/// * `words` - The words, in word list order.
+/// * `hash_algorithm` - The hash algorithm.
+///
+/// # Returns
+/// The row and column index of each word, in word list order.
+///
+/// # Errors
+/// Will return `Err` if the hash algorithm rejects a word.
+#[cfg(feature = "parallel")]
+fn hash_pairs<K: Key + ?Sized>(
+    words: &[&K],
+    hash_algorithm: &dyn HashAlgorithm<K>,
+) -> Result<Vec<(usize, usize)>, Error> {
+    let results: Vec<Result<(usize, usize), Error>> = words
+        .par_iter()
+        .map(|word| hash_algorithm.hash_pair(word))
+        .collect();
+    results.into_iter().collect()
+}
+
+/// A row in the 2D array.
+#[derive(Debug, PartialEq, Eq)]
 pub struct Row {
-    // The columns of the row.
-    // The key is the column index and the value is the index of the word in the
-    // word list.
-    cols: BTreeMap<usize, usize>, // col index, word index
+    // The columns of the row, sorted by column index.
+    // Each entry is the column index and the index of the word in the word list.
+    cols: Vec<(Index, Index)>, // col index, word index
 }
 
 impl<'a> TwoDArray {
@@ -43,6 +170,7 @@ impl<'a> TwoDArray {
     /// # Parameters
     /// * `word_list` - The word list to create the array from.
     /// * `hash_algorithm` - The hash algorithm to use.
+    /// * `options` - The options controlling how repeated words are handled.
     ///
     /// # Returns
     /// A new 2D array.
@@ -51,91 +179,255 @@ impl<'a> TwoDArray {
     /// Will return `Err` if there is a collision in the array.
     /// A collision occurs when two words hash to the same row and column.
     /// This will happen if the hash algorithm is not good enough. A new algorithm
-    /// will need to be tried.
-    pub fn new(word_list: &WordList, hash_algorithm: &dyn HashAlgorithm) -> Result<Self, Error> {
-        let num_words = word_list.len();
-
-        let mut self_ = TwoDArray {
-            rows: BTreeMap::new(),
-            rows_by_size: Vec::new(),
-            num_entries: num_words,
-            num_rows: 0,
-            last_row_index: 0,
-        };
+    /// will need to be tried. Repeated words only collide when the duplicate policy
+    /// rejects them.
+    pub fn new<K: Key + ?Sized, W: Borrow<K>>(
+        word_list: &WordList<W>,
+        hash_algorithm: &dyn HashAlgorithm<K>,
+        options: &Options,
+    ) -> Result<Self, Error> {
+        let allow_duplicates = options.get_duplicate_policy() == DuplicatePolicy::Allow;
+        let fold_case = hash_algorithm.folds_case();
+        let words: Vec<&K> = word_list.iter().map(Borrow::borrow).collect();
+        let hash_pairs = hash_pairs(&words, hash_algorithm)?;
+        Self::from_hash_pairs(hash_pairs, options, |prior, current, row, col| {
+            let prior: &K = word_list[from_index(prior) - 1].borrow();
+            let current: &K = word_list[from_index(current) - 1].borrow();
+            if allow_duplicates && compare_key(prior, fold_case) == compare_key(current, fold_case)
+            {
+                // The same key again, keep the first occurrence.
+                return Ok(());
+            }
+            Err(Error::new(Kind::TwoDArrayCollision {
+                word_a: prior.to_text().into_owned(),
+                word_b: current.to_text().into_owned(),
+                row,
+                col,
+            }))
+        })
+    }
 
-        {
-            // Calculate the indices that will be used in the 2D array.
-            let mut row_indices = Vec::with_capacity(num_words);
-            let mut col_indices = Vec::with_capacity(num_words);
-            for word in &word_list.list {
-                let row = hash_algorithm.h1(word)?;
-                row_indices.push(row);
+    /// Create a new 2D array from row and column indices that were computed
+    /// beforehand, without a word list or hash algorithm. The packing can then
+    /// be reused for any index pairs, see `OneDPackedArray::pack`.
+    ///
+    /// # Parameters
+    /// * `pairs` - The row and column index of each word, in word list order.
+    /// * `num_words` - The number of words, which must be the number of pairs.
+    /// * `options` - The options controlling how the rows are ordered.
+    ///
+    /// # Returns
+    /// A new 2D array.
+    ///
+    /// # Errors
+    /// Will return `Err` if the number of pairs is not `num_words`, or if two
+    /// words have the same row and column index. Without the words a collision
+    /// names them by their 1 based index, as in `word 2`.
+    #[cfg(feature = "internals")]
+    pub fn from_pairs(
+        pairs: &[(usize, usize)],
+        num_words: usize,
+        options: &Options,
+    ) -> Result<Self, Error> {
+        if pairs.len() != num_words {
+            return Err(Error::new(Kind::TwoDArrayError(format!(
+                "{} index pairs given for {num_words} words.",
+                pairs.len()
+            ))));
+        }
+        Self::from_hash_pairs(pairs.to_vec(), options, |prior, current, row, col| {
+            Err(Error::new(Kind::TwoDArrayCollision {
+                word_a: format!("word {}", from_index(prior)),
+                word_b: format!("word {}", from_index(current)),
+                row,
+                col,
+            }))
+        })
+    }
 
-                let col = hash_algorithm.h2(word)?;
-                col_indices.push(col);
-            }
+    /// Create a new 2D array from the row and column index of each word.
+    ///
+    /// # Parameters
+    /// * `hash_pairs` - The row and column index of each word, in word list
+    ///   order.
+    /// * `options` - The options controlling how the rows are ordered.
+    /// * `on_collision` - Called with the 1 based word indices of two words in
+    ///   the same cell, and the row and column index of the cell. Returns `Ok`
+    ///   to drop the later word as a repeat of the first, or the collision error.
+    ///
+    /// # Returns
+    /// A new 2D array.
+    ///
+    /// # Errors
+    /// Will return `Err` if an index overflows, or the error of `on_collision`.
+    fn from_hash_pairs(
+        hash_pairs: Vec<(usize, usize)>,
+        options: &Options,
+        mut on_collision: impl FnMut(Index, Index, usize, usize) -> Result<(), Error>,
+    ) -> Result<Self, Error> {
+        let num_words = hash_pairs.len();
+
+        // Calculate the indices that will be used in the 2D array.
+        let mut entries: Vec<(usize, Index, Index)> = Vec::with_capacity(num_words);
+        for (i, &(row, col)) in hash_pairs.iter().enumerate() {
+            // word list is 1 based
+            let (Some(col), Some(current_idx)) = (to_index(col), to_index(i + 1)) else {
+                return Err(Error::new(Kind::TwoDArrayError(format!(
+                    "Index overflow at word {}.",
+                    i + 1
+                ))));
+            };
+            entries.push((row, col, current_idx));
+        }
 
-            // * Fill the 2-D array with values.
-            let it = zip(row_indices, col_indices);
-            for (i, (r, c)) in it.enumerate() {
-                // Get the row to add to or create a new row if needed.
-                let row = self_.rows.entry(r).or_insert_with(|| Row {
-                    cols: BTreeMap::new(),
-                });
+        // Sorting groups the entries of each row together, by column, with the
+        // first occurrence of each cell first.
+        entries.sort_unstable();
 
-                let current_idx = i + 1; // word list is 1 based
-                if let Some(prior) = row.cols.insert(c, current_idx) {
-                    return Err(Error::new(Kind::TwoDArrayError(format!(
-                        "Collision: {} === {}",
-                        word_list.list[prior - 1],
-                        word_list.list[current_idx - 1]
-                    ))));
+        // * Fill the 2-D array with values, one row at a time.
+        let mut num_entries = num_words;
+        let mut built: Vec<(usize, Row)> = Vec::new();
+        for (r, c, current_idx) in entries {
+            if built.last().is_none_or(|(last, _)| *last != r) {
+                built.push((r, Row { cols: Vec::new() }));
+            }
+            let Some((_, row)) = built.last_mut() else {
+                continue;
+            };
+            if let Some(&(prior_c, prior)) = row.cols.last() {
+                if prior_c == c {
+                    on_collision(prior, current_idx, r, from_index(c))?;
+                    num_entries -= 1;
+                    continue;
                 }
             }
+            row.cols.push((c, current_idx));
         }
 
         // * Sort the rows of the array.
-        // Build secondary index, rows sorted by size.
-        let mut rows_by_size: Vec<(usize, usize)> = Vec::new();
-        for (i, r) in &self_.rows {
-            rows_by_size.push((r.cols.len(), *i));
+        // Build secondary index, rows sorted by size, ties broken by the policy.
+        let mut rows_by_size: Vec<(usize, &Row)> = built.iter().map(|(i, r)| (*i, r)).collect();
+        match options.get_row_tie_break() {
+            RowTieBreak::RowIndexAscending => {
+                rows_by_size.sort_by_key(|(i, r)| (Reverse(r.len()), *i));
+            }
+            RowTieBreak::RowIndexDescending => {
+                rows_by_size.sort_by_key(|(i, r)| (Reverse(r.len()), Reverse(*i)));
+            }
+            RowTieBreak::FirstColumn => {
+                rows_by_size
+                    .sort_by_key(|(i, r)| (Reverse(r.len()), r.cols.first().map(|c| c.0), *i));
+            }
+            RowTieBreak::Seeded(seed) => {
+                rows_by_size.sort_by_key(|(i, r)| {
+                    let key = u64::try_from(*i).map_or(u64::MAX, |i| mix(seed.wrapping_add(i)));
+                    (Reverse(r.len()), key, *i)
+                });
+            }
         }
-        rows_by_size.sort_by_key(|k| (Reverse(k.0)));
-        self_.rows_by_size = rows_by_size.iter().map(|a| a.1).collect::<Vec<usize>>();
-
-        self_.num_rows = self_.rows.len();
+        let rows_by_size: Vec<usize> = rows_by_size.iter().map(|(i, _)| *i).collect();
+
+        let num_rows = built.len();
+        let last_row_index = built.last().map_or(0, |(i, _)| *i);
+        let rows = if last_row_index < DENSE_ROW_FACTOR.saturating_mul(num_rows) {
+            let mut dense: Vec<Option<Row>> = Vec::new();
+            dense.resize_with(last_row_index + 1, || None);
+            for (i, row) in built {
+                dense[i] = Some(row);
+            }
+            Rows::Dense(dense)
+        } else {
+            Rows::Sparse(built.into_iter().collect())
+        };
 
-        self_.last_row_index = *self_.rows.last_key_value().unwrap().0;
+        Ok(TwoDArray {
+            rows,
+            rows_by_size,
+            num_entries,
+            num_rows,
+            last_row_index,
+            hash_pairs,
+        })
+    }
 
-        Ok(self_)
+    /// Get the row and column index of each word the array was created from.
+    ///
+    /// # Returns
+    /// The row and column index of each word, in word list order.
+    #[must_use]
+    pub fn get_hash_pairs(&self) -> &[(usize, usize)] {
+        &self.hash_pairs
     }
 
-    // Get the number of entries in the array.
-    //
-    // # Returns
-    // The number of entries in the array.
+    /// Get the number of entries in the array.
+    ///
+    /// # Returns
+    /// The number of entries in the array.
+    #[must_use]
     pub fn get_num_entries(&self) -> usize {
         self.num_entries
     }
 
-    // Get the index of the last row in the array.
-    //
-    // # Returns
-    // The index of the last row in the array.
+    /// Get the index of the last row in the array.
+    ///
+    /// # Returns
+    /// The index of the last row in the array.
+    #[must_use]
     pub fn get_last_row_index(&self) -> usize {
         self.last_row_index
     }
 
-    // Get the number of rows in the array.
-    // Only used for testing.
-    //
-    // # Returns
-    // The number of rows in the array.
-    #[cfg(test)]
+    /// Get the number of rows in the array.
+    ///
+    /// # Returns
+    /// The number of rows in the array.
+    #[must_use]
     pub fn get_num_rows(&self) -> usize {
         self.num_rows
     }
 
+    /// Gets a row of the array by its row index.
+    ///
+    /// # Parameters
+    /// * `row_index` - The row index of the row to get.
+    ///
+    /// # Returns
+    /// The row, or `None` if no word hashes to the row.
+    #[must_use]
+    pub fn row(&self, row_index: usize) -> Option<&Row> {
+        match &self.rows {
+            Rows::Dense(rows) => rows.get(row_index)?.as_ref(),
+            Rows::Sparse(rows) => rows.get(&row_index),
+        }
+    }
+
+    /// Iterates over the rows that at least one word hashes to.
+    ///
+    /// # Returns
+    /// An iterator over the row index and row of each used row, in row index
+    /// order.
+    #[must_use]
+    pub fn rows(&self) -> Box<dyn Iterator<Item = (usize, &Row)> + '_> {
+        match &self.rows {
+            Rows::Dense(rows) => Box::new(
+                rows.iter()
+                    .enumerate()
+                    .filter_map(|(row_index, row)| Some((row_index, row.as_ref()?))),
+            ),
+            Rows::Sparse(rows) => Box::new(rows.iter().map(|(row_index, row)| (*row_index, row))),
+        }
+    }
+
+    /// Iterates over the used rows, biggest row first. Rows of the same size
+    /// are in increasing row index order.
+    ///
+    /// # Returns
+    /// An iterator over the row index and row of each used row.
+    #[must_use]
+    pub fn rows_by_size(&self) -> RowSizeIterator<'_> {
+        RowSizeIterator::new(self)
+    }
+
     // Get a row of the array by its size.
     //
     // Index 0 is the row with the most entries.
@@ -148,36 +440,61 @@ impl<'a> TwoDArray {
     // # Returns
     // The row at the given index.
     fn get_row_by_size(&'a self, index: usize) -> Option<(usize, &'a Row)> {
-        if let Some(size_index) = self.rows_by_size.get(index) {
-            if let Some(row) = self.rows.get(size_index) {
-                return Some((*size_index, row));
-            }
-        }
-        None
+        let size_index = *self.rows_by_size.get(index)?;
+        Some((size_index, self.row(size_index)?))
     }
 }
 
 impl Row {
-    // Get the used column indices in the row.
-    //
-    // # Returns
-    // The used column indices in the row.
+    /// Iterates over the columns of the row without collecting them.
+    ///
+    /// # Returns
+    /// An iterator over the column index and the 1 based word index of each
+    /// column, sorted by column index.
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (usize, Index)> + '_ {
+        self.cols.iter().map(|(c, v)| (from_index(*c), *v))
+    }
+
+    /// Gets the used column indices in the row.
+    ///
+    /// # Returns
+    /// The used column indices in the row.
+    #[must_use]
     pub fn get_col_indices(&self) -> Vec<usize> {
-        let indices: Vec<_> = self.cols.keys().copied().collect();
-        indices
+        self.cols.iter().map(|(c, _)| from_index(*c)).collect()
     }
 
-    // Get the column values in the row.
-    //
-    // # Returns
-    // The column values in the row.
-    pub fn get_col_values(&self) -> Vec<usize> {
-        let values: Vec<_> = self.cols.values().copied().collect();
-        values
+    /// Gets the column values in the row.
+    ///
+    /// # Returns
+    /// The column values in the row, the 1 based word index of each column.
+    #[must_use]
+    pub fn get_col_values(&self) -> Vec<Index> {
+        self.cols.iter().map(|(_, v)| *v).collect()
+    }
+
+    /// Gets the number of words in the row.
+    ///
+    /// # Returns
+    /// The size of the row.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Returns true if no word is in the row.
+    ///
+    /// # Returns
+    /// True if the row is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cols.is_empty()
     }
 }
 
-// Iterator for the rows of a 2D array sorted by size.
+/// Iterator over the rows of a 2D array, biggest row first, see
+/// `TwoDArray::rows_by_size`.
 #[derive(Debug)]
 pub struct RowSizeIterator<'a> {
     // The 2D array to iterate over.
@@ -188,31 +505,47 @@ pub struct RowSizeIterator<'a> {
 }
 
 impl<'a> RowSizeIterator<'a> {
-    // Create a new iterator.
+    /// Create a new iterator.
+    ///
+    /// # Parameters
+    /// * `array` - The 2D array to iterate over.
+    ///
+    /// # Returns
+    /// An iterator starting at the biggest row of the array.
+    #[must_use]
     pub fn new(array: &'a TwoDArray) -> Self {
         RowSizeIterator {
             two_d_array: array,
             index: 0,
         }
     }
+}
+
+impl<'a> Iterator for RowSizeIterator<'a> {
+    type Item = (usize, &'a Row);
 
     // Get the next biggest row in the array.
-    //
-    // # Returns
-    // The next biggest row in the array.
-    pub fn next_biggest(&mut self) -> Option<(usize, &'a Row)> {
-        if let Some((index, row)) = self.two_d_array.get_row_by_size(self.index) {
-            self.index += 1;
-            return Some((index, row));
-        }
-        None
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.two_d_array.get_row_by_size(self.index)?;
+        self.index += 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self
+            .two_d_array
+            .rows_by_size
+            .len()
+            .saturating_sub(self.index);
+        (remaining, Some(remaining))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ElcAlgorithm;
+    use crate::{CaseFold, ElcAlgorithm};
+    use std::iter::zip;
 
     #[test]
     fn two_d_array_unit_test() {
@@ -221,7 +554,7 @@ mod tests {
         let mut word_list = WordList::new();
         word_list.push("WORD");
 
-        match TwoDArray::new(&word_list, &hash_algorithm) {
+        match TwoDArray::new(&word_list, &hash_algorithm, &Options::default()) {
             Ok(a) => {
                 assert_eq!(a.get_num_entries(), 1);
                 assert_eq!(a.get_num_rows(), 1);
@@ -232,13 +565,15 @@ mod tests {
 
         word_list.push("WIRE");
         word_list.push("ABLE");
-        match TwoDArray::new(&word_list, &hash_algorithm) {
+        match TwoDArray::new(&word_list, &hash_algorithm, &Options::default()) {
             Ok(a) => {
                 assert_eq!(a.get_num_entries(), 3);
                 assert_eq!(a.get_num_rows(), 2);
-                let mut it = RowSizeIterator::new(&a);
+                assert_eq!(a.get_hash_pairs(), [(22, 3), (22, 4), (0, 4)]);
+                let mut it = a.rows_by_size();
+                assert_eq!(it.size_hint(), (2, Some(2)));
                 println!("{it:?}");
-                if let Some((row_index, row)) = it.next_biggest() {
+                if let Some((row_index, row)) = it.next() {
                     assert_eq!(row_index, 22);
                     assert_eq!(row.cols.len(), 2);
                     assert_eq!(row.get_col_indices(), vec![3, 4]);
@@ -246,22 +581,172 @@ mod tests {
                 } else {
                     panic!("Unexpected iterator None");
                 }
-                if let Some((row_index, row)) = it.next_biggest() {
+                if let Some((row_index, row)) = it.next() {
                     assert_eq!(row_index, 0);
                     assert_eq!(row.cols.len(), 1);
                 } else {
                     panic!("Unexpected iterator None");
                 }
-                if let Some((_, _)) = it.next_biggest() {
+                if let Some((_, _)) = it.next() {
                     panic!("Unexpected iterator Some");
                 }
+                assert_eq!(
+                    a.rows_by_size()
+                        .filter(|(_, row)| row.len() == 1)
+                        .map(|(row_index, _)| row_index)
+                        .collect::<Vec<_>>(),
+                    vec![0]
+                );
             }
             Err(e) => panic!("Unexpected 2D array creation failure. {e}"),
         }
 
+        word_list.push("WIRE");
+        if let Ok(_a) = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()) {
+            panic!("Undetected duplicate.");
+        }
+        let options = Options::new().duplicate_policy(DuplicatePolicy::Allow);
+        match TwoDArray::new(&word_list, &hash_algorithm, &options) {
+            Ok(a) => assert_eq!(a.get_num_entries(), 3),
+            Err(e) => panic!("Unexpected 2D array creation failure. {e}"),
+        }
+
         word_list.push("WILD");
-        if let Ok(_a) = TwoDArray::new(&word_list, &hash_algorithm) {
+        if let Ok(_a) = TwoDArray::new(&word_list, &hash_algorithm, &options) {
             panic!("Undetected collision.");
         }
     }
+
+    #[test]
+    fn analyze_collisions_unit_test() {
+        let hash_algorithm = ElcAlgorithm::default();
+        let word_list: WordList = ["ADD", "AND", "END", "ABD", "ADD"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let collisions = analyze_collisions(&word_list, &hash_algorithm).unwrap();
+        println!("{collisions:?}");
+        assert_eq!(
+            collisions
+                .iter()
+                .map(|c| (c.first, c.second, c.row, c.col))
+                .collect::<Vec<_>>(),
+            [(0, 1, 0, 3), (0, 3, 0, 3)]
+        );
+
+        // Words that differ only in case are the same key when case is folded.
+        let word_list: WordList = ["ADD", "AND", "add"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let collisions = analyze_collisions(&word_list, &CaseFold::new(hash_algorithm.clone()));
+        assert_eq!(collisions.unwrap().len(), 1);
+        assert!(analyze_collisions(&word_list, &hash_algorithm).is_err());
+
+        let word_list: WordList = ["AND", "BEGIN"].into_iter().map(String::from).collect();
+        assert!(analyze_collisions(&word_list, &hash_algorithm)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn two_d_array_storage_unit_test() {
+        let hash_algorithm: ElcAlgorithm = ElcAlgorithm::default();
+
+        // Rows 0 and 22 of 23 are used, so the rows are kept in a map.
+        let word_list: WordList = ["WORD", "WIRE", "ABLE"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let a = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        assert!(matches!(a.rows, Rows::Sparse(_)));
+        assert_eq!(a.get_last_row_index(), 22);
+        assert_eq!(a.row(22).unwrap().get_col_values(), vec![1, 2]);
+        assert!(a.row(1).is_none());
+        assert_eq!(
+            a.rows()
+                .map(|(row_index, row)| (row_index, row.len()))
+                .collect::<Vec<_>>(),
+            [(0, 1), (22, 2)]
+        );
+
+        // Rows 0 and 1 are used, so the rows are kept in a vector.
+        let word_list: WordList = ["AB", "BA", "AC"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let a = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        assert!(matches!(a.rows, Rows::Dense(_)));
+        assert_eq!(a.get_num_rows(), 2);
+        assert_eq!(a.get_last_row_index(), 1);
+        let mut it = RowSizeIterator::new(&a);
+        let (row_index, row) = it.next().unwrap();
+        assert_eq!(row_index, 0);
+        assert_eq!(row.get_col_values(), vec![1, 3]);
+        assert!(row
+            .iter()
+            .eq(zip(row.get_col_indices(), row.get_col_values())));
+        assert_eq!(it.next().unwrap().0, 1);
+        assert!(a.row(2).is_none());
+        assert_eq!(
+            a.rows()
+                .map(|(row_index, row)| (row_index, row.len()))
+                .collect::<Vec<_>>(),
+            [(0, 2), (1, 1)]
+        );
+        assert!(!a.row(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn row_tie_break_unit_test() {
+        let hash_algorithm: ElcAlgorithm = ElcAlgorithm::default();
+
+        // Row 3 holds two words, rows 0, 1 and 2 one word each, in columns 2,
+        // 0 and 1.
+        let word_list: WordList = ["BA", "AC", "CB", "DA", "DB"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let order = |tie_break| {
+            let options = Options::default().row_tie_break(tie_break);
+            let a = TwoDArray::new(&word_list, &hash_algorithm, &options).unwrap();
+            a.rows_by_size()
+                .map(|(row_index, _)| row_index)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(order(RowTieBreak::RowIndexAscending), [3, 0, 1, 2]);
+        assert_eq!(order(RowTieBreak::RowIndexDescending), [3, 2, 1, 0]);
+        assert_eq!(order(RowTieBreak::FirstColumn), [3, 1, 2, 0]);
+
+        let seeded = order(RowTieBreak::Seeded(5));
+        println!("{seeded:?}");
+        assert_eq!(seeded[0], 3);
+        assert_eq!(seeded, order(RowTieBreak::Seeded(5)));
+        let mut sorted = seeded.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn hash_pairs_unit_test() {
+        let hash_algorithm = ElcAlgorithm::new(2, 26);
+        let word_list: WordList = ["AND", "BEGIN", "CHAR"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let a = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+        let expected: Vec<_> = word_list
+            .iter()
+            .map(|word| hash_algorithm.hash_pair(word).unwrap())
+            .collect();
+        assert_eq!(a.get_hash_pairs(), expected);
+
+        // The first word rejected in word list order is reported.
+        let word_list: WordList = ["AND", "A", "BEGIN", ""]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let e = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap_err();
+        assert!(e.to_string().contains("Expected word length (1)"), "{e}");
+    }
 }
@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+
+/// The largest block length tried by `TwoLevelRlt::new`, as a power of two.
+const MAX_BLOCK_BITS: u32 = 8;
+
+/// A row lookup table compressed into two levels.
+///
+/// The row index is split into a high part, the block, and a low part, the
+/// position in the block. The first level gives the start of each block in the
+/// second level, and identical blocks are kept only once. Row spaces where few
+/// rows are used are mostly blocks of zeros, so they shrink to a handful of
+/// blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwoLevelRlt {
+    /// The number of bits of the low part of the row index.
+    block_bits: u32,
+
+    /// The start of the block of each high part in `blocks`.
+    first_level: Vec<usize>,
+
+    /// The distinct blocks, one after the other.
+    blocks: Vec<isize>,
+}
+
+impl TwoLevelRlt {
+    /// Compresses the values of a row lookup table, with the block length that
+    /// gives the fewest entries in both levels.
+    ///
+    /// # Parameters
+    /// * `values` - The value of each row, indexed by row index.
+    ///
+    /// # Returns
+    /// The compressed table.
+    #[must_use]
+    pub fn new(values: &[isize]) -> Self {
+        (1..=MAX_BLOCK_BITS)
+            .map(|block_bits| Self::with_block_bits(values, block_bits))
+            .min_by_key(TwoLevelRlt::size)
+            .unwrap_or_else(|| Self::with_block_bits(values, 1))
+    }
+
+    /// Compresses the values of a row lookup table with a given block length.
+    ///
+    /// # Parameters
+    /// * `values` - The value of each row, indexed by row index.
+    /// * `block_bits` - The block length as a power of two.
+    ///
+    /// # Returns
+    /// The compressed table.
+    #[must_use]
+    pub fn with_block_bits(values: &[isize], block_bits: u32) -> Self {
+        let block_len = 1 << block_bits;
+        let mut starts: BTreeMap<Vec<isize>, usize> = BTreeMap::new();
+        let mut first_level = Vec::with_capacity(values.len().div_ceil(block_len));
+        let mut blocks = Vec::new();
+        for chunk in values.chunks(block_len) {
+            let mut block = chunk.to_vec();
+            block.resize(block_len, 0);
+            let start = *starts.entry(block).or_insert_with_key(|block| {
+                blocks.extend_from_slice(block);
+                blocks.len() - block_len
+            });
+            first_level.push(start);
+        }
+        Self {
+            block_bits,
+            first_level,
+            blocks,
+        }
+    }
+
+    /// Gets the value of a row.
+    ///
+    /// # Parameters
+    /// * `index` - The index of the row.
+    ///
+    /// # Returns
+    /// The value of the row, or None if the index is out of range.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&isize> {
+        self.blocks.get(self.position(index)?)
+    }
+
+    /// Finds where the value of a row is kept in the second level.
+    ///
+    /// # Parameters
+    /// * `index` - The index of the row.
+    ///
+    /// # Returns
+    /// The position of the value in the second level, or None if the index is
+    /// out of range.
+    #[must_use]
+    pub fn position(&self, index: usize) -> Option<usize> {
+        let start = self.first_level.get(index >> self.block_bits)?;
+        Some(start + (index & self.mask()))
+    }
+
+    /// Gets the number of bits of the low part of the row index.
+    ///
+    /// # Returns
+    /// The block length as a power of two.
+    #[must_use]
+    pub fn get_block_bits(&self) -> u32 {
+        self.block_bits
+    }
+
+    /// Gets the mask selecting the low part of the row index.
+    ///
+    /// # Returns
+    /// The block length minus 1.
+    #[must_use]
+    pub fn mask(&self) -> usize {
+        (1 << self.block_bits) - 1
+    }
+
+    /// Gets the first level.
+    ///
+    /// # Returns
+    /// The start of the block of each high part of the row index.
+    #[must_use]
+    pub fn get_first_level(&self) -> &[usize] {
+        &self.first_level
+    }
+
+    /// Gets the second level.
+    ///
+    /// # Returns
+    /// The distinct blocks, one after the other.
+    #[must_use]
+    pub fn get_blocks(&self) -> &[isize] {
+        &self.blocks
+    }
+
+    /// Gets the first level as a string.
+    ///
+    /// # Returns
+    /// The start of each block as a string.
+    #[must_use]
+    pub fn get_first_level_as_text(&self) -> String {
+        join(&self.first_level)
+    }
+
+    /// Gets the second level as a string.
+    ///
+    /// # Returns
+    /// The distinct blocks as a string.
+    #[must_use]
+    pub fn get_blocks_as_text(&self) -> String {
+        join(&self.blocks)
+    }
+
+    /// Gets the number of entries in both levels.
+    ///
+    /// # Returns
+    /// The number of entries.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.first_level.len() + self.blocks.len()
+    }
+
+    /// A pseudo code representation of reading the value of a row, given as
+    /// `row_index`, from the `row_block` and `row_lookup_table` tables.
+    ///
+    /// # Returns
+    /// The expression reading the value.
+    #[must_use]
+    pub fn lookup_as_text(&self) -> String {
+        format!(
+            "row_lookup_table[row_block[row_index >> {}] + (row_index & {})]",
+            self.block_bits,
+            self.mask()
+        )
+    }
+}
+
+/// Joins values into a comma separated string.
+///
+/// # Parameters
+/// * `values` - The values to join.
+///
+/// # Returns
+/// The values as a string.
+fn join<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_level_rlt_unit_test() {
+        let mut values = vec![0; 100];
+        values[3] = -2;
+        values[70] = 5;
+        values[71] = 6;
+
+        let two_level = TwoLevelRlt::with_block_bits(&values, 2);
+        println!("{two_level:?}");
+        assert_eq!(two_level.mask(), 3);
+        assert_eq!(two_level.get_first_level().len(), 25);
+        // Blocks 0, 17 and the all zero block.
+        assert_eq!(
+            two_level.get_blocks(),
+            &[0, 0, 0, -2, 0, 0, 0, 0, 0, 0, 5, 6]
+        );
+        assert_eq!(two_level.size(), 37);
+        assert!(two_level
+            .get_first_level_as_text()
+            .starts_with("0, 4, 4, 4, "));
+        assert_eq!(
+            two_level.get_blocks_as_text(),
+            "0, 0, 0, -2, 0, 0, 0, 0, 0, 0, 5, 6"
+        );
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(two_level.get(i), Some(value));
+        }
+        assert_eq!(two_level.get(100), None);
+        assert_eq!(two_level.position(71), Some(11));
+        assert_eq!(two_level.position(100), None);
+        assert_eq!(
+            two_level.lookup_as_text(),
+            "row_lookup_table[row_block[row_index >> 2] + (row_index & 3)]"
+        );
+
+        let two_level = TwoLevelRlt::new(&values);
+        assert!(two_level.size() < values.len());
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(two_level.get(i), Some(value));
+        }
+
+        let two_level = TwoLevelRlt::new(&[]);
+        assert_eq!(two_level.size(), 0);
+        assert_eq!(two_level.get(0), None);
+    }
+}
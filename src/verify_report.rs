@@ -0,0 +1,204 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::key::compare_key;
+use crate::{Key, WordList};
+
+/// The outcome of checking a hash function against a word list, see `verify`.
+///
+/// Repeated words are the same key, so they are expected to share a hash value
+/// and are not reported as collisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// The hash value of each word, indexed by word index.
+    hash_values: Vec<usize>,
+
+    /// The word indices of the different words that share a hash value, as the
+    /// first word with the hash value and a later word colliding with it.
+    collisions: Vec<(usize, usize)>,
+
+    /// The hash values that no word hashes to.
+    gaps: Vec<usize>,
+
+    /// The word indices of the words whose hash value is outside the range of
+    /// hash values.
+    out_of_range: Vec<usize>,
+}
+
+impl VerifyReport {
+    /// Creates a report from the hash value of each word.
+    ///
+    /// # Parameters
+    /// * `word_list` - The word list that was hashed.
+    /// * `hash_values` - The hash value of each word, indexed by word index.
+    /// * `fold_case` - Whether words that differ only in case are the same key.
+    /// * `hash_base` - The smallest hash value.
+    /// * `table_len` - The number of hash values.
+    pub(crate) fn new<K: Key + ?Sized, W: Borrow<K>>(
+        word_list: &WordList<W>,
+        hash_values: Vec<usize>,
+        fold_case: bool,
+        hash_base: usize,
+        table_len: usize,
+    ) -> Self {
+        let range = hash_base..hash_base + table_len;
+        let mut first_words: BTreeMap<usize, (usize, Cow<K>)> = BTreeMap::new();
+        let mut collisions = Vec::new();
+        for (word_index, (word, hash_value)) in word_list.iter().zip(&hash_values).enumerate() {
+            let key = compare_key(word.borrow(), fold_case);
+            match first_words.get(hash_value) {
+                Some((first, first_key)) if *first_key != key => {
+                    collisions.push((*first, word_index));
+                }
+                Some(_) => {}
+                None => {
+                    first_words.insert(*hash_value, (word_index, key));
+                }
+            }
+        }
+        let gaps = range
+            .clone()
+            .filter(|hash_value| !first_words.contains_key(hash_value))
+            .collect();
+        let out_of_range = hash_values
+            .iter()
+            .enumerate()
+            .filter(|(_, hash_value)| !range.contains(hash_value))
+            .map(|(word_index, _)| word_index)
+            .collect();
+        Self {
+            hash_values,
+            collisions,
+            gaps,
+            out_of_range,
+        }
+    }
+
+    /// Gets the hash value of each word.
+    ///
+    /// # Returns
+    /// The hash value of each word, indexed by the 0 based word index.
+    #[must_use]
+    pub fn get_hash_values(&self) -> &[usize] {
+        &self.hash_values
+    }
+
+    /// Gets the collisions found.
+    ///
+    /// # Returns
+    /// The 0 based word indices of the different words that share a hash value,
+    /// as the first word with the hash value and a later word colliding with it.
+    #[must_use]
+    pub fn get_collisions(&self) -> &[(usize, usize)] {
+        &self.collisions
+    }
+
+    /// Gets the gaps found. Gaps are expected when the table was packed with
+    /// slack.
+    ///
+    /// # Returns
+    /// The hash values that no word hashes to, in increasing order.
+    #[must_use]
+    pub fn get_gaps(&self) -> &[usize] {
+        &self.gaps
+    }
+
+    /// Gets the words whose hash value is out of range.
+    ///
+    /// # Returns
+    /// The 0 based word indices of the words whose hash value is outside the
+    /// range of hash values.
+    #[must_use]
+    pub fn get_out_of_range(&self) -> &[usize] {
+        &self.out_of_range
+    }
+
+    /// Returns true if no two different words share a hash value and every hash
+    /// value is in range.
+    ///
+    /// # Returns
+    /// True if there are no collisions and no out of range hash values.
+    #[must_use]
+    pub fn is_perfect(&self) -> bool {
+        self.collisions.is_empty() && self.out_of_range.is_empty()
+    }
+
+    /// Returns true if every hash value belongs to exactly one word.
+    ///
+    /// # Returns
+    /// True if there are neither collisions nor gaps.
+    #[must_use]
+    pub fn is_minimal(&self) -> bool {
+        self.is_perfect() && self.gaps.is_empty()
+    }
+}
+
+impl fmt::Display for VerifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let collisions = self
+            .collisions
+            .iter()
+            .map(|(first, later)| {
+                format!("words {first} and {later} at {}", self.hash_values[*first])
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let gaps = self
+            .gaps
+            .iter()
+            .map(|hash_value| format!("{hash_value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let out_of_range = self
+            .out_of_range
+            .iter()
+            .map(|word_index| format!("word {word_index} at {}", self.hash_values[*word_index]))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "{} collisions [{collisions}], {} gaps [{gaps}], {} out of range [{out_of_range}]",
+            self.collisions.len(),
+            self.gaps.len(),
+            self.out_of_range.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_report_unit_test() {
+        let word_list: WordList = ["A", "B", "a", "C"].into_iter().map(String::from).collect();
+
+        let report = VerifyReport::new::<str, String>(&word_list, vec![0, 1, 2, 3], false, 0, 4);
+        println!("{report:?}");
+        assert!(report.is_minimal());
+        assert_eq!(report.get_hash_values(), [0, 1, 2, 3]);
+
+        // Words that are the same key share a hash value without colliding.
+        let report = VerifyReport::new::<str, String>(&word_list, vec![2, 0, 2, 1], true, 0, 3);
+        assert!(report.is_minimal());
+
+        let report = VerifyReport::new::<str, String>(&word_list, vec![2, 0, 2, 0], false, 0, 4);
+        assert!(!report.is_perfect());
+        assert_eq!(report.get_collisions(), [(0, 2), (1, 3)]);
+        assert_eq!(report.get_gaps(), [1, 3]);
+        assert_eq!(
+            report.to_string(),
+            "2 collisions [words 0 and 2 at 2, words 1 and 3 at 0], 2 gaps [1, 3], 0 out of range []"
+        );
+
+        let report = VerifyReport::new::<str, String>(&word_list, vec![0, 1, 2, 5], false, 0, 4);
+        assert!(!report.is_perfect());
+        assert_eq!(report.get_out_of_range(), [3]);
+        assert!(report.to_string().ends_with("1 out of range [word 3 at 5]"));
+
+        let report = VerifyReport::new::<str, String>(&word_list, vec![1, 2, 3, 0], false, 1, 4);
+        assert_eq!(report.get_out_of_range(), [3]);
+        assert_eq!(report.get_gaps(), [4]);
+    }
+}
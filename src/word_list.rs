@@ -1,20 +1,411 @@
-use crate::{Error, Kind};
+use crate::key::compare_key;
+use crate::{Error, Key, Kind};
+use std::borrow::Borrow;
 use std::collections::BTreeSet;
+use std::ops::Index;
+use std::{slice, vec};
+
+/// How repeated words in a word list are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Repeated words make the word list invalid.
+    #[default]
+    Reject,
+
+    /// Repeated words are treated as the same key. All occurrences hash to the
+    /// same value, and the first occurrence is the canonical one.
+    Allow,
+}
+
+/// The characters words may be made of.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Charset {
+    /// The ASCII uppercase letters 'A' to 'Z'.
+    #[default]
+    Uppercase,
+
+    /// The ASCII uppercase letters, the digits '0' to '9' and '_', as used by
+    /// identifiers and keywords such as `INT8` or `FOR_EACH`.
+    Identifier,
+
+    /// Every printable ASCII character except space, for symbol sets such as
+    /// operators. Pair it with an alphabet that only has the characters that
+    /// appear in the words to keep the radix small.
+    Printable,
+
+    /// Every Unicode scalar value except whitespace and control characters, for
+    /// dictionaries in any script. There are too many to use all of them as an
+    /// alphabet, so the alphabet is inferred from the word list.
+    Unicode,
+}
+
+impl Charset {
+    /// Tests whether a character belongs to the character set.
+    ///
+    /// # Parameters
+    /// * `c` - The character to test.
+    ///
+    /// # Returns
+    /// True if `c` is in the character set.
+    #[must_use]
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            Charset::Uppercase => c.is_ascii_uppercase(),
+            Charset::Identifier => c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_',
+            Charset::Printable => c.is_ascii_graphic(),
+            Charset::Unicode => !c.is_whitespace() && !c.is_control(),
+        }
+    }
+
+    /// Gets the characters of the character set.
+    ///
+    /// # Returns
+    /// The characters, sorted, or `None` for `Unicode`, which has too many
+    /// characters to list.
+    #[must_use]
+    pub fn alphabet(self) -> Option<Vec<char>> {
+        (self != Charset::Unicode).then(|| {
+            (0..=127u8)
+                .map(char::from)
+                .filter(|c| self.contains(*c))
+                .collect()
+        })
+    }
+
+    /// Tests whether every character of a word belongs to the character set.
+    ///
+    /// # Parameters
+    /// * `word` - The word to test.
+    /// * `fold_case` - Whether lowercase letters are accepted too.
+    ///
+    /// # Returns
+    /// True if `word` is made of characters from the character set.
+    fn contains_word(self, word: &str, fold_case: bool) -> bool {
+        word.chars()
+            .all(|c| self.contains(if fold_case { c.to_ascii_uppercase() } else { c }))
+    }
+
+    /// Describes the words made of the character set, for error messages.
+    ///
+    /// # Parameters
+    /// * `fold_case` - Whether lowercase letters are accepted too.
+    ///
+    /// # Returns
+    /// A description of the valid words.
+    fn describe(self, fold_case: bool) -> &'static str {
+        match (self, fold_case) {
+            (Charset::Uppercase, false) => "ASCII upper case alphabetic",
+            (Charset::Uppercase, true) => "ASCII alphabetic",
+            (Charset::Identifier, false) => "ASCII upper case identifier",
+            (Charset::Identifier, true) => "ASCII identifier",
+            (Charset::Printable, _) => "printable ASCII",
+            (Charset::Unicode, _) => "printable Unicode",
+        }
+    }
+}
+
+/// The rules a word list must follow to be valid.
+///
+/// The default policy accepts lists of unique, ASCII uppercase alphabetic
+/// words of any length, the rules `WordList::is_valid` checks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    /// The characters words may be made of.
+    charset: Charset,
+
+    /// Whether lowercase letters are accepted and words are compared ignoring
+    /// case.
+    fold_case: bool,
+
+    /// The minimum length of a word, in characters.
+    min_len: usize,
+
+    /// The maximum length of a word, in characters.
+    max_len: Option<usize>,
+
+    /// How repeated words are handled.
+    duplicate_policy: DuplicatePolicy,
+}
+
+impl ValidationPolicy {
+    /// Creates a new policy with the default rules.
+    #[must_use]
+    pub fn new() -> Self {
+        ValidationPolicy::default()
+    }
+
+    /// Sets the characters words may be made of.
+    ///
+    /// # Parameters
+    /// * `charset` - The character set of the words.
+    #[must_use]
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Gets the characters words may be made of.
+    ///
+    /// # Returns
+    /// The character set of the words.
+    #[must_use]
+    pub fn get_charset(&self) -> Charset {
+        self.charset
+    }
+
+    /// Sets whether case is ignored.
+    ///
+    /// When set, lowercase ASCII letters are accepted wherever their uppercase
+    /// form is, words that differ only in case are repeats, and hashes are
+    /// generated with a `CaseFold` algorithm.
+    ///
+    /// # Parameters
+    /// * `fold_case` - True to ignore case.
+    #[must_use]
+    pub fn fold_case(mut self, fold_case: bool) -> Self {
+        self.fold_case = fold_case;
+        self
+    }
+
+    /// Gets whether case is ignored.
+    ///
+    /// # Returns
+    /// True if case is ignored.
+    #[must_use]
+    pub fn get_fold_case(&self) -> bool {
+        self.fold_case
+    }
+
+    /// Sets the minimum length of a word.
+    ///
+    /// # Parameters
+    /// * `min_len` - The minimum length, in characters.
+    #[must_use]
+    pub fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len;
+        self
+    }
+
+    /// Gets the minimum length of a word.
+    ///
+    /// # Returns
+    /// The minimum length, in characters.
+    #[must_use]
+    pub fn get_min_len(&self) -> usize {
+        self.min_len
+    }
+
+    /// Sets the maximum length of a word.
+    ///
+    /// # Parameters
+    /// * `max_len` - The maximum length, in characters.
+    #[must_use]
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Gets the maximum length of a word.
+    ///
+    /// # Returns
+    /// The maximum length in characters, or `None` if words may be of any length.
+    #[must_use]
+    pub fn get_max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Sets how repeated words are handled.
+    ///
+    /// # Parameters
+    /// * `duplicate_policy` - The policy for repeated words.
+    #[must_use]
+    pub fn duplicate_policy(mut self, duplicate_policy: DuplicatePolicy) -> Self {
+        self.duplicate_policy = duplicate_policy;
+        self
+    }
+
+    /// Gets how repeated words are handled.
+    ///
+    /// # Returns
+    /// The policy for repeated words.
+    #[must_use]
+    pub fn get_duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+}
+
+/// Statistics about a word list that help choose the `elc` and `num_vals` of
+/// an `ElcAlgorithm` before generating a hash.
+///
+/// Word lengths are counted in characters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WordListProfile {
+    /// The length of the shortest word. `elc` can be at most this long.
+    pub min_len: usize,
+
+    /// The length of the longest word.
+    pub max_len: usize,
+
+    /// The average word length.
+    pub avg_len: f64,
+
+    /// The sorted characters that appear in the words. Its length is the
+    /// smallest `num_vals` an inferred alphabet can have.
+    pub alphabet: Vec<char>,
+
+    /// The prefix and suffix length the counts below were taken with.
+    pub k: usize,
+
+    /// The number of distinct prefixes of length `k`, an upper bound on the
+    /// number of rows with an `elc` of `k`.
+    pub distinct_prefixes: usize,
+
+    /// The number of distinct suffixes of length `k`.
+    pub distinct_suffixes: usize,
+
+    /// The number of distinct pairs of a prefix and a suffix of length `k`.
+    /// An `elc` of `k` can only hash the list without collisions when this
+    /// equals the number of distinct words.
+    pub distinct_ends: usize,
+
+    /// The number of words that repeat an earlier word.
+    pub duplicates: usize,
+}
 
 /// A list of words that need to be accessed by a hash function.
+///
+/// The words are `String`s by default. A `WordList<&str>` borrows its words
+/// instead, so a large static keyword array is not copied before generation.
+/// See `ByteWordList` for byte strings that are not valid UTF-8.
 #[derive(Debug)]
-pub struct WordList {
+pub struct WordList<W = String> {
     /// The list of words.
-    pub list: Vec<String>,
+    pub list: Vec<W>,
 }
 
-impl WordList {
-    /// Creates a new empty word list.
+/// A list of byte strings that need to be accessed by a hash function.
+pub type ByteWordList = WordList<Vec<u8>>;
+
+impl<W> WordList<W> {
+    /// Returns the number of words in the list.
+    ///
+    /// # Returns
+    /// The number of words in the list.
     #[must_use]
-    pub fn new() -> Self {
-        WordList { list: Vec::new() }
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns true if the list is empty.
+    ///
+    /// # Returns
+    /// True if the list is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Iterates over the words of the list.
+    ///
+    /// # Returns
+    /// The words, in list order.
+    pub fn iter(&self) -> slice::Iter<'_, W> {
+        self.list.iter()
+    }
+
+    /// Gets the word at a position of the list.
+    ///
+    /// # Parameters
+    /// * `index` - The 0 based position of the word.
+    ///
+    /// # Returns
+    /// The word, or `None` if `index` is out of range.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&W> {
+        self.list.get(index)
+    }
+
+    /// Tests whether a word is in the list.
+    ///
+    /// # Parameters
+    /// * `word` - The word to look for.
+    ///
+    /// # Returns
+    /// True if `word` is in the list.
+    #[must_use]
+    pub fn contains<Q: PartialEq + ?Sized>(&self, word: &Q) -> bool
+    where
+        W: Borrow<Q>,
+    {
+        self.list.iter().any(|w| w.borrow() == word)
+    }
+
+    /// Removes the word at a position of the list, shifting the words after it.
+    ///
+    /// # Parameters
+    /// * `index` - The 0 based position of the word.
+    ///
+    /// # Returns
+    /// The removed word, or `None` if `index` is out of range.
+    pub fn remove(&mut self, index: usize) -> Option<W> {
+        (index < self.list.len()).then(|| self.list.remove(index))
+    }
+
+    /// Returns the number of distinct words in the list.
+    ///
+    /// # Returns
+    /// The number of distinct words in the list.
+    #[must_use]
+    pub fn num_distinct(&self) -> usize
+    where
+        W: Ord,
+    {
+        self.list.iter().collect::<BTreeSet<_>>().len()
+    }
+
+    /// Tests whether the keys in the list are valid. Any key the hash
+    /// algorithm accepts may be in the list.
+    ///
+    /// # Parameters
+    /// * `duplicate_policy` - How repeated keys are handled.
+    /// * `fold_case` - Whether keys are compared ignoring the case of ASCII
+    ///   letters.
+    ///
+    /// # Errors
+    /// Will return Err if the keys are not unique, unless `duplicate_policy`
+    /// allows repeats, or if the list is empty.
+    pub(crate) fn validate_keys<K: Key + ?Sized>(
+        &self,
+        duplicate_policy: DuplicatePolicy,
+        fold_case: bool,
+    ) -> Result<(), Error>
+    where
+        W: Borrow<K>,
+    {
+        if self.list.is_empty() {
+            return Err(Error::new(Kind::WordListError(
+                "Empty word list.".to_string(),
+            )));
+        }
+
+        let mut duplicate_checker = BTreeSet::new();
+        for (i, word) in self.list.iter().map(Borrow::borrow).enumerate() {
+            if !duplicate_checker.insert(compare_key(word, fold_case))
+                && duplicate_policy == DuplicatePolicy::Reject
+            {
+                return Err(Error::new(Kind::WordListError(format!(
+                    "Duplicate word detected: {} at position {}",
+                    word.to_text(),
+                    i + 1
+                ))));
+            }
+        }
+
+        Ok(())
     }
+}
 
+impl<W: Borrow<str>> WordList<W> {
     /// Tests whether all the words in the list are valid for the assumptions
     /// and limitations of the hashing implementation.
     /// * All words are ASCII uppercase alphabetic.
@@ -31,6 +422,82 @@ impl WordList {
     /// * unique
     /// * the list is empty
     pub fn is_valid(&self) -> Result<(), Error> {
+        self.is_valid_under(&ValidationPolicy::new())
+    }
+
+    /// Tests whether all the words in the list are valid for a character set.
+    ///
+    /// # Parameters
+    /// * `duplicate_policy` - How repeated words are handled.
+    /// * `charset` - The characters words may be made of.
+    ///
+    /// # Returns
+    /// * Ok(()) if the list is valid.
+    /// * Err(Error) if the list is invalid.
+    ///
+    /// # Errors
+    /// Will return Err if words are not:
+    /// * made of characters from `charset`
+    /// * unique, unless `duplicate_policy` allows repeats
+    /// * the list is empty
+    pub fn is_valid_for(
+        &self,
+        duplicate_policy: DuplicatePolicy,
+        charset: Charset,
+    ) -> Result<(), Error> {
+        self.is_valid_under(
+            &ValidationPolicy::new()
+                .duplicate_policy(duplicate_policy)
+                .charset(charset),
+        )
+    }
+
+    /// Tests whether all the words in the list are valid for a hash algorithm
+    /// that ignores case, such as `CaseFold`.
+    ///
+    /// # Parameters
+    /// * `duplicate_policy` - How repeated words are handled. Words that differ
+    ///   only in case are repeats.
+    ///
+    /// # Returns
+    /// * Ok(()) if the list is valid.
+    /// * Err(Error) if the list is invalid.
+    ///
+    /// # Errors
+    /// Will return Err if words are not:
+    /// * ASCII alphabetic
+    /// * unique ignoring case, unless `duplicate_policy` allows repeats
+    /// * the list is empty
+    pub fn is_valid_case_insensitive(
+        &self,
+        duplicate_policy: DuplicatePolicy,
+    ) -> Result<(), Error> {
+        self.is_valid_under(
+            &ValidationPolicy::new()
+                .duplicate_policy(duplicate_policy)
+                .fold_case(true),
+        )
+    }
+
+    /// Tests whether all the words in the list follow a validation policy.
+    ///
+    /// # Parameters
+    /// * `policy` - The rules the words must follow.
+    ///
+    /// # Returns
+    /// * Ok(()) if the list is valid.
+    /// * Err(Error) if the list is invalid.
+    ///
+    /// # Errors
+    /// Will return Err if words are not:
+    /// * made of characters from the character set of `policy`
+    /// * within the length bounds of `policy`
+    /// * unique, unless `policy` allows repeats
+    /// * the list is empty
+    pub fn is_valid_under(&self, policy: &ValidationPolicy) -> Result<(), Error> {
+        let charset = policy.get_charset();
+        let fold_case = policy.get_fold_case();
+
         // Check for empty list.
         if self.list.is_empty() {
             return Err(Error::new(Kind::WordListError(
@@ -39,20 +506,36 @@ impl WordList {
         }
 
         let mut duplicate_checker = BTreeSet::new();
-        for (i, word) in self.list.iter().enumerate() {
-            // Check for non ASCII upper case alphabetic words.
-            if !word
-                .chars()
-                .all(|c| c.is_ascii_alphabetic() && c.is_ascii_uppercase())
-            {
+        for (i, word) in self.list.iter().map(Borrow::borrow).enumerate() {
+            // Check for words with characters outside of the character set.
+            if !charset.contains_word(word, fold_case) {
                 return Err(Error::new(Kind::WordListError(format!(
-                    "Non ASCII upper case alphabetic word detected at {}.",
+                    "Non {} word detected at {}.",
+                    charset.describe(fold_case),
+                    i + 1
+                ))));
+            }
+
+            // Check for words outside of the length bounds.
+            let len = word.chars().count();
+            if len < policy.get_min_len() {
+                return Err(Error::new(Kind::WordListError(format!(
+                    "Word {word} at {} is shorter than {} characters.",
+                    i + 1,
+                    policy.get_min_len()
+                ))));
+            }
+            if let Some(max_len) = policy.get_max_len().filter(|max_len| len > *max_len) {
+                return Err(Error::new(Kind::WordListError(format!(
+                    "Word {word} at {} is longer than {max_len} characters.",
                     i + 1
                 ))));
             }
 
             // Check for duplicate words.
-            if !duplicate_checker.insert(word) {
+            if !duplicate_checker.insert(compare_key(word, fold_case))
+                && policy.get_duplicate_policy() == DuplicatePolicy::Reject
+            {
                 return Err(Error::new(Kind::WordListError(format!(
                     "Duplicate word detected: {} at position {}",
                     word,
@@ -64,47 +547,357 @@ impl WordList {
         Ok(())
     }
 
-    /// Returns the number of words in the list.
+    /// Returns the length of the longest word in the list.
     ///
     /// # Returns
-    /// The number of words in the list.
+    /// The length of the longest word in bytes, or 0 if the list is empty.
     #[must_use]
-    pub fn len(&self) -> usize {
-        self.list.len()
+    pub fn max_word_len(&self) -> usize {
+        self.list
+            .iter()
+            .map(|word| word.borrow().len())
+            .max()
+            .unwrap_or(0)
     }
 
-    /// Returns true if the list is empty.
+    /// Collects statistics about the words of the list.
+    ///
+    /// # Parameters
+    /// * `k` - The length of the prefixes and suffixes to count. Words shorter
+    ///   than `k` count as a whole.
     ///
     /// # Returns
-    /// True if the list is empty.
+    /// The statistics. All counts are 0 for an empty list.
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.list.is_empty()
+    pub fn profile(&self, k: usize) -> WordListProfile {
+        let lengths: Vec<usize> = self
+            .list
+            .iter()
+            .map(|word| word.borrow().chars().count())
+            .collect();
+        let total = lengths.iter().sum::<usize>();
+        let avg_len = if lengths.is_empty() {
+            0.0
+        } else {
+            f64::from(u32::try_from(total).unwrap_or(u32::MAX))
+                / f64::from(u32::try_from(lengths.len()).unwrap_or(u32::MAX))
+        };
+
+        let distinct: BTreeSet<&str> = self.list.iter().map(Borrow::borrow).collect();
+        let mut prefixes = BTreeSet::new();
+        let mut suffixes = BTreeSet::new();
+        let mut ends = BTreeSet::new();
+        for word in &distinct {
+            let prefix: String = word.chars().take(k).collect();
+            let suffix: String = word.chars().rev().take(k).collect();
+            prefixes.insert(prefix.clone());
+            suffixes.insert(suffix.clone());
+            ends.insert((prefix, suffix));
+        }
+
+        WordListProfile {
+            min_len: lengths.iter().copied().min().unwrap_or(0),
+            max_len: lengths.iter().copied().max().unwrap_or(0),
+            avg_len,
+            alphabet: distinct
+                .iter()
+                .flat_map(|word| word.chars())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            k,
+            distinct_prefixes: prefixes.len(),
+            distinct_suffixes: suffixes.len(),
+            distinct_ends: ends.len(),
+            duplicates: self.list.len() - distinct.len(),
+        }
+    }
+}
+
+impl WordList {
+    /// Creates a new empty word list.
+    #[must_use]
+    pub fn new() -> Self {
+        WordList { list: Vec::new() }
+    }
+
+    /// Tests whether all the words in the list are valid, handling repeated
+    /// words according to a policy.
+    ///
+    /// # Parameters
+    /// * `duplicate_policy` - How repeated words are handled.
+    ///
+    /// # Returns
+    /// * Ok(()) if the list is valid.
+    /// * Err(Error) if the list is invalid.
+    ///
+    /// # Errors
+    /// Will return Err if words are not:
+    /// * ASCII uppercase alphabetic
+    /// * unique, unless `duplicate_policy` allows repeats
+    /// * the list is empty
+    pub fn is_valid_with(&self, duplicate_policy: DuplicatePolicy) -> Result<(), Error> {
+        self.is_valid_under(&ValidationPolicy::new().duplicate_policy(duplicate_policy))
     }
 
     /// Adds a word to the list.
     ///
+    /// The word is not validated, which suits bulk loading. See `try_push` to
+    /// reject invalid words as they are added.
+    ///
     /// # Parameters
     /// * `word` - A word to be added to the list.
     pub fn push(&mut self, word: &str) {
         self.list.push(word.to_string());
     }
+
+    /// Adds a word to the list if it is valid, see `is_valid`.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be added to the list.
+    ///
+    /// # Errors
+    /// Will return `Err` without adding the word if it is not ASCII uppercase
+    /// alphabetic, or if it is already in the list.
+    pub fn try_push(&mut self, word: &str) -> Result<(), Error> {
+        self.try_push_for(word, Charset::Uppercase)
+    }
+
+    /// Adds a word to the list if it is valid for a character set, see
+    /// `is_valid_for`.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be added to the list.
+    /// * `charset` - The characters words may be made of.
+    ///
+    /// # Errors
+    /// Will return `Err` without adding the word if it has characters outside
+    /// of `charset`, or if it is already in the list.
+    pub fn try_push_for(&mut self, word: &str, charset: Charset) -> Result<(), Error> {
+        if !charset.contains_word(word, false) {
+            return Err(Error::new(Kind::WordListError(format!(
+                "Non {} word rejected: {word}",
+                charset.describe(false)
+            ))));
+        }
+        if self.contains(word) {
+            return Err(Error::new(Kind::WordListError(format!(
+                "Duplicate word rejected: {word}"
+            ))));
+        }
+        self.push(word);
+        Ok(())
+    }
+}
+
+impl<'a> WordList<&'a str> {
+    /// Tests whether all the words in the list are valid, handling repeated
+    /// words according to a policy.
+    ///
+    /// # Parameters
+    /// * `duplicate_policy` - How repeated words are handled.
+    ///
+    /// # Returns
+    /// * Ok(()) if the list is valid.
+    /// * Err(Error) if the list is invalid.
+    ///
+    /// # Errors
+    /// Will return Err if words are not:
+    /// * ASCII uppercase alphabetic
+    /// * unique, unless `duplicate_policy` allows repeats
+    /// * the list is empty
+    pub fn is_valid_with(&self, duplicate_policy: DuplicatePolicy) -> Result<(), Error> {
+        self.is_valid_under(&ValidationPolicy::new().duplicate_policy(duplicate_policy))
+    }
+
+    /// Adds a word to the list, without copying it.
+    ///
+    /// # Parameters
+    /// * `word` - A word to be added to the list.
+    pub fn push(&mut self, word: &'a str) {
+        self.list.push(word);
+    }
 }
 
-impl Default for WordList {
+impl ByteWordList {
+    /// Tests whether the byte strings in the list are valid, handling repeated
+    /// byte strings according to a policy. Any byte may appear in a byte string.
+    ///
+    /// # Parameters
+    /// * `duplicate_policy` - How repeated byte strings are handled.
+    ///
+    /// # Returns
+    /// * Ok(()) if the list is valid.
+    /// * Err(Error) if the list is invalid.
+    ///
+    /// # Errors
+    /// Will return Err if the byte strings are not unique, unless
+    /// `duplicate_policy` allows repeats, or if the list is empty.
+    pub fn is_valid_with(&self, duplicate_policy: DuplicatePolicy) -> Result<(), Error> {
+        self.validate_keys::<[u8]>(duplicate_policy, false)
+    }
+
+    /// Adds a byte string to the list.
+    ///
+    /// # Parameters
+    /// * `word` - A byte string to be added to the list.
+    pub fn push(&mut self, word: &[u8]) {
+        self.list.push(word.to_vec());
+    }
+}
+
+impl<W> Default for WordList<W> {
     fn default() -> Self {
-        WordList::new()
+        WordList { list: Vec::new() }
     }
 }
 
-impl FromIterator<String> for WordList {
-    /// Creates a new word list from an iterator of strings.
-    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
-        let mut wl = WordList::new();
-        for word in iter {
-            wl.push(&word);
+impl<W> From<Vec<W>> for WordList<W> {
+    /// Creates a new word list that takes ownership of a vector of words.
+    fn from(list: Vec<W>) -> Self {
+        WordList { list }
+    }
+}
+
+impl<W> Extend<W> for WordList<W> {
+    /// Adds words to the end of the list.
+    fn extend<I: IntoIterator<Item = W>>(&mut self, iter: I) {
+        self.list.extend(iter);
+    }
+}
+
+impl<W> IntoIterator for WordList<W> {
+    type Item = W;
+    type IntoIter = vec::IntoIter<W>;
+
+    /// Takes the words of the list, in list order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, W> IntoIterator for &'a WordList<W> {
+    type Item = &'a W;
+    type IntoIter = slice::Iter<'a, W>;
+
+    /// Iterates over the words of the list, in list order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.iter()
+    }
+}
+
+impl<W> Index<usize> for WordList<W> {
+    type Output = W;
+
+    /// Gets the word at a position of the list.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of range.
+    fn index(&self, index: usize) -> &W {
+        &self.list[index]
+    }
+}
+
+impl<W> FromIterator<W> for WordList<W> {
+    /// Creates a new word list from an iterator of words.
+    fn from_iter<I: IntoIterator<Item = W>>(iter: I) -> Self {
+        WordList {
+            list: iter.into_iter().collect(),
         }
-        wl
+    }
+}
+
+/// A step applied to each word by a `WordListBuilder`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    /// Remove leading and trailing whitespace.
+    Trim,
+
+    /// Convert ASCII letters to upper case.
+    Uppercase,
+
+    /// Drop the word if it is empty.
+    SkipEmpty,
+}
+
+/// Builds a word list from raw words, cleaning each word on the way in.
+///
+/// The cleaning steps are applied to each word in the order they were added to
+/// the builder. Repeated words are dropped last, once a word is fully cleaned.
+#[derive(Debug, Clone, Default)]
+pub struct WordListBuilder {
+    /// The steps applied to each word, in order.
+    steps: Vec<Step>,
+
+    /// Whether repeated words are dropped, keeping the first occurrence.
+    dedup: bool,
+}
+
+impl WordListBuilder {
+    /// Creates a builder that keeps words as they are.
+    #[must_use]
+    pub fn new() -> Self {
+        WordListBuilder::default()
+    }
+
+    /// Removes leading and trailing whitespace from each word.
+    #[must_use]
+    pub fn trim(mut self) -> Self {
+        self.steps.push(Step::Trim);
+        self
+    }
+
+    /// Converts the ASCII letters of each word to upper case.
+    #[must_use]
+    pub fn uppercase(mut self) -> Self {
+        self.steps.push(Step::Uppercase);
+        self
+    }
+
+    /// Drops empty words, such as blank lines.
+    #[must_use]
+    pub fn skip_empty(mut self) -> Self {
+        self.steps.push(Step::SkipEmpty);
+        self
+    }
+
+    /// Drops repeated words, keeping the first occurrence.
+    #[must_use]
+    pub fn dedup(mut self) -> Self {
+        self.dedup = true;
+        self
+    }
+
+    /// Builds a word list.
+    ///
+    /// # Parameters
+    /// * `words` - The raw words.
+    ///
+    /// # Returns
+    /// The cleaned word list. It is not validated, see `WordList::is_valid`.
+    pub fn build<I>(&self, words: I) -> WordList
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut seen = BTreeSet::new();
+        let mut word_list = WordList::new();
+        'words: for word in words {
+            let mut word = word.as_ref().to_string();
+            for step in &self.steps {
+                match step {
+                    Step::Trim => word = word.trim().to_string(),
+                    Step::Uppercase => word.make_ascii_uppercase(),
+                    Step::SkipEmpty if word.is_empty() => continue 'words,
+                    Step::SkipEmpty => {}
+                }
+            }
+            if self.dedup && !seen.insert(word.clone()) {
+                continue;
+            }
+            word_list.list.push(word);
+        }
+        word_list
     }
 }
 
@@ -116,6 +909,7 @@ mod tests {
     fn word_list_unit_test() {
         let mut wl: WordList = WordList::default();
         assert!(wl.is_empty());
+        assert_eq!(wl.max_word_len(), 0);
         if let Ok(()) = wl.is_valid() {
             panic!("Empty not detected.")
         }
@@ -133,6 +927,12 @@ mod tests {
         if let Ok(()) = wl.is_valid() {
             panic!("Duplicate not detected.");
         }
+        if let Err(e) = wl.is_valid_with(DuplicatePolicy::Allow) {
+            panic!("Unexpected invalid. {e}");
+        }
+        assert_eq!(wl.num_distinct(), 1);
+        wl.push("HI");
+        assert_eq!(wl.max_word_len(), 5);
         println!("{wl:?}");
 
         let mut wl2 = WordList::new();
@@ -151,4 +951,247 @@ mod tests {
             .collect();
         assert!(wl3.len() == 3);
     }
+
+    #[test]
+    fn word_list_charset_unit_test() {
+        assert!(Charset::Identifier.contains('_'));
+        assert!(!Charset::Uppercase.contains('8'));
+        assert_eq!(Charset::Uppercase.alphabet().map(|a| a.len()), Some(26));
+        assert_eq!(Charset::Identifier.alphabet().map(|a| a.len()), Some(37));
+        assert_eq!(Charset::Printable.alphabet().map(|a| a.len()), Some(94));
+        assert_eq!(Charset::Unicode.alphabet(), None);
+        assert!(Charset::Unicode.contains('\u{e9}'));
+        assert!(!Charset::Unicode.contains('\u{a0}'));
+        assert!(!Charset::Printable.contains(' '));
+
+        let wl: WordList = ["INT8", "FOR_EACH", "_"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        match wl.is_valid().unwrap_err().kind() {
+            Kind::WordListError(msg) => {
+                assert_eq!(msg, "Non ASCII upper case alphabetic word detected at 1.");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        if let Err(e) = wl.is_valid_for(DuplicatePolicy::Reject, Charset::Identifier) {
+            panic!("Unexpected invalid. {e}");
+        }
+
+        let wl: WordList = ["INT8", "int-8"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        match wl
+            .is_valid_under(
+                &ValidationPolicy::new()
+                    .charset(Charset::Identifier)
+                    .fold_case(true),
+            )
+            .unwrap_err()
+            .kind()
+        {
+            Kind::WordListError(msg) => {
+                assert_eq!(msg, "Non ASCII identifier word detected at 2.");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+    }
+
+    #[test]
+    fn word_list_builder_unit_test() {
+        let raw = ["  and ", "Begin", "", "AND", "begin\r", "  "];
+
+        let wl = WordListBuilder::new().build(raw);
+        assert_eq!(wl.list, raw);
+
+        let wl = WordListBuilder::new()
+            .trim()
+            .uppercase()
+            .skip_empty()
+            .dedup()
+            .build(raw);
+        assert_eq!(wl.list, ["AND", "BEGIN"]);
+        assert!(wl.is_valid().is_ok());
+
+        let wl = WordListBuilder::new().trim().uppercase().build(raw);
+        assert_eq!(wl.list, ["AND", "BEGIN", "", "AND", "BEGIN", ""]);
+
+        let wl = WordListBuilder::new()
+            .dedup()
+            .build(vec![String::from("A"); 3]);
+        assert_eq!(wl.list, ["A"]);
+        // Steps apply in the order they were added.
+        let wl = WordListBuilder::new().skip_empty().trim().build(["  "]);
+        assert_eq!(wl.list, [""]);
+        println!("{:?}", WordListBuilder::new().trim());
+    }
+
+    #[test]
+    fn byte_word_list_unit_test() {
+        let mut wl = ByteWordList::default();
+        assert!(wl.is_valid_with(DuplicatePolicy::Reject).is_err());
+        wl.push(b"\xffGET");
+        wl.push(b"PUT\x00");
+        assert_eq!(wl.len(), 2);
+        assert!(wl.is_valid_with(DuplicatePolicy::Reject).is_ok());
+
+        wl.push(b"\xffGET");
+        assert_eq!(wl.num_distinct(), 2);
+        match wl
+            .is_valid_with(DuplicatePolicy::Reject)
+            .unwrap_err()
+            .kind()
+        {
+            Kind::WordListError(msg) => {
+                assert_eq!(msg, "Duplicate word detected: \\xffGET at position 3");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        assert!(wl.is_valid_with(DuplicatePolicy::Allow).is_ok());
+
+        wl.push(b"put\x00");
+        assert!(wl
+            .validate_keys::<[u8]>(DuplicatePolicy::Allow, true)
+            .is_ok());
+        assert!(wl
+            .validate_keys::<[u8]>(DuplicatePolicy::Reject, false)
+            .is_err());
+    }
+
+    #[test]
+    fn word_list_collection_unit_test() {
+        let mut wl: WordList = vec!["AND".to_string(), "BEGIN".to_string()].into();
+        assert!(wl.contains("AND"));
+        assert!(!wl.contains("END"));
+        assert_eq!(wl.get(1).map(String::as_str), Some("BEGIN"));
+        assert_eq!(wl.get(2), None);
+        assert_eq!(wl[0], "AND");
+
+        wl.extend(["END".to_string(), "ELSE".to_string()]);
+        assert_eq!(wl.len(), 4);
+        assert_eq!(wl.iter().map(String::len).sum::<usize>(), 15);
+        assert_eq!((&wl).into_iter().count(), 4);
+
+        assert_eq!(wl.remove(1), Some("BEGIN".to_string()));
+        assert_eq!(wl.remove(3), None);
+        assert!(!wl.contains("BEGIN"));
+
+        let mut words = Vec::new();
+        for word in &wl {
+            words.push(word.as_str());
+        }
+        assert_eq!(words, ["AND", "END", "ELSE"]);
+        assert_eq!(wl.into_iter().collect::<Vec<_>>(), ["AND", "END", "ELSE"]);
+
+        let bytes: ByteWordList = vec![b"\xff".to_vec()].into();
+        assert!(bytes.contains(&b"\xff"[..]));
+    }
+
+    #[test]
+    fn word_list_profile_unit_test() {
+        let wl: WordList = ["ZOO", "ZAP", "PIZZA", "ZOO", "ZEBRA"]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let profile = wl.profile(1);
+        println!("{profile:?}");
+        assert_eq!(profile.min_len, 3);
+        assert_eq!(profile.max_len, 5);
+        assert!((profile.avg_len - 3.8).abs() < 1e-9);
+        assert_eq!(profile.alphabet, ['A', 'B', 'E', 'I', 'O', 'P', 'R', 'Z']);
+        assert_eq!(profile.k, 1);
+        assert_eq!(profile.distinct_prefixes, 2);
+        assert_eq!(profile.distinct_suffixes, 3);
+        assert_eq!(profile.distinct_ends, 4);
+        assert_eq!(profile.duplicates, 1);
+
+        let profile = wl.profile(2);
+        assert_eq!(profile.distinct_prefixes, 4);
+        assert_eq!(profile.distinct_ends, 4);
+
+        assert_eq!(
+            WordList::new().profile(1),
+            WordListProfile {
+                k: 1,
+                ..WordListProfile::default()
+            }
+        );
+    }
+
+    #[test]
+    fn word_list_try_push_unit_test() {
+        let mut wl = WordList::new();
+        wl.try_push("AND").unwrap();
+        wl.try_push("BEGIN").unwrap();
+        match wl.try_push("AND").unwrap_err().kind() {
+            Kind::WordListError(msg) => assert_eq!(msg, "Duplicate word rejected: AND"),
+            _ => panic!("Unexpected error type."),
+        }
+        match wl.try_push("INT8").unwrap_err().kind() {
+            Kind::WordListError(msg) => {
+                assert_eq!(msg, "Non ASCII upper case alphabetic word rejected: INT8");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        wl.try_push_for("INT8", Charset::Identifier).unwrap();
+        assert!(wl.try_push_for("int8", Charset::Identifier).is_err());
+        assert_eq!(wl.len(), 3);
+        assert!(wl
+            .is_valid_for(DuplicatePolicy::Reject, Charset::Identifier)
+            .is_ok());
+    }
+
+    #[test]
+    fn validation_policy_unit_test() {
+        let policy = ValidationPolicy::new();
+        assert_eq!(policy.get_charset(), Charset::Uppercase);
+        assert!(!policy.get_fold_case());
+        assert_eq!(policy.get_min_len(), 0);
+        assert_eq!(policy.get_max_len(), None);
+        assert_eq!(policy.get_duplicate_policy(), DuplicatePolicy::Reject);
+
+        let wl: WordList = ["AND", "begin", "ELSE", "And"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert!(wl.is_valid_under(&policy).is_err());
+        let policy = policy.fold_case(true);
+        assert!(wl.is_valid_under(&policy).is_err());
+        let policy = policy.duplicate_policy(DuplicatePolicy::Allow);
+        assert!(wl.is_valid_under(&policy).is_ok());
+
+        match wl.is_valid_under(&policy.min_len(4)).unwrap_err().kind() {
+            Kind::WordListError(msg) => {
+                assert_eq!(msg, "Word AND at 1 is shorter than 4 characters.");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        match wl.is_valid_under(&policy.max_len(4)).unwrap_err().kind() {
+            Kind::WordListError(msg) => {
+                assert_eq!(msg, "Word begin at 2 is longer than 4 characters.");
+            }
+            _ => panic!("Unexpected error type."),
+        }
+        assert!(wl.is_valid_under(&policy.min_len(3).max_len(5)).is_ok());
+    }
+
+    #[test]
+    fn borrowed_word_list_unit_test() {
+        const WORDS: [&str; 4] = ["AND", "BEGIN", "END", "Else"];
+
+        let mut wl: WordList<&str> = WORDS[..3].to_vec().into();
+        assert!(wl.is_valid().is_ok());
+        wl.push(WORDS[3]);
+        assert!(std::ptr::eq(wl[3], WORDS[3]));
+        assert!(wl.is_valid().is_err());
+        assert!(wl
+            .is_valid_case_insensitive(DuplicatePolicy::Reject)
+            .is_ok());
+        wl.push("AND");
+        assert!(wl.is_valid_with(DuplicatePolicy::Reject).is_err());
+        assert!(wl.is_valid_with(DuplicatePolicy::Allow).is_err());
+        assert_eq!(wl.max_word_len(), 5);
+        assert_eq!(wl.profile(1).duplicates, 1);
+    }
 }
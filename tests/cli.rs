@@ -0,0 +1,463 @@
+//! Runs the `msmp` binary, built with the `cli` feature.
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use common::pascal_keyword_subset_file;
+
+mod common;
+
+#[test]
+fn generate_stdout_cli_test() {
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json = String::from_utf8(output.stdout).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["table_len"], 8);
+    assert_eq!(
+        value["row_lookup_table"],
+        serde_json::json!([1, -6, -14, 0, -3])
+    );
+    assert_eq!(value["keys"].as_array().unwrap().len(), 8);
+    assert_eq!(
+        value["algorithm"],
+        serde_json::json!({
+            "name": "elc",
+            "parameters": {"elc_h1": 1, "elc_h2": 1, "num_vals": 26}
+        })
+    );
+    assert!(value["hash_function"]
+        .as_str()
+        .unwrap()
+        .ends_with("hash_value = (row_lookup_table[row_index] + col_index) % 8"));
+}
+
+#[test]
+fn generate_out_dir_cli_test() {
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("generate_out_dir_cli_test");
+    for (emit, file_name, table) in [
+        (
+            "c",
+            "pascal_keyword_subset.c",
+            "static const int16_t row_lookup_table[119] = {",
+        ),
+        (
+            "c-switch",
+            "pascal_keyword_subset.c",
+            "        return strcmp(word, \"ENTER\") == 0 ? TOKEN_ENTER : TOKEN_NONE;\n",
+        ),
+        (
+            "rust",
+            "pascal_keyword_subset.rs",
+            "pub static ROW_LOOKUP_TABLE: [i16; 119] = [",
+        ),
+        (
+            "rust-enum",
+            "pascal_keyword_subset.rs",
+            "    pub fn from_str(word: &str) -> Option<Keyword> {",
+        ),
+        ("python", "pascal_keyword_subset.py", "ROW_LOOKUP_TABLE = ["),
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+            .arg("generate")
+            .arg(pascal_keyword_subset_file())
+            .args(["--elc", "2", "--emit", emit, "-o"])
+            .arg(&out_dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let text = std::fs::read_to_string(out_dir.join(file_name)).unwrap();
+        assert!(text.contains(table), "{text}");
+        assert!(text.contains("\"ENTER\""));
+    }
+}
+
+#[test]
+fn generate_algorithm_cli_test() {
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("generate_algorithm_cli_test");
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--algorithm", "elc+len", "--param", "max_len=6", "-o"])
+        .arg(&out_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let table_file = out_dir.join("pascal_keyword_subset.json");
+    let value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&table_file).unwrap()).unwrap();
+    assert_eq!(value["algorithm"]["name"], "elc_with_length");
+    assert_eq!(value["algorithm"]["parameters"]["max_len"], 6);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("check")
+        .arg(pascal_keyword_subset_file())
+        .arg(&table_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--algorithm", "fnv", "--elc", "2"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("msmp: Spec Error: Unknown parameter 'elc' of the fnv algorithm"),
+        "{stderr}"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args([
+            "--algorithm",
+            "fnv",
+            "--param",
+            "num_rows=8",
+            "--param",
+            "num_cols=8",
+        ])
+        .args(["--emit", "rust-enum"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("msmp: Codegen Error: The fnv algorithm cannot describe"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn generate_algorithm_search_cli_test() {
+    let out_dir =
+        PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("generate_algorithm_search_cli_test");
+    for algorithm in ["cichelli", "elc+len", "position", "seeded"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+            .arg("generate")
+            .arg(pascal_keyword_subset_file())
+            .args(["--algorithm", algorithm, "-o"])
+            .arg(&out_dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{algorithm}");
+
+        let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+            .arg("check")
+            .arg(pascal_keyword_subset_file())
+            .arg(out_dir.join("pascal_keyword_subset.json"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{algorithm}");
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--algorithm", "fnv"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("parameter 'num_rows'"), "{stderr}");
+}
+
+#[test]
+fn generate_values_cli_test() {
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("generate_values_cli_test");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let values_file = out_dir.join("tokens.txt");
+    std::fs::write(
+        &values_file,
+        "AND 10\nBEGIN 11\nCHAR 12\nCONST 13\n\nELSE 14\nEND 15\nENTER 16\nEOF 17\n",
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--emit", "c", "--values"])
+        .arg(&values_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        text.contains("static const int value_table[8] = {\n"),
+        "{text}"
+    );
+    assert!(text.contains(": ENTER */\n"));
+
+    std::fs::write(&values_file, "AND 10\nBEGIN\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--emit", "c", "--values"])
+        .arg(&values_file)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("tokens.txt:2: expected a word and its value"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn generate_base_cli_test() {
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("generate_base_cli_test");
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--base", "1", "-o"])
+        .arg(&out_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let table_file = out_dir.join("pascal_keyword_subset.json");
+    let value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&table_file).unwrap()).unwrap();
+    assert_eq!(value["hash_base"], 1);
+    assert_eq!(value["keys"].as_array().unwrap().len(), 9);
+    assert_eq!(value["keys"][0], serde_json::Value::Null);
+    assert!(value["hash_function"]
+        .as_str()
+        .unwrap()
+        .ends_with("\nhash_value = hash_value + 1"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("check")
+        .arg(pascal_keyword_subset_file())
+        .arg(&table_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--base", "1", "--emit", "c"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        text.contains("static const char *const keys[1 + TABLE_LEN] = {NULL, "),
+        "{text}"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--base", &usize::MAX.to_string()])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("The hash base"), "{stderr}");
+
+    // Tables edited to a base that would not fit in memory.
+    let mut tables: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&table_file).unwrap()).unwrap();
+    tables["hash_base"] = serde_json::json!(u64::MAX);
+    let huge_base_file = out_dir.join("huge_base.json");
+    std::fs::write(&huge_base_file, tables.to_string()).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("check")
+        .arg(pascal_keyword_subset_file())
+        .arg(&huge_base_file)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("invalid 'hash_base'"), "{stderr}");
+}
+
+#[test]
+fn generate_self_test_cli_test() {
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--emit", "c-switch", "--self-test"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.contains("\n#ifdef MSMP_SELF_TEST\n"), "{text}");
+    assert!(text.contains("    failures += token_recognize(\"ENTER\") != TOKEN_ENTER;\n"));
+    assert!(text.ends_with("#endif /* MSMP_SELF_TEST */\n"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .args(["--emit", "rust-enum", "--self-test"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        text.contains("\n#[cfg(test)]\nmod keyword_self_test {\n"),
+        "{text}"
+    );
+    assert!(text.contains("            (\"ENTER\", Keyword::Enter),\n"));
+}
+
+#[test]
+fn generate_vectors_cli_test() {
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("generate_vectors_cli_test");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let csv_file = out_dir.join("vectors.csv");
+    let json_file = out_dir.join("vectors.json");
+    for (vectors_file, base) in [(&csv_file, "0"), (&json_file, "1")] {
+        let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+            .arg("generate")
+            .arg(pascal_keyword_subset_file())
+            .args(["--emit", "c", "--base", base, "--vectors"])
+            .arg(vectors_file)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    }
+
+    let csv = std::fs::read_to_string(&csv_file).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("word,hash_value"));
+    let hash_values: Vec<usize> = lines
+        .map(|line| line.split_once(',').unwrap().1.parse().unwrap())
+        .collect();
+    assert_eq!(hash_values.len(), 8);
+    assert!(csv.contains("\nAND,"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_file).unwrap()).unwrap();
+    let vectors = json.as_array().unwrap();
+    assert_eq!(vectors.len(), 8);
+    assert_eq!(vectors[0]["word"], "AND");
+    for (vector, hash_value) in vectors.iter().zip(hash_values) {
+        assert_eq!(vector["hash_value"], hash_value + 1);
+    }
+}
+
+#[test]
+fn usage_error_cli_test() {
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .args(["generate", "--emit", "go"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("msmp: invalid value 'go' for '--emit'\n"));
+    assert!(stderr.contains("Usage: msmp generate"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .args(["generate", "missing.txt"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn check_cli_test() {
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("check_cli_test");
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("generate")
+        .arg(pascal_keyword_subset_file())
+        .arg("-o")
+        .arg(&out_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let table_file = out_dir.join("pascal_keyword_subset.json");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("check")
+        .arg(pascal_keyword_subset_file())
+        .arg(&table_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .ends_with("is a minimal perfect hash of 8 words\n"));
+
+    // Tables written before the algorithm was recorded.
+    let mut tables: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&table_file).unwrap()).unwrap();
+    let fields = tables.as_object_mut().unwrap();
+    fields.remove("algorithm");
+    fields.insert("elc".to_string(), serde_json::json!(1));
+    let legacy_file = out_dir.join("legacy.json");
+    std::fs::write(&legacy_file, tables.to_string()).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("check")
+        .arg(pascal_keyword_subset_file())
+        .arg(&legacy_file)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // A keyword added after the table was generated.
+    let word_file = out_dir.join("words.txt");
+    let mut words = std::fs::read_to_string(pascal_keyword_subset_file()).unwrap();
+    words.push_str("\nEXIT\n");
+    std::fs::write(&word_file, words).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("check")
+        .arg(&word_file)
+        .arg(&table_file)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("is stale: 1 collisions"), "{stderr}");
+}
+
+#[test]
+fn analyze_cli_test() {
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("analyze")
+        .arg(pascal_keyword_subset_file())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    println!("{stdout}");
+    assert!(stdout.starts_with("words:    8 (0 duplicates)\n"));
+    assert!(stdout
+        .contains("\n  1           0      4            5             4        2.75          5\n"));
+    assert!(stdout
+        .contains("\n  2           0      7          119             2        1.25        238\n"));
+    assert!(stdout
+        .trim_end()
+        .ends_with("pascal_keyword_subset.txt --elc 1"));
+}
+
+#[test]
+fn bench_cli_test() {
+    let output = Command::new(env!("CARGO_BIN_EXE_msmp"))
+        .arg("bench")
+        .arg(pascal_keyword_subset_file())
+        .args(["--elc", "1..2", "--strategies", "first-fit,backtracking"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    println!("{stdout}");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("elc  strategy"));
+    assert!(lines[1].starts_with("  1  first-fit"));
+    assert!(lines[2].starts_with("  1  backtracking"));
+    assert!(lines[3].starts_with("  2  first-fit"));
+    assert!(lines[4].starts_with("  2  backtracking"));
+    assert!(lines[3].ends_with("          119  ok"));
+}
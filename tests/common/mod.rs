@@ -0,0 +1,42 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use msmp::{WordList, WordListBuilder};
+
+/// The file of the Pascal keyword subset used by the examples in the README.
+pub fn pascal_keyword_subset_file() -> PathBuf {
+    [".", "tests", "data", "pascal_keyword_subset.txt"]
+        .iter()
+        .collect()
+}
+
+/// Reads the Pascal keyword subset used by the examples in the README.
+pub fn pascal_keyword_subset() -> WordList {
+    let input_file_name = pascal_keyword_subset_file();
+    match load_word_list(&input_file_name) {
+        Some(word_list) => word_list,
+        None => panic!("Error processing {input_file_name:?}."),
+    }
+}
+
+/// Reads a word list, one word per line with surrounding whitespace trimmed.
+pub fn load_word_list(input_file_name: &Path) -> Option<WordList> {
+    let fh = match File::open(input_file_name) {
+        Ok(file) => file,
+        Err(err) => {
+            eprintln!("unable to read {input_file_name:?}: {err}.");
+            return None;
+        }
+    };
+
+    let reader = BufReader::new(fh);
+    let word_list = WordListBuilder::new()
+        .trim()
+        .build(reader.lines().map(|line| line.unwrap()));
+
+    Some(word_list)
+}
@@ -0,0 +1,90 @@
+// Generated by msmp.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Keyword {
+    /// "AND"
+    And,
+    /// "BEGIN"
+    Begin,
+    /// "CHAR"
+    Char,
+    /// "CONST"
+    Const,
+    /// "ELSE"
+    Else,
+    /// "END"
+    End,
+    /// "ENTER"
+    Enter,
+    /// "EOF"
+    Eof,
+}
+
+impl Keyword {
+    /// Recognizes a word.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(word: &str) -> Option<Keyword> {
+        static ROW_LOOKUP_TABLE: [i8; 5] = [1, -6, -14, 0, -3];
+        // The words in hash value order.
+        static WORDS: [Option<(&str, Keyword)>; 8] = [
+            Some(("END", Keyword::End)),
+            Some(("ELSE", Keyword::Else)),
+            Some(("EOF", Keyword::Eof)),
+            Some(("CHAR", Keyword::Char)),
+            Some(("AND", Keyword::And)),
+            Some(("CONST", Keyword::Const)),
+            Some(("ENTER", Keyword::Enter)),
+            Some(("BEGIN", Keyword::Begin)),
+        ];
+
+        if word.len() > 5 {
+            return None;
+        }
+        let row_index: usize = (u32::from(word.chars().next()?) as usize).checked_sub(65)?;
+        let col_index: usize = (u32::from(word.chars().next_back()?) as usize).checked_sub(65)?;
+        let sum = *ROW_LOOKUP_TABLE.get(row_index)? as isize + col_index as isize;
+        let hash_value = usize::try_from(sum).ok()? % 8;
+        match WORDS[hash_value] {
+            Some((key, keyword)) if key == word => Some(keyword),
+            _ => None,
+        }
+    }
+
+    /// Gets the word.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Keyword::And => "AND",
+            Keyword::Begin => "BEGIN",
+            Keyword::Char => "CHAR",
+            Keyword::Const => "CONST",
+            Keyword::Else => "ELSE",
+            Keyword::End => "END",
+            Keyword::Enter => "ENTER",
+            Keyword::Eof => "EOF",
+        }
+    }
+}
+
+#[cfg(test)]
+mod keyword_self_test {
+    use super::Keyword;
+
+    #[test]
+    fn recognizes_every_word() {
+        let words = [
+            ("AND", Keyword::And),
+            ("BEGIN", Keyword::Begin),
+            ("CHAR", Keyword::Char),
+            ("CONST", Keyword::Const),
+            ("ELSE", Keyword::Else),
+            ("END", Keyword::End),
+            ("ENTER", Keyword::Enter),
+            ("EOF", Keyword::Eof),
+        ];
+        for (word, variant) in words {
+            assert_eq!(Keyword::from_str(word), Some(variant), "{word}");
+            assert_eq!(variant.as_str(), word);
+        }
+        assert_eq!(Keyword::from_str(""), None);
+    }
+}
@@ -1,37 +1,22 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
 use std::path::PathBuf;
 
-use msmp::{generate_hash, ElcAlgorithm, WordList};
+use common::pascal_keyword_subset;
+use msmp::{
+    find_distinguishing_positions, generate_byte_hash, generate_byte_hash_with_options,
+    generate_hash, generate_hash_auto_with_options, generate_hash_seeded,
+    generate_hash_with_options, generate_key_hash, generate_key_hash_with_options, hash_fn_text,
+    rust_recognizer, rust_recognizer_self_test, verify, Backend, Backtracking, ByteElcAlgorithm,
+    ByteWordList, CaseFold, Charset, CichelliAlgorithm, ClosureAlgorithm, DuplicatePolicy,
+    ElcAlgorithm, ElcWithLength, Error, Expr, FnvAlgorithm, HashAlgorithm, HashTrace, Key, Kind,
+    Options, PerfectHashSet, Position, PositionAlgorithm, Profile, Rlt, RowTieBreak,
+    SeededAlgorithm, ValidationPolicy, Verification, WordList, JSON_FORMAT_VERSION, MAX_HASH_BASE,
+};
 
-fn load_word_list(input_file_name: &PathBuf) -> Option<WordList> {
-    let fh = match File::open(input_file_name) {
-        Ok(file) => file,
-        Err(err) => {
-            eprintln!("unable to read {:?}: {}.", input_file_name, err);
-            return None;
-        }
-    };
-
-    let reader = BufReader::new(fh);
-    let word_list: WordList = reader
-        .lines()
-        .map(|line| line.unwrap().trim().to_string())
-        .collect();
-
-    Some(word_list)
-}
+mod common;
 
 #[test]
 fn pascal_keyword_subset_integ_test() {
-    let input_file_name: PathBuf = [".", "tests", "data", "pascal_keyword_subset.txt"]
-        .iter()
-        .collect();
-    let word_list = match load_word_list(&input_file_name) {
-        Some(word_list) => word_list,
-        None => panic!("Error processing {:?}.", input_file_name),
-    };
+    let word_list = pascal_keyword_subset();
     assert_eq!(word_list.len(), 8);
 
     let hash_algorithm: ElcAlgorithm = ElcAlgorithm::default();
@@ -44,7 +29,1203 @@ fn pascal_keyword_subset_integ_test() {
             assert_eq!((hash.as_closure.cls)("BEGIN"), 7);
             assert_eq!((hash.as_closure.cls)("CHAR"), 3);
             assert_eq!((hash.as_closure.cls)("EOF"), 2);
+
+            let f = hash.as_fn.clone();
+            assert_eq!(f.table_size(), 8);
+            assert_eq!(f.hash("AND"), 4);
+            assert_eq!(f.try_hash("ENTER").unwrap(), 6);
+            assert!(f.try_hash("end").is_err());
+
+            let p = &hash.permutation;
+            assert_eq!(p.len(), 8);
+            assert_eq!(p.hash_of_word(0), Some(4));
+            assert_eq!(p.word_of_hash(7), Some(1));
+            for (i, word) in word_list.iter().enumerate() {
+                assert_eq!(p.hash_of_word(i), Some(f.hash(word)));
+            }
+
+            for (i, word) in word_list.iter().enumerate() {
+                assert_eq!(hash.lookup(word), Some(i));
+            }
+            assert_eq!(hash.lookup("end"), None);
+
+            let packed_array = &hash.packed_array;
+            assert_eq!(packed_array.len(), 8);
+            assert_eq!(packed_array.get(4), Some(1));
+            for (hash_value, word_index) in packed_array.iter().enumerate() {
+                assert_eq!(f.hash(&word_list[word_index - 1]), hash_value);
+            }
+        }
+        Err(e) => panic!("generate_hash failed {e}"),
+    }
+}
+
+#[test]
+fn row_bounds_integ_test() {
+    let word_list = pascal_keyword_subset();
+
+    let options = Options::new().row_bounds(true);
+    match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+        Ok(hash) => {
+            println!(":::\n{}:::", hash.as_string);
+            assert!(hash.as_string.contains("row_min_col = [3, 13, 17, 1, 3]\n"));
+            assert!(hash
+                .as_string
+                .contains("row_max_col = [3, 13, 19, 0, 17]\n"));
+            assert_eq!(hash.as_fn.try_hash("CHAR").unwrap(), 3);
+            assert_eq!(hash.as_fn.try_hash("EOF").unwrap(), 2);
+            assert!(hash.as_fn.try_hash("CHAZ").is_err());
+            assert!(hash.as_fn.try_hash("DONE").is_err());
+            assert_eq!(hash.as_fn.hash("CHAR"), 3);
+        }
+        Err(e) => panic!("generate_hash failed {e}"),
+    }
+}
+
+#[test]
+fn duplicate_words_integ_test() {
+    let mut word_list = pascal_keyword_subset();
+    let num_words = word_list.len();
+    word_list.push("CHAR");
+    word_list.push("EOF");
+
+    assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+    let options = Options::new().duplicate_policy(DuplicatePolicy::Allow);
+    match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+        Ok(hash) => {
+            assert_eq!(hash.as_fn.table_size(), num_words);
+            assert_eq!(hash.permutation.len(), num_words);
+            assert_eq!(hash.as_fn.hash("CHAR"), 3);
+            assert_eq!(hash.as_fn.hash("EOF"), 2);
+            assert_eq!(hash.permutation.hash_of_word(num_words), Some(3));
+            assert_eq!(hash.permutation.hash_of_word(num_words + 1), Some(2));
+            assert_eq!(hash.lookup("EOF"), hash.permutation.word_of_hash(2));
+            assert!(hash.lookup("EOF").is_some_and(|i| i < num_words));
+        }
+        Err(e) => panic!("generate_hash failed {e}"),
+    }
+}
+
+#[test]
+fn slack_integ_test() {
+    let word_list: WordList = ["AXXA", "AXXC", "AXXD", "BXXA", "BXXC", "BXXZ"]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+
+    assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+    let options = Options::new().slack_percent(50).store_keys(true);
+    match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+        Ok(hash) => {
+            assert_eq!(hash.table_len, 9);
+            assert_eq!(hash.as_fn.table_size(), 9);
+            assert_eq!(hash.permutation.len(), 9);
+            let mut hash_values: Vec<usize> = word_list
+                .iter()
+                .map(|word| (hash.as_closure.cls)(word))
+                .collect();
+            hash_values.sort_unstable();
+            hash_values.dedup();
+            assert_eq!(hash_values.len(), word_list.len());
+            assert!(hash_values.iter().all(|h| *h < hash.table_len));
+            for hash_value in 0..hash.table_len {
+                let word = hash.word_at(hash_value);
+                assert_eq!(word.is_some(), hash_values.contains(&hash_value));
+            }
+        }
+        Err(e) => panic!("generate_hash failed {e}"),
+    }
+
+    // Slack grows the table past lengths where two columns of a row collide.
+    let word_list = pascal_keyword_subset();
+    for slack_percent in [0, 5, 25, 50, 75, 100, 200] {
+        let options = Options::new().slack_percent(slack_percent);
+        let hash = generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options)
+            .unwrap_or_else(|e| panic!("slack {slack_percent}% failed {e}"));
+        assert!(hash.table_len >= word_list.len() * (100 + slack_percent) / 100);
+    }
+
+    let options = Options::new().slack_percent(usize::MAX / 4);
+    match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+        Ok(_) => panic!("Overflowing slack not detected."),
+        Err(e) => match e.kind() {
+            Kind::OneDPackedArrayError(s) => assert!(s.ends_with("slack is too large.")),
+            _ => panic!("Unexpected error type {e}."),
+        },
+    }
+}
+
+#[test]
+fn store_keys_integ_test() {
+    let word_list = pascal_keyword_subset();
+
+    let hash = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+    assert_eq!(hash.as_fn.get_keys(), None);
+    assert_eq!((hash.as_checked_closure.cls)("CHAZ"), Some(3));
+    assert_eq!((hash.as_checked_closure.cls)("end"), None);
+    assert_eq!(hash.try_hash("CHAR").unwrap(), 3);
+    // The plain closure gives invalid words a hash value, try_hash does not.
+    assert!((hash.as_closure.cls)("end") < hash.table_len);
+    assert!(hash.try_hash("end").is_err());
+    assert!(!hash.as_string.contains("key_table"));
+    assert_eq!(hash.word_at(3), None);
+    // Without the keys a non member may land on another word's position.
+    assert_eq!(hash.lookup("CHAZ"), hash.permutation.word_of_hash(3));
+
+    let options = Options::new().store_keys(true);
+    match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+        Ok(hash) => {
+            println!(":::\n{}:::", hash.as_string);
+            assert!(hash.as_string.contains("key_table = [\""));
+            assert!(hash
+                .as_string
+                .ends_with("if word != key_table[hash_value]:\n    not_found\n"));
+            for word in &word_list {
+                assert_eq!(
+                    (hash.as_checked_closure.cls)(word),
+                    Some(hash.as_fn.hash(word))
+                );
+            }
+            assert_eq!((hash.as_checked_closure.cls)("CHAZ"), None);
+            assert_eq!(hash.as_fn.find("CHAZ"), None);
+            assert_eq!(hash.as_fn.find("CHAR"), Some(3));
+            assert_eq!(hash.lookup("CHAZ"), None);
+            for (i, word) in word_list.iter().enumerate() {
+                assert_eq!(hash.lookup(word), Some(i));
+            }
+            assert_eq!(hash.word_at(3), Some("CHAR"));
+            assert_eq!(hash.word_at(8), None);
+            for word in &word_list {
+                assert_eq!(hash.word_at(hash.as_fn.hash(word)), Some(word.as_str()));
+            }
+        }
+        Err(e) => panic!("generate_hash failed {e}"),
+    }
+}
+
+#[test]
+fn case_fold_integ_test() {
+    let words = [
+        "And", "begin", "CHAR", "const", "Else", "end", "enTer", "eof",
+    ];
+    let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+
+    assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+    let options = Options::new().store_keys(true);
+    let hash_algorithm = CaseFold::new(ElcAlgorithm::default());
+    match generate_hash_with_options(&word_list, hash_algorithm, &options) {
+        Ok(hash) => {
+            println!(":::\n{}:::", hash.as_string);
+            assert!(hash
+                .as_string
+                .contains("word_letters = upper(word_letters)"));
+            assert!(hash.as_string.contains("\"BEGIN\""));
+            assert!(hash
+                .as_string
+                .ends_with("if upper(word) != key_table[hash_value]:\n    not_found\n"));
+            assert_eq!(
+                (hash.as_closure.cls)("begin"),
+                (hash.as_closure.cls)("BEGIN")
+            );
+            for word in words {
+                let hash_value = hash.as_fn.hash(word);
+                assert_eq!(
+                    hash.as_fn.find(&word.to_ascii_lowercase()),
+                    Some(hash_value)
+                );
+                assert_eq!(
+                    hash.as_fn.find(&word.to_ascii_uppercase()),
+                    Some(hash_value)
+                );
+                assert_eq!(hash.lookup(word), hash.lookup(&word.to_ascii_uppercase()));
+            }
+            assert_eq!(hash.as_fn.find("chaz"), None);
         }
         Err(e) => panic!("generate_hash failed {e}"),
     }
+
+    let word_list: WordList = ["BEGIN", "begin"]
+        .iter()
+        .map(|w| (*w).to_string())
+        .collect();
+    assert!(word_list.is_valid().is_err());
+    assert!(word_list
+        .is_valid_case_insensitive(DuplicatePolicy::Reject)
+        .is_err());
+    assert!(word_list
+        .is_valid_case_insensitive(DuplicatePolicy::Allow)
+        .is_ok());
+    let options = Options::new().duplicate_policy(DuplicatePolicy::Allow);
+    let hash =
+        generate_hash_with_options(&word_list, CaseFold::new(ElcAlgorithm::default()), &options)
+            .unwrap();
+    assert_eq!(hash.lookup("BEGIN"), Some(0));
+    assert_eq!(hash.lookup("begin"), Some(0));
+}
+
+#[test]
+fn validation_policy_integ_test() {
+    let word_list: WordList = ["Begin", "END", "else", "End"]
+        .iter()
+        .map(|w| (*w).to_string())
+        .collect();
+    assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+    let policy = ValidationPolicy::new()
+        .fold_case(true)
+        .duplicate_policy(DuplicatePolicy::Allow);
+    let options = Options::new().validation_policy(policy);
+    let hash = generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+    assert_eq!(hash.lookup("BEGIN"), Some(0));
+    assert_eq!(hash.lookup("end"), Some(1));
+    assert!(hash.as_string.contains("upper(word_letters)"));
+
+    let options = Options::new().validation_policy(policy.max_len(4));
+    assert!(generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).is_err());
+}
+
+#[test]
+fn borrowed_word_list_integ_test() {
+    static KEYWORDS: [&str; 6] = ["AND", "BEGIN", "CHAR", "DO", "ELSE", "END"];
+
+    let word_list: WordList<&str> = KEYWORDS.into_iter().collect();
+    let algorithm = ElcAlgorithm::from_word_list(1, &word_list);
+    let hash = generate_hash(&word_list, algorithm).unwrap();
+    for (i, word) in KEYWORDS.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+}
+
+#[test]
+fn pascal_keywords_integ_test() {
+    // The reserved words of Pascal, the keyword set of Cichelli's paper.
+    let word_list: WordList = [
+        "AND",
+        "ARRAY",
+        "BEGIN",
+        "CASE",
+        "CONST",
+        "DIV",
+        "DO",
+        "DOWNTO",
+        "ELSE",
+        "END",
+        "FILE",
+        "FOR",
+        "FUNCTION",
+        "GOTO",
+        "IF",
+        "IN",
+        "LABEL",
+        "MOD",
+        "NIL",
+        "NOT",
+        "OF",
+        "OR",
+        "PACKED",
+        "PROCEDURE",
+        "PROGRAM",
+        "RECORD",
+        "REPEAT",
+        "SET",
+        "THEN",
+        "TO",
+        "TYPE",
+        "UNTIL",
+        "VAR",
+        "WHILE",
+        "WITH",
+    ]
+    .iter()
+    .map(|w| (*w).to_string())
+    .collect();
+    assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+    let hash = generate_hash(&word_list, ElcWithLength::from_word_list(1, &word_list)).unwrap();
+    assert_eq!(hash.as_fn.table_size(), 35);
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+
+    let hash_algorithm = find_distinguishing_positions(&word_list).unwrap();
+    assert_eq!(hash_algorithm.get_max_len(), Some(10));
+    let hash = generate_hash(&word_list, hash_algorithm).unwrap();
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+
+    let hash_algorithm = CichelliAlgorithm::from_word_list(&word_list).unwrap();
+    let hash = generate_hash(&word_list, hash_algorithm).unwrap();
+    println!(":::\n{}:::", hash.as_string);
+    assert_eq!(hash.as_fn.table_size(), 35);
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+}
+
+#[test]
+fn position_algorithm_integ_test() {
+    let word_list: WordList = ["CAT", "COT", "CUT", "CAP", "COP"]
+        .iter()
+        .map(|w| (*w).to_string())
+        .collect();
+    assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+    let hash_algorithm =
+        PositionAlgorithm::from_word_list([Position::Start(1)], [Position::End(0)], &word_list);
+    let hash = generate_hash(&word_list, hash_algorithm).unwrap();
+    assert!(hash.as_string.contains("for x in [word_letters[1]]:"));
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+}
+
+#[test]
+fn identifier_charset_integ_test() {
+    let words = [
+        "INT8", "INT16", "INT32", "FOR_EACH", "FLOAT4", "FLOAT8", "UTF8", "_",
+    ];
+    let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+    assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+    let options = Options::new().charset(Charset::Identifier);
+    let auto = generate_hash_auto_with_options(&word_list, &options).unwrap();
+    let hash = &auto.hash_data;
+    let mut hash_values: Vec<usize> = words.iter().map(|w| hash.as_fn.hash(w)).collect();
+    hash_values.sort_unstable();
+    assert_eq!(hash_values, (0..words.len()).collect::<Vec<_>>());
+}
+
+#[test]
+fn custom_alphabet_integ_test() {
+    let words = ["==", "!=", "<=", ">=", "<<", ">>", "->", "=>"];
+    let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+    assert!(word_list.is_valid().is_err());
+
+    let hash_algorithm = ElcAlgorithm::with_alphabet(1, "=!<>-".chars()).unwrap();
+    hash_algorithm.check_word_list(&word_list).unwrap();
+    let options = Options::new().charset(Charset::Printable);
+    match generate_hash_with_options(&word_list, hash_algorithm, &options) {
+        Ok(hash) => {
+            println!(":::\n{}:::", hash.as_string);
+            assert!(hash.as_string.contains("alphabet = \"!-<=>\""));
+            let mut hash_values: Vec<usize> = words.iter().map(|w| hash.as_fn.hash(w)).collect();
+            hash_values.sort_unstable();
+            assert_eq!(hash_values, (0..words.len()).collect::<Vec<_>>());
+        }
+        Err(e) => panic!("generate_hash failed {e}"),
+    }
+}
+
+#[test]
+fn unicode_charset_integ_test() {
+    let words = [
+        "\u{e4}pfel",
+        "birne",
+        "kirsche",
+        "\u{df}tra\u{df}e",
+        "\u{3b1}\u{3bb}\u{3c6}\u{3b1}",
+        "\u{3c9}\u{3bc}\u{3ad}\u{3b3}\u{3b1}",
+        "\u{1f34e}",
+    ];
+    let word_list: WordList = words.iter().map(|w| (*w).to_string()).collect();
+    assert!(word_list.is_valid().is_err());
+    assert!(word_list
+        .is_valid_for(DuplicatePolicy::Reject, Charset::Unicode)
+        .is_ok());
+
+    let options = Options::new().charset(Charset::Unicode);
+    let auto = generate_hash_auto_with_options(&word_list, &options).unwrap();
+    let hash = &auto.hash_data;
+    let mut hash_values: Vec<usize> = words.iter().map(|w| hash.as_fn.hash(w)).collect();
+    hash_values.sort_unstable();
+    assert_eq!(hash_values, (0..words.len()).collect::<Vec<_>>());
+}
+
+#[test]
+fn byte_keys_integ_test() {
+    let words: [&[u8]; 6] = [
+        b"\x01HELO",
+        b"\x02DATA\xff",
+        b"\x03QUIT",
+        b"\x80\x00",
+        b"\xfe\xfe\x01",
+        b"\x7fPING\x80",
+    ];
+    let word_list: ByteWordList = words.iter().map(|w| w.to_vec()).collect();
+    assert!(word_list.is_valid_with(DuplicatePolicy::Reject).is_ok());
+
+    let hash_algorithm = ByteElcAlgorithm::from_word_list(1, &word_list);
+    let options = Options::new().store_keys(true);
+    match generate_byte_hash_with_options(&word_list, hash_algorithm, &options) {
+        Ok(hash) => {
+            println!(":::\n{}:::", hash.as_string);
+            assert!(hash.as_string.contains("b\"\\x01HELO\""));
+            let mut hash_values: Vec<usize> =
+                words.iter().map(|w| (hash.as_closure.cls)(w)).collect();
+            hash_values.sort_unstable();
+            assert_eq!(hash_values, (0..words.len()).collect::<Vec<_>>());
+            for (i, word) in words.iter().enumerate() {
+                let hash_value = hash.as_fn.hash(word);
+                assert_eq!((hash.as_checked_closure.cls)(word), Some(hash_value));
+                assert_eq!(hash.word_at(hash_value), Some(*word));
+                assert_eq!(hash.lookup(word), Some(i));
+            }
+            assert_eq!(hash.as_fn.find(b"\x01HELP"), None);
+            assert_eq!(hash.max_word_len, 6);
+        }
+        Err(e) => panic!("generate_byte_hash failed {e}"),
+    }
+
+    let word_list: ByteWordList = [b"\xffA".to_vec(), b"\xffA".to_vec()].into_iter().collect();
+    assert!(
+        generate_byte_hash(&word_list, ByteElcAlgorithm::from_word_list(1, &word_list)).is_err()
+    );
+}
+
+/// A user defined key, a square of a chess board.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct Square {
+    file: u8,
+    rank: u8,
+}
+
+impl Key for Square {}
+
+/// Hashes a square by its file and rank.
+struct SquareAlgorithm;
+
+impl HashAlgorithm<Square> for SquareAlgorithm {
+    fn h1(&self, square: &Square) -> Result<usize, Error> {
+        Ok(usize::from(square.file))
+    }
+
+    fn h2(&self, square: &Square) -> Result<usize, Error> {
+        Ok(usize::from(square.rank))
+    }
+
+    fn h1_as_text(&self) -> String {
+        "square.file".to_string()
+    }
+
+    fn h2_as_text(&self) -> String {
+        "square.rank".to_string()
+    }
+}
+
+#[test]
+fn user_key_integ_test() {
+    let squares: Vec<Square> = [(0, 0), (0, 1), (4, 2), (4, 3), (7, 4), (7, 6), (3, 5)]
+        .iter()
+        .map(|&(file, rank)| Square { file, rank })
+        .collect();
+    let word_list: WordList<Square> = squares.iter().cloned().collect();
+
+    let options = Options::new().store_keys(true);
+    match generate_key_hash_with_options(&word_list, SquareAlgorithm, &options) {
+        Ok(hash) => {
+            println!(":::\n{}:::", hash.as_string);
+            assert!(hash.as_string.contains("row_index = square.file"));
+            assert!(hash.as_string.contains("Square { file: 3, rank: 5 }"));
+            let mut hash_values: Vec<usize> = squares.iter().map(|s| hash.as_fn.hash(s)).collect();
+            hash_values.sort_unstable();
+            assert_eq!(hash_values, (0..squares.len()).collect::<Vec<_>>());
+            for (i, square) in squares.iter().enumerate() {
+                assert_eq!(hash.lookup(square), Some(i));
+                assert_eq!(hash.word_at(hash.as_fn.hash(square)), Some(square));
+            }
+            assert_eq!(hash.as_fn.find(&Square { file: 0, rank: 3 }), None);
+        }
+        Err(e) => panic!("generate_key_hash failed {e}"),
+    }
+
+    let word_list: WordList<Square> = vec![Square::default(), Square::default()]
+        .into_iter()
+        .collect();
+    assert!(generate_key_hash(&word_list, SquareAlgorithm).is_err());
+}
+
+#[test]
+fn perfect_hash_set_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let words: Vec<String> = word_list.iter().cloned().collect();
+
+    let set = match PerfectHashSet::try_from(word_list) {
+        Ok(set) => set,
+        Err(e) => panic!("PerfectHashSet::try_from failed {e}"),
+    };
+    assert_eq!(set.len(), words.len());
+    assert!(set.iter().eq(words.iter().map(String::as_str)));
+    assert!(words.iter().all(|word| set.contains(word)));
+    assert!(!set.contains("CHAZ"));
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&set).unwrap();
+        assert!(json.starts_with(r#"["AND","BEGIN","#));
+        let copy: PerfectHashSet = serde_json::from_str(&json).unwrap();
+        assert!(words.iter().all(|word| copy.hash(word) == set.hash(word)));
+        assert!(serde_json::from_str::<PerfectHashSet>(r#"["AND","AND"]"#).is_err());
+    }
+}
+
+#[test]
+fn seeded_algorithm_integ_test() {
+    let word_list: WordList = [
+        "ALPHA", "AREA", "AURA", "AQUA", "ARENA", "ALGEBRA", "AGENDA",
+    ]
+    .iter()
+    .map(|w| (*w).to_string())
+    .collect();
+    assert!(generate_hash(&word_list, ElcAlgorithm::default()).is_err());
+
+    let seeded = generate_hash_seeded(&word_list, &Options::default(), 1000).unwrap();
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(seeded.hash_data.lookup(word), Some(i));
+    }
+
+    // The found seed reproduces the hash.
+    let hash_algorithm = SeededAlgorithm::new(seeded.hash_algorithm.get_seed(), 7, 7);
+    let hash = generate_hash(&word_list, hash_algorithm).unwrap();
+    assert_eq!(hash.as_string, seeded.hash_data.as_string);
+}
+
+#[test]
+fn fnv_algorithm_integ_test() {
+    let word_list: WordList = [
+        "PRE_A_POST",
+        "PRE_B_POST",
+        "PRE_C_POST",
+        "PRE_D_POST",
+        "PRE_E_POST",
+    ]
+    .iter()
+    .map(|w| (*w).to_string())
+    .collect();
+    let options = Options::new().charset(Charset::Identifier);
+    assert!(generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).is_err());
+
+    let hash = generate_hash_with_options(&word_list, FnvAlgorithm::new(5, 8), &options).unwrap();
+    assert!(hash.as_string.contains("0x100000001b3"));
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+}
+
+#[test]
+fn chd_backend_integ_test() {
+    let word_list: WordList = (0..20_000).map(|i| format!("KEY_{i}")).collect();
+    let options = Options::new()
+        .charset(Charset::Identifier)
+        .backend(Backend::Chd);
+    let hash = generate_hash_with_options(&word_list, FnvAlgorithm::new(5_000, 1 << 20), &options)
+        .unwrap();
+    assert_eq!(hash.table_len, word_list.len());
+    assert!(hash.as_string.contains("row_multiplier = ["));
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+}
+
+#[test]
+fn chm_backend_integ_test() {
+    let word_list: WordList = (0..20_000).map(|i| format!("KEY_{i}")).collect();
+    let options = Options::new()
+        .charset(Charset::Identifier)
+        .backend(Backend::Chm);
+    let hash = generate_hash_with_options(&word_list, FnvAlgorithm::new(5_000, 1 << 20), &options)
+        .unwrap();
+    assert_eq!(hash.table_len, word_list.len());
+    assert!(hash.as_string.contains("vertex_values = ["));
+    assert!(hash.as_string.contains("function mix(z):"));
+    let text = hash_fn_text(&word_list, &hash, Profile::Size).unwrap();
+    assert!(text.starts_with("vertex_values: i16[24603] = ["));
+    assert!(text.contains("\n    hash_value = (vertex_values[mix(x ^ "));
+    // Words hash to their position in the word list.
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.as_fn.hash(word), i);
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+
+    let word_list = pascal_keyword_subset();
+    let options = Options::new().backend(Backend::Chm).store_keys(true);
+    let hash = generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+    assert_eq!(hash.lookup("ENDX"), None);
+}
+
+#[test]
+fn closure_algorithm_integ_test() {
+    let word_list: WordList = ["ADD", "AND", "BEGIN", "END"]
+        .iter()
+        .map(|w| (*w).to_string())
+        .collect();
+    let hash_algorithm = ClosureAlgorithm::new(
+        |word: &str| usize::from(word.as_bytes()[0] - b'A'),
+        |word: &str| usize::from(word.as_bytes()[1] - b'A'),
+        "\n        val = ord(word[0]) - ord('A')",
+        "\n        val = ord(word[1]) - ord('A')",
+    );
+    let hash = generate_hash(&word_list, hash_algorithm).unwrap();
+    assert!(hash
+        .as_string
+        .contains("row_index = \n        val = ord(word[0]) - ord('A')"));
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(hash.lookup(word), Some(i));
+    }
+}
+
+#[test]
+fn verify_integ_test() {
+    let word_list: WordList = ["AND", "BEGIN", "END", "EXIT", "FOR"]
+        .iter()
+        .map(|w| (*w).to_string())
+        .collect();
+    let hash_algorithm = ElcAlgorithm::default();
+    let hash = generate_hash(&word_list, hash_algorithm.clone()).unwrap();
+    let report = verify(&word_list, hash.as_fn.get_rlt(), &hash_algorithm).unwrap();
+    assert!(report.is_minimal());
+    for (i, word) in word_list.iter().enumerate() {
+        assert_eq!(report.get_hash_values()[i], hash.as_fn.hash(word));
+    }
+
+    // A hand-edited table is checked without regenerating the hash.
+    let rlt = Rlt::with_values(vec![0; hash.as_fn.get_rlt().len()], word_list.len());
+    let report = verify(&word_list, &rlt, &hash_algorithm).unwrap();
+    println!("{report:?}");
+    assert!(!report.is_perfect());
+}
+
+#[test]
+fn verification_integ_test() {
+    let word_list: WordList = ["AND", "BEGIN", "END", "EXIT", "FOR"]
+        .iter()
+        .map(|w| (*w).to_string())
+        .collect();
+    let verified = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+    for verification in [Verification::DebugOnly, Verification::Never] {
+        let options = Options::new().verification(verification);
+        let hash =
+            generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+        assert_eq!(hash.as_string, verified.as_string);
+    }
+}
+
+#[test]
+fn normalize_rlt_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let hash = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+    assert_eq!(hash.as_fn.get_rlt().as_slice(), &[1, -6, -14, 0, -3]);
+    assert!(!hash.as_fn.get_rlt().is_normalized());
+
+    let options = Options::new().normalize_rlt(true);
+    let normalized =
+        generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+    println!("{}", normalized.as_string);
+    let rlt = normalized.as_fn.get_rlt();
+    assert!(rlt.is_normalized());
+    assert_eq!(rlt.as_slice(), &[1, 2, 2, 0, 5]);
+    assert!(normalized
+        .as_string
+        .contains("row_lookup_table = [1, 2, 2, 0, 5]"));
+    for word in &word_list {
+        assert_eq!(normalized.as_fn.hash(word), hash.as_fn.hash(word));
+    }
+}
+
+#[test]
+fn sparse_rlt_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let hash_algorithm = ElcAlgorithm::new(2, 26);
+    let hash = generate_hash(&word_list, hash_algorithm.clone()).unwrap();
+    assert_eq!(hash.as_fn.get_rlt().len(), 119);
+
+    let options = Options::new().sparse_rlt(true).row_bounds(true);
+    let sparse = generate_hash_with_options(&word_list, hash_algorithm, &options).unwrap();
+    println!("{}", sparse.as_string);
+    let rlt = sparse.as_fn.get_rlt();
+    assert_eq!(rlt.get_rows(), Some(&[13, 30, 59, 66, 115, 117, 118][..]));
+    assert_eq!(rlt.len(), 7);
+    assert!(sparse
+        .as_string
+        .starts_with("row_keys = [13, 30, 59, 66, 115, 117, 118]\n"));
+    assert!(sparse
+        .as_string
+        .contains("row_index = binary_search(row_keys, row_index)\n"));
+    for word in &word_list {
+        assert_eq!(sparse.as_fn.hash(word), hash.as_fn.hash(word));
+    }
+    // A row that is not kept is rejected.
+    assert!(sparse.as_fn.try_hash("XYZ").is_err());
+
+    let text = hash_fn_text(&word_list, &sparse, Profile::Size).unwrap();
+    assert!(text.starts_with("row_keys: u8[7] = ["));
+    assert!(text.contains("\nrow_lookup_table: i16[7] = ["));
+}
+
+#[test]
+fn two_level_rlt_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let hash_algorithm = ElcAlgorithm::new(2, 26);
+    let options = Options::new().two_level_rlt(true);
+    let hash = generate_hash_with_options(&word_list, hash_algorithm, &options).unwrap();
+    println!("{}", hash.as_string);
+    let rlt = hash.as_fn.get_rlt();
+    let two_level = rlt.get_two_level().unwrap();
+    assert_eq!(rlt.len(), 119);
+    assert_eq!(two_level.size(), 58);
+    for row_index in 0..rlt.len() {
+        assert_eq!(two_level.get(row_index), rlt.get(row_index));
+    }
+    assert!(hash.as_string.starts_with("row_block = [0, 0, 0, 4, "));
+    assert!(hash.as_string.contains(
+        "hash_value = (row_lookup_table[row_block[row_index >> 2] + (row_index & 3)] + col_index) % 8"
+    ));
+
+    let text = hash_fn_text(&word_list, &hash, Profile::Size).unwrap();
+    assert!(text.starts_with("row_block: u8[30] = ["));
+    assert!(text.contains("\nrow_lookup_table: i16[28] = ["));
+}
+
+#[test]
+fn hash_base_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let hash = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+
+    let options = Options::new().hash_base(1).store_keys(true);
+    let one_based =
+        generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+    println!("{}", one_based.as_string);
+    let rlt = one_based.as_fn.get_rlt();
+    assert_eq!(rlt.get_hash_base(), 1);
+    assert_eq!(rlt.as_slice(), hash.as_fn.get_rlt().as_slice());
+    assert!(one_based.as_string.ends_with(
+        "if word != key_table[hash_value]:\n    not_found\nhash_value = hash_value + 1\n"
+    ));
+    for (word_index, word) in word_list.iter().enumerate() {
+        let hash_value = one_based.as_fn.hash(word);
+        assert_eq!(hash_value, hash.as_fn.hash(word) + 1);
+        assert_eq!(one_based.explain(word).unwrap().hash_value, hash_value);
+        assert_eq!(one_based.as_fn.find(word), Some(hash_value));
+        assert_eq!(one_based.lookup(word), Some(word_index));
+        assert_eq!(one_based.word_at(hash_value), Some(word.as_str()));
+    }
+    assert_eq!(one_based.word_at(0), None);
+    assert!(verify(&word_list, rlt, &ElcAlgorithm::default())
+        .unwrap()
+        .is_minimal());
+
+    let text = hash_fn_text(&word_list, &one_based, Profile::Size).unwrap();
+    assert!(text.ends_with("    return hash_value + 1\n"));
+    let spec: serde_json::Value = serde_json::from_str(&one_based.to_json()).unwrap();
+    assert_eq!(spec["hash_base"], 1);
+
+    let chm = generate_hash_with_options(
+        &word_list,
+        ElcAlgorithm::default(),
+        &options.backend(Backend::Chm),
+    )
+    .unwrap();
+    let mut hash_values: Vec<usize> = word_list.iter().map(|w| chm.as_fn.hash(w)).collect();
+    hash_values.sort_unstable();
+    assert_eq!(hash_values, (1..=8).collect::<Vec<_>>());
+
+    for hash_base in [MAX_HASH_BASE + 1, usize::MAX] {
+        let options = Options::new().hash_base(hash_base);
+        match generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options) {
+            Ok(_) => panic!("A hash base of {hash_base} should be rejected"),
+            Err(e) => match e.kind() {
+                Kind::HashError(s) => assert!(s.starts_with("The hash base"), "{s}"),
+                _ => panic!("Unexpected error type"),
+            },
+        }
+    }
+    let options = Options::new().hash_base(MAX_HASH_BASE);
+    let hash = generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+    assert_eq!(hash.as_fn.hash("AND") / MAX_HASH_BASE, 1);
+}
+
+#[test]
+fn power_of_two_table_integ_test() {
+    let word_list: WordList = ["AND", "BEGIN", "CHAR", "CONST", "ELSE", "END"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    let options = Options::new().power_of_two_table(true);
+    let hash = generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+    println!("{}", hash.as_string);
+    assert_eq!(hash.table_len, 8);
+    assert_eq!(hash.as_fn.get_rlt().get_mask(), Some(7));
+    assert!(hash
+        .as_string
+        .ends_with("hash_value = (row_lookup_table[row_index] + col_index) & 7\n"));
+    let mut hash_values: Vec<usize> = word_list.iter().map(|w| hash.as_fn.hash(w)).collect();
+    hash_values.sort_unstable();
+    hash_values.dedup();
+    assert_eq!(hash_values.len(), word_list.len());
+    for word in &word_list {
+        let trace = hash.explain(word).unwrap();
+        assert_eq!(trace.mask, Some(7));
+        assert_eq!(trace.hash_value, hash.as_fn.hash(word));
+        assert_eq!(trace.hash_value, usize::try_from(trace.sum).unwrap() % 8);
+    }
+    let text = hash_fn_text(&word_list, &hash, Profile::Size).unwrap();
+    assert!(text.contains("    hash_value = (row_lookup_table[row_index] + col_index) & 7\n"));
+    let recognizer = rust_recognizer(&word_list, &hash, "Keyword").unwrap();
+    assert!(recognizer.contains("let hash_value = usize::try_from(sum).ok()? & 7;\n"));
+
+    let options = options.slack_percent(50);
+    let slack = generate_hash_with_options(&word_list, ElcAlgorithm::default(), &options).unwrap();
+    assert_eq!(slack.table_len, 16);
+    assert!(slack.as_string.contains(") & 15\n"));
+}
+
+#[test]
+fn row_tie_break_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let hash_algorithm = ElcAlgorithm::new(2, 26);
+    let mut tables = Vec::new();
+    for tie_break in [
+        RowTieBreak::RowIndexAscending,
+        RowTieBreak::RowIndexDescending,
+        RowTieBreak::FirstColumn,
+        RowTieBreak::Seeded(3),
+    ] {
+        let options = Options::new().row_tie_break(tie_break);
+        let hash = generate_hash_with_options(&word_list, hash_algorithm.clone(), &options)
+            .unwrap_or_else(|e| panic!("{tie_break:?} failed {e}"));
+        let again =
+            generate_hash_with_options(&word_list, hash_algorithm.clone(), &options).unwrap();
+        assert_eq!(hash.as_string, again.as_string);
+        tables.push(hash.as_fn.get_rlt().as_slice().to_vec());
+    }
+    // Breaking ties another way places the rows elsewhere.
+    assert_ne!(tables[0], tables[1]);
+}
+
+#[test]
+fn pack_events_integ_test() {
+    let word_list: WordList = (0..64).map(|i| format!("KEY_{i}")).collect();
+    let options = Options::new().charset(Charset::Identifier);
+    let hash =
+        generate_hash_with_options(&word_list, FnvAlgorithm::new(32, 256), &options).unwrap();
+    assert!(hash.packed_array.pack_events().is_empty());
+
+    let options = options.record_pack_events(true);
+    let hash =
+        generate_hash_with_options(&word_list, FnvAlgorithm::new(32, 256), &options).unwrap();
+    let events = hash.packed_array.pack_events();
+    // With first fit every row ends at the one offset it was placed at.
+    let placed: Vec<(usize, isize)> = events
+        .iter()
+        .filter(|event| event.conflict.is_none())
+        .map(|event| (event.row_index, event.offset))
+        .collect();
+    assert_eq!(placed, hash.packed_array.placements());
+    assert!(events.len() > placed.len());
+    for event in events.iter().filter(|event| event.conflict.is_some()) {
+        assert!(event.conflict < Some(hash.table_len));
+    }
+
+    // Backtracking tries the same offsets when it never has to back up.
+    let options = options.packing_strategy(Backtracking);
+    let backtracked =
+        generate_hash_with_options(&word_list, FnvAlgorithm::new(32, 256), &options).unwrap();
+    assert_eq!(backtracked.packed_array.pack_events(), events);
+}
+
+#[test]
+fn explain_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let hash = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+    let trace = hash.explain("EOF").unwrap();
+    assert_eq!(
+        trace,
+        HashTrace {
+            row_index: 4,
+            col_index: 5,
+            rlt_index: 4,
+            rlt_value: -3,
+            multiplier: None,
+            scaled_col_index: 5,
+            sum: 2,
+            modulus: 8,
+            mask: None,
+            hash_base: 0,
+            hash_value: 2,
+        }
+    );
+    for word in &word_list {
+        assert_eq!(
+            hash.explain(word).unwrap().hash_value,
+            hash.as_fn.hash(word)
+        );
+    }
+    assert_eq!(
+        hash.explain("Z").unwrap_err().to_string(),
+        hash.try_hash("Z").unwrap_err().to_string()
+    );
+
+    let options = Options::new().sparse_rlt(true);
+    let sparse =
+        generate_hash_with_options(&word_list, ElcAlgorithm::new(2, 26), &options).unwrap();
+    for word in &word_list {
+        let trace = sparse.explain(word).unwrap();
+        assert!(trace.rlt_index < sparse.as_fn.get_rlt().len());
+        assert_eq!(trace.hash_value, sparse.as_fn.hash(word));
+    }
+
+    let word_list: WordList = (0..64).map(|i| format!("KEY_{i}")).collect();
+    let options = Options::new()
+        .charset(Charset::Identifier)
+        .backend(Backend::Chd);
+    let chd = generate_hash_with_options(&word_list, FnvAlgorithm::new(32, 256), &options).unwrap();
+    for word in &word_list {
+        let trace = chd.explain(word).unwrap();
+        assert!(trace.multiplier.is_some());
+        assert_eq!(trace.hash_value, chd.as_fn.hash(word));
+    }
+
+    let word_list: WordList = (0..16).map(|i| format!("KEY_{i}")).collect();
+    let chm = generate_hash_with_options(
+        &word_list,
+        FnvAlgorithm::new(4, 256),
+        &options.backend(Backend::Chm),
+    )
+    .unwrap();
+    assert!(chm.explain("KEY_0").is_err());
+}
+
+#[test]
+fn to_json_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let hash = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+    let spec: serde_json::Value = serde_json::from_str(&hash.to_json()).unwrap();
+    assert_eq!(spec["format_version"], JSON_FORMAT_VERSION);
+    assert_eq!(spec["algorithm"]["name"], "elc");
+    assert_eq!(
+        spec["algorithm"]["parameters"],
+        serde_json::json!({"elc_h1": 1, "elc_h2": 1, "num_vals": 26})
+    );
+    assert_eq!(spec["algorithm"]["folds_case"], false);
+    assert_eq!(
+        spec["algorithm"]["h1"],
+        ElcAlgorithm::default().h1_as_text()
+    );
+    assert_eq!(spec["backend"], "packed");
+    assert_eq!(
+        spec["row_lookup_table"],
+        serde_json::json!({"element_type": "i8", "values": [1, -6, -14, 0, -3]})
+    );
+    assert_eq!(spec["table_len"], 8);
+    assert_eq!(spec["hash_base"], 0);
+    assert_eq!(spec["max_word_len"], 5);
+    assert_eq!(spec["hash_function"], hash.as_string);
+
+    let options = Options::new().sparse_rlt(true);
+    let sparse = generate_hash_with_options(
+        &word_list,
+        CaseFold::new(ElcAlgorithm::new(2, 26)),
+        &options,
+    )
+    .unwrap();
+    let spec: serde_json::Value = serde_json::from_str(&sparse.to_json()).unwrap();
+    assert_eq!(spec["algorithm"]["name"], "elc");
+    assert_eq!(spec["algorithm"]["folds_case"], true);
+    assert_eq!(
+        spec["row_lookup_table"]["rows"].as_array().unwrap().len(),
+        sparse.as_fn.get_rlt().len()
+    );
+
+    let word_list: WordList = (0..64).map(|i| format!("KEY_{i}")).collect();
+    let options = Options::new()
+        .charset(Charset::Identifier)
+        .backend(Backend::Chd);
+    let chd = generate_hash_with_options(&word_list, FnvAlgorithm::new(32, 256), &options).unwrap();
+    let spec: serde_json::Value = serde_json::from_str(&chd.to_json()).unwrap();
+    assert_eq!(spec["algorithm"]["name"], "fnv");
+    assert_eq!(
+        spec["algorithm"]["parameters"],
+        serde_json::json!({"num_rows": 32, "num_cols": 256})
+    );
+    assert_eq!(spec["backend"], "chd");
+    assert_eq!(
+        spec["row_lookup_table"]["multipliers"]
+            .as_array()
+            .unwrap()
+            .len(),
+        chd.as_fn.get_rlt().len()
+    );
+
+    let word_list: WordList = (0..16).map(|i| format!("KEY_{i}")).collect();
+    let chm = generate_hash_with_options(
+        &word_list,
+        SeededAlgorithm::new(7, 4, 256),
+        &options.backend(Backend::Chm),
+    )
+    .unwrap();
+    let spec: serde_json::Value = serde_json::from_str(&chm.to_json()).unwrap();
+    assert_eq!(spec["algorithm"]["name"], "seeded");
+    assert_eq!(spec["algorithm"]["parameters"]["seed"], 7);
+    assert_eq!(spec["backend"], "chm");
+    assert!(spec.get("row_lookup_table").is_none());
+    assert_eq!(spec["chm"]["keys"].as_array().unwrap().len(), 3);
+    assert_eq!(spec["table_len"], 16);
+}
+
+#[test]
+fn expr_integ_test() {
+    let word_list = pascal_keyword_subset();
+    let algorithms: Vec<Box<dyn HashAlgorithm>> = vec![
+        Box::new(ElcAlgorithm::default()),
+        Box::new(ElcAlgorithm::new(2, 26).with_elcs(1, 2)),
+        Box::new(ElcAlgorithm::from_word_list(2, &word_list)),
+        Box::new(CaseFold::new(ElcAlgorithm::default())),
+        Box::new(ElcWithLength::from_word_list(1, &word_list)),
+        Box::new(
+            PositionAlgorithm::from_word_list([Position::Start(1)], [Position::End(0)], &word_list)
+                .with_length(6),
+        ),
+        Box::new(CichelliAlgorithm::from_word_list(&word_list).unwrap()),
+    ];
+    for hash_algorithm in &algorithms {
+        let h1_expr = hash_algorithm.h1_expr().unwrap();
+        let h2_expr = hash_algorithm.h2_expr().unwrap();
+        for word in &word_list {
+            let chars: Vec<char> = word.chars().collect();
+            assert_eq!(
+                h1_expr.eval(&chars),
+                hash_algorithm.h1(word).ok(),
+                "{h1_expr}"
+            );
+            assert_eq!(
+                h2_expr.eval(&chars),
+                hash_algorithm.h2(word).ok(),
+                "{h2_expr}"
+            );
+        }
+    }
+
+    assert_eq!(
+        ElcAlgorithm::new(2, 26).h1_expr().unwrap().to_string(),
+        "(((word[0] - 65) * 26) + (word[1] - 65))"
+    );
+    assert_eq!(
+        ElcAlgorithm::new(2, 26).h2_expr().unwrap(),
+        (Expr::Char(Position::End(0)) - Expr::Const(65)) * Expr::Const(26)
+            + (Expr::Char(Position::End(1)) - Expr::Const(65))
+    );
+    let hash_algorithm: Box<dyn HashAlgorithm> = Box::new(FnvAlgorithm::new(4, 256));
+    assert_eq!(hash_algorithm.h1_expr(), None);
+}
+
+#[cfg(feature = "internals")]
+#[test]
+fn internals_integ_test() {
+    use msmp::TwoDArray;
+
+    let word_list = pascal_keyword_subset();
+    let hash_algorithm = ElcAlgorithm::default();
+    let two_d_array = TwoDArray::new(&word_list, &hash_algorithm, &Options::default()).unwrap();
+    assert_eq!(two_d_array.get_num_entries(), word_list.len());
+    let rows: Vec<_> = two_d_array.rows().collect();
+    assert_eq!(rows.len(), two_d_array.get_num_rows());
+    assert_eq!(
+        rows.iter().map(|(_, row)| row.len()).sum::<usize>(),
+        word_list.len()
+    );
+    for (row_index, row) in &rows {
+        assert_eq!(two_d_array.row(*row_index), Some(*row));
+    }
+
+    let hash = generate_hash(&word_list, hash_algorithm).unwrap();
+    let placements = hash.packed_array.placements();
+    assert_eq!(placements.len(), rows.len());
+    for (row_index, offset) in placements {
+        assert_eq!(hash.as_fn.get_rlt().get(*row_index), Some(offset));
+    }
+}
+
+#[cfg(feature = "internals")]
+#[test]
+fn from_pairs_integ_test() {
+    use msmp::{OneDPackedArray, TwoDArray};
+
+    // The first and last letter of AND, BEGIN, CHAR, CONST, ELSE, END, ENTER
+    // and EOF.
+    let pairs = [
+        (0, 3),
+        (1, 13),
+        (2, 17),
+        (2, 19),
+        (4, 4),
+        (4, 3),
+        (4, 17),
+        (4, 5),
+    ];
+    let options = Options::default();
+    let two_d_array = TwoDArray::from_pairs(&pairs, pairs.len(), &options).unwrap();
+    assert_eq!(two_d_array.get_hash_pairs(), pairs);
+    let packed = OneDPackedArray::pack(&two_d_array, &options).unwrap();
+    assert_eq!(packed.get_rlt().as_slice(), &[1, -6, -14, 0, -3]);
+
+    let e = TwoDArray::from_pairs(&pairs, 9, &options).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "2D Array Error: 8 index pairs given for 9 words."
+    );
+    let e = TwoDArray::from_pairs(&[(1, 2), (0, 0), (1, 2)], 3, &options).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "2D Array Collision: word 1 === word 3 at row 1, column 2"
+    );
+}
+
+/// The recognizer emitted by `rust_recognizer` for the Pascal keyword subset,
+/// with its `rust_recognizer_self_test`.
+mod pascal_keyword_recognizer {
+    include!("data/pascal_keyword_recognizer.rs");
+}
+
+#[test]
+fn rust_recognizer_integ_test() {
+    use pascal_keyword_recognizer::Keyword;
+
+    let word_list = pascal_keyword_subset();
+    let hash = generate_hash(&word_list, ElcAlgorithm::default()).unwrap();
+    let recognizer_file_name: PathBuf = [".", "tests", "data", "pascal_keyword_recognizer.rs"]
+        .iter()
+        .collect();
+    assert_eq!(
+        format!(
+            "{}\n{}",
+            rust_recognizer(&word_list, &hash, "Keyword").unwrap(),
+            rust_recognizer_self_test(&word_list, &hash, "Keyword")
+        ),
+        std::fs::read_to_string(recognizer_file_name).unwrap()
+    );
+
+    for word in &word_list {
+        let keyword = Keyword::from_str(word).unwrap();
+        assert_eq!(keyword.as_str(), word);
+    }
+    assert_eq!(Keyword::from_str("BEGIN"), Some(Keyword::Begin));
+    assert_eq!(Keyword::from_str("ENTER"), Some(Keyword::Enter));
+    assert_eq!(Keyword::from_str("ERROR"), None);
+    assert_eq!(Keyword::from_str("begin"), None);
+    assert_eq!(Keyword::from_str("ZZ"), None);
+    assert_eq!(Keyword::from_str(""), None);
+
+    let hash = generate_hash(&word_list, FnvAlgorithm::new(8, 8)).unwrap();
+    assert_eq!(
+        rust_recognizer(&word_list, &hash, "Keyword")
+            .unwrap_err()
+            .to_string(),
+        "Codegen Error: The fnv algorithm cannot describe h1 and h2 as expressions."
+    );
 }
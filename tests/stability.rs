@@ -0,0 +1,112 @@
+//! Golden outputs for fixed word lists.
+//!
+//! Generated tables must be byte-identical across platforms and releases, so
+//! that they can be regenerated in reproducible builds. A change that makes
+//! one of these tests fail changes the generated hashes, and has to be called
+//! out as such in the release notes, along with the new golden values.
+
+use common::pascal_keyword_subset;
+use msmp::{
+    generate_hash, generate_hash_with_options, Backend, Backtracking, Charset, ElcAlgorithm,
+    FnvAlgorithm, Options, RowTieBreak, WordList,
+};
+
+mod common;
+
+/// Generates the word list `KEY_0` to `KEY_{num_words - 1}`.
+fn keys(num_words: usize) -> WordList {
+    (0..num_words).map(|i| format!("KEY_{i}")).collect()
+}
+
+/// The options for the generated word lists, which hold digits and underscores.
+fn identifier_options() -> Options {
+    Options::new().charset(Charset::Identifier)
+}
+
+#[test]
+fn readme_example_stability_test() {
+    let hash = generate_hash(&pascal_keyword_subset(), ElcAlgorithm::default()).unwrap();
+    let rlt = hash.as_fn.get_rlt();
+    assert_eq!(rlt.as_slice(), &[1, -6, -14, 0, -3]);
+    assert_eq!(rlt.to_le_bytes(), [0x01, 0xfa, 0xf2, 0x00, 0xfd]);
+    assert_eq!(
+        hash.as_string,
+        "row_lookup_table = [1, -6, -14, 0, -3]\n\
+         row_index = \n        \
+         val = 0\n        \
+         for x in word_letters[0..0]:\n            \
+         val += (val * 26) + ord(x) - ord('A')\n\
+         col_index = \n        \
+         val = 0\n        \
+         for x in word_letters[-1..-1]:\n            \
+         val += (val * 26) + ord(x) - ord('A')\n\
+         hash_value = (row_lookup_table[row_index] + col_index) % 8\n"
+    );
+}
+
+#[test]
+fn sparse_rlt_stability_test() {
+    let options = Options::new().sparse_rlt(true);
+    let hash =
+        generate_hash_with_options(&pascal_keyword_subset(), ElcAlgorithm::new(2, 26), &options)
+            .unwrap();
+    let rlt = hash.as_fn.get_rlt();
+    assert_eq!(rlt.get_rows(), Some(&[13, 30, 59, 66, 115, 117, 118][..]));
+    assert_eq!(rlt.as_slice(), &[-90, -344, -438, -507, -116, -91, -137]);
+}
+
+#[test]
+fn first_fit_stability_test() {
+    let hash =
+        generate_hash_with_options(&keys(64), FnvAlgorithm::new(32, 256), &identifier_options())
+            .unwrap();
+    assert_eq!(
+        hash.as_fn.get_rlt().as_slice(),
+        &[
+            -182, 27, -26, -117, -71, 6, -181, -37, -73, -1, 0, 0, -206, -55, -69, -20, -90, 0, 26,
+            -158, 4, -25, 27, -62, 0, -179, -40, -75, 9, 0, 0, 52
+        ]
+    );
+}
+
+#[test]
+fn backtracking_seeded_tie_break_stability_test() {
+    let options = identifier_options()
+        .row_tie_break(RowTieBreak::Seeded(1))
+        .packing_strategy(Backtracking);
+    let hash = generate_hash_with_options(&keys(64), FnvAlgorithm::new(32, 256), &options).unwrap();
+    assert_eq!(
+        hash.as_fn.get_rlt().as_slice(),
+        &[
+            -155, 1, -29, -107, -59, 11, -185, -18, -73, 9, 0, 0, -199, -55, -79, -3, -103, 0, 32,
+            -185, -3, -39, 30, -69, 0, -182, -40, -73, 5, 0, 0, 35
+        ]
+    );
+}
+
+#[test]
+fn chd_backend_stability_test() {
+    let options = identifier_options().backend(Backend::Chd);
+    let hash = generate_hash_with_options(&keys(64), FnvAlgorithm::new(32, 256), &options).unwrap();
+    assert!(hash.as_string.starts_with(
+        "row_lookup_table = [10, 27, 38, 11, 57, 6, 11, 27, 55, 63, 0, 0, 50, 9, 59, 44, 38, 0, \
+         26, 34, 4, 39, 27, 2, 0, 13, 24, 53, 9, 0, 0, 52]\n\
+         row_multiplier = [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, \
+         1, 1, 1, 1, 1, 1, 1, 1, 1]\n"
+    ));
+}
+
+#[test]
+fn chm_backend_stability_test() {
+    let options = identifier_options().backend(Backend::Chm);
+    let hash = generate_hash_with_options(&keys(16), FnvAlgorithm::new(4, 256), &options).unwrap();
+    assert!(hash.as_string.starts_with(
+        "vertex_values = [0, 13, 0, 15, 0, 0, 5, 7, 0, 1, 9, 0, 0, 2, 14, 2, 10, 1, 2, 14, 0, 11, \
+         1, 12]\n"
+    ));
+    assert!(hash.as_string.contains(
+        "hash_value = (vertex_values[mix(x ^ 0x1e535eede31428f0) % 8] + \
+         vertex_values[8 + mix(x ^ 0xb7a4712c74562914) % 8] + \
+         vertex_values[16 + mix(x ^ 0xb6bf613dbebb45dc) % 8]) % 16"
+    ));
+}